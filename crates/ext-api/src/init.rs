@@ -0,0 +1,44 @@
+//! Opening storage: the SQLite registry under the data directory and the
+//! projects directory. Done at startup, and again when the user retries
+//! after a failure.
+
+use ext_core::settings::Settings;
+use ext_db::paths::fs_error;
+use ext_db::Database;
+use ext_error::{AppError, Result};
+use std::path::{Path, PathBuf};
+
+/// Opens `data_dir/db/app.db` and the projects directory from settings,
+/// which defaults to `data_dir/projects`.
+pub async fn open_database(data_dir: &Path, settings: &Settings) -> Result<Database> {
+    let db_dir = data_dir.join("db");
+    let projects_dir = settings
+        .projects_dir
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| data_dir.join("projects"));
+
+    tokio::fs::create_dir_all(&db_dir)
+        .await
+        .map_err(|e| fs_error("create database directory", &db_dir, e))?;
+
+    let db_path = db_dir.join("app.db");
+    let db_url = format!(
+        "sqlite://{}?mode=rwc",
+        db_path.to_string_lossy().replace('\\', "/")
+    );
+
+    let projects_dir = projects_dir.to_str().ok_or_else(|| {
+        AppError::Validation(format!(
+            "Projects directory '{}' is not valid UTF-8",
+            projects_dir.display()
+        ))
+    })?;
+    Database::new(&db_url, projects_dir).await.map_err(|e| match e {
+        AppError::Database(message) => AppError::Database(format!(
+            "Failed to open '{}': {message}",
+            db_path.display()
+        )),
+        other => other,
+    })
+}
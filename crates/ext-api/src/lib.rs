@@ -1,39 +1,3470 @@
-use std::sync::Arc;
+pub mod init;
+mod locks;
+pub mod logs;
+pub mod tasks;
+pub mod watcher;
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock};
+use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::sync::Mutex;
 
-use ext_db::{Database, Project};
-use ext_error::AppError;
+use ext_core::audit::{AuditAction, AuditEntry, MAX_AUDIT_PAGE_SIZE};
+use ext_core::bulk_import::{self, BulkImportProgress, BulkImportReport};
+use ext_core::combinations::{self, DesignCode, LoadCase, LoadCombination};
+use ext_core::deep_link::DeepLink;
+use ext_core::diagnostics::{Diagnostics, OrphanedRowAction, RepairReport};
+use ext_core::drift::{self, DriftCheckReport, DEFAULT_DRIFT_LIMIT_RATIO};
+use ext_core::duplicates::{self, DuplicateProjectSet, MergeOptions};
+use ext_core::e2k::{self, EtabsModel};
+use ext_core::events::{ProjectEvent, ProjectFileModified};
+use ext_core::{CreateProjectOptions, DEFAULT_TRASH_RETENTION_DAYS, MAX_RECENT_PROJECTS};
+use ext_core::estimate::{self, LateralEstimate, LateralEstimateInput};
+use ext_core::export::{self, ExportFormat, ExportTable, TableRows};
+use ext_core::frame_forces::{self, FrameForceFilter, FrameForceImport, FrameForcePage};
+use ext_core::modal::{self, ModalResults};
+use ext_core::model_diff::{self, ModelDiff};
+use ext_core::notes::{Note, NoteRevision};
+use ext_core::relocation::RelocationReport;
+use ext_core::report::{self, DriftSummary, ProjectSummary, ReportFormat};
+use ext_core::results::{
+    self, AnalysisResults, AnalysisRun, BaseReaction, ModalPeriod, PasteImportReport, ResultTable,
+    StoryDrift,
+};
+use ext_core::search::SearchHit;
+use ext_core::seismic::{self, SeismicBaseShear, SeismicParams, SeismicStory};
+use ext_core::settings::{Settings, SettingsPatch};
+use ext_core::sidecar::{
+    EtabsConnection, EtabsModelInfo, LoadCombinationsReport, OpenModelReport, SidecarClient,
+};
+use ext_core::tasks::{TaskInfo, TaskKind};
+use ext_core::templates::ProjectTemplate;
+use ext_core::wind::{self, WindInput, WindPressures};
+use ext_core::{EtabsStatus, InitStatus};
+use ext_db::{Database, Project, ProjectBackup, ProjectFile, ProjectStatus, StorageStatus, Tag};
+use ext_error::{AppError, Result};
+use serde::Serialize;
+use tracing::instrument;
+use uuid::Uuid;
+use locks::ProjectLocks;
+use tasks::{TaskContext, TaskManager};
+use watcher::{FileWatcher, WatchedFile};
+
+/// Parses a project id received from the frontend.
+pub fn parse_project_id(value: &str) -> Result<Uuid> {
+    Uuid::parse_str(value.trim())
+        .map_err(|_| AppError::Validation(format!("Invalid project id: '{}'", value)))
+}
+
+/// Parses an attached file id received from the frontend.
+pub fn parse_file_id(value: &str) -> Result<Uuid> {
+    Uuid::parse_str(value.trim())
+        .map_err(|_| AppError::Validation(format!("Invalid file id: '{}'", value)))
+}
+
+/// Parses a tag id received from the frontend.
+pub fn parse_tag_id(value: &str) -> Result<Uuid> {
+    Uuid::parse_str(value.trim())
+        .map_err(|_| AppError::Validation(format!("Invalid tag id: '{}'", value)))
+}
+
+/// Parses a project template id received from the frontend.
+pub fn parse_template_id(value: &str) -> Result<Uuid> {
+    Uuid::parse_str(value.trim())
+        .map_err(|_| AppError::Validation(format!("Invalid template id: '{}'", value)))
+}
+
+/// Parses an analysis run id received from the frontend.
+pub fn parse_run_id(value: &str) -> Result<Uuid> {
+    Uuid::parse_str(value.trim())
+        .map_err(|_| AppError::Validation(format!("Invalid run id: '{}'", value)))
+}
+
+/// Parses a note id received from the frontend.
+pub fn parse_note_id(value: &str) -> Result<Uuid> {
+    Uuid::parse_str(value.trim())
+        .map_err(|_| AppError::Validation(format!("Invalid note id: '{}'", value)))
+}
+
+/// Parses a note revision id received from the frontend.
+pub fn parse_revision_id(value: &str) -> Result<Uuid> {
+    Uuid::parse_str(value.trim())
+        .map_err(|_| AppError::Validation(format!("Invalid revision id: '{}'", value)))
+}
+
+/// Parses a background task id received from the frontend.
+pub fn parse_task_id(value: &str) -> Result<Uuid> {
+    Uuid::parse_str(value.trim())
+        .map_err(|_| AppError::Validation(format!("Invalid task id: '{}'", value)))
+}
+
+/// Receives project events after successful mutations. The desktop app
+/// forwards them to the webview; tests record them.
+pub trait EventSink: Send + Sync {
+    fn emit(&self, event: &ProjectEvent) -> Result<()>;
+
+    /// Progress of a running bulk import; sinks without a progress bar
+    /// ignore it
+    fn emit_bulk_import_progress(&self, _progress: &BulkImportProgress) -> Result<()> {
+        Ok(())
+    }
+
+    /// `task:progress` or `task:completed` for a background task; sinks
+    /// without a task list ignore it
+    fn emit_task(&self, _event: &str, _task: &TaskInfo) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The database, or why it couldn't be opened
+enum Storage {
+    Ready(Arc<Database>),
+    Failed { data_dir: PathBuf, error: AppError },
+}
 
 pub struct AppState {
-    pub db: Arc<Mutex<Database>>,
+    /// Replaced when a retry opens storage after a failed start
+    storage: RwLock<Storage>,
+    /// Held while a project's folder is written, so writes to one project
+    /// don't interleave; other projects and reads are not blocked
+    project_locks: ProjectLocks,
+    /// Held while checking a project name is free and saving it, so two
+    /// projects can't claim the same name. Taken before any project lock.
+    names: Mutex<()>,
+    settings: Mutex<Settings>,
+    /// Where settings are persisted; `None` keeps them in memory only
+    settings_path: Option<PathBuf>,
+    /// Attached ETABS instance, reused until disconnected
+    etabs: Mutex<Option<EtabsConnection>>,
+    /// Background tasks started this session
+    tasks: Arc<TaskManager>,
+    /// Name recorded in the project locks this instance takes, shown to
+    /// other users who try to edit the project
+    lock_holder: String,
+    events: Option<Arc<dyn EventSink>>,
+    /// Watches files of open projects for edits made outside the app
+    watcher: Option<FileWatcher>,
+    /// Reported in diagnostics so users can find the logs to send
+    log_dir: Option<PathBuf>,
+    /// Where the backend's own JSON log is written
+    backend_log_dir: Option<PathBuf>,
 }
 
 impl AppState {
     pub fn new(db: Database) -> Self {
+        Self::with_storage(Storage::Ready(Arc::new(db)), Settings::default(), None)
+    }
+
+    /// Creates the state with settings loaded from `settings_path`, applying
+    /// the ones that take effect immediately.
+    pub fn with_settings(db: Database, settings: Settings, settings_path: PathBuf) -> Self {
+        apply_database_settings(&db, &settings);
+        Self::with_storage(Storage::Ready(Arc::new(db)), settings, Some(settings_path))
+    }
+
+    /// Creates the state after storage in `data_dir` failed to open, so the
+    /// app can still start and show `error`. Settings work; everything that
+    /// needs the database fails until [`AppState::retry_initialization`]
+    /// succeeds.
+    pub fn degraded(
+        data_dir: PathBuf,
+        error: AppError,
+        settings: Settings,
+        settings_path: Option<PathBuf>,
+    ) -> Self {
+        Self::with_storage(Storage::Failed { data_dir, error }, settings, settings_path)
+    }
+
+    fn with_storage(storage: Storage, settings: Settings, settings_path: Option<PathBuf>) -> Self {
         Self {
-            db: Arc::new(Mutex::new(db)),
+            storage: RwLock::new(storage),
+            project_locks: ProjectLocks::default(),
+            names: Mutex::new(()),
+            settings: Mutex::new(settings),
+            settings_path,
+            etabs: Mutex::new(None),
+            tasks: Arc::default(),
+            lock_holder: ext_db::locks::default_lock_holder(),
+            events: None,
+            watcher: None,
+            log_dir: None,
+            backend_log_dir: None,
+        }
+    }
+
+    pub fn with_event_sink(mut self, sink: Arc<dyn EventSink>) -> Self {
+        self.events = Some(sink);
+        self
+    }
+
+    /// Uses `watcher` for open projects; feed its receiver to
+    /// [`AppState::watch_file_changes`].
+    pub fn with_file_watcher(mut self, watcher: FileWatcher) -> Self {
+        self.watcher = Some(watcher);
+        self
+    }
+
+    pub fn with_log_dir(mut self, log_dir: PathBuf) -> Self {
+        self.log_dir = Some(log_dir);
+        self
+    }
+
+    pub fn with_backend_log_dir(mut self, dir: PathBuf) -> Self {
+        self.backend_log_dir = Some(dir);
+        self
+    }
+
+    /// Names this instance in project locks instead of the OS user and
+    /// machine.
+    pub fn with_lock_holder(mut self, holder: String) -> Self {
+        self.lock_holder = holder;
+        self
+    }
+
+    /// Runs `work` in the background as a task of `kind` and returns the
+    /// task id at once. `work` reports progress and checks for cancellation
+    /// through the [`TaskContext`] it's given; what it returns becomes the
+    /// task's result. `work` must own what it uses, so callers hand it a
+    /// way back to the state, such as the app handle. Must be called from
+    /// within the async runtime.
+    pub fn spawn_task<F, Fut, T>(&self, kind: TaskKind, work: F) -> Uuid
+    where
+        F: FnOnce(TaskContext) -> Fut,
+        Fut: Future<Output = Result<T>> + Send + 'static,
+        T: Serialize + Send + 'static,
+    {
+        let task = TaskContext::start(&self.tasks, self.events.clone(), kind);
+        let id = task.id();
+        let work = tokio::spawn(work(task.clone()));
+        tokio::spawn(async move {
+            // A panic in the work fails the task instead of leaving it running
+            let outcome = match work.await {
+                Ok(result) => result.and_then(|value| {
+                    serde_json::to_value(value).map_err(|e| {
+                        AppError::Internal(format!("Failed to serialize task result: {e}"))
+                    })
+                }),
+                Err(e) => Err(AppError::Internal(format!("Task failed: {e}"))),
+            };
+            task.finish(outcome);
+        });
+        id
+    }
+
+    /// Tasks of this session, most recently started first.
+    pub fn get_tasks(&self) -> Vec<TaskInfo> {
+        self.tasks.list()
+    }
+
+    pub fn get_task(&self, task_id: Uuid) -> Result<TaskInfo> {
+        self.tasks
+            .get(task_id)
+            .ok_or_else(|| AppError::NotFound(format!("Task {task_id} not found")))
+    }
+
+    /// Stops a running task at its next checkpoint. Returns false when it
+    /// already finished.
+    pub fn cancel_task(&self, task_id: Uuid) -> Result<bool> {
+        self.tasks.cancel(task_id)
+    }
+
+    /// Cancels the running bulk import tasks, each stopping after its
+    /// current file. Returns false when none is running.
+    pub fn cancel_bulk_import(&self) -> bool {
+        let mut cancelled = false;
+        for task in self.tasks.list() {
+            if task.kind == TaskKind::BulkImport && !task.is_finished() {
+                cancelled |= self.cancel_task(task.id).unwrap_or(false);
+            }
+        }
+        cancelled
+    }
+
+    /// The open database, or `Internal("storage not initialized")` while
+    /// the app runs without one.
+    pub fn db(&self) -> Result<Arc<Database>> {
+        match &*self.storage.read().unwrap_or_else(PoisonError::into_inner) {
+            Storage::Ready(db) => Ok(db.clone()),
+            Storage::Failed { .. } => {
+                Err(AppError::Internal("storage not initialized".to_string()))
+            }
+        }
+    }
+
+    pub fn init_status(&self) -> InitStatus {
+        match &*self.storage.read().unwrap_or_else(PoisonError::into_inner) {
+            Storage::Ready(_) => InitStatus::Ready,
+            Storage::Failed { data_dir, error } => InitStatus::Failed {
+                data_dir: data_dir.to_string_lossy().to_string(),
+                error: error.clone(),
+            },
+        }
+    }
+
+    /// Tries again to open storage after a failed start, in `data_dir` or
+    /// the directory that failed. A directory chosen here is used for this
+    /// session only. Does nothing once storage is open.
+    #[instrument(skip_all)]
+    pub async fn retry_initialization(&self, data_dir: Option<PathBuf>) -> Result<InitStatus> {
+        let data_dir = {
+            let storage = self.storage.read().unwrap_or_else(PoisonError::into_inner);
+            match (&*storage, data_dir) {
+                (Storage::Ready(_), _) => return Ok(InitStatus::Ready),
+                (Storage::Failed { .. }, Some(data_dir)) => data_dir,
+                (Storage::Failed { data_dir, .. }, None) => data_dir.clone(),
+            }
+        };
+
+        let settings = self.settings.lock().await.clone();
+        let storage = match init::open_database(&data_dir, &settings).await {
+            Ok(db) => {
+                apply_database_settings(&db, &settings);
+                tracing::info!("Storage opened in '{}' after retrying", data_dir.display());
+                Storage::Ready(Arc::new(db))
+            }
+            Err(error) => {
+                tracing::warn!("Storage in '{}' still failed: {error}", data_dir.display());
+                Storage::Failed { data_dir, error }
+            }
+        };
+
+        let mut current = self.storage.write().unwrap_or_else(PoisonError::into_inner);
+        // Another retry may have succeeded meanwhile
+        if matches!(*current, Storage::Failed { .. }) {
+            *current = storage;
+        }
+        drop(current);
+        Ok(self.init_status())
+    }
+
+    fn emit_bulk_import_progress(&self, progress: BulkImportProgress) {
+        if let Some(sink) = &self.events
+            && let Err(e) = sink.emit_bulk_import_progress(&progress)
+        {
+            tracing::warn!("Failed to emit bulk import progress: {e}");
+        }
+    }
+
+    /// Sends `event` to the sink. The change it reports is already saved,
+    /// so a delivery failure is logged rather than returned.
+    fn emit(&self, event: ProjectEvent) {
+        if let Some(sink) = &self.events
+            && let Err(e) = sink.emit(&event)
+        {
+            tracing::warn!("Failed to emit {} for {}: {e}", event.name(), event.project_id());
+        }
+    }
+
+    #[instrument(skip_all)]
+    pub async fn get_settings(&self) -> Settings {
+        self.settings.lock().await.clone()
+    }
+
+    /// Applies a partial update. A new projects directory is used from the
+    /// next start, without moving any projects there; use
+    /// [`AppState::relocate_projects_dir`] to move them. Everything else
+    /// takes effect immediately.
+    #[instrument(skip_all)]
+    pub async fn update_settings(&self, patch: SettingsPatch) -> Result<Settings> {
+        let mut current = self.settings.lock().await;
+
+        let mut updated = current.clone();
+        updated.apply(patch);
+        updated.validate()?;
+
+        if let Some(dir) = &updated.projects_dir
+            && Path::new(dir).is_file()
+        {
+            return Err(AppError::Validation(format!(
+                "projects_dir '{dir}' is a file, not a directory"
+            )));
+        }
+        if let Some(path) = &updated.etabs_install_path
+            && !Path::new(path).is_dir()
+        {
+            return Err(AppError::Validation(format!(
+                "etabs_install_path '{path}' is not an existing directory"
+            )));
+        }
+
+        if let Some(settings_path) = &self.settings_path {
+            ext_db::settings::save_settings(settings_path, &updated).await?;
+        }
+        if let Ok(db) = self.db() {
+            apply_database_settings(&db, &updated);
         }
+
+        *current = updated.clone();
+        Ok(updated)
+    }
+
+    /// Moves every project folder to `destination` and makes it the
+    /// projects directory, now and on later starts. Folders are copied and
+    /// verified first; if any fails, or `task` is cancelled, the copies are
+    /// removed and the app stays on the old directory. The originals are
+    /// removed once the switch is made, unless `keep_original` is set.
+    #[instrument(skip_all, fields(destination = %destination.display(), task_id = %task.id()))]
+    pub async fn relocate_projects_dir(
+        &self,
+        destination: &Path,
+        keep_original: bool,
+        task: &TaskContext,
+    ) -> Result<RelocationReport> {
+        // No project is created, saved or deleted while its folder moves
+        let _names = self.names.lock().await;
+        let old = self.db()?;
+        let old_dir = PathBuf::from(&old.storage_status().projects_dir);
+        let folders = old.project_folders().await?;
+        let mut held = Vec::with_capacity(folders.len());
+        for id in &folders {
+            held.push(self.project_locks.lock(*id).await);
+        }
+
+        old.check_relocation_target(destination, &folders).await?;
+        let total = folders.len();
+        old.copy_project_folders(destination, &folders, |copied| {
+            task.set_progress(copied as f32 / total as f32);
+            task.checkpoint()
+        })
+        .await?;
+
+        let new = Arc::new(old.with_projects_dir(destination));
+        {
+            let mut settings = self.settings.lock().await;
+            let mut updated = settings.clone();
+            updated.projects_dir = Some(destination.to_string_lossy().to_string());
+            if let Some(settings_path) = &self.settings_path
+                && let Err(e) = ext_db::settings::save_settings(settings_path, &updated).await
+            {
+                new.remove_project_folders(&folders).await;
+                return Err(e);
+            }
+            *settings = updated;
+        }
+        *self.storage.write().unwrap_or_else(PoisonError::into_inner) = Storage::Ready(new.clone());
+        tracing::info!(
+            "Moved {total} project folders from '{}' to '{}'",
+            old_dir.display(),
+            destination.display()
+        );
+
+        if let Some(watcher) = &self.watcher {
+            for project_id in watcher.open_projects() {
+                let id = project_id.to_string();
+                watcher.unwatch_project(project_id);
+                let files_dir = new.project_files_dir(&id);
+                let watched = match new.list_project_files(&id).await {
+                    Ok(files) => watcher.watch_project(project_id, &files_dir, &files),
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = watched {
+                    tracing::warn!("Failed to watch project {project_id} after the move: {e}");
+                }
+            }
+        }
+
+        let left_behind = if keep_original {
+            folders
+                .iter()
+                .map(|id| old_dir.join(id.to_string()))
+                .collect()
+        } else {
+            old.remove_project_folders(&folders).await
+        };
+        Ok(RelocationReport {
+            projects_dir: new.storage_status().projects_dir.clone(),
+            copied: total,
+            left_behind: left_behind
+                .iter()
+                .map(|folder| folder.to_string_lossy().to_string())
+                .collect(),
+        })
+    }
+
+    #[instrument(skip_all)]
+    pub async fn create_project(&self, name: String, description: String) -> Result<Project> {
+        self.create_project_with_options(name, description, CreateProjectOptions::default())
+            .await
     }
 
-    pub async fn create_project(
+    #[instrument(skip_all)]
+    pub async fn create_project_with_options(
         &self,
         name: String,
         description: String,
-    ) -> Result<Project, String> {
-        let project = Project::new(name, description);
+        options: CreateProjectOptions,
+    ) -> Result<Project> {
+        let mut project = Project::new(name.trim().to_string(), description);
+        project.validate()?;
+
+        let _names = self.names.lock().await;
+        let db = self.db()?;
+        if options.auto_suffix_name {
+            let existing: Vec<String> = db
+                .list_projects()
+                .await?
+                .into_iter()
+                .map(|p| p.name)
+                .collect();
+            project.name = duplicates::next_available_name(&project.name, &existing);
+            // The suffix can push a long name over the limit
+            project.validate()?;
+        } else if db.find_project_by_name(&project.name).await?.is_some() {
+            return Err(AppError::Validation("project name already exists".to_string()));
+        }
 
-        let db = self.db.lock().await;
-        db.save_project(&project)
+        db.create_project(&project).await?;
+
+        self.emit(ProjectEvent::Created(project.clone()));
+        Ok(project)
+    }
+
+    /// Creates a project for every ETABS model in `dir`, named after the
+    /// file, and attaches the model. Models already attached to a project,
+    /// and files that fail, are reported as skipped. Cancelling `task`
+    /// stops the import after the current file.
+    #[instrument(skip_all, fields(dir = %dir.display(), recursive, task_id = %task.id()))]
+    pub async fn bulk_import(
+        &self,
+        dir: &Path,
+        recursive: bool,
+        task: &TaskContext,
+    ) -> Result<BulkImportReport> {
+        let db = self.db()?;
+        let mut report = BulkImportReport::default();
+        let files = scan_etabs_files(dir, recursive, &mut report.skipped).await?;
+
+        let total = files.len();
+        for (i, path) in files.iter().enumerate() {
+            if task.is_cancelled() {
+                report.cancelled = true;
+                break;
+            }
+            match self.import_model_as_project(&db, path).await {
+                Ok(project) => report.created.push(project),
+                Err(reason) => report.skipped.push((path.display().to_string(), reason)),
+            }
+            self.emit_bulk_import_progress(BulkImportProgress {
+                current: i + 1,
+                total,
+                path: path.display().to_string(),
+            });
+            task.set_progress((i + 1) as f32 / total as f32);
+        }
+
+        Ok(report)
+    }
+
+    /// One file of a bulk import; the error is the reason it was skipped.
+    async fn import_model_as_project(
+        &self,
+        db: &Database,
+        path: &Path,
+    ) -> std::result::Result<Project, String> {
+        if let Some(existing) = db.find_attached_copy(path).await.map_err(|e| e.to_string())? {
+            let owner = db
+                .load_project(&existing.project_id.to_string())
+                .await
+                .ok()
+                .flatten()
+                .map_or_else(|| existing.project_id.to_string(), |project| project.name);
+            return Err(format!("already attached to project '{owner}'"));
+        }
+
+        let options = CreateProjectOptions {
+            auto_suffix_name: true,
+        };
+        let project = self
+            .create_project_with_options(bulk_import::project_name(path), String::new(), options)
             .await
-            .map_err(|e: AppError| e.to_string())?;
+            .map_err(|e| e.to_string())?;
+
+        let _project = self.project_locks.lock(project.id).await;
+        let file = match db.add_file_to_project(&project.id.to_string(), path).await {
+            Ok(file) => file,
+            Err(e) => {
+                // Don't leave an empty project behind for a file that wasn't imported
+                let _ = db.discard_project(&project.id.to_string()).await;
+                self.emit(ProjectEvent::deleted(project.id));
+                return Err(e.to_string());
+            }
+        };
+        if let Some(watcher) = &self.watcher {
+            watcher.watch_file(&file);
+        }
+        self.emit(ProjectEvent::files_changed(project.id));
+        Ok(project)
+    }
+
+    /// Lists projects with `status`, active ones when no status is given.
+    #[instrument(skip_all)]
+    pub async fn get_projects(&self, status: Option<ProjectStatus>) -> Result<Vec<Project>> {
+        let db = self.db()?;
+        db.list_projects_with_status(status.unwrap_or_default()).await
+    }
+
+    #[instrument(skip_all, fields(project_id = %id))]
+    pub async fn get_project(&self, id: Uuid) -> Result<Project> {
+        let db = self.db()?;
+        db.load_project(&id.to_string())
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project {} not found", id)))
+    }
+
+    /// Canonical `etabext://` link to an existing project.
+    #[instrument(skip_all, fields(project_id = %id))]
+    pub async fn get_project_deep_link(&self, id: Uuid) -> Result<String> {
+        let project = self.get_project(id).await?;
+        Ok(DeepLink::Project(project.id).url())
+    }
+
+    /// Resolves an `etabext://` link to the project it points at.
+    #[instrument(skip_all)]
+    pub async fn resolve_deep_link(&self, url: &str) -> Result<Project> {
+        match DeepLink::parse(url)? {
+            DeepLink::Project(id) => self.get_project(id).await,
+        }
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn update_project(
+        &self,
+        project_id: Uuid,
+        name: String,
+        description: String,
+    ) -> Result<Project> {
+        let _names = self.names.lock().await;
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        let mut project = db
+            .load_project(&project_id.to_string())
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+        project.name = name.trim().to_string();
+        project.description = description;
+        project.validate()?;
+
+        if let Some(other) = db.find_project_by_name(&project.name).await?
+            && other.id != project.id
+        {
+            return Err(AppError::Validation("project name already exists".to_string()));
+        }
+
+        let project = db.update_project(&project).await?;
+        self.emit(ProjectEvent::Updated(project.clone()));
+        Ok(project)
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn archive_project(&self, project_id: Uuid) -> Result<Project> {
+        self.set_project_status(project_id, ProjectStatus::Archived).await
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn restore_project(&self, project_id: Uuid) -> Result<Project> {
+        self.set_project_status(project_id, ProjectStatus::Active).await
+    }
 
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn trash_project(&self, project_id: Uuid) -> Result<Project> {
+        self.set_project_status(project_id, ProjectStatus::Trashed).await
+    }
+
+    async fn set_project_status(&self, project_id: Uuid, status: ProjectStatus) -> Result<Project> {
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        let project = db.set_project_status(&project_id.to_string(), status).await?;
+        self.emit(ProjectEvent::Updated(project.clone()));
         Ok(project)
     }
 
-    pub async fn get_projects(&self) -> Result<Vec<Project>, String> {
-        let db = self.db.lock().await;
-        db.list_projects()
+    /// Permanently deletes projects that have been in the trash for longer
+    /// than `retention_days` (30 by default). Returns how many were removed.
+    #[instrument(skip_all)]
+    pub async fn empty_trash(&self, retention_days: Option<u32>) -> Result<usize> {
+        let days = retention_days.unwrap_or(DEFAULT_TRASH_RETENTION_DAYS);
+        let db = self.db()?;
+        let purged = db
+            .purge_trashed_projects(chrono::Duration::days(i64::from(days)))
+            .await?;
+        for &project_id in &purged {
+            self.close_watch(project_id);
+            self.emit(ProjectEvent::deleted(project_id));
+        }
+        Ok(purged.len())
+    }
+
+    /// Projects in the trash, which can still be restored or purged.
+    #[instrument(skip_all)]
+    pub async fn list_trashed_projects(&self) -> Result<Vec<Project>> {
+        self.get_projects(Some(ProjectStatus::Trashed)).await
+    }
+
+    /// Permanently deletes a project that is in the trash. Other projects
+    /// have to be trashed first, so nothing is deleted by one stray click.
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn purge_project(&self, project_id: Uuid) -> Result<()> {
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        let id = project_id.to_string();
+        let project = db
+            .load_project(&id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project {project_id} not found")))?;
+        if project.status != ProjectStatus::Trashed {
+            return Err(AppError::Validation(format!(
+                "Project '{}' is not in the trash",
+                project.name
+            )));
+        }
+
+        db.delete_project(&id).await?;
+        self.close_watch(project_id);
+        self.emit(ProjectEvent::deleted(project_id));
+        Ok(())
+    }
+
+    /// Moves the project to the trash, where it can still be restored.
+    /// [`Self::purge_project`] is the only permanent delete.
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn delete_project(&self, project_id: Uuid) -> Result<Project> {
+        self.trash_project(project_id).await
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn add_project_file(&self, project_id: Uuid, path: &Path) -> Result<ProjectFile> {
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        let file = db.add_file_to_project(&project_id.to_string(), path).await?;
+        if let Some(watcher) = &self.watcher {
+            watcher.watch_file(&file);
+        }
+        self.emit(ProjectEvent::files_changed(project_id));
+        Ok(file)
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn get_project_files(&self, project_id: Uuid) -> Result<Vec<ProjectFile>> {
+        let db = self.db()?;
+        db.list_project_files(&project_id.to_string()).await
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id, file_id = %file_id))]
+    pub async fn remove_project_file(&self, project_id: Uuid, file_id: Uuid) -> Result<()> {
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        db.remove_file_from_project(&project_id.to_string(), &file_id.to_string())
+            .await?;
+        if let Some(watcher) = &self.watcher {
+            watcher.unwatch_file(file_id);
+        }
+        self.emit(ProjectEvent::files_changed(project_id));
+        Ok(())
+    }
+
+    /// Takes the project's lock, records it as recently opened and starts
+    /// watching its attached files for edits made outside the app, e.g.
+    /// saving the model in ETABS. Returns the current files. Fails with
+    /// [`AppError::Locked`] while another app instance has it open.
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn open_project(&self, project_id: Uuid) -> Result<Vec<ProjectFile>> {
+        let id = project_id.to_string();
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        db.acquire_project_lock(&id, self.lock_holder.clone())
+            .await?;
+        let project = db.touch_project_opened(&id).await?;
+        self.emit(ProjectEvent::Updated(project));
+
+        let files = db.list_project_files(&id).await?;
+        if let Some(watcher) = &self.watcher {
+            watcher.watch_project(project_id, &db.project_files_dir(&id), &files)?;
+        }
+        Ok(files)
+    }
+
+    /// Lists up to `limit` (1 to 50) projects for the start screen, most
+    /// recently opened first.
+    #[instrument(skip_all)]
+    pub async fn get_recent_projects(&self, limit: usize) -> Result<Vec<Project>> {
+        if !(1..=MAX_RECENT_PROJECTS).contains(&limit) {
+            return Err(AppError::Validation(format!(
+                "limit must be between 1 and {MAX_RECENT_PROJECTS} (got {limit})"
+            )));
+        }
+
+        let db = self.db()?;
+        db.list_recent_projects(limit).await
+    }
+
+    /// Returns page `page` (from 0) of the project's change history, newest
+    /// first, optionally only entries with `action`.
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn get_audit_log(
+        &self,
+        project_id: Uuid,
+        page: usize,
+        page_size: usize,
+        action: Option<AuditAction>,
+    ) -> Result<Vec<AuditEntry>> {
+        if !(1..=MAX_AUDIT_PAGE_SIZE).contains(&page_size) {
+            return Err(AppError::Validation(format!(
+                "page_size must be between 1 and {MAX_AUDIT_PAGE_SIZE} (got {page_size})"
+            )));
+        }
+
+        let id = project_id.to_string();
+        let db = self.db()?;
+        if db.load_project(&id).await?.is_none() {
+            return Err(AppError::NotFound(format!("Project {id} not found")));
+        }
+        db.list_audit_log(&id, action, page as u64, page_size as u64)
+            .await
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn create_note(
+        &self,
+        project_id: Uuid,
+        title: String,
+        body_markdown: String,
+    ) -> Result<Note> {
+        let note = Note::new(project_id, title, body_markdown);
+        note.validate()?;
+
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        db.create_note(&note).await?;
+
+        Ok(note)
+    }
+
+    /// Saves a new title and body; the previous version is kept in the
+    /// note's history. Saving an unchanged note does nothing.
+    #[instrument(skip_all, fields(note_id = %note_id))]
+    pub async fn update_note(
+        &self,
+        note_id: Uuid,
+        title: String,
+        body_markdown: String,
+    ) -> Result<Note> {
+        let note = self.find_note(note_id).await?;
+        let _project = self.project_locks.lock(note.project_id).await;
+        // Re-read under the lock so the revision is the version being replaced
+        let note = self.find_note(note_id).await?;
+
+        let edited = note.edited(title, body_markdown);
+        if edited.title == note.title && edited.body_markdown == note.body_markdown {
+            return Ok(note);
+        }
+        edited.validate()?;
+
+        let db = self.db()?;
+        db.update_note(&edited).await?;
+        Ok(edited)
+    }
+
+    #[instrument(skip_all, fields(note_id = %note_id))]
+    pub async fn delete_note(&self, note_id: Uuid) -> Result<()> {
+        let note = self.find_note(note_id).await?;
+        let _project = self.project_locks.lock(note.project_id).await;
+        let db = self.db()?;
+        db.delete_note(&note_id.to_string()).await
+    }
+
+    /// Notes of a project, most recently edited first.
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn get_notes(&self, project_id: Uuid) -> Result<Vec<Note>> {
+        let id = project_id.to_string();
+        let db = self.db()?;
+        if db.load_project(&id).await?.is_none() {
+            return Err(AppError::NotFound(format!("Project {id} not found")));
+        }
+        db.list_notes(&id).await
+    }
+
+    /// Earlier versions of a note, newest first.
+    #[instrument(skip_all, fields(note_id = %note_id))]
+    pub async fn get_note_history(&self, note_id: Uuid) -> Result<Vec<NoteRevision>> {
+        self.find_note(note_id).await?;
+        let db = self.db()?;
+        db.list_note_revisions(&note_id.to_string()).await
+    }
+
+    /// Makes an earlier version the current one. The version it replaces
+    /// goes into the history like any other edit, so a restore can itself
+    /// be undone.
+    #[instrument(skip_all, fields(note_id = %note_id, revision_id = %revision_id))]
+    pub async fn restore_note_revision(&self, note_id: Uuid, revision_id: Uuid) -> Result<Note> {
+        let db = self.db()?;
+        let revision = db
+            .load_note_revision(&revision_id.to_string())
+            .await?
+            .filter(|revision| revision.note_id == note_id)
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Revision {revision_id} of note {note_id} not found"))
+            })?;
+
+        self.update_note(note_id, revision.title, revision.body_markdown)
             .await
-            .map_err(|e: AppError| e.to_string())
+    }
+
+    async fn find_note(&self, note_id: Uuid) -> Result<Note> {
+        let db = self.db()?;
+        db.load_note(&note_id.to_string())
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Note {note_id} not found")))
+    }
+
+    /// Stops watching the project's files and releases its lock.
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn close_project(&self, project_id: Uuid) -> Result<()> {
+        self.close_watch(project_id);
+        let db = self.db()?;
+        db.release_project_lock(&project_id.to_string()).await
+    }
+
+    /// Releases every project lock this instance holds; call on exit.
+    #[instrument(skip_all)]
+    pub async fn release_project_locks(&self) {
+        // Storage that never opened holds no locks
+        let Ok(db) = self.db() else {
+            return;
+        };
+        if let Err(e) = db.release_all_project_locks().await {
+            tracing::warn!("Failed to release project locks: {e}");
+        }
+    }
+
+    fn close_watch(&self, project_id: Uuid) {
+        if let Some(watcher) = &self.watcher {
+            watcher.unwatch_project(project_id);
+        }
+    }
+
+    /// Re-hashes an attached file that changed on disk and emits
+    /// `project:file-modified` if its contents changed or it was deleted.
+    #[instrument(skip_all, fields(project_id = %project_id, file_id = %file_id))]
+    pub async fn refresh_project_file(
+        &self,
+        project_id: Uuid,
+        file_id: Uuid,
+    ) -> Result<Option<ProjectFile>> {
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        let updated = db
+            .refresh_file_metadata(&project_id.to_string(), &file_id.to_string())
+            .await?;
+        if let Some(file) = &updated {
+            self.emit(ProjectEvent::FileModified(ProjectFileModified::from(file)));
+        }
+        Ok(updated)
+    }
+
+    /// Handles the watcher's change notifications until it is dropped. A
+    /// file that cannot be refreshed is logged and skipped.
+    pub async fn watch_file_changes(&self, mut changes: UnboundedReceiver<WatchedFile>) {
+        while let Some(WatchedFile { project_id, file_id }) = changes.recv().await {
+            if let Err(e) = self.refresh_project_file(project_id, file_id).await {
+                tracing::warn!("Failed to refresh file {file_id} of project {project_id}: {e}");
+            }
+        }
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn list_backups(&self, project_id: Uuid) -> Result<Vec<ProjectBackup>> {
+        let db = self.db()?;
+        db.list_backups(&project_id.to_string()).await
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id, backup_id = %backup_id))]
+    pub async fn restore_backup(&self, project_id: Uuid, backup_id: &str) -> Result<Project> {
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        let project = db.restore_backup(&project_id.to_string(), backup_id).await?;
+        self.emit(ProjectEvent::Updated(project.clone()));
+        Ok(project)
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn export_project(&self, project_id: Uuid, dest: &Path) -> Result<()> {
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        db.export_project(&project_id.to_string(), dest).await
+    }
+
+    /// Exports the project to a `.zip` bundle at `dest_path`, e.g. to hand
+    /// it to a colleague or file it with QA records. The bundle is the
+    /// same zip as [`Self::export_project`] writes, so importing it brings
+    /// back the project's files, notes and analysis runs.
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn export_project_bundle(&self, project_id: Uuid, dest_path: &Path) -> Result<()> {
+        self.export_project(project_id, dest_path).await
+    }
+
+    #[instrument(skip_all)]
+    pub async fn import_project(&self, archive: &Path) -> Result<Project> {
+        let _names = self.names.lock().await;
+        let db = self.db()?;
+        let project = db.import_project(archive).await?;
+        self.emit(ProjectEvent::Created(project.clone()));
+        Ok(project)
+    }
+
+    /// Copies a project, e.g. to start the next design phase from this one.
+    /// Without `new_name` the copy is named "<name> - Copy", suffixed with
+    /// " (2)", " (3)", ... when that is taken.
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn duplicate_project(
+        &self,
+        project_id: Uuid,
+        new_name: Option<String>,
+        include_results: bool,
+    ) -> Result<Project> {
+        let id = project_id.to_string();
+        let _names = self.names.lock().await;
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        let source = db
+            .load_project(&id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project {id} not found")))?;
+
+        let name = match new_name {
+            Some(name) => {
+                let name = name.trim().to_string();
+                if db.find_project_by_name(&name).await?.is_some() {
+                    return Err(AppError::Validation("project name already exists".to_string()));
+                }
+                name
+            }
+            None => {
+                let existing: Vec<String> = db
+                    .list_projects()
+                    .await?
+                    .into_iter()
+                    .map(|p| p.name)
+                    .collect();
+                duplicates::next_available_name(&format!("{} - Copy", source.name), &existing)
+            }
+        };
+        // Also catches a suffix that pushes a long name over the limit
+        Project::new(name.clone(), source.description).validate()?;
+
+        let copy = db.duplicate_project(&id, &name, include_results).await?;
+        self.emit(ProjectEvent::Created(copy.clone()));
+        Ok(copy)
+    }
+
+    #[instrument(skip_all)]
+    pub async fn create_tag(&self, name: String, color: String) -> Result<Tag> {
+        let tag = Tag::new(name.trim().to_string(), color.trim().to_string());
+        tag.validate()?;
+
+        let db = self.db()?;
+        db.create_tag(&tag).await?;
+
+        Ok(tag)
+    }
+
+    #[instrument(skip_all)]
+    pub async fn list_tags(&self) -> Result<Vec<Tag>> {
+        let db = self.db()?;
+        db.list_tags().await
+    }
+
+    #[instrument(skip_all, fields(tag_id = %tag_id))]
+    pub async fn delete_tag(&self, tag_id: Uuid) -> Result<()> {
+        let db = self.db()?;
+        db.delete_tag(&tag_id.to_string()).await
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn set_project_tags(&self, project_id: Uuid, tag_ids: Vec<Uuid>) -> Result<Vec<Tag>> {
+        let tag_ids: Vec<String> = tag_ids.iter().map(Uuid::to_string).collect();
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        db.set_project_tags(&project_id.to_string(), &tag_ids).await
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn get_project_tags(&self, project_id: Uuid) -> Result<Vec<Tag>> {
+        let db = self.db()?;
+        db.list_project_tags(&project_id.to_string()).await
+    }
+
+    #[instrument(skip_all, fields(tag_id = %tag_id))]
+    pub async fn get_projects_by_tag(&self, tag_id: Uuid) -> Result<Vec<Project>> {
+        let db = self.db()?;
+        db.list_projects_by_tag(&tag_id.to_string()).await
+    }
+
+    #[instrument(skip_all)]
+    pub async fn search_projects(&self, query: &str) -> Result<Vec<SearchHit>> {
+        let db = self.db()?;
+        db.search_projects(query).await
+    }
+
+    #[instrument(skip_all)]
+    pub async fn reindex_projects(&self) -> Result<u64> {
+        let db = self.db()?;
+        db.reindex_projects().await
+    }
+
+    /// Built-in templates first, then the user's by name.
+    #[instrument(skip_all)]
+    pub async fn list_templates(&self) -> Result<Vec<ProjectTemplate>> {
+        let db = self.db()?;
+        db.list_templates().await
+    }
+
+    /// Saves the project's tags, load cases and design code as a template.
+    /// Attached files and results are specific to the project and left out.
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn create_template_from_project(
+        &self,
+        project_id: Uuid,
+        name: String,
+    ) -> Result<ProjectTemplate> {
+        let id = project_id.to_string();
+        let db = self.db()?;
+        let project = db
+            .load_project(&id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project {id} not found")))?;
+
+        let tags = db.list_project_tags(&id).await?;
+        let cases = db.load_load_cases(&id).await?;
+        let code = DesignCode::of_generated(&db.load_load_combinations(&id).await?);
+
+        let template =
+            ProjectTemplate::from_project(&project, name.trim().to_string(), &tags, cases, code);
+        template.validate()?;
+        db.save_template(&template).await?;
+
+        Ok(template)
+    }
+
+    /// Creates a project seeded with the template's tags and load cases.
+    #[instrument(skip_all, fields(template_id = %template_id))]
+    pub async fn create_project_from_template(
+        &self,
+        template_id: Uuid,
+        name: String,
+    ) -> Result<Project> {
+        let db = self.db()?;
+        let template = db
+            .find_template(template_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Template {template_id} not found")))?;
+
+        let project = Project::new(name.trim().to_string(), template.description.clone());
+        project.validate()?;
+
+        let _names = self.names.lock().await;
+        if db.find_project_by_name(&project.name).await?.is_some() {
+            return Err(AppError::Validation("project name already exists".to_string()));
+        }
+        db.create_project_from_template(&project, &template).await?;
+
+        self.emit(ProjectEvent::Created(project.clone()));
+        Ok(project)
+    }
+
+    /// Deletes a user template; built-in templates cannot be deleted.
+    #[instrument(skip_all, fields(template_id = %template_id))]
+    pub async fn delete_template(&self, template_id: Uuid) -> Result<()> {
+        let db = self.db()?;
+        db.delete_template(template_id).await
+    }
+
+    #[instrument(skip_all)]
+    pub async fn find_duplicate_projects(&self) -> Result<Vec<DuplicateProjectSet>> {
+        let projects = self.get_projects(None).await?;
+        let db = self.db()?;
+        let mut files = Vec::new();
+        for project in &projects {
+            files.extend(db.list_project_files(&project.id.to_string()).await?);
+        }
+        Ok(duplicates::find_duplicate_projects(&projects, &files))
+    }
+
+    /// Merges project `absorb_id` into `keep_id`, e.g. one of a set found by
+    /// [`Self::find_duplicate_projects`]. The absorbed project ends up in
+    /// the trash.
+    #[instrument(skip_all, fields(keep_id = %keep_id, absorb_id = %absorb_id))]
+    pub async fn merge_projects(
+        &self,
+        keep_id: Uuid,
+        absorb_id: Uuid,
+        options: MergeOptions,
+    ) -> Result<Project> {
+        // Always in id order, so two merges of the same pair can't deadlock
+        let (first, second) = (keep_id.min(absorb_id), keep_id.max(absorb_id));
+        let _first = self.project_locks.lock(first).await;
+        let _second = if second != first {
+            Some(self.project_locks.lock(second).await)
+        } else {
+            None
+        };
+        let db = self.db()?;
+        let kept = db
+            .merge_projects(&keep_id.to_string(), &absorb_id.to_string(), &options)
+            .await?;
+        if let Some(absorbed) = db.load_project(&absorb_id.to_string()).await? {
+            self.emit(ProjectEvent::Updated(absorbed));
+        }
+        self.emit(ProjectEvent::Updated(kept.clone()));
+        Ok(kept)
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn estimate_lateral(
+        &self,
+        project_id: Uuid,
+        input: LateralEstimateInput,
+    ) -> Result<LateralEstimate> {
+        let estimate = estimate::estimate_lateral(&input)?;
+
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        db.save_lateral_estimate(&project_id.to_string(), &estimate).await?;
+
+        Ok(estimate)
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn get_lateral_estimate(&self, project_id: Uuid) -> Result<Option<LateralEstimate>> {
+        let db = self.db()?;
+        db.load_lateral_estimate(&project_id.to_string()).await
+    }
+
+    /// Computes the ELF base shear and story forces and saves them with the
+    /// project, replacing the previous ones.
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn save_seismic_base_shear(
+        &self,
+        project_id: Uuid,
+        params: SeismicParams,
+        stories: Vec<SeismicStory>,
+    ) -> Result<SeismicBaseShear> {
+        let base_shear = seismic::seismic_base_shear(&params, &stories)?;
+
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        db.save_seismic_base_shear(&project_id.to_string(), &base_shear).await?;
+
+        Ok(base_shear)
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn get_seismic_base_shear(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Option<SeismicBaseShear>> {
+        let db = self.db()?;
+        db.load_seismic_base_shear(&project_id.to_string()).await
+    }
+
+    /// Computes MWFRS wall pressures and saves them with the project,
+    /// replacing the previous ones.
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn save_wind_pressures(
+        &self,
+        project_id: Uuid,
+        input: WindInput,
+    ) -> Result<WindPressures> {
+        let pressures = wind::compute_wind_pressures(&input)?;
+
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        db.save_wind_pressures(&project_id.to_string(), &pressures).await?;
+
+        Ok(pressures)
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn get_wind_pressures(&self, project_id: Uuid) -> Result<Option<WindPressures>> {
+        let db = self.db()?;
+        db.load_wind_pressures(&project_id.to_string()).await
+    }
+
+    /// Generates code combinations for the cases and replaces the project's
+    /// saved cases and combinations with them.
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn generate_load_combinations(
+        &self,
+        project_id: Uuid,
+        cases: Vec<LoadCase>,
+        code: DesignCode,
+    ) -> Result<Vec<LoadCombination>> {
+        let generated = combinations::generate_combinations(cases.clone(), code)?;
+
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        db.save_load_cases(&project_id.to_string(), &cases).await?;
+        db.save_load_combinations(&project_id.to_string(), &generated).await?;
+
+        Ok(generated)
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn get_load_combinations(&self, project_id: Uuid) -> Result<Vec<LoadCombination>> {
+        let db = self.db()?;
+        db.load_load_combinations(&project_id.to_string()).await
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn save_analysis_results(
+        &self,
+        project_id: Uuid,
+        run_label: String,
+        results: AnalysisResults,
+    ) -> Result<AnalysisRun> {
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        db.save_analysis_results(&project_id.to_string(), &run_label, &results)
+            .await
+    }
+
+    /// Imports one ETABS table export into the run labelled `run_label`,
+    /// replacing that table if the run was imported before.
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn import_results_csv(
+        &self,
+        project_id: Uuid,
+        run_label: String,
+        table: ResultTable,
+        path: &Path,
+    ) -> Result<AnalysisRun> {
+        let parsed = results::import_results_csv(table, path)?;
+
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        db.save_result_table(&project_id.to_string(), &run_label, table, &parsed)
+            .await
+    }
+
+    /// Imports table text copied from the ETABS tables window into the run
+    /// labelled `run_label`, replacing that table if the run had it. The
+    /// table is detected from the header unless `table_hint` names it; rows
+    /// that can't be read are left out and listed in the report.
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn paste_import_table(
+        &self,
+        project_id: Uuid,
+        run_label: String,
+        raw_text: &str,
+        table_hint: Option<ResultTable>,
+    ) -> Result<PasteImportReport> {
+        let pasted = results::parse_pasted_table(raw_text, table_hint)?;
+
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        let run = db
+            .save_result_table(&project_id.to_string(), &run_label, pasted.table, &pasted.results)
+            .await?;
+        Ok(PasteImportReport {
+            run,
+            table: pasted.table,
+            imported_rows: pasted.row_count(),
+            failed_rows: pasted.failed_rows,
+        })
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn get_analysis_runs(&self, project_id: Uuid) -> Result<Vec<AnalysisRun>> {
+        let db = self.db()?;
+        db.list_analysis_runs(&project_id.to_string()).await
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id, run_id = %run_id))]
+    pub async fn get_story_drifts(&self, project_id: Uuid, run_id: Uuid) -> Result<Vec<StoryDrift>> {
+        let db = self.db()?;
+        db.list_story_drifts(&project_id.to_string(), &run_id.to_string())
+            .await
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id, run_id = %run_id))]
+    pub async fn get_modal_periods(
+        &self,
+        project_id: Uuid,
+        run_id: Uuid,
+    ) -> Result<Vec<ModalPeriod>> {
+        let db = self.db()?;
+        db.list_modal_periods(&project_id.to_string(), &run_id.to_string())
+            .await
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id, run_id = %run_id))]
+    pub async fn get_base_reactions(
+        &self,
+        project_id: Uuid,
+        run_id: Uuid,
+    ) -> Result<Vec<BaseReaction>> {
+        let db = self.db()?;
+        db.list_base_reactions(&project_id.to_string(), &run_id.to_string())
+            .await
+    }
+
+    /// Checks a run's story drifts against `limit_ratio` · hsx, taking story
+    /// heights from the project's imported ETABS model. With `write_csv` the
+    /// report is also saved as `reports/drift-check-<run label>.csv`.
+    #[instrument(skip_all, fields(project_id = %project_id, run_id = %run_id))]
+    pub async fn check_drift_limits(
+        &self,
+        project_id: Uuid,
+        run_id: Uuid,
+        limit_ratio: f64,
+        write_csv: bool,
+    ) -> Result<Vec<DriftCheckReport>> {
+        // Only the CSV report touches the project folder
+        let _project = if write_csv {
+            Some(self.project_locks.lock(project_id).await)
+        } else {
+            None
+        };
+        let project_id = project_id.to_string();
+        let run_id = run_id.to_string();
+        let db = self.db()?;
+
+        let run = db.find_analysis_run(&project_id, &run_id).await?;
+        let drifts = db.list_story_drifts(&project_id, &run_id).await?;
+        let model = db.load_etabs_model(&project_id).await?.ok_or_else(|| {
+            AppError::Validation(
+                "Story heights are unknown; import the ETABS model (.e2k) first".to_string(),
+            )
+        })?;
+
+        let report = drift::check_drift_limits(&drifts, &model.stories, limit_ratio)?;
+
+        if write_csv {
+            let file_name = format!("drift-check-{}.csv", report_file_stem(&run.label));
+            db.write_project_report(&project_id, &file_name, &drift::report_csv(&report))
+                .await?;
+        }
+
+        Ok(report)
+    }
+
+    /// Writes the project summary report (metadata, attached files, load
+    /// combinations, a drift check of the latest run at 0.020·hsx and
+    /// notes) to `dest` and attaches it to the project. Returns the
+    /// report's path. A cancelled `task` stops before the report is written.
+    #[instrument(skip_all, fields(project_id = %project_id, task_id = %task.id()))]
+    pub async fn generate_project_report(
+        &self,
+        project_id: Uuid,
+        format: ReportFormat,
+        dest: &Path,
+        task: &TaskContext,
+    ) -> Result<PathBuf> {
+        let _project = self.project_locks.lock(project_id).await;
+        let id = project_id.to_string();
+        let db = self.db()?;
+        let project = db
+            .load_project(&id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project {id} not found")))?;
+
+        let summary = ProjectSummary {
+            files: db.list_project_files(&id).await?,
+            combinations: db.load_load_combinations(&id).await?,
+            drift: self.latest_drift_check(&id).await?,
+            notes: db.list_notes(&id).await?,
+            timezone: self.settings.lock().await.timezone,
+            project,
+        };
+        task.set_progress(0.4);
+        task.checkpoint()?;
+
+        let content = match format {
+            ReportFormat::Html => report::render_html(&summary).into_bytes(),
+            ReportFormat::Pdf => report::render_pdf(&summary),
+        };
+        task.set_progress(0.8);
+        task.checkpoint()?;
+
+        ext_db::storage::write_atomic(dest, &content)
+            .await
+            .map_err(|e| ext_db::paths::fs_error("write report", dest, e))?;
+
+        let file = db.attach_generated_file(&id, dest).await?;
+        if let Some(watcher) = &self.watcher {
+            watcher.watch_file(&file);
+        }
+        self.emit(ProjectEvent::files_changed(project_id));
+
+        Ok(dest.to_path_buf())
+    }
+
+    /// Writes one table to `dest`. Result tables come from `run_id`, or the
+    /// most recently imported run; a project without runs exports just the
+    /// headers. CSV numbers are in the unit system from settings.
+    #[instrument(skip_all, fields(project_id = %project_id, run_id = ?run_id))]
+    pub async fn export_table(
+        &self,
+        project_id: Uuid,
+        table: ExportTable,
+        format: ExportFormat,
+        run_id: Option<Uuid>,
+        dest: &Path,
+    ) -> Result<PathBuf> {
+        let id = project_id.to_string();
+        let db = self.db()?;
+        db.load_project(&id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project {id} not found")))?;
+
+        let run_id = match run_id {
+            Some(run_id) => Some(run_id.to_string()),
+            None if table.needs_run() => db
+                .list_analysis_runs(&id)
+                .await?
+                .first()
+                .map(|run| run.id.to_string()),
+            None => None,
+        };
+        let rows = match (table, run_id) {
+            (ExportTable::LoadCombinations, _) => {
+                TableRows::LoadCombinations(db.load_load_combinations(&id).await?)
+            }
+            (ExportTable::StoryDrifts, Some(run_id)) => {
+                TableRows::StoryDrifts(db.list_story_drifts(&id, &run_id).await?)
+            }
+            (ExportTable::ModalPeriods, Some(run_id)) => {
+                TableRows::ModalPeriods(db.list_modal_periods(&id, &run_id).await?)
+            }
+            (ExportTable::BaseReactions, Some(run_id)) => {
+                TableRows::BaseReactions(db.list_base_reactions(&id, &run_id).await?)
+            }
+            (ExportTable::StoryDrifts, None) => TableRows::StoryDrifts(Vec::new()),
+            (ExportTable::ModalPeriods, None) => TableRows::ModalPeriods(Vec::new()),
+            (ExportTable::BaseReactions, None) => TableRows::BaseReactions(Vec::new()),
+        };
+
+        let units = self.settings.lock().await.units;
+        let content = export::render(&rows, format, units)?;
+        ext_db::storage::write_atomic(dest, &content)
+            .await
+            .map_err(|e| ext_db::paths::fs_error("write export", dest, e))?;
+
+        Ok(dest.to_path_buf())
+    }
+
+    /// Drift check of the most recently imported run, or `None` when there
+    /// is no run or model, or the check can't be made.
+    async fn latest_drift_check(&self, project_id: &str) -> Result<Option<DriftSummary>> {
+        let db = self.db()?;
+        let Some(run) = db.list_analysis_runs(project_id).await?.into_iter().next() else {
+            return Ok(None);
+        };
+        let Some(model) = db.load_etabs_model(project_id).await? else {
+            return Ok(None);
+        };
+
+        let drifts = db.list_story_drifts(project_id, &run.id.to_string()).await?;
+        Ok(
+            drift::check_drift_limits(&drifts, &model.stories, DEFAULT_DRIFT_LIMIT_RATIO)
+                .ok()
+                .map(|rows| DriftSummary {
+                    run_label: run.label,
+                    limit_ratio: DEFAULT_DRIFT_LIMIT_RATIO,
+                    rows,
+                }),
+        )
+    }
+
+    /// Parses an `.e2k` export and attaches the model to the project. A
+    /// cancelled `task` stops before the model is saved.
+    #[instrument(skip_all, fields(project_id = %project_id, task_id = %task.id()))]
+    pub async fn import_etabs_model(
+        &self,
+        project_id: Uuid,
+        path: &Path,
+        task: &TaskContext,
+    ) -> Result<EtabsModel> {
+        task.checkpoint()?;
+        // Large models take a while to parse; keep it off the async workers
+        let path = path.to_path_buf();
+        let model = run_blocking(move || e2k::parse_e2k(&path)).await?;
+        task.set_progress(0.8);
+        task.checkpoint()?;
+
+        let _project = self.project_locks.lock(project_id).await;
+        let db = self.db()?;
+        db.save_etabs_model(&project_id.to_string(), &model).await?;
+
+        Ok(model)
+    }
+
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn get_etabs_model(&self, project_id: Uuid) -> Result<Option<EtabsModel>> {
+        let db = self.db()?;
+        db.load_etabs_model(&project_id.to_string()).await
+    }
+
+    /// Compares two attached `.e2k` files, `file_id_a` being the earlier
+    /// revision. With `write_json` the diff is also saved as
+    /// `reports/model-diff-<a>-vs-<b>.json`.
+    #[instrument(
+        skip_all,
+        fields(project_id = %project_id, file_id_a = %file_id_a, file_id_b = %file_id_b)
+    )]
+    pub async fn compare_etabs_files(
+        &self,
+        project_id: Uuid,
+        file_id_a: Uuid,
+        file_id_b: Uuid,
+        write_json: bool,
+    ) -> Result<ModelDiff> {
+        // Only the JSON report touches the project folder
+        let _project = if write_json {
+            Some(self.project_locks.lock(project_id).await)
+        } else {
+            None
+        };
+        let id = project_id.to_string();
+        let db = self.db()?;
+
+        let (file_a, path_a) = db.find_project_file(&id, &file_id_a.to_string()).await?;
+        let (file_b, path_b) = db.find_project_file(&id, &file_id_b.to_string()).await?;
+        let diff = model_diff::diff_models(
+            &parse_attached_e2k(&file_a, &path_a)?,
+            &parse_attached_e2k(&file_b, &path_b)?,
+        );
+
+        if write_json {
+            let stem = |file: &ProjectFile| {
+                let name = Path::new(&file.original_name).file_stem().unwrap_or_default();
+                report_file_stem(&name.to_string_lossy())
+            };
+            let file_name = format!("model-diff-{}-vs-{}.json", stem(&file_a), stem(&file_b));
+            let content = serde_json::to_string_pretty(&diff)
+                .map_err(|e| AppError::Internal(format!("Failed to serialize model diff: {e}")))?;
+            db.write_project_report(&id, &file_name, &content).await?;
+        }
+
+        Ok(diff)
+    }
+
+    /// Reports database and projects-folder health without changing anything.
+    #[instrument(skip_all)]
+    pub async fn get_diagnostics(&self) -> Result<Diagnostics> {
+        let db = self.db()?;
+        let mut diagnostics = db.diagnostics().await?;
+        diagnostics.log_dir = self
+            .log_dir
+            .as_ref()
+            .map(|dir| dir.to_string_lossy().to_string());
+        Ok(diagnostics)
+    }
+
+    /// Fixes the orphans found by [`AppState::get_diagnostics`]; see
+    /// [`Database::repair_storage`].
+    #[instrument(skip_all)]
+    pub async fn repair_storage(&self, orphaned_rows: OrphanedRowAction) -> Result<RepairReport> {
+        let _names = self.names.lock().await;
+        let db = self.db()?;
+        let report = db.repair_storage(orphaned_rows).await?;
+
+        for id in report.registered.iter().chain(&report.trashed) {
+            if let Some(project) = db.load_project(&id.to_string()).await? {
+                let event = if report.registered.contains(id) {
+                    ProjectEvent::Created(project)
+                } else {
+                    ProjectEvent::Updated(project)
+                };
+                self.emit(event);
+            }
+        }
+        for id in &report.removed {
+            self.close_watch(*id);
+            self.emit(ProjectEvent::deleted(*id));
+        }
+
+        Ok(report)
+    }
+
+    /// The last `limit` lines of the backend log, oldest first; at most
+    /// [`logs::MAX_RECENT_LOG_LINES`].
+    pub async fn get_recent_backend_logs(&self, limit: usize) -> Result<Vec<String>> {
+        match &self.backend_log_dir {
+            Some(dir) => logs::recent_lines(dir, limit).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    #[instrument(skip_all)]
+    pub async fn get_storage_status(&self) -> Result<StorageStatus> {
+        let db = self.db()?;
+        Ok(db.storage_status().clone())
+    }
+
+    /// Attaches to the running ETABS instance, replacing any earlier
+    /// connection.
+    #[instrument(skip_all)]
+    pub async fn etabs_connect(&self) -> Result<EtabsStatus> {
+        let mut etabs = self.etabs.lock().await;
+
+        let (connection, status) = run_blocking(|| {
+            let connection = EtabsConnection::attach_to_running_instance(SidecarClient::locate()?)?;
+            let status = connection.status()?;
+            Ok((connection, status))
+        })
+        .await?;
+
+        *etabs = Some(connection);
+        Ok(status)
+    }
+
+    #[instrument(skip_all)]
+    pub async fn etabs_get_model_info(&self) -> Result<EtabsModelInfo> {
+        let connection = self.etabs_connection().await?;
+
+        run_blocking(move || connection.get_model_info()).await
+    }
+
+    /// Pulls the story drifts of `load_cases`, all cases when it's empty,
+    /// from the model open in the connected ETABS. They are saved in the
+    /// project as the run named after the model file, so they can be
+    /// plotted again without ETABS; pulling again replaces that run's drifts.
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn etabs_get_story_drifts(
+        &self,
+        project_id: Uuid,
+        load_cases: Vec<String>,
+    ) -> Result<Vec<StoryDrift>> {
+        let connection = self.etabs_connection().await?;
+        let _project = self.project_locks.lock(project_id).await;
+        let project = self.get_project(project_id).await?;
+
+        let (model, drifts) = run_blocking(move || connection.get_story_drifts()).await?;
+        let drifts = results::select_load_cases(drifts, &load_cases)?;
+
+        let results = AnalysisResults {
+            story_drifts: drifts.clone(),
+            ..AnalysisResults::default()
+        };
+        let db = self.db()?;
+        db.save_result_table(
+            &project.id.to_string(),
+            &model_run_label(&model),
+            ResultTable::StoryDrifts,
+            &results,
+        )
+        .await?;
+        Ok(drifts)
+    }
+
+    /// Base reactions from the connected ETABS model, saved in the project
+    /// as the run named after the model file. They are also written to
+    /// `reports/base-reactions-<run label>.csv` in the display units, ready
+    /// for foundation design. No `load_cases` means every case and
+    /// combination.
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn etabs_get_base_reactions(
+        &self,
+        project_id: Uuid,
+        load_cases: Vec<String>,
+    ) -> Result<Vec<BaseReaction>> {
+        let connection = self.etabs_connection().await?;
+        let _project = self.project_locks.lock(project_id).await;
+        let project = self.get_project(project_id).await?;
+
+        let (model, reactions) = run_blocking(move || connection.get_base_reactions()).await?;
+        let reactions = results::select_load_cases(reactions, &load_cases)?;
+
+        let label = model_run_label(&model);
+        let results = AnalysisResults {
+            base_reactions: reactions.clone(),
+            ..AnalysisResults::default()
+        };
+        let project_id = project.id.to_string();
+        let db = self.db()?;
+        db.save_result_table(&project_id, &label, ResultTable::BaseReactions, &results)
+            .await?;
+
+        let units = self.settings.lock().await.units;
+        let csv = export::render_csv(&TableRows::BaseReactions(reactions.clone()), units);
+        let file_name = format!("base-reactions-{}.csv", report_file_stem(&label));
+        db.write_project_report(&project_id, &file_name, &csv)
+            .await?;
+        Ok(reactions)
+    }
+
+    /// Writes the project's generated load combinations into the model open
+    /// in the connected ETABS, replacing combinations of the same name.
+    /// ETABS is left to the user to review and save.
+    #[instrument(skip_all, fields(project_id = %project_id))]
+    pub async fn etabs_write_load_combinations(
+        &self,
+        project_id: Uuid,
+    ) -> Result<LoadCombinationsReport> {
+        let project = self.get_project(project_id).await?;
+        let db = self.db()?;
+        let combinations = db.load_load_combinations(&project.id.to_string()).await?;
+        if combinations.is_empty() {
+            return Err(AppError::Validation(format!(
+                "Project '{}' has no load combinations; generate them first",
+                project.name
+            )));
+        }
+
+        let connection = self.etabs_connection().await?;
+        run_blocking(move || connection.add_load_combinations(&combinations)).await
+    }
+
+    /// Extracts the frame member forces of the connected ETABS model into
+    /// the project, as the run named after the model file. The table is
+    /// read back a page at a time with [`Self::get_frame_forces`]. A
+    /// cancelled `task` stops before anything is saved.
+    #[instrument(skip_all, fields(project_id = %project_id, task_id = %task.id()))]
+    pub async fn etabs_extract_frame_forces(
+        &self,
+        project_id: Uuid,
+        task: &TaskContext,
+    ) -> Result<FrameForceImport> {
+        let connection = self.etabs_connection().await?;
+        let project = self.get_project(project_id).await?;
+
+        let (model, forces) = run_blocking(move || connection.get_frame_forces()).await?;
+        task.checkpoint()?;
+
+        let _project = self.project_locks.lock(project.id).await;
+        let db = self.db()?;
+        let run = db
+            .save_frame_forces(&project.id.to_string(), &model_run_label(&model), &forces)
+            .await?;
+        Ok(FrameForceImport {
+            run,
+            rows: forces.len(),
+        })
+    }
+
+    /// Returns page `page` (from 0) of a run's frame forces, optionally
+    /// only those of one story, load case or combination, or element.
+    #[instrument(skip_all, fields(project_id = %project_id, run_id = %run_id))]
+    pub async fn get_frame_forces(
+        &self,
+        project_id: Uuid,
+        run_id: Uuid,
+        filter: FrameForceFilter,
+        page: usize,
+        page_size: usize,
+    ) -> Result<FrameForcePage> {
+        frame_forces::validate_page_size(page_size)?;
+
+        let db = self.db()?;
+        db.query_frame_forces(
+            &project_id.to_string(),
+            &run_id.to_string(),
+            &filter,
+            page as u64,
+            page_size as u64,
+        )
+        .await
+    }
+
+    /// Modal periods, frequencies and mass participation of `load_case`
+    /// (optional when there is a single modal case), with the ASCE 7 90%
+    /// participation check. Served from the latest run with modal periods;
+    /// with `refresh`, or when there is none, they are pulled from the
+    /// connected ETABS and saved under the run named after the model file.
+    #[instrument(skip_all, fields(project_id = %project_id, refresh))]
+    pub async fn get_modal_results(
+        &self,
+        project_id: Uuid,
+        load_case: Option<String>,
+        refresh: bool,
+    ) -> Result<ModalResults> {
+        let project = self.get_project(project_id).await?;
+        let project_id = project.id.to_string();
+        let db = self.db()?;
+
+        if !refresh {
+            for run in db.list_analysis_runs(&project_id).await? {
+                let periods = db
+                    .list_modal_periods(&project_id, &run.id.to_string())
+                    .await?;
+                if !periods.is_empty() {
+                    return modal::modal_results(&periods, load_case.as_deref());
+                }
+            }
+        }
+
+        let connection = self.etabs_connection().await?;
+        let (model, periods) = run_blocking(move || connection.get_modal_periods()).await?;
+        let modal = modal::modal_results(&periods, load_case.as_deref())?;
+
+        let results = AnalysisResults {
+            modal_periods: periods,
+            ..AnalysisResults::default()
+        };
+        let _project = self.project_locks.lock(project.id).await;
+        db.save_result_table(
+            &project_id,
+            &model_run_label(&model),
+            ResultTable::ModalPeriods,
+            &results,
+        )
+        .await?;
+        Ok(modal)
+    }
+
+    /// Opens an `.edb` model, in a hidden ETABS instance that also runs the
+    /// analysis, or in the user's running ETABS. See
+    /// [`SidecarClient::open_model`].
+    #[instrument(skip_all, fields(path = %path.display(), hidden))]
+    pub async fn etabs_open_model(&self, path: &Path, hidden: bool) -> Result<OpenModelReport> {
+        let path = path.to_path_buf();
+        run_blocking(move || SidecarClient::locate()?.open_model(&path, hidden)).await
+    }
+
+    #[instrument(skip_all)]
+    pub async fn etabs_disconnect(&self) {
+        self.etabs.lock().await.take();
+    }
+
+    async fn etabs_connection(&self) -> Result<EtabsConnection> {
+        self.etabs
+            .lock()
+            .await
+            .clone()
+            .ok_or_else(|| AppError::Etabs("Not connected to ETABS. Connect first.".to_string()))
+    }
+}
+
+/// Applies the settings the database reads while running.
+fn apply_database_settings(db: &Database, settings: &Settings) {
+    db.set_backup_retention(settings.backup_retention);
+    db.set_lock_timeout(chrono::Duration::hours(settings.lock_timeout_hours.into()));
+}
+
+/// Makes a run label safe to use in a file name.
+fn report_file_stem(label: &str) -> String {
+    label
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
+/// Label of the run that results pulled from ETABS are saved under: the
+/// model's file name without its extension.
+fn model_run_label(model: &str) -> String {
+    Path::new(model).file_stem().map_or_else(
+        || model.to_string(),
+        |stem| stem.to_string_lossy().to_string(),
+    )
+}
+
+/// Parses an attached file, which has to be an `.e2k` export.
+fn parse_attached_e2k(file: &ProjectFile, path: &Path) -> Result<EtabsModel> {
+    let is_e2k = Path::new(&file.original_name)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("e2k"));
+    if !is_e2k {
+        return Err(AppError::Validation(format!(
+            "'{}' is not an ETABS text export (.e2k)",
+            file.original_name
+        )));
+    }
+    if file.missing {
+        return Err(AppError::NotFound(format!(
+            "'{}' was deleted from the project folder",
+            file.original_name
+        )));
+    }
+    e2k::parse_e2k(path)
+}
+
+/// ETABS models in `dir`, and in its subfolders when `recursive`, sorted
+/// by path. Subfolders that can't be read are added to `skipped`.
+async fn scan_etabs_files(
+    dir: &Path,
+    recursive: bool,
+    skipped: &mut Vec<(String, String)>,
+) -> Result<Vec<PathBuf>> {
+    if !tokio::fs::metadata(dir).await.is_ok_and(|meta| meta.is_dir()) {
+        return Err(AppError::Validation(format!("'{}' is not a folder", dir.display())));
+    }
+
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(folder) = pending.pop() {
+        let mut entries = match tokio::fs::read_dir(&folder).await {
+            Ok(entries) => entries,
+            Err(e) if folder == dir => return Err(ext_db::paths::fs_error("read folder", dir, e)),
+            Err(e) => {
+                skipped.push((folder.display().to_string(), e.to_string()));
+                continue;
+            }
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            // Symlinked folders are not followed, so links can't loop
+            match entry.file_type().await {
+                Ok(kind) if kind.is_dir() && recursive => pending.push(path),
+                Ok(kind) if kind.is_file() && bulk_import::is_etabs_file(&path) => {
+                    files.push(path)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Runs sidecar calls off the async runtime; each one waits on a process.
+async fn run_blocking<T, F>(f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| AppError::Internal(format!("ETABS task failed: {e}")))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn state(temp: &tempfile::TempDir) -> AppState {
+        let db = Database::new("sqlite::memory:", temp.path().to_str().unwrap())
+            .await
+            .expect("Failed to open database");
+        AppState::new(db)
+    }
+
+    /// Records emitted events as (name, project id), optionally failing
+    #[derive(Default)]
+    struct RecordingSink {
+        events: std::sync::Mutex<Vec<(&'static str, Uuid)>>,
+        fail: bool,
+    }
+
+    impl EventSink for RecordingSink {
+        fn emit(&self, event: &ProjectEvent) -> Result<()> {
+            self.events.lock().unwrap().push((event.name(), event.project_id()));
+            if self.fail {
+                return Err(AppError::Internal("webview closed".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    async fn state_with_sink(temp: &tempfile::TempDir, sink: &Arc<RecordingSink>) -> AppState {
+        state(temp).await.with_event_sink(sink.clone())
+    }
+
+    #[tokio::test]
+    async fn test_mutations_emit_events_in_order() {
+        use ext_core::events::{
+            PROJECT_CREATED, PROJECT_DELETED, PROJECT_FILES_CHANGED, PROJECT_UPDATED,
+        };
+
+        let temp = tempfile::tempdir().unwrap();
+        let sink = Arc::new(RecordingSink::default());
+        let state = state_with_sink(&temp, &sink).await;
+
+        let project = state
+            .create_project("Tower A".to_string(), String::new())
+            .await
+            .unwrap();
+        let id = project.id;
+        state
+            .update_project(id, "Tower B".to_string(), String::new())
+            .await
+            .unwrap();
+
+        let source = temp.path().join("Tower.e2k");
+        std::fs::write(&source, "$ STORIES - IN SEQUENCE FROM TOP\n").unwrap();
+        let file = state.add_project_file(id, &source).await.unwrap();
+        state.remove_project_file(id, file.id).await.unwrap();
+        state.archive_project(id).await.unwrap();
+        state.delete_project(id).await.unwrap();
+        state.purge_project(id).await.unwrap();
+
+        assert_eq!(
+            *sink.events.lock().unwrap(),
+            vec![
+                (PROJECT_CREATED, id),
+                (PROJECT_UPDATED, id),
+                (PROJECT_FILES_CHANGED, id),
+                (PROJECT_FILES_CHANGED, id),
+                (PROJECT_UPDATED, id),
+                (PROJECT_UPDATED, id),
+                (PROJECT_DELETED, id),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_mutation_emits_nothing() {
+        let temp = tempfile::tempdir().unwrap();
+        let sink = Arc::new(RecordingSink::default());
+        let state = state_with_sink(&temp, &sink).await;
+
+        state.create_project("  ".to_string(), String::new()).await.unwrap_err();
+        state
+            .update_project(Uuid::new_v4(), "Tower A".to_string(), String::new())
+            .await
+            .unwrap_err();
+
+        assert!(sink.events.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_outside_edit_emits_file_modified() {
+        use ext_core::events::{PROJECT_FILES_CHANGED, PROJECT_FILE_MODIFIED, PROJECT_UPDATED};
+
+        let temp = tempfile::tempdir().unwrap();
+        let sink = Arc::new(RecordingSink::default());
+        let state = state_with_sink(&temp, &sink).await;
+        let project = state
+            .create_project("Tower A".to_string(), String::new())
+            .await
+            .unwrap();
+        let source = temp.path().join("Tower.EDB");
+        std::fs::write(&source, "v1").unwrap();
+        let file = state.add_project_file(project.id, &source).await.unwrap();
+        assert_eq!(state.open_project(project.id).await.unwrap(), vec![file.clone()]);
+
+        // Saving with identical contents is not a change
+        assert_eq!(state.refresh_project_file(project.id, file.id).await.unwrap(), None);
+
+        let copy = temp.path().join(project.id.to_string()).join(&file.stored_path);
+        std::fs::write(&copy, "version 2").unwrap();
+        let edited = state.refresh_project_file(project.id, file.id).await.unwrap().unwrap();
+        assert_eq!(edited.size, 9);
+
+        std::fs::remove_file(&copy).unwrap();
+        let missing = state.refresh_project_file(project.id, file.id).await.unwrap().unwrap();
+        assert!(missing.missing);
+
+        let names: Vec<_> = sink.events.lock().unwrap().iter().map(|(name, _)| *name).collect();
+        assert_eq!(
+            names[1..],
+            [
+                PROJECT_FILES_CHANGED,
+                PROJECT_UPDATED,
+                PROJECT_FILE_MODIFIED,
+                PROJECT_FILE_MODIFIED
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_emit_failure_does_not_fail_the_save() {
+        let temp = tempfile::tempdir().unwrap();
+        let sink = Arc::new(RecordingSink {
+            fail: true,
+            ..Default::default()
+        });
+        let state = state_with_sink(&temp, &sink).await;
+
+        let project = state
+            .create_project("Tower A".to_string(), String::new())
+            .await
+            .unwrap();
+
+        assert_eq!(state.get_project(project.id).await.unwrap().name, "Tower A");
+        assert_eq!(sink.events.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_project_bundle_imports_back() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let project = state
+            .create_project("Tower A".to_string(), String::new())
+            .await
+            .unwrap();
+        state
+            .save_analysis_results(project.id, "Rev A".to_string(), AnalysisResults::default())
+            .await
+            .unwrap();
+
+        let bundle = temp.path().join("tower.zip");
+        state
+            .export_project_bundle(project.id, &bundle)
+            .await
+            .unwrap();
+        let imported = state.import_project(&bundle).await.unwrap();
+        assert_eq!(imported.name, "Tower A (2)");
+        let runs = state.get_analysis_runs(imported.id).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].label, "Rev A");
+    }
+
+    #[tokio::test]
+    async fn test_update_settings_keeps_unchanged_fields() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        state
+            .update_settings(SettingsPatch {
+                backup_retention: Some(25),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let settings = state
+            .update_settings(SettingsPatch {
+                units: Some(ext_core::units::UnitSystem::Imperial),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(settings.backup_retention, 25);
+        assert_eq!(settings.units, ext_core::units::UnitSystem::Imperial);
+    }
+
+    #[tokio::test]
+    async fn test_update_settings_rejects_missing_etabs_path() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let missing = temp.path().join("ETABS 21");
+
+        let err = state
+            .update_settings(SettingsPatch {
+                etabs_install_path: Some(missing.to_string_lossy().to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        assert_eq!(state.get_settings().await, Settings::default());
+    }
+
+    #[test]
+    fn test_parse_project_id() {
+        let id = Uuid::new_v4();
+        assert_eq!(parse_project_id(&id.to_string()).unwrap(), id);
+        assert_eq!(parse_project_id(&id.to_string().to_uppercase()).unwrap(), id);
+    }
+
+    #[test]
+    fn test_parse_invalid_project_id_is_validation_error() {
+        match parse_project_id("not-a-uuid") {
+            Err(AppError::Validation(message)) => assert!(message.contains("not-a-uuid")),
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_model_info_requires_connection() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        assert!(matches!(
+            state.etabs_get_model_info().await,
+            Err(AppError::Etabs(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_drift_check_writes_csv_report() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let project = state
+            .create_project("Tower A".to_string(), String::new())
+            .await
+            .unwrap();
+
+        let e2k = temp.path().join("tower.e2k");
+        std::fs::write(
+            &e2k,
+            "$ STORIES - IN SEQUENCE FROM TOP\n\
+             STORY \"Story2\"  HEIGHT 3.5\n\
+             STORY \"Base\"  ELEV 0\n",
+        )
+        .unwrap();
+        state
+            .import_etabs_model(project.id, &e2k, &TaskContext::detached())
+            .await
+            .unwrap();
+
+        let results = AnalysisResults {
+            story_drifts: vec![StoryDrift {
+                story: "Story2".to_string(),
+                load_case: "EQX".to_string(),
+                direction: ext_core::results::Direction::X,
+                drift: 0.01,
+            }],
+            ..Default::default()
+        };
+        let run = state
+            .save_analysis_results(project.id, "Rev A".to_string(), results)
+            .await
+            .unwrap();
+
+        let report = state
+            .check_drift_limits(project.id, run.id, 0.02, true)
+            .await
+            .unwrap();
+        assert_eq!(report.len(), 1);
+        assert!((report[0].utilization - 0.5).abs() < 1e-12);
+
+        let csv = temp
+            .path()
+            .join(project.id.to_string())
+            .join("reports")
+            .join("drift-check-Rev_A.csv");
+        assert!(std::fs::read_to_string(csv).unwrap().contains("Story2,X,EQX"));
+    }
+
+    #[tokio::test]
+    async fn test_open_project_moves_it_to_the_top_of_recent() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let first = state
+            .create_project("Tower A".to_string(), String::new())
+            .await
+            .unwrap();
+        let second = state
+            .create_project("Tower B".to_string(), String::new())
+            .await
+            .unwrap();
+
+        state.open_project(first.id).await.unwrap();
+
+        let recent = state.get_recent_projects(10).await.unwrap();
+        let ids: Vec<Uuid> = recent.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![first.id, second.id]);
+        assert!(recent[0].last_opened_at.is_some());
+        // Opening is not an edit
+        assert_eq!(recent[0].updated_at, first.updated_at);
+
+        for limit in [0, 51] {
+            assert!(matches!(
+                state.get_recent_projects(limit).await,
+                Err(AppError::Validation(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_audit_log_records_mutations_newest_first() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let project = state
+            .create_project("Tower A".to_string(), String::new())
+            .await
+            .unwrap();
+        let id = project.id;
+        state
+            .update_project(id, "Tower B".to_string(), String::new())
+            .await
+            .unwrap();
+        let source = temp.path().join("Tower.e2k");
+        std::fs::write(&source, "$ STORIES - IN SEQUENCE FROM TOP\n").unwrap();
+        state.add_project_file(id, &source).await.unwrap();
+        state.trash_project(id).await.unwrap();
+
+        let log = state.get_audit_log(id, 0, 50, None).await.unwrap();
+        let actions: Vec<AuditAction> = log.iter().map(|entry| entry.action).collect();
+        assert_eq!(
+            actions,
+            vec![
+                AuditAction::Trashed,
+                AuditAction::FileAttached,
+                AuditAction::Updated,
+                AuditAction::Created,
+            ]
+        );
+        assert_eq!(log[1].detail, "Attached \"Tower.e2k\"");
+        assert_eq!(log[2].detail, "Renamed \"Tower A\" to \"Tower B\"");
+
+        let second_page = state.get_audit_log(id, 1, 3, None).await.unwrap();
+        assert_eq!(second_page, vec![log[3].clone()]);
+        let renames = state
+            .get_audit_log(id, 0, 50, Some(AuditAction::Updated))
+            .await
+            .unwrap();
+        assert_eq!(renames, vec![log[2].clone()]);
+        assert!(matches!(
+            state.get_audit_log(id, 0, 0, None).await,
+            Err(AppError::Validation(_))
+        ));
+
+        // Only purging the project deletes the log
+        state.purge_project(id).await.unwrap();
+        assert!(state
+            .db()
+            .unwrap()
+            .list_audit_log(&id.to_string(), None, 0, 50)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_names_copy_after_the_original() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let project = state
+            .create_project("Tower A".to_string(), "Phase 1".to_string())
+            .await
+            .unwrap();
+
+        let first = state.duplicate_project(project.id, None, false).await.unwrap();
+        assert_eq!(first.name, "Tower A - Copy");
+        assert_eq!(first.description, "Phase 1");
+        let second = state.duplicate_project(project.id, None, false).await.unwrap();
+        assert_eq!(second.name, "Tower A - Copy (2)");
+
+        let taken = state
+            .duplicate_project(project.id, Some(" Tower A ".to_string()), false)
+            .await;
+        assert!(matches!(taken, Err(AppError::Validation(_))));
+        let renamed = state
+            .duplicate_project(project.id, Some("Tower A - Phase 2".to_string()), false)
+            .await
+            .unwrap();
+        assert_eq!(renamed.name, "Tower A - Phase 2");
+    }
+
+    #[test]
+    fn test_report_file_stem_replaces_unsafe_characters() {
+        assert_eq!(report_file_stem(" Rev B/2 "), "Rev_B_2");
+    }
+
+    #[tokio::test]
+    async fn test_project_from_template_round_trips_through_a_user_template() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let templates = state.list_templates().await.unwrap();
+        let shear_wall = templates
+            .iter()
+            .find(|template| template.name == "Concrete Shear Wall Building")
+            .unwrap();
+
+        let project = state
+            .create_project_from_template(shear_wall.id, "Tower A".to_string())
+            .await
+            .unwrap();
+        let tags: Vec<String> = state
+            .get_project_tags(project.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|tag| tag.name)
+            .collect();
+        assert_eq!(tags, vec!["Concrete", "Shear Wall"]);
+        let combinations = state.get_load_combinations(project.id).await.unwrap();
+        assert!(combinations[0].name.starts_with("LRFD"));
+
+        let file = temp.path().join("Tower.e2k");
+        std::fs::write(&file, "$ STORIES - IN SEQUENCE FROM TOP\n").unwrap();
+        state.add_project_file(project.id, &file).await.unwrap();
+        let saved = state
+            .create_template_from_project(project.id, "Our Towers".to_string())
+            .await
+            .unwrap();
+        assert_eq!(saved.default_load_cases, shear_wall.default_load_cases);
+        assert_eq!(saved.default_settings, shear_wall.default_settings);
+
+        // Existing tags are reused rather than duplicated
+        let copy = state
+            .create_project_from_template(saved.id, "Tower B".to_string())
+            .await
+            .unwrap();
+        assert!(state.get_project_files(copy.id).await.unwrap().is_empty());
+        assert_eq!(state.get_project_tags(copy.id).await.unwrap().len(), 2);
+        assert_eq!(state.list_tags().await.unwrap().len(), 2);
+
+        assert!(matches!(
+            state.delete_template(shear_wall.id).await,
+            Err(AppError::Validation(_))
+        ));
+        state.delete_template(saved.id).await.unwrap();
+        assert_eq!(state.list_templates().await.unwrap().len(), templates.len());
+    }
+
+    #[tokio::test]
+    async fn test_project_report_is_written_and_attached() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let project = state
+            .create_project("Tower A".to_string(), "Phase 1".to_string())
+            .await
+            .unwrap();
+
+        let dest = temp.path().join("Tower A summary.html");
+        let path = state
+            .generate_project_report(
+                project.id,
+                ReportFormat::Html,
+                &dest,
+                &TaskContext::detached(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(path, dest);
+        let html = std::fs::read_to_string(&dest).unwrap();
+        assert!(html.contains("<h1>Tower A</h1>"));
+        // No files, combinations, results or notes yet
+        assert_eq!(html.matches("class=\"not-available\"").count(), 4);
+
+        let files = state.get_project_files(project.id).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].original_name, "Tower A summary.html");
+
+        let dest = temp.path().join("summary.pdf");
+        state
+            .generate_project_report(
+                project.id,
+                ReportFormat::Pdf,
+                &dest,
+                &TaskContext::detached(),
+            )
+            .await
+            .unwrap();
+        assert!(std::fs::read(&dest).unwrap().starts_with(b"%PDF-"));
+        assert_eq!(state.get_project_files(project.id).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_initialization_switches_to_working_directory() {
+        let temp = tempfile::tempdir().unwrap();
+        // A file where the data directory should be
+        let blocked = temp.path().join("blocked");
+        std::fs::write(&blocked, "").unwrap();
+        let error = init::open_database(&blocked, &Settings::default())
+            .await
+            .unwrap_err();
+
+        let state = AppState::degraded(blocked.clone(), error, Settings::default(), None);
+        assert!(matches!(state.init_status(), InitStatus::Failed { .. }));
+        assert!(matches!(
+            state.get_projects(None).await,
+            Err(AppError::Internal(message)) if message == "storage not initialized"
+        ));
+        // Settings still work, so the projects directory can be changed
+        assert!(state.get_settings().await.projects_dir.is_none());
+
+        match state.retry_initialization(None).await.unwrap() {
+            InitStatus::Failed { data_dir, .. } => {
+                assert_eq!(data_dir, blocked.to_string_lossy())
+            }
+            InitStatus::Ready => panic!("blocked directory opened"),
+        }
+
+        let data_dir = temp.path().join("data");
+        let status = state
+            .retry_initialization(Some(data_dir.clone()))
+            .await
+            .unwrap();
+        assert!(matches!(status, InitStatus::Ready));
+        assert!(data_dir.join("db").join("app.db").exists());
+
+        let project = state
+            .create_project("Tower A".to_string(), String::new())
+            .await
+            .unwrap();
+        assert!(data_dir.join("projects").join(project.id.to_string()).exists());
+        // Once open, retrying keeps the current storage
+        let status = state.retry_initialization(Some(blocked)).await.unwrap();
+        assert!(matches!(status, InitStatus::Ready));
+        assert_eq!(state.get_projects(None).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_deep_link_resolves_to_project() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let project = state
+            .create_project("Tower A".to_string(), String::new())
+            .await
+            .unwrap();
+
+        let url = state.get_project_deep_link(project.id).await.unwrap();
+        assert_eq!(url, format!("etabext://project/{}", project.id));
+        let resolved = state.resolve_deep_link(&format!("{url}/?tab=files")).await.unwrap();
+        assert_eq!(resolved.id, project.id);
+
+        let missing = format!("etabext://project/{}", Uuid::new_v4());
+        assert!(matches!(
+            state.resolve_deep_link(&missing).await,
+            Err(AppError::NotFound(_))
+        ));
+        assert!(matches!(
+            state.get_project_deep_link(Uuid::new_v4()).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_export_table_uses_latest_run_and_settings_units() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let project = state
+            .create_project("Tower A".to_string(), String::new())
+            .await
+            .unwrap();
+
+        // No runs yet: just the header
+        let dest = temp.path().join("reactions.csv");
+        state
+            .export_table(project.id, ExportTable::BaseReactions, ExportFormat::Csv, None, &dest)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&dest).unwrap().lines().count(), 1);
+
+        let results = AnalysisResults {
+            base_reactions: vec![BaseReaction {
+                load_case: "Dead".to_string(),
+                fx: 0.0,
+                fy: 0.0,
+                fz: 4_448.221_615_260_5,
+                mx: 0.0,
+                my: 0.0,
+                mz: 0.0,
+            }],
+            ..Default::default()
+        };
+        state
+            .save_analysis_results(project.id, "Rev A".to_string(), results)
+            .await
+            .unwrap();
+        state
+            .update_settings(SettingsPatch {
+                units: Some(ext_core::units::UnitSystem::Imperial),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        state
+            .export_table(project.id, ExportTable::BaseReactions, ExportFormat::Csv, None, &dest)
+            .await
+            .unwrap();
+        let csv = std::fs::read_to_string(&dest).unwrap();
+        let row: Vec<&str> = csv.lines().nth(1).unwrap().split(',').collect();
+        assert!(csv.starts_with("Output Case,FX (kip),"));
+        assert_eq!(row[0], "Dead");
+        // 1 kip
+        assert!((row[3].parse::<f64>().unwrap() - 1.0).abs() < 1e-9);
+
+        let dest = temp.path().join("reactions.json");
+        state
+            .export_table(project.id, ExportTable::BaseReactions, ExportFormat::Json, None, &dest)
+            .await
+            .unwrap();
+        let json: Vec<BaseReaction> =
+            serde_json::from_str(&std::fs::read_to_string(&dest).unwrap()).unwrap();
+        assert_eq!(json[0].fz, 4_448.221_615_260_5);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_operations_keep_files_consistent() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = Arc::new(state(&temp).await);
+        let seed = state
+            .create_project("Seed".to_string(), "Revision 0".to_string())
+            .await
+            .unwrap();
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for i in 0..50 {
+            let state = state.clone();
+            tasks.spawn(async move {
+                match i % 3 {
+                    0 => state
+                        .create_project(format!("Tower {i}"), String::new())
+                        .await
+                        .map(drop),
+                    1 => state.get_projects(None).await.map(drop),
+                    _ => state
+                        .update_project(seed.id, "Seed".to_string(), format!("Revision {i}"))
+                        .await
+                        .map(drop),
+                }
+            });
+        }
+        let finished = tokio::time::timeout(std::time::Duration::from_secs(60), async {
+            while let Some(result) = tasks.join_next().await {
+                result.unwrap().unwrap();
+            }
+        })
+        .await;
+        assert!(finished.is_ok(), "concurrent operations deadlocked");
+
+        let projects = state.get_projects(None).await.unwrap();
+        assert_eq!(projects.len(), 18);
+        for project in &projects {
+            let path = temp.path().join(project.id.to_string()).join("project.json");
+            let on_disk: Project =
+                serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+            assert_eq!(on_disk.name, project.name);
+            assert_eq!(on_disk.description, project.description);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bulk_import_creates_project_per_model() {
+        let temp = tempfile::tempdir().unwrap();
+        let models = tempfile::tempdir().unwrap();
+        std::fs::create_dir(models.path().join("Archive")).unwrap();
+        std::fs::write(models.path().join("Tower.edb"), b"tower").unwrap();
+        std::fs::write(models.path().join("Podium.E2K"), b"podium").unwrap();
+        std::fs::write(models.path().join("notes.txt"), b"notes").unwrap();
+        std::fs::write(models.path().join("Archive/Tower.edb"), b"tower rev 1").unwrap();
+        let state = state(&temp).await;
+        let task = TaskContext::detached();
+
+        let shallow = state.bulk_import(models.path(), false, &task).await.unwrap();
+        let mut names: Vec<_> = shallow.created.iter().map(|p| p.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["Podium", "Tower"]);
+        assert!(shallow.skipped.is_empty());
+        assert!(!shallow.cancelled);
+        for project in &shallow.created {
+            assert_eq!(state.get_project_files(project.id).await.unwrap().len(), 1);
+        }
+
+        // Models imported above are matched by content and skipped; the
+        // archived revision gets a suffixed name
+        let deep = state.bulk_import(models.path(), true, &task).await.unwrap();
+        assert_eq!(deep.created.len(), 1);
+        assert_eq!(deep.created[0].name, "Tower (2)");
+        assert_eq!(deep.skipped.len(), 2);
+        assert!(deep.skipped.iter().all(|(_, reason)| reason.contains("already attached")));
+
+        assert!(matches!(
+            state.bulk_import(&models.path().join("notes.txt"), true, &task).await,
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    /// Cancels the task it's watching on the first progress event
+    struct CancellingSink {
+        task: TaskContext,
+    }
+
+    impl EventSink for CancellingSink {
+        fn emit(&self, _event: &ProjectEvent) -> Result<()> {
+            Ok(())
+        }
+
+        fn emit_bulk_import_progress(&self, _progress: &BulkImportProgress) -> Result<()> {
+            self.task.cancel();
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bulk_import_stops_when_cancelled() {
+        let temp = tempfile::tempdir().unwrap();
+        let models = tempfile::tempdir().unwrap();
+        for i in 0..5 {
+            std::fs::write(models.path().join(format!("Model {i}.e2k")), format!("{i}")).unwrap();
+        }
+        let task = TaskContext::detached();
+        let sink = Arc::new(CancellingSink { task: task.clone() });
+        let state = state(&temp).await.with_event_sink(sink);
+
+        let report = state.bulk_import(models.path(), false, &task).await.unwrap();
+        assert_eq!(report.created.len(), 1);
+        assert!(report.cancelled);
+        assert_eq!(state.get_projects(None).await.unwrap().len(), 1);
+    }
+
+    /// Waits until the task is no longer running
+    async fn finished_task(state: &AppState, task_id: Uuid) -> TaskInfo {
+        for _ in 0..200 {
+            let task = state.get_task(task_id).unwrap();
+            if task.is_finished() {
+                return task;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        panic!("task {task_id} did not finish");
+    }
+
+    #[tokio::test]
+    async fn test_spawned_tasks_finish_with_result_or_error() {
+        use ext_core::tasks::TaskStatus;
+
+        let temp = tempfile::tempdir().unwrap();
+        let state = Arc::new(state(&temp).await);
+        let project = state
+            .create_project("Tower A".to_string(), String::new())
+            .await
+            .unwrap();
+        let e2k = temp.path().join("tower.e2k");
+        std::fs::write(&e2k, "$ STORIES - IN SEQUENCE FROM TOP\n  STORY \"Base\" ELEV 0\n")
+            .unwrap();
+
+        let task_id = state.spawn_task(TaskKind::ImportEtabsModel, {
+            let (state, e2k) = (state.clone(), e2k.clone());
+            move |task| async move { state.import_etabs_model(project.id, &e2k, &task).await }
+        });
+        let task = finished_task(&state, task_id).await;
+        assert_eq!(task.status, TaskStatus::Done);
+        assert_eq!(task.progress, 1.0);
+        let model: EtabsModel = serde_json::from_value(task.result.unwrap()).unwrap();
+        assert_eq!(model.stories.len(), 1);
+        assert!(state.get_etabs_model(project.id).await.unwrap().is_some());
+
+        let missing = temp.path().join("missing.e2k");
+        let task_id = state.spawn_task(TaskKind::ImportEtabsModel, {
+            let state = state.clone();
+            move |task| async move { state.import_etabs_model(project.id, &missing, &task).await }
+        });
+        let task = finished_task(&state, task_id).await;
+        assert_eq!(task.status, TaskStatus::Failed);
+        assert!(task.error.unwrap().contains("missing.e2k"));
+
+        assert_eq!(state.get_tasks().len(), 2);
+        assert!(matches!(state.get_task(Uuid::new_v4()), Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_task_stops_at_next_checkpoint() {
+        use ext_core::tasks::TaskStatus;
+
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let (started, mut running) = tokio::sync::mpsc::unbounded_channel();
+        let steps = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+        let task_id = state.spawn_task(TaskKind::BulkImport, {
+            let steps = steps.clone();
+            move |task| async move {
+                while task.checkpoint().is_ok() {
+                    steps.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    let _ = started.send(());
+                    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+                }
+                task.checkpoint()
+            }
+        });
+        running.recv().await.unwrap();
+
+        assert!(state.cancel_task(task_id).unwrap());
+        let task = finished_task(&state, task_id).await;
+        assert_eq!(task.status, TaskStatus::Cancelled);
+        assert!(task.error.is_none());
+
+        // Nothing runs after the checkpoint that saw the cancellation
+        let after = steps.load(std::sync::atomic::Ordering::SeqCst);
+        tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+        assert_eq!(steps.load(std::sync::atomic::Ordering::SeqCst), after);
+        assert!(!state.cancel_task(task_id).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_bulk_import_cancels_the_running_import_task() {
+        use ext_core::tasks::TaskStatus;
+
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        assert!(!state.cancel_bulk_import());
+
+        let (started, mut running) = tokio::sync::mpsc::unbounded_channel();
+        let task_id = state.spawn_task(TaskKind::BulkImport, move |task| async move {
+            let _ = started.send(());
+            while task.checkpoint().is_ok() {
+                tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            }
+            task.checkpoint()
+        });
+        running.recv().await.unwrap();
+
+        assert!(state.cancel_bulk_import());
+        let task = finished_task(&state, task_id).await;
+        assert_eq!(task.status, TaskStatus::Cancelled);
+        assert!(!state.cancel_bulk_import());
+    }
+
+    #[tokio::test]
+    async fn test_seismic_base_shear_is_saved_and_duplicated() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let project = state
+            .create_project("Tower".to_string(), String::new())
+            .await
+            .unwrap();
+        assert!(state.get_seismic_base_shear(project.id).await.unwrap().is_none());
+
+        let params = SeismicParams {
+            sds: 1.0,
+            sd1: 0.6,
+            s1: 0.5,
+            r: 8.0,
+            ie: 1.0,
+            t: 1.0,
+            tl: 8.0,
+            building_weight: 2000.0,
+        };
+        let stories = vec![SeismicStory {
+            name: "Roof".to_string(),
+            height: 4.0,
+            weight: 2000.0,
+        }];
+        let saved = state
+            .save_seismic_base_shear(project.id, params, stories)
+            .await
+            .unwrap();
+        assert!((saved.result.base_shear - 150.0).abs() < 1e-9);
+
+        let loaded = state.get_seismic_base_shear(project.id).await.unwrap().unwrap();
+        assert_eq!(loaded.result, saved.result);
+        assert_eq!(loaded.story_forces[0].force, Some(saved.result.base_shear));
+
+        let copy = state.duplicate_project(project.id, None, false).await.unwrap();
+        assert!(state.get_seismic_base_shear(copy.id).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_wind_pressures_are_saved_with_project() {
+        use ext_core::units::{Length, LengthUnit, Speed, SpeedUnit};
+        use ext_core::wind::{Exposure, WindStory};
+
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let project = state
+            .create_project("Tower".to_string(), String::new())
+            .await
+            .unwrap();
+        let input = WindInput {
+            v: Speed::from(45.0, SpeedUnit::MetersPerSecond),
+            exposure: Exposure::B,
+            kzt: 1.0,
+            kd: 0.85,
+            ke: 1.0,
+            gust_factor: 0.85,
+            length: Length::from(30.0, LengthUnit::Meter),
+            width: Length::from(20.0, LengthUnit::Meter),
+            stories: vec![WindStory {
+                name: "Roof".to_string(),
+                elevation: Length::from(12.0, LengthUnit::Meter),
+            }],
+        };
+
+        let saved = state.save_wind_pressures(project.id, input).await.unwrap();
+        let loaded = state.get_wind_pressures(project.id).await.unwrap().unwrap();
+        assert_eq!(loaded.qh, saved.qh);
+        assert_eq!(loaded.rows.len(), 1);
+
+        let missing = state.save_wind_pressures(Uuid::new_v4(), saved.input).await;
+        assert!(matches!(missing, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_note_history_restore_and_export() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let project = state
+            .create_project("Tower".to_string(), String::new())
+            .await
+            .unwrap();
+
+        let note = state
+            .create_note(project.id, "Loads".to_string(), "SDL 1.5 kPa".to_string())
+            .await
+            .unwrap();
+        state
+            .update_note(note.id, "Loads".to_string(), "SDL 2.0 kPa".to_string())
+            .await
+            .unwrap();
+        // Saving the same text again is not a revision
+        state
+            .update_note(note.id, "Loads".to_string(), "SDL 2.0 kPa".to_string())
+            .await
+            .unwrap();
+
+        let history = state.get_note_history(note.id).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].body_markdown, "SDL 1.5 kPa");
+
+        let restored = state
+            .restore_note_revision(note.id, history[0].id)
+            .await
+            .unwrap();
+        assert_eq!(restored.body_markdown, "SDL 1.5 kPa");
+        assert_eq!(state.get_note_history(note.id).await.unwrap().len(), 2);
+
+        let oversized = "x".repeat(ext_core::notes::MAX_NOTE_BODY_BYTES + 1);
+        let result = state.update_note(note.id, "Loads".to_string(), oversized).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        let archive = temp.path().join("tower.etabx");
+        state.export_project(project.id, &archive).await.unwrap();
+        let imported = state.import_project(&archive).await.unwrap();
+        let notes = state.get_notes(imported.id).await.unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_ne!(notes[0].id, note.id);
+        assert_eq!(notes[0].body_markdown, "SDL 1.5 kPa");
+
+        state.delete_note(note.id).await.unwrap();
+        assert!(state.get_notes(project.id).await.unwrap().is_empty());
+        assert!(matches!(
+            state.get_note_history(note.id).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_compare_etabs_files_reports_changes() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let project = state
+            .create_project("Tower".to_string(), String::new())
+            .await
+            .unwrap();
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("../ext-core/tests/fixtures");
+        let a = state
+            .add_project_file(project.id, &fixtures.join("two_story.e2k"))
+            .await
+            .unwrap();
+        let b = state
+            .add_project_file(project.id, &fixtures.join("two_story_rev.e2k"))
+            .await
+            .unwrap();
+
+        let diff = state
+            .compare_etabs_files(project.id, a.id, b.id, true)
+            .await
+            .unwrap();
+        assert_eq!(diff.changes.len(), 4);
+        let saved = temp
+            .path()
+            .join(project.id.to_string())
+            .join("reports")
+            .join("model-diff-two_story-vs-two_story_rev.json");
+        let saved: ModelDiff =
+            serde_json::from_str(&std::fs::read_to_string(saved).unwrap()).unwrap();
+        assert_eq!(saved, diff);
+
+        let unchanged = state
+            .compare_etabs_files(project.id, a.id, a.id, false)
+            .await
+            .unwrap();
+        assert!(unchanged.is_empty());
+
+        let other = state
+            .create_project("Podium".to_string(), String::new())
+            .await
+            .unwrap();
+        let result = state.compare_etabs_files(other.id, a.id, b.id, false).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_paste_import_table_saves_detected_table() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let project = state
+            .create_project("Tower".to_string(), String::new())
+            .await
+            .unwrap();
+
+        let text = "Story\tOutput Case\tCase Type\tDirection\tDrift\n\
+                    Story2\tEQX\tLinStatic\tX\t0.004\n\
+                    Story1\tEQX\tLinStatic\tZ\t0.003\n";
+        let report = state
+            .paste_import_table(project.id, "Rev A".to_string(), text, None)
+            .await
+            .unwrap();
+        assert_eq!(report.table, ResultTable::StoryDrifts);
+        assert_eq!(report.imported_rows, 1);
+        assert_eq!(report.failed_rows.len(), 1);
+        assert_eq!(report.failed_rows[0].line, 3);
+
+        let drifts = state
+            .get_story_drifts(project.id, report.run.id)
+            .await
+            .unwrap();
+        assert_eq!(drifts.len(), 1);
+        assert_eq!(drifts[0].story, "Story2");
+
+        let result = state
+            .paste_import_table(project.id, "Rev A".to_string(), "Story\tDrift\n", None)
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_open_project_locks_it_until_closed() {
+        let temp = tempfile::tempdir().unwrap();
+        // Two instances sharing one data directory
+        let url = format!("sqlite://{}?mode=rwc", temp.path().join("projects.db").display());
+        let projects_dir = temp.path().join("projects");
+        let projects_dir = projects_dir.to_str().unwrap();
+        let first = AppState::new(Database::new(&url, projects_dir).await.unwrap())
+            .with_lock_holder("jane@WS-042".to_string());
+        let second = AppState::new(Database::new(&url, projects_dir).await.unwrap())
+            .with_lock_holder("raj@WS-017".to_string());
+        let project = first
+            .create_project("Tower".to_string(), String::new())
+            .await
+            .unwrap();
+
+        first.open_project(project.id).await.unwrap();
+        match second.open_project(project.id).await {
+            Err(AppError::Locked(holder)) => assert_eq!(holder, "jane@WS-042"),
+            other => panic!("expected locked error, got {other:?}"),
+        }
+        let result = second
+            .create_note(project.id, "Loads".to_string(), String::new())
+            .await;
+        assert!(matches!(result, Err(AppError::Locked(_))));
+        first
+            .create_note(project.id, "Loads".to_string(), String::new())
+            .await
+            .unwrap();
+
+        first.close_project(project.id).await.unwrap();
+        second.open_project(project.id).await.unwrap();
+        second.release_project_locks().await;
+        first.open_project(project.id).await.unwrap();
+    }
+
+    /// State on `data_dir` as the app opens it at startup, with five
+    /// projects that each have an attached file
+    async fn state_with_projects(data_dir: &Path) -> (AppState, Vec<Uuid>) {
+        let settings_path = data_dir.join("settings.json");
+        let settings = Settings::default();
+        let db = init::open_database(data_dir, &settings).await.unwrap();
+        let state = AppState::with_settings(db, settings, settings_path);
+
+        let source = data_dir.join("Tower.e2k");
+        std::fs::write(&source, "$ STORIES - IN SEQUENCE FROM TOP\n").unwrap();
+        let mut ids = Vec::new();
+        for i in 1..=5 {
+            let project = state
+                .create_project(format!("Tower {i}"), String::new())
+                .await
+                .unwrap();
+            state.add_project_file(project.id, &source).await.unwrap();
+            ids.push(project.id);
+        }
+        // Folders are moved in id order
+        ids.sort();
+        (state, ids)
+    }
+
+    #[tokio::test]
+    async fn test_relocation_failing_on_third_project_keeps_old_directory() {
+        let temp = tempfile::tempdir().unwrap();
+        let data_dir = temp.path().join("data");
+        let (state, ids) = state_with_projects(&data_dir).await;
+        let old_dir = data_dir.join("projects");
+        let destination = temp.path().join("moved");
+
+        // A file where the third project's copy is written makes it fail
+        std::fs::create_dir_all(&destination).unwrap();
+        let blocker = destination.join(format!("{}.partial", ids[2]));
+        std::fs::write(&blocker, "").unwrap();
+
+        let task = TaskContext::detached();
+        let result = state
+            .relocate_projects_dir(&destination, false, &task)
+            .await;
+        assert!(matches!(result, Err(AppError::FileSystem(_))), "{result:?}");
+
+        let db = state.db().unwrap();
+        assert_eq!(db.storage_status().projects_dir, old_dir.to_string_lossy());
+        assert_eq!(state.get_settings().await.projects_dir, None);
+        assert!(!data_dir.join("settings.json").exists());
+        for id in &ids {
+            assert!(!destination.join(id.to_string()).exists());
+            let files = db.list_project_files(&id.to_string()).await.unwrap();
+            assert!(
+                old_dir
+                    .join(id.to_string())
+                    .join(&files[0].stored_path)
+                    .is_file()
+            );
+        }
+        assert_eq!(state.get_projects(None).await.unwrap().len(), 5);
+
+        let nested = old_dir.join("moved");
+        let result = state.relocate_projects_dir(&nested, false, &task).await;
+        assert!(matches!(result, Err(AppError::Validation(_))), "{result:?}");
+    }
+
+    #[tokio::test]
+    async fn test_relocated_projects_dir_is_used_after_restart() {
+        let temp = tempfile::tempdir().unwrap();
+        let data_dir = temp.path().join("data");
+        let (state, ids) = state_with_projects(&data_dir).await;
+        let old_dir = data_dir.join("projects");
+        let destination = temp.path().join("moved");
+
+        let report = state
+            .relocate_projects_dir(&destination, false, &TaskContext::detached())
+            .await
+            .unwrap();
+        assert_eq!(report.copied, 5);
+        assert!(report.left_behind.is_empty());
+        for id in &ids {
+            assert!(!old_dir.join(id.to_string()).exists());
+        }
+
+        let project = state.get_project(ids[0]).await.unwrap();
+        state
+            .update_project(project.id, "Tower 1 rev B".to_string(), String::new())
+            .await
+            .unwrap();
+        assert!(
+            destination
+                .join(ids[0].to_string())
+                .join("project.json")
+                .is_file()
+        );
+
+        let settings = ext_db::settings::load_settings(&data_dir.join("settings.json"))
+            .await
+            .unwrap();
+        let reopened = init::open_database(&data_dir, &settings).await.unwrap();
+        assert_eq!(
+            reopened.storage_status().projects_dir,
+            destination.to_string_lossy()
+        );
+        for id in &ids {
+            let files = reopened.list_project_files(&id.to_string()).await.unwrap();
+            let stored = destination.join(id.to_string()).join(&files[0].stored_path);
+            assert_eq!(
+                std::fs::read_to_string(stored).unwrap(),
+                "$ STORIES - IN SEQUENCE FROM TOP\n"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_only_trashed_projects_can_be_purged() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let project = state
+            .create_project("Tower".to_string(), String::new())
+            .await
+            .unwrap();
+
+        let result = state.purge_project(project.id).await;
+        assert!(matches!(result, Err(AppError::Validation(_))), "{result:?}");
+        assert!(temp.path().join(project.id.to_string()).is_dir());
+
+        state.trash_project(project.id).await.unwrap();
+        let trashed = state.list_trashed_projects().await.unwrap();
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].id, project.id);
+
+        state.purge_project(project.id).await.unwrap();
+        assert!(state.list_trashed_projects().await.unwrap().is_empty());
+        assert!(!temp.path().join(project.id.to_string()).exists());
+        assert!(matches!(
+            state.purge_project(project.id).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_modal_results_come_from_the_latest_cached_run() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let project = state
+            .create_project("Tower".to_string(), String::new())
+            .await
+            .unwrap();
+
+        // Nothing cached and no ETABS to pull from
+        let result = state.get_modal_results(project.id, None, false).await;
+        assert!(matches!(result, Err(AppError::Etabs(_))), "{result:?}");
+
+        let mode = |mode, ux, uy| ModalPeriod {
+            load_case: "Modal".to_string(),
+            mode,
+            period: 1.0 / f64::from(mode),
+            ux,
+            uy,
+            rz: 0.0,
+        };
+        let results = AnalysisResults {
+            modal_periods: vec![mode(1, 0.85, 0.02), mode(2, 0.08, 0.9)],
+            ..Default::default()
+        };
+        state
+            .save_analysis_results(project.id, "Rev A".to_string(), results)
+            .await
+            .unwrap();
+
+        let modal = state
+            .get_modal_results(project.id, None, false)
+            .await
+            .unwrap();
+        assert_eq!(modal.modes.len(), 2);
+        assert_eq!(modal.modes[1].frequency, 2.0);
+        assert_eq!(modal.mode_reaching_90_x, Some(2));
+        assert!(modal.meets_mass_participation);
+
+        let result = state.get_modal_results(project.id, None, true).await;
+        assert!(matches!(result, Err(AppError::Etabs(_))), "{result:?}");
+    }
+
+    #[tokio::test]
+    async fn test_base_reactions_need_an_etabs_connection() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let project = state
+            .create_project("Tower".to_string(), String::new())
+            .await
+            .unwrap();
+
+        let result = state.etabs_get_base_reactions(project.id, Vec::new()).await;
+        assert!(matches!(result, Err(AppError::Etabs(_))), "{result:?}");
+        let reports = temp.path().join(project.id.to_string()).join("reports");
+        assert!(!reports.exists());
+        let runs = state.get_analysis_runs(project.id).await.unwrap();
+        assert!(runs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_frame_forces_are_filtered_and_paged() {
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let project = state
+            .create_project("Tower".to_string(), String::new())
+            .await
+            .unwrap();
+
+        use ext_core::frame_forces::FrameForce;
+
+        let force = |story: &str, frame: &str, load_case: &str, station| FrameForce {
+            story: story.to_string(),
+            frame: frame.to_string(),
+            load_case: load_case.to_string(),
+            station,
+            p: -1.0e6,
+            v2: 0.0,
+            v3: 0.0,
+            t: 0.0,
+            m2: 0.0,
+            m3: 2.0e4,
+        };
+        let mut forces = Vec::new();
+        for story in ["Level 1", "Level 2"] {
+            for frame in ["C1", "C2"] {
+                for case in ["Dead", "1.2D+1.6L"] {
+                    forces.push(force(story, frame, case, 0.0));
+                    forces.push(force(story, frame, case, 3.5));
+                }
+            }
+        }
+        let db = state.db().unwrap();
+        let run = db
+            .save_frame_forces(&project.id.to_string(), "Tower", &forces)
+            .await
+            .unwrap();
+
+        let all = state
+            .get_frame_forces(project.id, run.id, FrameForceFilter::default(), 0, 5)
+            .await
+            .unwrap();
+        assert_eq!(all.total, 16);
+        assert_eq!(all.rows, forces[..5]);
+
+        let filter = FrameForceFilter {
+            story: Some("Level 2".to_string()),
+            load_case: Some("1.2D+1.6L".to_string()),
+            frame: None,
+        };
+        let page = state
+            .get_frame_forces(project.id, run.id, filter.clone(), 1, 3)
+            .await
+            .unwrap();
+        assert_eq!(page.total, 4);
+        assert_eq!(page.rows.len(), 1);
+        assert_eq!(page.rows[0].frame, "C2");
+        assert_eq!(page.rows[0].station, 3.5);
+
+        let result = state
+            .get_frame_forces(project.id, run.id, filter, 0, 0)
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))), "{result:?}");
+
+        // Saving the run again replaces its rows
+        db.save_frame_forces(&project.id.to_string(), "Tower", &forces[..2])
+            .await
+            .unwrap();
+        let all = state
+            .get_frame_forces(project.id, run.id, FrameForceFilter::default(), 0, 50)
+            .await
+            .unwrap();
+        assert_eq!(all.total, 2);
+
+        let copy = state
+            .duplicate_project(project.id, None, true)
+            .await
+            .unwrap();
+        let copied_run = state.get_analysis_runs(copy.id).await.unwrap()[0].id;
+        let copied = state
+            .get_frame_forces(copy.id, copied_run, FrameForceFilter::default(), 0, 50)
+            .await
+            .unwrap();
+        assert_eq!(copied.rows, forces[..2]);
+    }
+
+    #[tokio::test]
+    async fn test_writing_combinations_needs_generated_ones() {
+        use ext_core::combinations::LoadCaseKind;
+
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let project = state
+            .create_project("Tower".to_string(), String::new())
+            .await
+            .unwrap();
+
+        let result = state.etabs_write_load_combinations(project.id).await;
+        assert!(matches!(result, Err(AppError::Validation(_))), "{result:?}");
+
+        let cases = vec![LoadCase {
+            name: "Dead".to_string(),
+            kind: LoadCaseKind::Dead,
+        }];
+        state
+            .generate_load_combinations(project.id, cases, DesignCode::Asce7Lrfd)
+            .await
+            .unwrap();
+        // Generated, but there is no ETABS to write them to
+        let result = state.etabs_write_load_combinations(project.id).await;
+        assert!(matches!(result, Err(AppError::Etabs(_))), "{result:?}");
+    }
+
+    #[tokio::test]
+    async fn test_projects_with_the_same_model_file_are_duplicates() {
+        use ext_core::duplicates::DuplicateReason;
+
+        let temp = tempfile::tempdir().unwrap();
+        let state = state(&temp).await;
+        let fixture =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../ext-core/tests/fixtures/two_story.e2k");
+        let mut ids = Vec::new();
+        for name in ["Riverside", "Harbour View"] {
+            let project = state
+                .create_project(name.to_string(), String::new())
+                .await
+                .unwrap();
+            state.add_project_file(project.id, &fixture).await.unwrap();
+            ids.push(project.id);
+        }
+
+        let sets = state.find_duplicate_projects().await.unwrap();
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0].reason, DuplicateReason::SameModelFile);
+        let mut found: Vec<Uuid> = sets[0].projects.iter().map(|p| p.id).collect();
+        found.sort();
+        ids.sort();
+        assert_eq!(found, ids);
     }
 }
@@ -0,0 +1,58 @@
+//! Per-project locks for operations that touch a project's folder.
+//!
+//! The database serializes its own writes, so reads and writes of different
+//! projects run concurrently. A project's folder is only rewritten by one
+//! operation at a time, so two saves can't interleave their `project.json`
+//! writes.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct ProjectLocks {
+    locks: Mutex<HashMap<Uuid, Arc<AsyncMutex<()>>>>,
+}
+
+impl ProjectLocks {
+    /// Waits until no other operation holds `project_id`'s folder.
+    pub async fn lock(&self, project_id: Uuid) -> OwnedMutexGuard<()> {
+        let lock = {
+            let mut locks = self.locks.lock().unwrap_or_else(PoisonError::into_inner);
+            // Drop locks nobody holds or waits for, so the map stays small
+            locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+            locks.entry(project_id).or_default().clone()
+        };
+        lock.lock_owned().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_same_project_waits_other_project_does_not() {
+        let locks = Arc::new(ProjectLocks::default());
+        let a = Uuid::new_v4();
+        let held = locks.lock(a).await;
+
+        let other = tokio::time::timeout(Duration::from_millis(100), locks.lock(Uuid::new_v4()));
+        assert!(other.await.is_ok());
+
+        let waiting = tokio::spawn({
+            let locks = locks.clone();
+            async move { locks.lock(a).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiting.is_finished());
+
+        drop(held);
+        tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .expect("lock was not released")
+            .unwrap();
+    }
+}
@@ -0,0 +1,119 @@
+//! The backend log: JSON lines written by the desktop app's tracing
+//! subscriber, one file per day.
+
+use ext_db::paths::fs_error;
+use ext_error::Result;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+/// Daily files are named `backend.log.<yyyy-mm-dd>`
+pub const BACKEND_LOG_PREFIX: &str = "backend.log";
+
+/// Most lines returned by one request
+pub const MAX_RECENT_LOG_LINES: usize = 1000;
+
+/// Only the end of the file is read; a day at debug level can be large
+const TAIL_BYTES: u64 = 1024 * 1024;
+
+/// The last `limit` lines of today's log (or the newest one), oldest first.
+/// No log file yet is not an error.
+pub async fn recent_lines(dir: &Path, limit: usize) -> Result<Vec<String>> {
+    let Some(path) = newest_log_file(dir).await? else {
+        return Ok(Vec::new());
+    };
+
+    let mut file = fs::File::open(&path)
+        .await
+        .map_err(|e| fs_error("open log file", &path, e))?;
+    let length = file
+        .metadata()
+        .await
+        .map_err(|e| fs_error("read log file", &path, e))?
+        .len();
+    let start = length.saturating_sub(TAIL_BYTES);
+    file.seek(SeekFrom::Start(start))
+        .await
+        .map_err(|e| fs_error("read log file", &path, e))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)
+        .await
+        .map_err(|e| fs_error("read log file", &path, e))?;
+
+    let text = String::from_utf8_lossy(&bytes);
+    let mut lines: Vec<&str> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    // Reading from the middle of the file starts mid-line
+    if start > 0 && !lines.is_empty() {
+        lines.remove(0);
+    }
+    let skip = lines.len().saturating_sub(limit.min(MAX_RECENT_LOG_LINES));
+    Ok(lines[skip..].iter().map(|line| line.to_string()).collect())
+}
+
+/// Dated names sort chronologically, so the newest is the largest.
+async fn newest_log_file(dir: &Path) -> Result<Option<PathBuf>> {
+    let mut entries = match fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(fs_error("read log directory", dir, e)),
+    };
+
+    let mut newest: Option<PathBuf> = None;
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|e| fs_error("read log directory", dir, e))?
+    {
+        let name = entry.file_name();
+        if name.to_string_lossy().starts_with(BACKEND_LOG_PREFIX)
+            && newest
+                .as_ref()
+                .is_none_or(|current| current.file_name() < Some(name.as_os_str()))
+        {
+            newest = Some(entry.path());
+        }
+    }
+    Ok(newest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_recent_lines_come_from_newest_file() {
+        let temp = tempfile::tempdir().unwrap();
+        assert!(recent_lines(temp.path(), 10).await.unwrap().is_empty());
+
+        std::fs::write(temp.path().join("backend.log.2026-10-14"), "{\"day\":14}\n").unwrap();
+        let today: String = (0..5).map(|i| format!("{{\"line\":{i}}}\n")).collect();
+        std::fs::write(temp.path().join("backend.log.2026-10-15"), today).unwrap();
+        std::fs::write(temp.path().join("ui-logs.log"), "not ours\n").unwrap();
+
+        let lines = recent_lines(temp.path(), 2).await.unwrap();
+        assert_eq!(lines, vec!["{\"line\":3}", "{\"line\":4}"]);
+        assert_eq!(recent_lines(temp.path(), 100).await.unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_large_file_skips_partial_first_line() {
+        let temp = tempfile::tempdir().unwrap();
+        let line = format!("{{\"message\":\"{}\"}}\n", "x".repeat(1000));
+        let count = (TAIL_BYTES as usize / line.len()) + 10;
+        std::fs::write(
+            temp.path().join("backend.log.2026-10-15"),
+            line.repeat(count),
+        )
+        .unwrap();
+
+        let lines = recent_lines(temp.path(), MAX_RECENT_LOG_LINES)
+            .await
+            .unwrap();
+        assert!(!lines.is_empty());
+        assert!(lines.iter().all(|read| *read == line.trim_end()));
+    }
+}
@@ -0,0 +1,252 @@
+//! Background tasks of the session: the registry the frontend polls, and
+//! the context task work uses to report progress and notice cancellation.
+//!
+//! Cancelling a task only cancels its token; the work stops at its next
+//! [`TaskContext::checkpoint`], between steps that are safe to stop after.
+
+use crate::EventSink;
+use chrono::Utc;
+use ext_core::tasks::{
+    TaskInfo, TaskKind, TaskStatus, MAX_FINISHED_TASKS, TASK_COMPLETED, TASK_PROGRESS,
+};
+use ext_error::{AppError, Result};
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+struct Task {
+    info: TaskInfo,
+    token: CancellationToken,
+}
+
+/// Running tasks, and finished ones up to [`MAX_FINISHED_TASKS`]
+#[derive(Default)]
+pub struct TaskManager {
+    tasks: Mutex<Vec<Task>>,
+}
+
+impl TaskManager {
+    /// All known tasks, most recently started first.
+    pub fn list(&self) -> Vec<TaskInfo> {
+        let tasks = self.tasks.lock().unwrap_or_else(PoisonError::into_inner);
+        tasks.iter().rev().map(|task| task.info.clone()).collect()
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<TaskInfo> {
+        let tasks = self.tasks.lock().unwrap_or_else(PoisonError::into_inner);
+        tasks
+            .iter()
+            .find(|task| task.info.id == id)
+            .map(|task| task.info.clone())
+    }
+
+    /// Asks a running task to stop. Returns false when it already finished.
+    pub fn cancel(&self, id: Uuid) -> Result<bool> {
+        let tasks = self.tasks.lock().unwrap_or_else(PoisonError::into_inner);
+        let task = tasks
+            .iter()
+            .find(|task| task.info.id == id)
+            .ok_or_else(|| AppError::NotFound(format!("Task {id} not found")))?;
+        if task.info.is_finished() {
+            return Ok(false);
+        }
+        task.token.cancel();
+        Ok(true)
+    }
+
+    fn start(&self, kind: TaskKind) -> (TaskInfo, CancellationToken) {
+        let info = TaskInfo::new(kind);
+        let token = CancellationToken::new();
+        let mut tasks = self.tasks.lock().unwrap_or_else(PoisonError::into_inner);
+        tasks.push(Task {
+            info: info.clone(),
+            token: token.clone(),
+        });
+        (info, token)
+    }
+
+    fn update(&self, id: Uuid, apply: impl FnOnce(&mut TaskInfo)) -> Option<TaskInfo> {
+        let mut tasks = self.tasks.lock().unwrap_or_else(PoisonError::into_inner);
+        let task = tasks.iter_mut().find(|task| task.info.id == id)?;
+        apply(&mut task.info);
+        let info = task.info.clone();
+
+        // Tasks finish one at a time, so at most one has to go
+        let finished = tasks.iter().filter(|task| task.info.is_finished()).count();
+        if finished > MAX_FINISHED_TASKS
+            && let Some(oldest) = tasks
+                .iter()
+                .enumerate()
+                .filter(|(_, task)| task.info.is_finished())
+                .min_by_key(|(_, task)| task.info.finished_at)
+                .map(|(i, _)| i)
+        {
+            tasks.remove(oldest);
+        }
+        Some(info)
+    }
+}
+
+/// Handed to task work to report progress and check for cancellation
+#[derive(Clone)]
+pub struct TaskContext {
+    id: Uuid,
+    token: CancellationToken,
+    tasks: Option<Arc<TaskManager>>,
+    events: Option<Arc<dyn EventSink>>,
+}
+
+impl TaskContext {
+    /// A context for running task work inline rather than in the
+    /// background. Progress goes nowhere, and only [`TaskContext::cancel`]
+    /// cancels it.
+    pub fn detached() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            token: CancellationToken::new(),
+            tasks: None,
+            events: None,
+        }
+    }
+
+    pub(crate) fn start(
+        tasks: &Arc<TaskManager>,
+        events: Option<Arc<dyn EventSink>>,
+        kind: TaskKind,
+    ) -> Self {
+        let (info, token) = tasks.start(kind);
+        let context = Self {
+            id: info.id,
+            token,
+            tasks: Some(tasks.clone()),
+            events,
+        };
+        context.emit(TASK_PROGRESS, &info);
+        context
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Fails once the task has been cancelled.
+    pub fn checkpoint(&self) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(AppError::Validation(format!(
+                "Task {} was cancelled",
+                self.id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Records `progress`, a fraction from 0 to 1, and sends `task:progress`.
+    pub fn set_progress(&self, progress: f32) {
+        let progress = progress.clamp(0.0, 1.0);
+        if let Some(info) = self.update(|info| info.progress = progress) {
+            self.emit(TASK_PROGRESS, &info);
+        }
+    }
+
+    /// Records how the work ended and sends `task:completed`. A cancelled
+    /// task keeps whatever result its work returned.
+    pub(crate) fn finish(&self, outcome: Result<serde_json::Value>) {
+        let cancelled = self.is_cancelled();
+        let finished = self.update(|info| {
+            info.finished_at = Some(Utc::now());
+            match outcome {
+                Ok(value) => {
+                    info.result = Some(value);
+                    if !cancelled {
+                        info.progress = 1.0;
+                    }
+                }
+                Err(e) if !cancelled => info.error = Some(e.to_string()),
+                Err(_) => {}
+            }
+            info.status = if cancelled {
+                TaskStatus::Cancelled
+            } else if info.error.is_some() {
+                TaskStatus::Failed
+            } else {
+                TaskStatus::Done
+            };
+        });
+        if let Some(info) = finished {
+            self.emit(TASK_COMPLETED, &info);
+        }
+    }
+
+    fn update(&self, apply: impl FnOnce(&mut TaskInfo)) -> Option<TaskInfo> {
+        self.tasks.as_ref()?.update(self.id, apply)
+    }
+
+    fn emit(&self, event: &str, info: &TaskInfo) {
+        if let Some(sink) = &self.events
+            && let Err(e) = sink.emit_task(event, info)
+        {
+            tracing::warn!("Failed to emit {event}: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_only_running_tasks() {
+        let tasks = Arc::new(TaskManager::default());
+        let task = TaskContext::start(&tasks, None, TaskKind::BulkImport);
+        assert!(task.checkpoint().is_ok());
+
+        assert!(tasks.cancel(task.id()).unwrap());
+        assert!(task.checkpoint().is_err());
+        task.finish(Ok(serde_json::Value::Null));
+        assert_eq!(tasks.get(task.id()).unwrap().status, TaskStatus::Cancelled);
+
+        assert!(!tasks.cancel(task.id()).unwrap());
+        assert!(matches!(
+            tasks.cancel(Uuid::new_v4()),
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_failed_task_keeps_error() {
+        let tasks = Arc::new(TaskManager::default());
+        let task = TaskContext::start(&tasks, None, TaskKind::ProjectReport);
+        task.set_progress(1.5);
+        assert_eq!(tasks.get(task.id()).unwrap().progress, 1.0);
+
+        task.finish(Err(AppError::Etabs("e2k line 3: bad".to_string())));
+        let info = tasks.get(task.id()).unwrap();
+        assert_eq!(info.status, TaskStatus::Failed);
+        assert!(info.error.unwrap().contains("e2k line 3"));
+        assert!(info.finished_at.is_some());
+    }
+
+    #[test]
+    fn test_oldest_finished_tasks_are_dropped() {
+        let tasks = Arc::new(TaskManager::default());
+        let running = TaskContext::start(&tasks, None, TaskKind::BulkImport);
+        let first = TaskContext::start(&tasks, None, TaskKind::ImportEtabsModel);
+        first.finish(Ok(serde_json::Value::Null));
+        for _ in 0..MAX_FINISHED_TASKS {
+            TaskContext::start(&tasks, None, TaskKind::ImportEtabsModel)
+                .finish(Ok(serde_json::Value::Null));
+        }
+
+        assert_eq!(tasks.list().len(), MAX_FINISHED_TASKS + 1);
+        assert!(tasks.get(first.id()).is_none());
+        assert!(tasks.get(running.id()).is_some());
+    }
+}
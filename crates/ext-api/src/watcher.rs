@@ -0,0 +1,230 @@
+//! Watches the stored copies of files attached to open projects, so edits
+//! made in ETABS while the app is running are noticed.
+//!
+//! Each open project's `files/` folder is watched rather than the files
+//! themselves, since a save that replaces the file would end a per-file
+//! watch. Events are debounced because ETABS saves in several passes.
+
+use ext_core::ProjectFile;
+use ext_error::{AppError, Result};
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// Quiet period after the last write before a file is re-hashed
+pub const FILE_WATCH_DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// Attached file whose stored copy changed on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchedFile {
+    pub project_id: Uuid,
+    pub file_id: Uuid,
+}
+
+pub struct FileWatcher {
+    debouncer: Mutex<Debouncer<RecommendedWatcher>>,
+    /// Stored file names, which start with the file id and so are unique
+    /// across projects. Shared with the debouncer's event handler.
+    files: Arc<Mutex<HashMap<OsString, WatchedFile>>>,
+    /// Watched `files/` folder of each open project
+    projects: Mutex<HashMap<Uuid, PathBuf>>,
+}
+
+impl FileWatcher {
+    /// Starts the watcher. Changed files are sent to the returned receiver
+    /// once they have been quiet for `debounce`.
+    pub fn new(debounce: Duration) -> Result<(Self, mpsc::UnboundedReceiver<WatchedFile>)> {
+        let files: Arc<Mutex<HashMap<OsString, WatchedFile>>> = Arc::default();
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        let watched = files.clone();
+        let debouncer = new_debouncer(debounce, move |result: DebounceEventResult| {
+            let events = match result {
+                Ok(events) => events,
+                Err(e) => {
+                    tracing::warn!("File watcher error: {e}");
+                    return;
+                }
+            };
+            let watched = lock(&watched);
+            for event in events {
+                if let Some(file) = event.path.file_name().and_then(|name| watched.get(name)) {
+                    // The receiver is only gone while the app shuts down
+                    let _ = sender.send(*file);
+                }
+            }
+        })
+        .map_err(|e| AppError::Internal(format!("Failed to start file watcher: {e}")))?;
+
+        let watcher = Self {
+            debouncer: Mutex::new(debouncer),
+            files,
+            projects: Mutex::default(),
+        };
+        Ok((watcher, receiver))
+    }
+
+    /// Starts watching a project's attached files. Opening a project that
+    /// is already open only refreshes its file list.
+    pub fn watch_project(
+        &self,
+        project_id: Uuid,
+        files_dir: &Path,
+        files: &[ProjectFile],
+    ) -> Result<()> {
+        let mut projects = lock(&self.projects);
+        if !projects.contains_key(&project_id) {
+            std::fs::create_dir_all(files_dir).map_err(|e| {
+                AppError::FileSystem(format!(
+                    "Failed to create '{}': {e}",
+                    files_dir.display()
+                ))
+            })?;
+            lock(&self.debouncer)
+                .watcher()
+                .watch(files_dir, RecursiveMode::NonRecursive)
+                .map_err(|e| {
+                    AppError::FileSystem(format!(
+                        "Failed to watch '{}': {e}",
+                        files_dir.display()
+                    ))
+                })?;
+            projects.insert(project_id, files_dir.to_path_buf());
+        }
+
+        let mut watched = lock(&self.files);
+        watched.retain(|_, file| file.project_id != project_id);
+        for file in files {
+            if let Some(name) = stored_name(file) {
+                watched.insert(name, watched_file(file));
+            }
+        }
+        Ok(())
+    }
+
+    /// Stops watching a project's files; closing a project that is not
+    /// open does nothing.
+    pub fn unwatch_project(&self, project_id: Uuid) {
+        if let Some(files_dir) = lock(&self.projects).remove(&project_id) {
+            lock(&self.files).retain(|_, file| file.project_id != project_id);
+            // The folder is gone when the project was deleted
+            let _ = lock(&self.debouncer).watcher().unwatch(&files_dir);
+        }
+    }
+
+    pub fn is_open(&self, project_id: Uuid) -> bool {
+        lock(&self.projects).contains_key(&project_id)
+    }
+
+    /// Projects whose files are being watched.
+    pub fn open_projects(&self) -> Vec<Uuid> {
+        lock(&self.projects).keys().copied().collect()
+    }
+
+    /// Adds a newly attached file if its project is open.
+    pub fn watch_file(&self, file: &ProjectFile) {
+        if self.is_open(file.project_id)
+            && let Some(name) = stored_name(file)
+        {
+            lock(&self.files).insert(name, watched_file(file));
+        }
+    }
+
+    pub fn unwatch_file(&self, file_id: Uuid) {
+        lock(&self.files).retain(|_, file| file.file_id != file_id);
+    }
+}
+
+fn stored_name(file: &ProjectFile) -> Option<OsString> {
+    Path::new(&file.stored_path).file_name().map(OsString::from)
+}
+
+fn watched_file(file: &ProjectFile) -> WatchedFile {
+    WatchedFile {
+        project_id: file.project_id,
+        file_id: file.id,
+    }
+}
+
+/// A panic while holding a lock leaves the maps consistent, so poisoning
+/// is ignored rather than taking the watcher down.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attached(project_id: Uuid, name: &str) -> ProjectFile {
+        let id = Uuid::new_v4();
+        ProjectFile {
+            id,
+            project_id,
+            original_name: name.to_string(),
+            stored_path: format!("files/{id}_{name}"),
+            size: 0,
+            sha256: String::new(),
+            added_at: chrono::Utc::now(),
+            missing: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_debounced_write_burst_is_reported_once() {
+        let temp = tempfile::tempdir().unwrap();
+        let (watcher, mut changes) = FileWatcher::new(Duration::from_millis(200)).unwrap();
+        let project_id = Uuid::new_v4();
+        let file = attached(project_id, "Tower.EDB");
+        let files_dir = temp.path().join("files");
+        watcher
+            .watch_project(project_id, &files_dir, std::slice::from_ref(&file))
+            .unwrap();
+
+        let path = temp.path().join(&file.stored_path);
+        for pass in 0..3 {
+            std::fs::write(&path, format!("pass {pass}")).unwrap();
+        }
+        std::fs::write(files_dir.join("Tower.$et"), "backup").unwrap();
+
+        let changed = tokio::time::timeout(Duration::from_secs(10), changes.recv())
+            .await
+            .expect("no change reported")
+            .unwrap();
+        assert_eq!(changed, watched_file(&file));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(600), changes.recv())
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_files_of_closed_projects_are_not_watched() {
+        let temp = tempfile::tempdir().unwrap();
+        let (watcher, _changes) = FileWatcher::new(FILE_WATCH_DEBOUNCE).unwrap();
+        let project_id = Uuid::new_v4();
+
+        watcher.watch_file(&attached(project_id, "Tower.EDB"));
+        assert!(lock(&watcher.files).is_empty());
+
+        watcher
+            .watch_project(project_id, &temp.path().join("files"), &[])
+            .unwrap();
+        let file = attached(project_id, "Tower.EDB");
+        watcher.watch_file(&file);
+        assert_eq!(lock(&watcher.files).len(), 1);
+
+        watcher.unwatch_file(file.id);
+        assert!(lock(&watcher.files).is_empty());
+
+        watcher.unwatch_project(project_id);
+        assert!(!watcher.is_open(project_id));
+    }
+}
@@ -0,0 +1,161 @@
+//! History of changes made to a project, kept for traceability of
+//! engineering decisions.
+
+use crate::{Project, ProjectStatus};
+use chrono::{DateTime, Utc};
+use ext_error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Most entries one page of the audit log can hold
+pub const MAX_AUDIT_PAGE_SIZE: usize = 200;
+
+/// Kind of change recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum AuditAction {
+    Created,
+    Imported,
+    Updated,
+    Archived,
+    /// Brought back from the archive or the trash
+    Restored,
+    Trashed,
+    FileAttached,
+    FileRemoved,
+    /// Another project was merged into this one, or this one into another
+    Merged,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Created => "Created",
+            AuditAction::Imported => "Imported",
+            AuditAction::Updated => "Updated",
+            AuditAction::Archived => "Archived",
+            AuditAction::Restored => "Restored",
+            AuditAction::Trashed => "Trashed",
+            AuditAction::FileAttached => "FileAttached",
+            AuditAction::FileRemoved => "FileRemoved",
+            AuditAction::Merged => "Merged",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "Created" => Ok(AuditAction::Created),
+            "Imported" => Ok(AuditAction::Imported),
+            "Updated" => Ok(AuditAction::Updated),
+            "Archived" => Ok(AuditAction::Archived),
+            "Restored" => Ok(AuditAction::Restored),
+            "Trashed" => Ok(AuditAction::Trashed),
+            "FileAttached" => Ok(AuditAction::FileAttached),
+            "FileRemoved" => Ok(AuditAction::FileRemoved),
+            "Merged" => Ok(AuditAction::Merged),
+            other => Err(AppError::Validation(format!("Unknown audit action: '{other}'"))),
+        }
+    }
+}
+
+/// One recorded change to a project
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct AuditEntry {
+    #[ts(type = "string")]
+    pub id: Uuid,
+    #[ts(type = "string")]
+    pub project_id: Uuid,
+    pub action: AuditAction,
+    /// Human-readable description, e.g. `Renamed "Tower A" to "Tower B"`
+    pub detail: String,
+    #[ts(type = "string")]
+    pub timestamp: DateTime<Utc>,
+}
+
+impl AuditEntry {
+    pub fn new(project_id: Uuid, action: AuditAction, detail: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            project_id,
+            action,
+            detail,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Describes an edit of `before` into `after`. A status change is
+    /// recorded as its own action; otherwise the changed fields are named.
+    pub fn for_update(before: &Project, after: &Project) -> Self {
+        let (action, detail) = if before.status != after.status {
+            match after.status {
+                ProjectStatus::Active if before.status == ProjectStatus::Trashed => {
+                    (AuditAction::Restored, "Restored from trash".to_string())
+                }
+                ProjectStatus::Active => {
+                    (AuditAction::Restored, "Restored from archive".to_string())
+                }
+                ProjectStatus::Archived => (AuditAction::Archived, "Archived".to_string()),
+                ProjectStatus::Trashed => (AuditAction::Trashed, "Moved to trash".to_string()),
+            }
+        } else {
+            let mut changes = Vec::new();
+            if before.name != after.name {
+                changes.push(format!("Renamed \"{}\" to \"{}\"", before.name, after.name));
+            }
+            if before.description != after.description {
+                changes.push("Changed description".to_string());
+            }
+            if changes.is_empty() {
+                changes.push("Saved without changes".to_string());
+            }
+            (AuditAction::Updated, changes.join("; "))
+        };
+
+        Self::new(after.id, action, detail)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_detail_names_changes() {
+        let before = Project::new("Tower A".to_string(), String::new());
+        let mut after = before.clone();
+        after.name = "Tower B".to_string();
+        after.description = "Phase 2".to_string();
+
+        let entry = AuditEntry::for_update(&before, &after);
+        assert_eq!(entry.action, AuditAction::Updated);
+        assert_eq!(entry.detail, "Renamed \"Tower A\" to \"Tower B\"; Changed description");
+
+        after.set_status(ProjectStatus::Trashed);
+        assert_eq!(AuditEntry::for_update(&before, &after).action, AuditAction::Trashed);
+        let restored = AuditEntry::for_update(&after, &before);
+        assert_eq!(restored.action, AuditAction::Restored);
+        assert_eq!(restored.detail, "Restored from trash");
+    }
+
+    #[test]
+    fn test_action_round_trips_through_str() {
+        for action in [AuditAction::Created, AuditAction::FileAttached, AuditAction::Trashed] {
+            assert_eq!(AuditAction::parse(action.as_str()).unwrap(), action);
+        }
+        assert!(AuditAction::parse("Renamed").is_err());
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        AuditAction::export(&Default::default()).expect("Failed to export AuditAction");
+        AuditEntry::export(&Default::default()).expect("Failed to export AuditEntry");
+    }
+}
@@ -0,0 +1,85 @@
+//! Registering a folder of existing ETABS models as projects in one go,
+//! one project per model file.
+
+use crate::Project;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use ts_rs::TS;
+
+/// Event sent after each file of a bulk import
+pub const BULK_IMPORT_PROGRESS: &str = "bulk-import:progress";
+
+/// Extensions of the ETABS files a bulk import picks up
+pub const ETABS_EXTENSIONS: [&str; 2] = ["edb", "e2k"];
+
+/// Payload of `bulk-import:progress`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct BulkImportProgress {
+    /// Files handled so far, including `path`
+    pub current: usize,
+    pub total: usize,
+    pub path: String,
+}
+
+/// Outcome of a bulk import
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct BulkImportReport {
+    pub created: Vec<Project>,
+    /// (path, reason) of files that were not imported
+    pub skipped: Vec<(String, String)>,
+    /// Stopped by the user; files after the last one reported were not
+    /// looked at
+    pub cancelled: bool,
+}
+
+/// Whether `path` has an ETABS model extension, in any case.
+pub fn is_etabs_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ETABS_EXTENSIONS.iter().any(|e| ext.eq_ignore_ascii_case(e)))
+}
+
+/// Project name for a model file: its name without the extension.
+pub fn project_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().trim().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_etabs_files_are_recognized_by_extension() {
+        assert!(is_etabs_file(Path::new("Tower A.edb")));
+        assert!(is_etabs_file(Path::new("models/Tower A.E2K")));
+        assert!(!is_etabs_file(Path::new("Tower A.$et")));
+        assert!(!is_etabs_file(Path::new("Tower A.edb.bak")));
+        assert!(!is_etabs_file(Path::new("edb")));
+    }
+
+    #[test]
+    fn test_project_name_drops_extension_only() {
+        assert_eq!(
+            project_name(Path::new("dir/Tower A Rev.2.edb")),
+            "Tower A Rev.2"
+        );
+        assert_eq!(project_name(Path::new(" Podium .e2k")), "Podium");
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        BulkImportProgress::export(&Default::default())
+            .expect("Failed to export BulkImportProgress");
+        BulkImportReport::export(&Default::default()).expect("Failed to export BulkImportReport");
+    }
+}
@@ -0,0 +1,405 @@
+//! Load combination presets (ASCE 7-16 §2.3 strength, §2.4 allowable stress).
+//!
+//! Wind and seismic cases are expanded per case and in both directions.
+//! Combinations whose governing load has no case are skipped; other absent
+//! loads are simply left out. Vertical seismic (Ev) is not generated.
+
+use ext_error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use ts_rs::TS;
+
+/// What a load case represents, which decides its factors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum LoadCaseKind {
+    Dead,
+    Live,
+    RoofLive,
+    Snow,
+    Wind,
+    Seismic,
+}
+
+/// A load case defined in the ETABS model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct LoadCase {
+    pub name: String,
+    pub kind: LoadCaseKind,
+}
+
+/// Design method the combinations are generated for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum DesignCode {
+    Asce7Lrfd,
+    Asce7Asd,
+}
+
+/// A linear combination of load cases
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct LoadCombination {
+    pub name: String,
+    /// (case name, factor); lateral cases are negative in the reverse direction
+    pub factors: Vec<(String, f64)>,
+}
+
+/// One load in a combination template
+#[derive(Debug, Clone, Copy)]
+enum Load {
+    /// Every case of the kind; left out when there are none
+    Kind(LoadCaseKind),
+    /// "Lr or S": one combination per kind present; left out when neither is
+    RoofOrSnow,
+    /// One combination per case and direction; the template is skipped
+    /// when there are none
+    Lateral(LoadCaseKind),
+}
+
+struct Template {
+    terms: &'static [(Load, f64)],
+    /// Skip the template unless this load has a case
+    governing: Option<Load>,
+}
+
+const fn template(terms: &'static [(Load, f64)]) -> Template {
+    Template {
+        terms,
+        governing: None,
+    }
+}
+
+const fn governed_by(load: Load, terms: &'static [(Load, f64)]) -> Template {
+    Template {
+        terms,
+        governing: Some(load),
+    }
+}
+
+use Load::{Kind, Lateral, RoofOrSnow};
+use LoadCaseKind::{Dead, Live, Seismic, Snow, Wind};
+
+const LRFD: &[Template] = &[
+    template(&[(Kind(Dead), 1.4)]),
+    template(&[(Kind(Dead), 1.2), (Kind(Live), 1.6), (RoofOrSnow, 0.5)]),
+    governed_by(RoofOrSnow, &[(Kind(Dead), 1.2), (RoofOrSnow, 1.6), (Kind(Live), 1.0)]),
+    governed_by(RoofOrSnow, &[(Kind(Dead), 1.2), (RoofOrSnow, 1.6), (Lateral(Wind), 0.5)]),
+    template(&[
+        (Kind(Dead), 1.2),
+        (Lateral(Wind), 1.0),
+        (Kind(Live), 1.0),
+        (RoofOrSnow, 0.5),
+    ]),
+    template(&[(Kind(Dead), 0.9), (Lateral(Wind), 1.0)]),
+    template(&[
+        (Kind(Dead), 1.2),
+        (Lateral(Seismic), 1.0),
+        (Kind(Live), 1.0),
+        (Kind(Snow), 0.2),
+    ]),
+    template(&[(Kind(Dead), 0.9), (Lateral(Seismic), 1.0)]),
+];
+
+const ASD: &[Template] = &[
+    template(&[(Kind(Dead), 1.0)]),
+    template(&[(Kind(Dead), 1.0), (Kind(Live), 1.0)]),
+    governed_by(RoofOrSnow, &[(Kind(Dead), 1.0), (RoofOrSnow, 1.0)]),
+    governed_by(RoofOrSnow, &[(Kind(Dead), 1.0), (Kind(Live), 0.75), (RoofOrSnow, 0.75)]),
+    template(&[(Kind(Dead), 1.0), (Lateral(Wind), 0.6)]),
+    template(&[(Kind(Dead), 1.0), (Lateral(Seismic), 0.7)]),
+    template(&[
+        (Kind(Dead), 1.0),
+        (Kind(Live), 0.75),
+        (Lateral(Wind), 0.45),
+        (RoofOrSnow, 0.75),
+    ]),
+    template(&[
+        (Kind(Dead), 1.0),
+        (Kind(Live), 0.75),
+        (Lateral(Seismic), 0.525),
+        (Kind(Snow), 0.75),
+    ]),
+    template(&[(Kind(Dead), 0.6), (Lateral(Wind), 0.6)]),
+    template(&[(Kind(Dead), 0.6), (Lateral(Seismic), 0.7)]),
+];
+
+impl DesignCode {
+    fn templates(&self) -> &'static [Template] {
+        match self {
+            DesignCode::Asce7Lrfd => LRFD,
+            DesignCode::Asce7Asd => ASD,
+        }
+    }
+
+    /// The code `combinations` were generated for, read from their names;
+    /// `None` when they were not generated here.
+    pub fn of_generated(combinations: &[LoadCombination]) -> Option<Self> {
+        let first = combinations.first()?;
+        [DesignCode::Asce7Lrfd, DesignCode::Asce7Asd]
+            .into_iter()
+            .find(|code| {
+                first
+                    .name
+                    .strip_prefix(code.prefix())
+                    .is_some_and(|number| number.parse::<usize>().is_ok())
+            })
+    }
+
+    fn prefix(&self) -> &'static str {
+        match self {
+            DesignCode::Asce7Lrfd => "LRFD",
+            DesignCode::Asce7Asd => "ASD",
+        }
+    }
+}
+
+/// Rejects blank or repeated case names and inputs without a dead load.
+pub fn validate_cases(cases: &[LoadCase]) -> Result<()> {
+    let mut errors = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (i, case) in cases.iter().enumerate() {
+        let name = case.name.trim();
+        if name.is_empty() {
+            errors.push(format!("cases[{i}].name must not be empty"));
+        } else if !seen.insert(name.to_lowercase()) {
+            errors.push(format!("cases[{i}].name '{name}' is used more than once"));
+        }
+    }
+    if !cases.iter().any(|case| case.kind == Dead) {
+        errors.push("at least one Dead case is required".to_string());
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Validation(errors.join("; ")))
+    }
+}
+
+/// Generates the preset combinations of `code` for the given cases, named
+/// `LRFD1`, `LRFD2`, … in code order. Duplicates are dropped.
+pub fn generate_combinations(
+    cases: Vec<LoadCase>,
+    code: DesignCode,
+) -> Result<Vec<LoadCombination>> {
+    validate_cases(&cases)?;
+
+    let of_kind = |kind: LoadCaseKind| -> Vec<&str> {
+        cases
+            .iter()
+            .filter(|case| case.kind == kind)
+            .map(|case| case.name.trim())
+            .collect()
+    };
+    // The alternatives each term expands to; each is a list of (case, factor)
+    let alternatives = |load: Load, factor: f64| -> Vec<Vec<(String, f64)>> {
+        let all = |kind: LoadCaseKind| {
+            of_kind(kind)
+                .into_iter()
+                .map(|name| (name.to_string(), factor))
+                .collect::<Vec<_>>()
+        };
+        match load {
+            Kind(kind) => vec![all(kind)],
+            RoofOrSnow => {
+                let present: Vec<_> = [LoadCaseKind::RoofLive, Snow]
+                    .into_iter()
+                    .map(all)
+                    .filter(|terms| !terms.is_empty())
+                    .collect();
+                if present.is_empty() {
+                    vec![Vec::new()]
+                } else {
+                    present
+                }
+            }
+            Lateral(kind) => of_kind(kind)
+                .into_iter()
+                .flat_map(|name| [factor, -factor].map(|f| vec![(name.to_string(), f)]))
+                .collect(),
+        }
+    };
+    let has_case = |load: Load| match load {
+        Kind(kind) | Lateral(kind) => !of_kind(kind).is_empty(),
+        RoofOrSnow => !of_kind(LoadCaseKind::RoofLive).is_empty() || !of_kind(Snow).is_empty(),
+    };
+
+    let mut combinations: Vec<Vec<(String, f64)>> = Vec::new();
+    for template in code.templates() {
+        if template.governing.is_some_and(|load| !has_case(load)) {
+            continue;
+        }
+
+        let mut expanded: Vec<Vec<(String, f64)>> = vec![Vec::new()];
+        for &(load, factor) in template.terms {
+            let options = alternatives(load, factor);
+            expanded = expanded
+                .iter()
+                .flat_map(|prefix| {
+                    options.iter().map(move |option| {
+                        let mut factors = prefix.clone();
+                        factors.extend(option.iter().cloned());
+                        factors
+                    })
+                })
+                .collect();
+        }
+
+        for factors in expanded {
+            if !factors.is_empty() && !combinations.contains(&factors) {
+                combinations.push(factors);
+            }
+        }
+    }
+
+    Ok(combinations
+        .into_iter()
+        .enumerate()
+        .map(|(i, factors)| LoadCombination {
+            name: format!("{}{}", code.prefix(), i + 1),
+            factors,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn case(name: &str, kind: LoadCaseKind) -> LoadCase {
+        LoadCase {
+            name: name.to_string(),
+            kind,
+        }
+    }
+
+    fn standard_cases() -> Vec<LoadCase> {
+        vec![
+            case("D", Dead),
+            case("L", Live),
+            case("W", Wind),
+            case("E", Seismic),
+        ]
+    }
+
+    fn factors(combinations: &[LoadCombination]) -> Vec<Vec<(&str, f64)>> {
+        combinations
+            .iter()
+            .map(|c| c.factors.iter().map(|(name, f)| (name.as_str(), *f)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_lrfd_standard_set() {
+        let combinations = generate_combinations(standard_cases(), DesignCode::Asce7Lrfd).unwrap();
+
+        assert_eq!(
+            factors(&combinations),
+            vec![
+                vec![("D", 1.4)],
+                vec![("D", 1.2), ("L", 1.6)],
+                vec![("D", 1.2), ("W", 1.0), ("L", 1.0)],
+                vec![("D", 1.2), ("W", -1.0), ("L", 1.0)],
+                vec![("D", 0.9), ("W", 1.0)],
+                vec![("D", 0.9), ("W", -1.0)],
+                vec![("D", 1.2), ("E", 1.0), ("L", 1.0)],
+                vec![("D", 1.2), ("E", -1.0), ("L", 1.0)],
+                vec![("D", 0.9), ("E", 1.0)],
+                vec![("D", 0.9), ("E", -1.0)],
+            ]
+        );
+        assert_eq!(combinations[0].name, "LRFD1");
+        assert_eq!(combinations[9].name, "LRFD10");
+    }
+
+    #[test]
+    fn test_asd_standard_set() {
+        let combinations = generate_combinations(standard_cases(), DesignCode::Asce7Asd).unwrap();
+
+        assert_eq!(
+            factors(&combinations),
+            vec![
+                vec![("D", 1.0)],
+                vec![("D", 1.0), ("L", 1.0)],
+                vec![("D", 1.0), ("W", 0.6)],
+                vec![("D", 1.0), ("W", -0.6)],
+                vec![("D", 1.0), ("E", 0.7)],
+                vec![("D", 1.0), ("E", -0.7)],
+                vec![("D", 1.0), ("L", 0.75), ("W", 0.45)],
+                vec![("D", 1.0), ("L", 0.75), ("W", -0.45)],
+                vec![("D", 1.0), ("L", 0.75), ("E", 0.525)],
+                vec![("D", 1.0), ("L", 0.75), ("E", -0.525)],
+                vec![("D", 0.6), ("W", 0.6)],
+                vec![("D", 0.6), ("W", -0.6)],
+                vec![("D", 0.6), ("E", 0.7)],
+                vec![("D", 0.6), ("E", -0.7)],
+            ]
+        );
+        assert!(combinations.iter().all(|c| c.name.starts_with("ASD")));
+        assert_eq!(DesignCode::of_generated(&combinations), Some(DesignCode::Asce7Asd));
+    }
+
+    #[test]
+    fn test_missing_wind_skips_wind_combinations() {
+        let cases = vec![case("D", Dead), case("L", Live), case("E", Seismic)];
+        let combinations = generate_combinations(cases, DesignCode::Asce7Lrfd).unwrap();
+
+        assert_eq!(combinations.len(), 6);
+        assert!(combinations.iter().all(|c| c.factors.iter().all(|(name, _)| name != "W")));
+    }
+
+    #[test]
+    fn test_roof_live_and_snow_each_get_combinations() {
+        let cases = vec![case("D", Dead), case("Lr", LoadCaseKind::RoofLive), case("S", Snow)];
+        let combinations = generate_combinations(cases, DesignCode::Asce7Lrfd).unwrap();
+
+        let all = factors(&combinations);
+        assert!(all.contains(&vec![("D", 1.2), ("Lr", 1.6)]));
+        assert!(all.contains(&vec![("D", 1.2), ("S", 1.6)]));
+        assert!(all.contains(&vec![("D", 1.2), ("Lr", 0.5)]));
+    }
+
+    #[test]
+    fn test_each_wind_direction_is_expanded() {
+        let cases = vec![case("D", Dead), case("WX", Wind), case("WY", Wind)];
+        let combinations = generate_combinations(cases, DesignCode::Asce7Lrfd).unwrap();
+
+        // 1.4D, 1.2D, then 1.2D ± W and 0.9D ± W for both directions
+        assert_eq!(combinations.len(), 10);
+    }
+
+    #[test]
+    fn test_dead_case_is_required() {
+        let cases = vec![case("L", Live), case("l", Live)];
+        match generate_combinations(cases, DesignCode::Asce7Asd) {
+            Err(AppError::Validation(message)) => {
+                assert!(message.contains("more than once"));
+                assert!(message.contains("Dead"));
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        LoadCaseKind::export(&Default::default()).expect("Failed to export LoadCaseKind");
+        LoadCase::export(&Default::default()).expect("Failed to export LoadCase");
+        DesignCode::export(&Default::default()).expect("Failed to export DesignCode");
+        LoadCombination::export(&Default::default()).expect("Failed to export LoadCombination");
+    }
+}
@@ -0,0 +1,118 @@
+//! `etabext://` links that open the app at a specific place, such as a
+//! "Copy link" pasted into a chat or an email.
+
+use ext_error::{AppError, Result};
+use uuid::Uuid;
+
+/// URL scheme registered with the operating system
+pub const DEEP_LINK_SCHEME: &str = "etabext";
+
+/// Sent with the `Project` a link points at
+pub const NAVIGATE_PROJECT: &str = "navigate:project";
+/// Sent with the `AppError` when a link can't be followed
+pub const NAVIGATE_ERROR: &str = "navigate:error";
+
+/// Where a deep link points
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeepLink {
+    Project(Uuid),
+}
+
+impl DeepLink {
+    /// Canonical URL: lowercase hyphenated id, no trailing slash.
+    pub fn url(&self) -> String {
+        match self {
+            DeepLink::Project(id) => format!("{DEEP_LINK_SCHEME}://project/{id}"),
+        }
+    }
+
+    /// Parses `etabext://project/<uuid>`. The scheme and host are case
+    /// insensitive; trailing slashes, a query and a fragment are ignored.
+    pub fn parse(url: &str) -> Result<Self> {
+        let invalid =
+            |reason: &str| AppError::Validation(format!("Invalid link '{url}': {reason}"));
+
+        let url = url.trim();
+        let rest = url
+            .split_once("://")
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case(DEEP_LINK_SCHEME))
+            .map(|(_, rest)| rest)
+            .ok_or_else(|| invalid(&format!("expected a {DEEP_LINK_SCHEME}:// link")))?;
+        let path = rest.split(['?', '#']).next().unwrap_or_default();
+
+        let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+        match (segments.next(), segments.next(), segments.next()) {
+            (Some(kind), Some(id), None) if kind.eq_ignore_ascii_case("project") => {
+                Uuid::parse_str(id)
+                    .map(DeepLink::Project)
+                    .map_err(|_| invalid(&format!("'{id}' is not a project id")))
+            }
+            _ => Err(invalid("unknown path")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+    fn project(url: &str) -> Uuid {
+        match DeepLink::parse(url) {
+            Ok(DeepLink::Project(id)) => id,
+            Err(e) => panic!("{url}: {e:?}"),
+        }
+    }
+
+    #[test]
+    fn test_canonical_url_round_trips() {
+        let id = Uuid::parse_str(ID).unwrap();
+        let url = DeepLink::Project(id).url();
+        assert_eq!(url, format!("etabext://project/{ID}"));
+        assert_eq!(project(&url), id);
+    }
+
+    #[test]
+    fn test_trailing_slashes_are_ignored() {
+        let id = Uuid::parse_str(ID).unwrap();
+        assert_eq!(project(&format!("etabext://project/{ID}/")), id);
+        assert_eq!(project(&format!("etabext://project//{ID}//")), id);
+    }
+
+    #[test]
+    fn test_uppercase_uuid_and_scheme() {
+        let id = Uuid::parse_str(ID).unwrap();
+        let upper = ID.to_uppercase();
+        assert_eq!(project(&format!("etabext://project/{upper}")), id);
+        assert_eq!(project(&format!("ETABEXT://Project/{upper}")), id);
+    }
+
+    #[test]
+    fn test_query_and_fragment_are_ignored() {
+        let id = Uuid::parse_str(ID).unwrap();
+        assert_eq!(
+            project(&format!("etabext://project/{ID}?tab=files&from=mail")),
+            id
+        );
+        assert_eq!(project(&format!("etabext://project/{ID}/?tab=files")), id);
+        assert_eq!(project(&format!("etabext://project/{ID}#drift")), id);
+    }
+
+    #[test]
+    fn test_invalid_links_are_rejected() {
+        for url in [
+            "etabext://project/not-a-uuid".to_string(),
+            "etabext://project/".to_string(),
+            format!("etabext://settings/{ID}"),
+            format!("etabext://project/{ID}/files"),
+            format!("https://project/{ID}"),
+            format!("project/{ID}"),
+        ] {
+            assert!(
+                matches!(DeepLink::parse(&url), Err(AppError::Validation(_))),
+                "{url} was accepted"
+            );
+        }
+    }
+}
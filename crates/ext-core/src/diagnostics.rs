@@ -0,0 +1,90 @@
+//! Snapshot of the app's storage state for support requests, and the
+//! report of an explicit storage repair.
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Storage health, gathered without modifying anything
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct Diagnostics {
+    /// SQLite file; `None` for an in-memory database
+    pub database_path: Option<String>,
+    /// Size of the database file in bytes
+    #[ts(type = "number")]
+    pub database_size: u64,
+    /// "ok", or the problems reported by SQLite's integrity_check
+    pub integrity_check: String,
+    pub projects_dir: String,
+    /// Bytes available on the volume holding the projects directory, if known
+    #[ts(type = "number | null")]
+    pub free_space: Option<u64>,
+    #[ts(type = "number")]
+    pub project_count: u64,
+    /// Project folders without a registry row
+    #[ts(type = "Array<string>")]
+    pub orphaned_folders: Vec<Uuid>,
+    /// Registered projects whose folder is missing
+    #[ts(type = "Array<string>")]
+    pub orphaned_rows: Vec<Uuid>,
+    pub log_dir: Option<String>,
+}
+
+impl Diagnostics {
+    pub fn is_healthy(&self) -> bool {
+        self.integrity_check == "ok"
+            && self.orphaned_folders.is_empty()
+            && self.orphaned_rows.is_empty()
+    }
+}
+
+/// What a storage repair does with projects whose folder is missing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum OrphanedRowAction {
+    /// Move them to the trash, so they can be reviewed before removal
+    #[default]
+    Trash,
+    /// Delete their rows right away
+    Remove,
+}
+
+/// What a storage repair changed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct RepairReport {
+    /// Orphaned folders registered again from their project.json
+    #[ts(type = "Array<string>")]
+    pub registered: Vec<Uuid>,
+    /// Projects without a folder that were moved to the trash
+    #[ts(type = "Array<string>")]
+    pub trashed: Vec<Uuid>,
+    /// Projects without a folder whose rows were deleted
+    #[ts(type = "Array<string>")]
+    pub removed: Vec<Uuid>,
+    /// Folders that could not be registered, with the reason
+    pub skipped: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        Diagnostics::export(&Default::default()).expect("Failed to export Diagnostics");
+        OrphanedRowAction::export(&Default::default())
+            .expect("Failed to export OrphanedRowAction");
+        RepairReport::export(&Default::default()).expect("Failed to export RepairReport");
+    }
+}
@@ -0,0 +1,268 @@
+//! Story drift check against an allowable drift ratio, e.g. 0.020·hsx for
+//! ASCE 7-16 Table 12.12-1 risk category I/II "all other structures".
+//!
+//! Drift ratios come from an imported analysis run and story heights from
+//! the project's `.e2k` model; drifts and limits are reported in metres.
+
+use crate::e2k::Story;
+use crate::results::{Direction, StoryDrift};
+use crate::units::LengthUnit;
+use ext_error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Allowable drift ratio used when none is given: ASCE 7-16 Table 12.12-1,
+/// risk category I/II, all other structures
+pub const DEFAULT_DRIFT_LIMIT_RATIO: f64 = 0.020;
+
+/// Drift check of one story in one direction, enveloped over load cases
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct DriftCheckReport {
+    pub story: String,
+    pub direction: Direction,
+    /// Load case producing the largest drift
+    pub load_case: String,
+    /// Story drift Δ = drift ratio · hsx, in metres
+    pub drift: f64,
+    /// Allowable drift Δa = limit ratio · hsx, in metres
+    pub limit: f64,
+    pub utilization: f64,
+    pub pass: bool,
+    /// Highest utilization in its direction
+    pub governing: bool,
+}
+
+/// Checks every story and direction in `drifts` against `limit_ratio`.
+///
+/// Rows are ordered X then Y, each following the model's story order. Fails
+/// with a validation error naming every story without a positive height.
+pub fn check_drift_limits(
+    drifts: &[StoryDrift],
+    stories: &[Story],
+    limit_ratio: f64,
+) -> Result<Vec<DriftCheckReport>> {
+    if !(limit_ratio.is_finite() && limit_ratio > 0.0) {
+        return Err(AppError::Validation(format!(
+            "limit_ratio must be positive (got {limit_ratio})"
+        )));
+    }
+    if drifts.is_empty() {
+        return Err(AppError::Validation(
+            "The analysis run has no story drifts".to_string(),
+        ));
+    }
+
+    // Largest drift of each story and direction over all load cases
+    let mut envelope: Vec<&StoryDrift> = Vec::new();
+    for drift in drifts {
+        match envelope
+            .iter_mut()
+            .find(|e| e.story == drift.story && e.direction == drift.direction)
+        {
+            Some(existing) if drift.drift.abs() > existing.drift.abs() => *existing = drift,
+            Some(_) => {}
+            None => envelope.push(drift),
+        }
+    }
+
+    let story_index = |name: &str| stories.iter().position(|s| s.name == name);
+
+    let mut missing: Vec<&str> = Vec::new();
+    let mut rows = Vec::with_capacity(envelope.len());
+    for drift in envelope {
+        let height = story_index(&drift.story)
+            .map(|i| stories[i].height.to(LengthUnit::Meter))
+            .filter(|h| h.is_finite() && *h > 0.0);
+        let Some(height) = height else {
+            if !missing.contains(&drift.story.as_str()) {
+                missing.push(&drift.story);
+            }
+            continue;
+        };
+
+        let delta = drift.drift.abs() * height;
+        let limit = limit_ratio * height;
+        let utilization = delta / limit;
+        rows.push(DriftCheckReport {
+            story: drift.story.clone(),
+            direction: drift.direction,
+            load_case: drift.load_case.clone(),
+            drift: delta,
+            limit,
+            utilization,
+            pass: utilization <= 1.0,
+            governing: false,
+        });
+    }
+
+    if !missing.is_empty() {
+        return Err(AppError::Validation(format!(
+            "No story height for {}; re-import the ETABS model so every story has a height",
+            missing
+                .iter()
+                .map(|name| format!("'{name}'"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )));
+    }
+
+    rows.sort_by_key(|row| (row.direction == Direction::Y, story_index(&row.story)));
+
+    for direction in [Direction::X, Direction::Y] {
+        let governing = rows
+            .iter_mut()
+            .filter(|row| row.direction == direction)
+            .reduce(|best, row| if row.utilization > best.utilization { row } else { best });
+        if let Some(row) = governing {
+            row.governing = true;
+        }
+    }
+
+    Ok(rows)
+}
+
+/// Renders the report as CSV for calc packages.
+pub fn report_csv(rows: &[DriftCheckReport]) -> String {
+    let mut csv =
+        String::from("Story,Direction,Load Case,Drift (m),Limit (m),Utilization,Pass,Governing\n");
+    for row in rows {
+        csv.push_str(&format!(
+            "{},{},{},{:.6},{:.6},{:.3},{},{}\n",
+            csv_cell(&row.story),
+            row.direction.as_str(),
+            csv_cell(&row.load_case),
+            row.drift,
+            row.limit,
+            row.utilization,
+            if row.pass { "PASS" } else { "FAIL" },
+            if row.governing { "Yes" } else { "" },
+        ));
+    }
+    csv
+}
+
+/// Quotes a cell containing a separator, quote or line break, or with
+/// whitespace at either end that a reader would trim.
+pub(crate) fn csv_cell(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) || value.trim() != value {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::Length;
+
+    fn story(name: &str, height: f64) -> Story {
+        Story {
+            name: name.to_string(),
+            height: Length::from(height, LengthUnit::Meter),
+            elevation: Length::default(),
+            master_story: false,
+            similar_to: None,
+        }
+    }
+
+    fn drift(story: &str, load_case: &str, direction: Direction, drift: f64) -> StoryDrift {
+        StoryDrift {
+            story: story.to_string(),
+            load_case: load_case.to_string(),
+            direction,
+            drift,
+        }
+    }
+
+    fn stories() -> Vec<Story> {
+        vec![story("Roof", 3.0), story("L2", 4.0), story("Base", 0.0)]
+    }
+
+    #[test]
+    fn test_utilization_and_governing_story() {
+        let drifts = vec![
+            drift("L2", "EQX", Direction::X, 0.010),
+            drift("Roof", "EQX", Direction::X, 0.015),
+            drift("Roof", "EQY", Direction::Y, -0.025),
+            drift("L2", "EQY", Direction::Y, 0.012),
+        ];
+        let rows = check_drift_limits(&drifts, &stories(), 0.020).unwrap();
+
+        let summary: Vec<_> = rows
+            .iter()
+            .map(|r| (r.story.as_str(), r.direction, r.pass, r.governing))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Roof", Direction::X, true, true),
+                ("L2", Direction::X, true, false),
+                ("Roof", Direction::Y, false, true),
+                ("L2", Direction::Y, true, false),
+            ]
+        );
+
+        // Roof X: Δ = 0.015 · 3.0 = 0.045, Δa = 0.020 · 3.0 = 0.060
+        assert!((rows[0].drift - 0.045).abs() < 1e-12);
+        assert!((rows[0].limit - 0.060).abs() < 1e-12);
+        assert!((rows[0].utilization - 0.75).abs() < 1e-12);
+        // Roof Y drifts in the negative direction: 0.025 / 0.020
+        assert!((rows[2].utilization - 1.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_envelope_keeps_worst_load_case() {
+        let drifts = vec![
+            drift("Roof", "EQX", Direction::X, 0.004),
+            drift("Roof", "WX", Direction::X, 0.006),
+        ];
+        let rows = check_drift_limits(&drifts, &stories(), 0.020).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].load_case, "WX");
+    }
+
+    #[test]
+    fn test_missing_story_height_is_an_error() {
+        let drifts = vec![
+            drift("Base", "EQX", Direction::X, 0.001),
+            drift("Mezzanine", "EQX", Direction::X, 0.002),
+        ];
+        match check_drift_limits(&drifts, &stories(), 0.020) {
+            Err(AppError::Validation(message)) => {
+                assert!(message.contains("'Base'"));
+                assert!(message.contains("'Mezzanine'"));
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_limit_ratio_must_be_positive() {
+        let drifts = vec![drift("Roof", "EQX", Direction::X, 0.004)];
+        assert!(matches!(
+            check_drift_limits(&drifts, &stories(), 0.0),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_report_csv_quotes_cells() {
+        let drifts = vec![drift("Roof", "EQX, 5% ecc", Direction::X, 0.015)];
+        let rows = check_drift_limits(&drifts, &stories(), 0.020).unwrap();
+        let csv = report_csv(&rows);
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            "Roof,X,\"EQX, 5% ecc\",0.045000,0.060000,0.750,PASS,Yes"
+        );
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        DriftCheckReport::export(&Default::default()).expect("Failed to export DriftCheckReport");
+    }
+}
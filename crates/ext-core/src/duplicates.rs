@@ -0,0 +1,380 @@
+use crate::bulk_import::is_etabs_file;
+use crate::{Project, ProjectFile};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Why a set of projects was flagged as possible duplicates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum DuplicateReason {
+    /// Names are identical after normalization (case, spacing, punctuation)
+    SameName,
+    /// Names are within a small edit distance of each other
+    SimilarName,
+    /// An attached ETABS model file (.edb or .e2k) has identical contents
+    SameModelFile,
+}
+
+/// Candidate group of projects that may describe the same building
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct DuplicateProjectSet {
+    pub reason: DuplicateReason,
+    /// Largest edit distance between normalized names within the set
+    pub max_distance: usize,
+    /// SHA-256 of the shared model file, for `SameModelFile` sets
+    #[serde(default)]
+    pub model_sha256: Option<String>,
+    pub projects: Vec<Project>,
+}
+
+/// What to do when merging a project into another and both have the same
+/// kind of data. Attached files, notes, analysis runs and tags are always
+/// added to the kept project; the data below is moved when the kept
+/// project has none, and only replaces the kept project's own when chosen.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+#[serde(default)]
+pub struct MergeOptions {
+    /// Replace the imported ETABS model
+    pub replace_model: bool,
+    /// Replace the load cases and generated load combinations
+    pub replace_combinations: bool,
+    /// Replace the lateral estimate, seismic base shear and wind pressures
+    pub replace_calculations: bool,
+}
+
+/// Lowercases, drops punctuation and collapses whitespace so that
+/// "Tower-A", "tower a" and "  TOWER  A " compare equal.
+pub fn normalize_project_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Whether two project names collide under the case-insensitive uniqueness rule.
+pub fn names_collide(a: &str, b: &str) -> bool {
+    a.trim().to_lowercase() == b.trim().to_lowercase()
+}
+
+/// Returns `name` if it is free, otherwise the first free ETABS-style variant
+/// "name (2)", "name (3)", ... An existing " (n)" suffix on `name` is
+/// continued rather than nested.
+pub fn next_available_name(name: &str, existing: &[String]) -> String {
+    let taken = |candidate: &str| existing.iter().any(|e| names_collide(e, candidate));
+
+    let name = name.trim();
+    if !taken(name) {
+        return name.to_string();
+    }
+
+    let (stem, start) = split_copy_suffix(name);
+    (start..)
+        .map(|n| format!("{stem} ({n})"))
+        .find(|candidate| !taken(candidate))
+        .expect("an unused suffix always exists")
+}
+
+/// Splits "Tower A (3)" into ("Tower A", 4); names without a numeric suffix
+/// start at 2.
+fn split_copy_suffix(name: &str) -> (&str, usize) {
+    if let Some(open) = name.rfind(" (")
+        && let Some(digits) = name[open + 2..].strip_suffix(')')
+        && let Ok(n) = digits.parse::<usize>()
+    {
+        return (&name[..open], n + 1);
+    }
+    (name, 2)
+}
+
+/// Levenshtein distance over Unicode scalar values.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Edit distance tolerated for a normalized name of `len` characters.
+/// Very short names must match exactly to avoid grouping "A1" with "B1".
+fn distance_threshold(len: usize) -> usize {
+    match len {
+        0..=4 => 0,
+        5..=11 => 1,
+        _ => 2,
+    }
+}
+
+/// Groups projects that have an ETABS model file with the same contents
+/// among their attached `files`, and projects whose names are identical or
+/// nearly identical after normalization. Name grouping is transitive; a
+/// name match already covered by a model file set is not repeated.
+/// Projects without a match are omitted.
+pub fn find_duplicate_projects(
+    projects: &[Project],
+    files: &[ProjectFile],
+) -> Vec<DuplicateProjectSet> {
+    let names: Vec<String> = projects
+        .iter()
+        .map(|p| normalize_project_name(&p.name))
+        .collect();
+
+    let mut sets = same_model_file_sets(projects, files, &names);
+    for set in similar_name_sets(projects, &names) {
+        let covered = sets.iter().any(|model_set| {
+            set.projects
+                .iter()
+                .all(|p| model_set.projects.iter().any(|m| m.id == p.id))
+        });
+        if !covered {
+            sets.push(set);
+        }
+    }
+    sets
+}
+
+/// One set per model file hash attached to more than one project, in the
+/// order the hashes first appear in `files`.
+fn same_model_file_sets(
+    projects: &[Project],
+    files: &[ProjectFile],
+    names: &[String],
+) -> Vec<DuplicateProjectSet> {
+    let index_of: HashMap<Uuid, usize> = projects
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (p.id, i))
+        .collect();
+
+    let mut hashes: Vec<(&str, Vec<usize>)> = Vec::new();
+    for file in files {
+        let Some(&project) = index_of.get(&file.project_id) else {
+            continue;
+        };
+        if !is_etabs_file(Path::new(&file.original_name)) {
+            continue;
+        }
+        match hashes.iter_mut().find(|(hash, _)| *hash == file.sha256) {
+            Some((_, members)) if !members.contains(&project) => members.push(project),
+            Some(_) => {}
+            None => hashes.push((file.sha256.as_str(), vec![project])),
+        }
+    }
+
+    hashes
+        .into_iter()
+        .filter(|(_, members)| members.len() > 1)
+        .map(|(hash, members)| DuplicateProjectSet {
+            reason: DuplicateReason::SameModelFile,
+            max_distance: max_name_distance(&members, names),
+            model_sha256: Some(hash.to_string()),
+            projects: members.into_iter().map(|i| projects[i].clone()).collect(),
+        })
+        .collect()
+}
+
+fn similar_name_sets(projects: &[Project], names: &[String]) -> Vec<DuplicateProjectSet> {
+    // Union-find over project indices.
+    let mut parent: Vec<usize> = (0..projects.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    for (i, a) in names.iter().enumerate() {
+        for (j, b) in names.iter().enumerate().skip(i + 1) {
+            let threshold = distance_threshold(a.chars().count().min(b.chars().count()));
+            if levenshtein(a, b) <= threshold {
+                let (ri, rj) = (root(&mut parent, i), root(&mut parent, j));
+                if ri != rj {
+                    parent[rj] = ri;
+                }
+            }
+        }
+    }
+
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut group_of_root: HashMap<usize, usize> = HashMap::new();
+    for i in 0..projects.len() {
+        let r = root(&mut parent, i);
+        let index = *group_of_root.entry(r).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[index].push(i);
+    }
+
+    groups
+        .into_iter()
+        .filter(|members| members.len() > 1)
+        .map(|members| {
+            let max_distance = max_name_distance(&members, names);
+            DuplicateProjectSet {
+                reason: if max_distance == 0 {
+                    DuplicateReason::SameName
+                } else {
+                    DuplicateReason::SimilarName
+                },
+                max_distance,
+                model_sha256: None,
+                projects: members.into_iter().map(|i| projects[i].clone()).collect(),
+            }
+        })
+        .collect()
+}
+
+fn max_name_distance(members: &[usize], names: &[String]) -> usize {
+    let mut max_distance = 0;
+    for (k, &i) in members.iter().enumerate() {
+        for &j in &members[k + 1..] {
+            max_distance = max_distance.max(levenshtein(&names[i], &names[j]));
+        }
+    }
+    max_distance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str) -> Project {
+        Project::new(name.to_string(), String::new())
+    }
+
+    #[test]
+    fn test_normalize_project_name() {
+        assert_eq!(normalize_project_name("  TOWER-A  (Phase 2) "), "tower a phase 2");
+        assert_eq!(normalize_project_name("Tháp  Đông"), "tháp đông");
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("tháp", "thap"), 1);
+        assert_eq!(levenshtein("same", "same"), 0);
+    }
+
+    #[test]
+    fn test_same_name_different_case() {
+        let projects = vec![project("Tower A"), project("tower a"), project("Podium")];
+        let sets = find_duplicate_projects(&projects, &[]);
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0].reason, DuplicateReason::SameName);
+        assert_eq!(sets[0].projects.len(), 2);
+    }
+
+    #[test]
+    fn test_similar_long_names() {
+        let projects = vec![
+            project("Riverside Residential Tower"),
+            project("Riverside Residental Tower"),
+        ];
+        let sets = find_duplicate_projects(&projects, &[]);
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0].reason, DuplicateReason::SimilarName);
+        assert_eq!(sets[0].max_distance, 1);
+    }
+
+    #[test]
+    fn test_short_names_must_match_exactly() {
+        let projects = vec![project("B1"), project("B2")];
+        assert!(find_duplicate_projects(&projects, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_same_model_file_groups_projects() {
+        let projects = vec![project("Tower A"), project("Block 7"), project("Tower-A")];
+        let file = |project: &Project, name: &str, sha256: &str| ProjectFile {
+            id: Uuid::new_v4(),
+            project_id: project.id,
+            original_name: name.to_string(),
+            stored_path: format!("files/{name}"),
+            size: 1,
+            sha256: sha256.to_string(),
+            added_at: chrono::Utc::now(),
+            missing: false,
+        };
+        let files = vec![
+            file(&projects[0], "Tower.EDB", "aa"),
+            file(&projects[1], "tower-copy.edb", "aa"),
+            file(&projects[2], "Tower.edb", "aa"),
+            // Same contents, but not a model file
+            file(&projects[0], "notes.pdf", "bb"),
+            file(&projects[1], "notes.pdf", "bb"),
+        ];
+
+        let sets = find_duplicate_projects(&projects, &files);
+        // "Tower A" and "Tower-A" also match by name, but that set is
+        // already covered by the model file set
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0].reason, DuplicateReason::SameModelFile);
+        assert_eq!(sets[0].model_sha256.as_deref(), Some("aa"));
+        assert_eq!(sets[0].projects.len(), 3);
+    }
+
+    #[test]
+    fn test_names_collide_case_insensitively() {
+        assert!(names_collide("Tower A", "tower a"));
+        assert!(names_collide("Tháp Đông", "THÁP ĐÔNG"));
+        assert!(!names_collide("Tower A", "Tower B"));
+    }
+
+    #[test]
+    fn test_next_available_name_free() {
+        assert_eq!(next_available_name("Tower A", &["Podium".to_string()]), "Tower A");
+    }
+
+    #[test]
+    fn test_next_available_name_increments() {
+        let mut existing = vec!["tower a".to_string()];
+        assert_eq!(next_available_name("Tower A", &existing), "Tower A (2)");
+
+        existing.push("Tower A (2)".to_string());
+        assert_eq!(next_available_name("Tower A", &existing), "Tower A (3)");
+    }
+
+    #[test]
+    fn test_next_available_name_continues_existing_suffix() {
+        let existing = vec!["Tower A".to_string(), "Tower A (2)".to_string()];
+        assert_eq!(next_available_name("Tower A (2)", &existing), "Tower A (3)");
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        DuplicateReason::export(&Default::default()).expect("Failed to export DuplicateReason");
+        DuplicateProjectSet::export(&Default::default()).expect("Failed to export DuplicateProjectSet");
+        MergeOptions::export(&Default::default()).expect("Failed to export MergeOptions");
+    }
+}
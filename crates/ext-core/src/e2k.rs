@@ -0,0 +1,687 @@
+//! Parser for the ETABS `.e2k` text export.
+//!
+//! Only the tables needed to describe the frame geometry and its load
+//! patterns are read; every other table is skipped. Lengths and stresses
+//! are converted from the file's `UNITS` record to SI; files without one
+//! are read as kN-m.
+
+use crate::units::{ForceUnit, Length, LengthUnit, Stress, UnitSet};
+use ext_error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use ts_rs::TS;
+
+/// Story level, listed from the top down as in the `.e2k` file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct Story {
+    pub name: String,
+    /// Height below this story's floor; 0 for the base
+    pub height: Length,
+    /// Floor elevation, accumulated from the base story
+    pub elevation: Length,
+    pub master_story: bool,
+    pub similar_to: Option<String>,
+}
+
+/// Material property definition
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct Material {
+    pub name: String,
+    /// ETABS material type, e.g. "Steel" or "Concrete"
+    pub material_type: Option<String>,
+    /// Unit weight in N/m³
+    pub weight_per_volume: Option<f64>,
+    pub elastic_modulus: Option<Stress>,
+    pub poisson_ratio: Option<f64>,
+    pub fc: Option<Stress>,
+    pub fy: Option<Stress>,
+}
+
+/// Frame section definition
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct FrameSection {
+    pub name: String,
+    pub material: Option<String>,
+    /// ETABS shape name, e.g. "Concrete Rectangular" or "I/Wide Flange"
+    pub shape: Option<String>,
+    pub depth: Option<Length>,
+    pub width: Option<Length>,
+}
+
+/// Plan point; ETABS repeats plan points on every story
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct Point {
+    pub name: String,
+    pub x: Length,
+    pub y: Length,
+    /// Offset from the story level, only present for special points
+    pub z: Option<Length>,
+}
+
+/// Kind of line object
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum LineKind {
+    Column,
+    Beam,
+    Brace,
+}
+
+/// Line object connecting two plan points
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct LineObject {
+    pub name: String,
+    pub kind: LineKind,
+    pub point_i: String,
+    pub point_j: String,
+    /// Number of stories a column or brace spans downward; 0 for beams
+    pub stories_spanned: u32,
+}
+
+/// Load pattern definition
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct LoadPattern {
+    pub name: String,
+    /// ETABS load pattern type, e.g. "Dead", "Live" or "Seismic"
+    pub pattern_type: Option<String>,
+    /// Multiple of the structure's self weight the pattern includes
+    pub self_weight_multiplier: f64,
+}
+
+/// Structural model read from an `.e2k` file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct EtabsModel {
+    /// Units the file was exported in, for displaying values the same way
+    #[serde(default)]
+    pub units: UnitSet,
+    pub stories: Vec<Story>,
+    pub materials: Vec<Material>,
+    pub frame_sections: Vec<FrameSection>,
+    pub points: Vec<Point>,
+    pub lines: Vec<LineObject>,
+    /// Missing from models saved before load patterns were read
+    #[serde(default)]
+    pub load_patterns: Vec<LoadPattern>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Table {
+    Controls,
+    Stories,
+    Materials,
+    FrameSections,
+    Points,
+    Lines,
+    LoadPatterns,
+    Skipped,
+}
+
+impl Table {
+    /// Maps a `$ ...` header to a table; headers such as
+    /// "STORIES - IN SEQUENCE FROM TOP" carry a trailing note.
+    fn from_header(header: &str) -> Self {
+        let name = header.split(" - ").next().unwrap_or("").trim();
+        match name.to_ascii_uppercase().as_str() {
+            "CONTROLS" => Table::Controls,
+            "STORIES" => Table::Stories,
+            "MATERIAL PROPERTIES" => Table::Materials,
+            "FRAME SECTIONS" => Table::FrameSections,
+            "POINT COORDINATES" => Table::Points,
+            "LINE CONNECTIVITIES" => Table::Lines,
+            "LOAD PATTERNS" => Table::LoadPatterns,
+            _ => Table::Skipped,
+        }
+    }
+}
+
+/// Reads and parses an `.e2k` file. Files exported by older ETABS versions
+/// are not always UTF-8, so invalid bytes are replaced rather than rejected.
+pub fn parse_e2k(path: &Path) -> Result<EtabsModel> {
+    let bytes = std::fs::read(path).map_err(|e| {
+        AppError::FileSystem(format!("Failed to read '{}': {e}", path.display()))
+    })?;
+    parse_e2k_str(&String::from_utf8_lossy(&bytes))
+}
+
+/// Parses the contents of an `.e2k` file.
+pub fn parse_e2k_str(text: &str) -> Result<EtabsModel> {
+    let mut model = EtabsModel::default();
+    let mut explicit_elevations = Vec::new();
+    let mut table = Table::Skipped;
+    // Large models have tens of thousands of points and lines
+    let mut point_names = HashSet::new();
+    let (mut materials, mut sections, mut patterns) =
+        (HashMap::new(), HashMap::new(), HashMap::new());
+
+    for (index, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('$') {
+            table = Table::from_header(header);
+            continue;
+        }
+        if table == Table::Skipped {
+            continue;
+        }
+        if table == Table::Controls {
+            // CONTROLS comes first in ETABS exports, ahead of any value
+            // that needs converting; its other records are not needed.
+            if line.get(..5).is_some_and(|k| k.eq_ignore_ascii_case("UNITS")) {
+                model.units = Record::parse(index + 1, line)?.units()?;
+            }
+            continue;
+        }
+
+        let units = model.units;
+        let record = Record::parse(index + 1, line)?;
+        match table {
+            Table::Stories => {
+                let (story, elevation) = record.story(units)?;
+                model.stories.push(story);
+                explicit_elevations.push(elevation);
+            }
+            Table::Materials => record.material(&mut model.materials, &mut materials, units)?,
+            Table::FrameSections => {
+                record.frame_section(&mut model.frame_sections, &mut sections, units)?
+            }
+            Table::Points => {
+                let point = record.point(units)?;
+                point_names.insert(point.name.clone());
+                model.points.push(point);
+            }
+            Table::Lines => {
+                let line_object = record.line_object()?;
+                for point in [&line_object.point_i, &line_object.point_j] {
+                    if !point_names.contains(point) {
+                        return Err(record.error(format!(
+                            "LINE \"{}\" references unknown point \"{point}\"",
+                            line_object.name
+                        )));
+                    }
+                }
+                model.lines.push(line_object);
+            }
+            Table::LoadPatterns => record.load_pattern(&mut model.load_patterns, &mut patterns)?,
+            Table::Controls | Table::Skipped => unreachable!(),
+        }
+    }
+
+    // Stories are listed top down; accumulate heights from the base up.
+    let mut elevation = Length::default();
+    for (story, explicit) in model.stories.iter_mut().zip(explicit_elevations).rev() {
+        elevation = explicit.unwrap_or(elevation + story.height);
+        story.elevation = elevation;
+    }
+
+    Ok(model)
+}
+
+/// One data line: a keyword, a quoted name and the remaining tokens
+struct Record {
+    line: usize,
+    keyword: String,
+    name: String,
+    rest: Vec<String>,
+}
+
+impl Record {
+    fn parse(line: usize, text: &str) -> Result<Self> {
+        let mut tokens = tokenize(text).map_err(|message| e2k_error(line, message))?;
+        if tokens.len() < 2 {
+            return Err(e2k_error(line, "expected a keyword followed by a name".to_string()));
+        }
+        let rest = tokens.split_off(2);
+        let name = tokens.pop().unwrap_or_default();
+        let keyword = tokens.pop().unwrap_or_default().to_ascii_uppercase();
+        Ok(Self {
+            line,
+            keyword,
+            name,
+            rest,
+        })
+    }
+
+    fn error(&self, message: String) -> AppError {
+        e2k_error(self.line, message)
+    }
+
+    fn expect_keyword(&self, expected: &str) -> Result<()> {
+        if self.keyword != expected {
+            return Err(self.error(format!("expected {expected}, found {}", self.keyword)));
+        }
+        Ok(())
+    }
+
+    /// Splits the remaining tokens into `KEY value` pairs.
+    fn pairs(&self) -> Result<Vec<(String, &str)>> {
+        if self.rest.len() % 2 != 0 {
+            let key = self.rest.last().map(String::as_str).unwrap_or_default();
+            return Err(self.error(format!("{key} has no value")));
+        }
+        Ok(self
+            .rest
+            .chunks(2)
+            .map(|pair| (pair[0].to_ascii_uppercase(), pair[1].as_str()))
+            .collect())
+    }
+
+    fn number(&self, key: &str, value: &str) -> Result<f64> {
+        value
+            .parse()
+            .map_err(|_| self.error(format!("invalid number '{value}' for {key}")))
+    }
+
+    fn length(&self, key: &str, value: &str, units: UnitSet) -> Result<Length> {
+        Ok(Length::from(self.number(key, value)?, units.length))
+    }
+
+    fn stress(&self, key: &str, value: &str, units: UnitSet) -> Result<Stress> {
+        Ok(Stress::from(self.number(key, value)?, units.stress()))
+    }
+
+    /// `UNITS "KN" "M" "C"`: force, length and temperature
+    fn units(&self) -> Result<UnitSet> {
+        self.expect_keyword("UNITS")?;
+        let force = ForceUnit::from_etabs(&self.name)
+            .ok_or_else(|| self.error(format!("unknown force unit '{}'", self.name)))?;
+        let length = self.rest.first().map(String::as_str).unwrap_or_default();
+        let length = LengthUnit::from_etabs(length)
+            .ok_or_else(|| self.error(format!("unknown length unit '{length}'")))?;
+        Ok(UnitSet { force, length })
+    }
+
+    fn story(&self, units: UnitSet) -> Result<(Story, Option<Length>)> {
+        self.expect_keyword("STORY")?;
+        let mut story = Story {
+            name: self.name.clone(),
+            height: Length::default(),
+            elevation: Length::default(),
+            master_story: false,
+            similar_to: None,
+        };
+        let mut elevation = None;
+        for (key, value) in self.pairs()? {
+            match key.as_str() {
+                "HEIGHT" => story.height = self.length(&key, value, units)?,
+                "ELEV" => elevation = Some(self.length(&key, value, units)?),
+                "MASTERSTORY" => story.master_story = value.eq_ignore_ascii_case("yes"),
+                "SIMILARTO" => story.similar_to = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        Ok((story, elevation))
+    }
+
+    /// Position of the entry this record names, adding one the first time
+    /// the name is seen; `indices` maps names to positions in `entries`.
+    fn entry<T>(
+        &self,
+        entries: &mut Vec<T>,
+        indices: &mut HashMap<String, usize>,
+        new: impl FnOnce(String) -> T,
+    ) -> usize {
+        *indices.entry(self.name.clone()).or_insert_with(|| {
+            entries.push(new(self.name.clone()));
+            entries.len() - 1
+        })
+    }
+
+    /// Materials are spread across several lines that repeat the name.
+    fn material(
+        &self,
+        materials: &mut Vec<Material>,
+        indices: &mut HashMap<String, usize>,
+        units: UnitSet,
+    ) -> Result<()> {
+        self.expect_keyword("MATERIAL")?;
+        let index = self.entry(materials, indices, |name| Material {
+            name,
+            ..Default::default()
+        });
+        let material = &mut materials[index];
+        for (key, value) in self.pairs()? {
+            match key.as_str() {
+                "TYPE" => material.material_type = Some(value.to_string()),
+                "WEIGHTPERVOLUME" => {
+                    let weight = self.number(&key, value)?;
+                    material.weight_per_volume =
+                        Some(weight * units.force.to_si() / units.length.to_si().powi(3));
+                }
+                "E" => material.elastic_modulus = Some(self.stress(&key, value, units)?),
+                "U" => material.poisson_ratio = Some(self.number(&key, value)?),
+                "FC" => material.fc = Some(self.stress(&key, value, units)?),
+                "FY" => material.fy = Some(self.stress(&key, value, units)?),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn frame_section(
+        &self,
+        sections: &mut Vec<FrameSection>,
+        indices: &mut HashMap<String, usize>,
+        units: UnitSet,
+    ) -> Result<()> {
+        self.expect_keyword("FRAMESECTION")?;
+        let index = self.entry(sections, indices, |name| FrameSection {
+            name,
+            ..Default::default()
+        });
+        let section = &mut sections[index];
+        for (key, value) in self.pairs()? {
+            match key.as_str() {
+                "MATERIAL" => section.material = Some(value.to_string()),
+                "SHAPE" => section.shape = Some(value.to_string()),
+                "D" => section.depth = Some(self.length(&key, value, units)?),
+                "B" => section.width = Some(self.length(&key, value, units)?),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Auto lateral load records such as `SEISMIC` share the table and
+    /// are skipped.
+    fn load_pattern(
+        &self,
+        patterns: &mut Vec<LoadPattern>,
+        indices: &mut HashMap<String, usize>,
+    ) -> Result<()> {
+        if self.keyword != "LOADPATTERN" {
+            return Ok(());
+        }
+        let index = self.entry(patterns, indices, |name| LoadPattern {
+            name,
+            ..Default::default()
+        });
+        let pattern = &mut patterns[index];
+        for (key, value) in self.pairs()? {
+            match key.as_str() {
+                "TYPE" => pattern.pattern_type = Some(value.to_string()),
+                "SELFWEIGHT" => pattern.self_weight_multiplier = self.number(&key, value)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn point(&self, units: UnitSet) -> Result<Point> {
+        self.expect_keyword("POINT")?;
+        let coordinates = self
+            .rest
+            .iter()
+            .map(|value| self.length("coordinate", value, units))
+            .collect::<Result<Vec<_>>>()?;
+        match coordinates[..] {
+            [x, y] => Ok(Point {
+                name: self.name.clone(),
+                x,
+                y,
+                z: None,
+            }),
+            [x, y, z] => Ok(Point {
+                name: self.name.clone(),
+                x,
+                y,
+                z: Some(z),
+            }),
+            _ => Err(self.error(format!(
+                "POINT \"{}\" needs 2 or 3 coordinates, found {}",
+                self.name,
+                coordinates.len()
+            ))),
+        }
+    }
+
+    fn line_object(&self) -> Result<LineObject> {
+        self.expect_keyword("LINE")?;
+        let (kind, point_i, point_j, spanned) = match &self.rest[..] {
+            [kind, i, j] => (kind, i, j, None),
+            [kind, i, j, spanned] => (kind, i, j, Some(spanned)),
+            _ => {
+                return Err(self.error(format!(
+                    "LINE \"{}\" needs a type and two points",
+                    self.name
+                )));
+            }
+        };
+        let kind = match kind.to_ascii_uppercase().as_str() {
+            "COLUMN" => LineKind::Column,
+            "BEAM" => LineKind::Beam,
+            "BRACE" => LineKind::Brace,
+            other => return Err(self.error(format!("unknown line type '{other}'"))),
+        };
+        let stories_spanned = match spanned {
+            Some(value) => value
+                .parse()
+                .map_err(|_| self.error(format!("invalid story count '{value}'")))?,
+            None => 0,
+        };
+        Ok(LineObject {
+            name: self.name.clone(),
+            kind,
+            point_i: point_i.clone(),
+            point_j: point_j.clone(),
+            stories_spanned,
+        })
+    }
+}
+
+fn e2k_error(line: usize, message: String) -> AppError {
+    AppError::Etabs(format!("e2k line {line}: {message}"))
+}
+
+/// Splits on whitespace, keeping double-quoted strings together.
+fn tokenize(line: &str) -> std::result::Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut token = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some(c) => token.push(c),
+                    None => return Err("unterminated quoted string".to_string()),
+                }
+            }
+            tokens.push(token);
+        } else {
+            let mut token = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' {
+                    break;
+                }
+                token.push(c);
+                chars.next();
+            }
+            tokens.push(token);
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::UnitSystem;
+
+    fn fixture(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    fn expect_etabs_error(text: &str, line: usize) -> String {
+        match parse_e2k_str(text) {
+            Err(AppError::Etabs(message)) => {
+                assert!(
+                    message.starts_with(&format!("e2k line {line}:")),
+                    "unexpected message: {message}"
+                );
+                message
+            }
+            other => panic!("expected ETABS error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_two_story_fixture() {
+        let model = parse_e2k(&fixture("two_story.e2k")).unwrap();
+
+        let stories: Vec<(&str, f64)> = model
+            .stories
+            .iter()
+            .map(|s| (s.name.as_str(), s.elevation.to(LengthUnit::Meter)))
+            .collect();
+        assert_eq!(stories, vec![("Story2", 7.0), ("Story1", 3.5), ("Base", 0.0)]);
+        assert_eq!(model.units, UnitSet::default());
+        assert!(model.stories[0].master_story);
+        assert_eq!(model.stories[1].similar_to.as_deref(), Some("Story2"));
+
+        assert_eq!(model.materials.len(), 2);
+        let concrete = &model.materials[1];
+        assert_eq!(concrete.name, "C30");
+        assert_eq!(concrete.material_type.as_deref(), Some("Concrete"));
+        let kn_m2 = model.units.stress();
+        assert_eq!(concrete.elastic_modulus.map(|e| e.to(kn_m2)), Some(31_000_000.0));
+        assert_eq!(concrete.fc.map(|fc| fc.to(kn_m2)), Some(30_000.0));
+
+        assert_eq!(model.frame_sections.len(), 2);
+        assert_eq!(model.frame_sections[0].shape.as_deref(), Some("Concrete Rectangular"));
+        assert_eq!(model.frame_sections[0].depth, Some(Length::from(0.5, LengthUnit::Meter)));
+
+        assert_eq!(model.points.len(), 4);
+        assert_eq!(model.lines.len(), 6);
+        let columns = model.lines.iter().filter(|l| l.kind == LineKind::Column).count();
+        assert_eq!(columns, 4);
+        assert_eq!(model.lines[0].stories_spanned, 1);
+
+        let patterns: Vec<&str> = model.load_patterns.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(patterns, vec!["Dead", "Live"]);
+    }
+
+    #[test]
+    fn test_unknown_tables_are_skipped() {
+        let text = "$ GRIDS\n  GRIDSYSTEM \"G1\" TYPE \"CARTESIAN\" BUBBLESIZE 1.25\n\
+                    $ POINT COORDINATES\n  POINT \"1\" 0 0\n";
+        let model = parse_e2k_str(text).unwrap();
+        assert_eq!(model.points.len(), 1);
+    }
+
+    #[test]
+    fn test_load_patterns_skip_auto_lateral_records() {
+        let text = "$ LOAD PATTERNS\n  LOADPATTERN \"Dead\" TYPE \"Dead\" SELFWEIGHT 1\n\
+                    LOADPATTERN \"EQX\" TYPE \"Seismic\" SELFWEIGHT 0\n\
+                    SEISMIC \"EQX\" \"ASCE 7-16\" DIR \"X\" ECC 0.05\n";
+        let model = parse_e2k_str(text).unwrap();
+        assert_eq!(model.load_patterns.len(), 2);
+        assert_eq!(model.load_patterns[0].self_weight_multiplier, 1.0);
+        assert_eq!(model.load_patterns[1].pattern_type.as_deref(), Some("Seismic"));
+
+        let message = expect_etabs_error("$ LOAD PATTERNS\n  LOADPATTERN \"L\" SELFWEIGHT x\n", 2);
+        assert!(message.contains("SELFWEIGHT"));
+    }
+
+    #[test]
+    fn test_quoted_names_with_spaces() {
+        let text = "$ STORIES - IN SEQUENCE FROM TOP\n  STORY \"Level 1\" ELEV 2.5\n";
+        let model = parse_e2k_str(text).unwrap();
+        assert_eq!(model.stories[0].name, "Level 1");
+        assert_eq!(model.stories[0].elevation.to(LengthUnit::Meter), 2.5);
+    }
+
+    #[test]
+    fn test_values_are_converted_from_file_units() {
+        let text = "$ CONTROLS\n  UNITS \"KIP\" \"IN\" \"F\"\n\
+                    $ STORIES - IN SEQUENCE FROM TOP\n\
+                    STORY \"L2\" HEIGHT 144\n  STORY \"Base\" ELEV 0\n\
+                    $ MATERIAL PROPERTIES\n  MATERIAL \"A992\" FY 50\n";
+        let model = parse_e2k_str(text).unwrap();
+        assert_eq!(model.units.force, ForceUnit::Kip);
+
+        let height = model.stories[0].height.to(LengthUnit::Foot);
+        assert!((height - 12.0).abs() < 1e-12);
+        let fy = model.materials[0].fy.unwrap().to(UnitSystem::Metric.stress());
+        assert!((fy - 344.737_864_658_4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_unknown_unit_is_rejected() {
+        let message = expect_etabs_error("$ CONTROLS\n  UNITS \"KN\" \"YD\" \"C\"\n", 2);
+        assert!(message.contains("'YD'"));
+    }
+
+    #[test]
+    fn test_invalid_number_reports_line() {
+        let text = "$ STORIES - IN SEQUENCE FROM TOP\n  STORY \"S1\" HEIGHT abc\n";
+        let message = expect_etabs_error(text, 2);
+        assert!(message.contains("HEIGHT"));
+    }
+
+    #[test]
+    fn test_unterminated_quote_reports_line() {
+        let text = "$ POINT COORDINATES\n  POINT \"1\" 0 0\n  POINT \"2 0 0\n";
+        expect_etabs_error(text, 3);
+    }
+
+    #[test]
+    fn test_line_with_unknown_point_is_rejected() {
+        let text = "$ POINT COORDINATES\n  POINT \"1\" 0 0\n\
+                    $ LINE CONNECTIVITIES\n  LINE \"B1\" BEAM \"1\" \"9\" 0\n";
+        let message = expect_etabs_error(text, 4);
+        assert!(message.contains("\"9\""));
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        Story::export(&Default::default()).expect("Failed to export Story");
+        Material::export(&Default::default()).expect("Failed to export Material");
+        FrameSection::export(&Default::default()).expect("Failed to export FrameSection");
+        Point::export(&Default::default()).expect("Failed to export Point");
+        LineKind::export(&Default::default()).expect("Failed to export LineKind");
+        LineObject::export(&Default::default()).expect("Failed to export LineObject");
+        LoadPattern::export(&Default::default()).expect("Failed to export LoadPattern");
+        EtabsModel::export(&Default::default()).expect("Failed to export EtabsModel");
+    }
+}
@@ -0,0 +1,354 @@
+//! Early-stage lateral estimates that do not need an ETABS model.
+//!
+//! All quantities are SI: heights in m, weights in kN, stiffnesses in kN/m,
+//! periods in s.
+
+use chrono::{DateTime, Utc};
+use ext_error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Gravitational acceleration in m/s²
+const GRAVITY: f64 = 9.806_65;
+
+/// Approximate lateral system used to pick Ct and x (ASCE 7-16 Table 12.8-2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum LateralSystem {
+    SteelMomentFrame,
+    ConcreteMomentFrame,
+    BracedFrame,
+    ShearWall,
+    Dual,
+}
+
+impl LateralSystem {
+    /// Returns (Ct, x) in SI units, for hn in metres.
+    pub fn period_coefficients(&self) -> (f64, f64) {
+        match self {
+            LateralSystem::SteelMomentFrame => (0.0724, 0.8),
+            LateralSystem::ConcreteMomentFrame => (0.0466, 0.9),
+            LateralSystem::BracedFrame => (0.0731, 0.75),
+            LateralSystem::ShearWall | LateralSystem::Dual => (0.0488, 0.75),
+        }
+    }
+}
+
+/// One manually entered story, listed from the bottom up
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct StoryEstimateInput {
+    pub name: String,
+    /// Floor-to-floor height in m
+    pub height: f64,
+    /// Seismic weight in kN
+    pub weight: f64,
+    /// Lateral story stiffness in kN/m, needed for the Rayleigh estimate
+    pub stiffness: Option<f64>,
+}
+
+/// Inputs for a rough lateral estimate
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct LateralEstimateInput {
+    pub system: LateralSystem,
+    pub stories: Vec<StoryEstimateInput>,
+    /// Seismic response coefficient Cs; when given, story forces are reported
+    pub cs: Option<f64>,
+}
+
+/// Vertical distribution of the ELF base shear to one story
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct StoryForceRow {
+    pub story: String,
+    pub elevation: f64,
+    pub weight: f64,
+    pub cvx: f64,
+    pub force: Option<f64>,
+}
+
+/// Result of a lateral estimate, stored with the project for later comparison
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct LateralEstimate {
+    pub system: LateralSystem,
+    pub ct: f64,
+    pub x: f64,
+    pub total_height: f64,
+    pub total_weight: f64,
+    pub approximate_period: f64,
+    pub rayleigh_period: Option<f64>,
+    pub k: f64,
+    pub base_shear: Option<f64>,
+    pub story_forces: Vec<StoryForceRow>,
+
+    #[ts(type = "string")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl LateralEstimateInput {
+    /// Rejects zero or negative quantities, naming every offending field.
+    pub fn validate(&self) -> Result<()> {
+        let mut errors = Vec::new();
+
+        if self.stories.is_empty() {
+            errors.push("stories must not be empty".to_string());
+        }
+        for (i, story) in self.stories.iter().enumerate() {
+            if !(story.height.is_finite() && story.height > 0.0) {
+                errors.push(format!("stories[{i}].height must be positive"));
+            }
+            if !(story.weight.is_finite() && story.weight > 0.0) {
+                errors.push(format!("stories[{i}].weight must be positive"));
+            }
+            if let Some(stiffness) = story.stiffness
+                && !(stiffness.is_finite() && stiffness > 0.0)
+            {
+                errors.push(format!("stories[{i}].stiffness must be positive"));
+            }
+        }
+        if let Some(cs) = self.cs
+            && !(cs.is_finite() && cs > 0.0)
+        {
+            errors.push("cs must be positive".to_string());
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::Validation(errors.join("; ")))
+        }
+    }
+}
+
+/// Approximate fundamental period Ta = Ct·hn^x (ASCE 7-16 Eq. 12.8-7).
+pub fn approximate_period(system: LateralSystem, total_height: f64) -> f64 {
+    let (ct, x) = system.period_coefficients();
+    ct * total_height.powf(x)
+}
+
+/// Distribution exponent k (ASCE 7-16 §12.8.3), interpolated between
+/// T = 0.5 s and T = 2.5 s.
+pub fn distribution_exponent(period: f64) -> f64 {
+    if period <= 0.5 {
+        1.0
+    } else if period >= 2.5 {
+        2.0
+    } else {
+        1.0 + (period - 0.5) / 2.0
+    }
+}
+
+/// Vertical distribution factors Cvx for weights at the given elevations.
+pub fn vertical_distribution(elevations: &[f64], weights: &[f64], k: f64) -> Vec<f64> {
+    let terms: Vec<f64> = elevations
+        .iter()
+        .zip(weights)
+        .map(|(h, w)| w * h.powf(k))
+        .collect();
+    let sum: f64 = terms.iter().sum();
+    terms.iter().map(|t| t / sum).collect()
+}
+
+/// Rayleigh period from story weights, stiffnesses and a lateral force
+/// pattern, all listed from the bottom up.
+pub fn rayleigh_period(weights: &[f64], stiffnesses: &[f64], forces: &[f64]) -> f64 {
+    // Story shear is the sum of forces at and above each story.
+    let mut shears: Vec<f64> = forces
+        .iter()
+        .rev()
+        .scan(0.0, |running, force| {
+            *running += force;
+            Some(*running)
+        })
+        .collect();
+    shears.reverse();
+
+    let mut displacement = 0.0;
+    let mut numerator = 0.0;
+    let mut denominator = 0.0;
+    for ((weight, force), (shear, stiffness)) in weights
+        .iter()
+        .zip(forces)
+        .zip(shears.iter().zip(stiffnesses))
+    {
+        displacement += shear / stiffness;
+        numerator += weight * displacement * displacement;
+        denominator += force * displacement;
+    }
+
+    2.0 * std::f64::consts::PI * (numerator / (GRAVITY * denominator)).sqrt()
+}
+
+/// Runs the full estimate: approximate period, Rayleigh period when every
+/// story has a stiffness, and the ELF vertical distribution.
+pub fn estimate_lateral(input: &LateralEstimateInput) -> Result<LateralEstimate> {
+    input.validate()?;
+
+    let mut elevations = Vec::with_capacity(input.stories.len());
+    let mut elevation = 0.0;
+    for story in &input.stories {
+        elevation += story.height;
+        elevations.push(elevation);
+    }
+    let weights: Vec<f64> = input.stories.iter().map(|s| s.weight).collect();
+
+    let total_height = elevation;
+    let total_weight: f64 = weights.iter().sum();
+    let (ct, x) = input.system.period_coefficients();
+    let ta = approximate_period(input.system, total_height);
+    let k = distribution_exponent(ta);
+    let cvx = vertical_distribution(&elevations, &weights, k);
+
+    let stiffnesses: Option<Vec<f64>> = input.stories.iter().map(|s| s.stiffness).collect();
+    let rayleigh = stiffnesses.map(|stiffnesses| rayleigh_period(&weights, &stiffnesses, &cvx));
+
+    let base_shear = input.cs.map(|cs| cs * total_weight);
+    let story_forces = input
+        .stories
+        .iter()
+        .enumerate()
+        .map(|(i, story)| StoryForceRow {
+            story: story.name.clone(),
+            elevation: elevations[i],
+            weight: story.weight,
+            cvx: cvx[i],
+            force: base_shear.map(|v| v * cvx[i]),
+        })
+        .collect();
+
+    Ok(LateralEstimate {
+        system: input.system,
+        ct,
+        x,
+        total_height,
+        total_weight,
+        approximate_period: ta,
+        rayleigh_period: rayleigh,
+        k,
+        base_shear,
+        story_forces,
+        created_at: Utc::now(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn story(name: &str, height: f64, weight: f64, stiffness: Option<f64>) -> StoryEstimateInput {
+        StoryEstimateInput {
+            name: name.to_string(),
+            height,
+            weight,
+            stiffness,
+        }
+    }
+
+    #[test]
+    fn test_approximate_period_concrete_moment_frame() {
+        // 10 stories at 3.5 m: Ta = 0.0466 · 35^0.9
+        let ta = approximate_period(LateralSystem::ConcreteMomentFrame, 35.0);
+        assert!((ta - 1.143).abs() < 1e-3, "ta = {ta}");
+    }
+
+    #[test]
+    fn test_distribution_exponent_interpolation() {
+        assert_eq!(distribution_exponent(0.3), 1.0);
+        assert_eq!(distribution_exponent(1.5), 1.5);
+        assert_eq!(distribution_exponent(3.0), 2.0);
+    }
+
+    #[test]
+    fn test_vertical_distribution_sums_to_one() {
+        let cvx = vertical_distribution(&[3.0, 6.0, 9.0], &[100.0, 100.0, 80.0], 1.0);
+        let sum: f64 = cvx.iter().sum();
+        assert!((sum - 1.0).abs() < 1e-12);
+        // w·h = 300, 600, 720 over 1620
+        assert!((cvx[2] - 720.0 / 1620.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rayleigh_single_story_matches_closed_form() {
+        // One degree of freedom: T = 2π·sqrt(m/k) with m = W/g.
+        let t = rayleigh_period(&[1000.0], &[50_000.0], &[1.0]);
+        let expected = 2.0 * std::f64::consts::PI * (1000.0 / GRAVITY / 50_000.0).sqrt();
+        assert!((t - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_estimate_with_forces_and_rayleigh() {
+        let input = LateralEstimateInput {
+            system: LateralSystem::ShearWall,
+            stories: vec![
+                story("L1", 4.0, 5000.0, Some(400_000.0)),
+                story("L2", 3.5, 4800.0, Some(380_000.0)),
+                story("Roof", 3.5, 3500.0, Some(350_000.0)),
+            ],
+            cs: Some(0.1),
+        };
+        let estimate = estimate_lateral(&input).unwrap();
+
+        assert_eq!(estimate.total_height, 11.0);
+        let base_shear = estimate.base_shear.unwrap();
+        assert!((base_shear - 1330.0).abs() < 1e-9);
+        assert!(estimate.rayleigh_period.is_some());
+
+        let total_force: f64 = estimate.story_forces.iter().filter_map(|r| r.force).sum();
+        assert!((total_force - 1330.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rayleigh_skipped_without_all_stiffnesses() {
+        let input = LateralEstimateInput {
+            system: LateralSystem::SteelMomentFrame,
+            stories: vec![story("L1", 4.0, 100.0, Some(1000.0)), story("L2", 4.0, 100.0, None)],
+            cs: None,
+        };
+        let estimate = estimate_lateral(&input).unwrap();
+        assert!(estimate.rayleigh_period.is_none());
+        assert!(estimate.story_forces.iter().all(|r| r.force.is_none()));
+    }
+
+    #[test]
+    fn test_validation_names_fields() {
+        let input = LateralEstimateInput {
+            system: LateralSystem::Dual,
+            stories: vec![story("L1", 0.0, 100.0, None), story("L2", 3.0, -5.0, Some(0.0))],
+            cs: None,
+        };
+        match estimate_lateral(&input) {
+            Err(AppError::Validation(message)) => {
+                assert!(message.contains("stories[0].height"));
+                assert!(message.contains("stories[1].weight"));
+                assert!(message.contains("stories[1].stiffness"));
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        LateralSystem::export(&Default::default()).expect("Failed to export LateralSystem");
+        StoryEstimateInput::export(&Default::default()).expect("Failed to export StoryEstimateInput");
+        LateralEstimateInput::export(&Default::default()).expect("Failed to export LateralEstimateInput");
+        StoryForceRow::export(&Default::default()).expect("Failed to export StoryForceRow");
+        LateralEstimate::export(&Default::default()).expect("Failed to export LateralEstimate");
+    }
+}
@@ -0,0 +1,120 @@
+//! Events sent to the frontend after project data changes, so it can
+//! refresh without re-polling.
+
+use crate::{Project, ProjectFile};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+pub const PROJECT_CREATED: &str = "project:created";
+pub const PROJECT_UPDATED: &str = "project:updated";
+pub const PROJECT_DELETED: &str = "project:deleted";
+pub const PROJECT_FILES_CHANGED: &str = "project:files-changed";
+pub const PROJECT_FILE_MODIFIED: &str = "project:file-modified";
+
+/// Payload of events that only identify the affected project
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct ProjectRef {
+    #[ts(type = "string")]
+    pub project_id: Uuid,
+}
+
+/// Payload of `project:file-modified`, sent when an attached file's stored
+/// copy is edited or deleted outside the app
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct ProjectFileModified {
+    #[ts(type = "string")]
+    pub project_id: Uuid,
+    #[ts(type = "string")]
+    pub file_id: Uuid,
+    /// Size in bytes
+    #[ts(type = "number")]
+    pub size: u64,
+    pub sha256: String,
+    pub missing: bool,
+}
+
+impl From<&ProjectFile> for ProjectFileModified {
+    fn from(file: &ProjectFile) -> Self {
+        Self {
+            project_id: file.project_id,
+            file_id: file.id,
+            size: file.size,
+            sha256: file.sha256.clone(),
+            missing: file.missing,
+        }
+    }
+}
+
+/// A change to project data. Serializes to the event payload alone; the
+/// event name comes from [`ProjectEvent::name`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum ProjectEvent {
+    Created(Project),
+    Updated(Project),
+    Deleted(ProjectRef),
+    /// Files were attached to or removed from the project
+    FilesChanged(ProjectRef),
+    FileModified(ProjectFileModified),
+}
+
+impl ProjectEvent {
+    pub fn deleted(project_id: Uuid) -> Self {
+        ProjectEvent::Deleted(ProjectRef { project_id })
+    }
+
+    pub fn files_changed(project_id: Uuid) -> Self {
+        ProjectEvent::FilesChanged(ProjectRef { project_id })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProjectEvent::Created(_) => PROJECT_CREATED,
+            ProjectEvent::Updated(_) => PROJECT_UPDATED,
+            ProjectEvent::Deleted(_) => PROJECT_DELETED,
+            ProjectEvent::FilesChanged(_) => PROJECT_FILES_CHANGED,
+            ProjectEvent::FileModified(_) => PROJECT_FILE_MODIFIED,
+        }
+    }
+
+    pub fn project_id(&self) -> Uuid {
+        match self {
+            ProjectEvent::Created(project) | ProjectEvent::Updated(project) => project.id,
+            ProjectEvent::Deleted(reference) | ProjectEvent::FilesChanged(reference) => {
+                reference.project_id
+            }
+            ProjectEvent::FileModified(modified) => modified.project_id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_is_serialized_without_tag() {
+        let project = Project::new("Tower A".to_string(), String::new());
+        let created = serde_json::to_value(ProjectEvent::Created(project.clone())).unwrap();
+        assert_eq!(created["name"], "Tower A");
+
+        let deleted = serde_json::to_value(ProjectEvent::deleted(project.id)).unwrap();
+        assert_eq!(deleted, serde_json::json!({ "project_id": project.id.to_string() }));
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        ProjectRef::export(&Default::default()).expect("Failed to export ProjectRef");
+        ProjectFileModified::export(&Default::default())
+            .expect("Failed to export ProjectFileModified");
+    }
+}
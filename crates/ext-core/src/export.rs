@@ -0,0 +1,405 @@
+//! Exporting result tables and load combinations for spreadsheets and
+//! other tools.
+//!
+//! CSV is for people: one header row, cells quoted as needed and forces
+//! and moments in the user's unit system, with the unit in the header.
+//! JSON is for programs and holds the same structs the TypeScript bindings
+//! describe, so its quantities stay in SI.
+
+use crate::combinations::LoadCombination;
+use crate::drift::csv_cell;
+use crate::results::{BaseReaction, ModalPeriod, StoryDrift};
+use crate::units::UnitSystem;
+use ext_error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// A table that can be exported from a project
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum ExportTable {
+    StoryDrifts,
+    ModalPeriods,
+    BaseReactions,
+    LoadCombinations,
+}
+
+impl ExportTable {
+    /// Result tables come from an analysis run; combinations don't
+    pub fn needs_run(&self) -> bool {
+        !matches!(self, ExportTable::LoadCombinations)
+    }
+}
+
+/// File format of a table export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// The rows of one exported table
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableRows {
+    StoryDrifts(Vec<StoryDrift>),
+    ModalPeriods(Vec<ModalPeriod>),
+    BaseReactions(Vec<BaseReaction>),
+    LoadCombinations(Vec<LoadCombination>),
+}
+
+impl TableRows {
+    pub fn table(&self) -> ExportTable {
+        match self {
+            TableRows::StoryDrifts(_) => ExportTable::StoryDrifts,
+            TableRows::ModalPeriods(_) => ExportTable::ModalPeriods,
+            TableRows::BaseReactions(_) => ExportTable::BaseReactions,
+            TableRows::LoadCombinations(_) => ExportTable::LoadCombinations,
+        }
+    }
+}
+
+/// Renders `rows` in `format`. An empty table is a header row in CSV and
+/// an empty array in JSON.
+pub fn render(rows: &TableRows, format: ExportFormat, units: UnitSystem) -> Result<String> {
+    match format {
+        ExportFormat::Csv => Ok(render_csv(rows, units)),
+        ExportFormat::Json => render_json(rows),
+    }
+}
+
+/// Header columns of a table's CSV, with units where they apply.
+pub fn csv_headers(table: ExportTable, units: UnitSystem) -> Vec<String> {
+    let force = units.force().symbol();
+    let moment = units.moment().symbol();
+    match table {
+        ExportTable::StoryDrifts => vec![
+            "Story".to_string(),
+            "Output Case".to_string(),
+            "Direction".to_string(),
+            "Drift".to_string(),
+        ],
+        ExportTable::ModalPeriods => vec![
+            "Case".to_string(),
+            "Mode".to_string(),
+            "Period (s)".to_string(),
+            "UX".to_string(),
+            "UY".to_string(),
+            "RZ".to_string(),
+        ],
+        ExportTable::BaseReactions => vec![
+            "Output Case".to_string(),
+            format!("FX ({force})"),
+            format!("FY ({force})"),
+            format!("FZ ({force})"),
+            format!("MX ({moment})"),
+            format!("MY ({moment})"),
+            format!("MZ ({moment})"),
+        ],
+        ExportTable::LoadCombinations => vec![
+            "Combination".to_string(),
+            "Load Case".to_string(),
+            "Factor".to_string(),
+        ],
+    }
+}
+
+/// Renders `rows` as CSV. Base reactions are stored in N and N·m and
+/// written in `units`; drift ratios, periods, mass ratios and factors have
+/// no unit. Numbers are written in full so they read back exactly.
+pub fn render_csv(rows: &TableRows, units: UnitSystem) -> String {
+    let mut lines = vec![csv_headers(rows.table(), units)];
+    match rows {
+        TableRows::StoryDrifts(drifts) => lines.extend(drifts.iter().map(|row| {
+            vec![
+                row.story.clone(),
+                row.load_case.clone(),
+                row.direction.as_str().to_string(),
+                row.drift.to_string(),
+            ]
+        })),
+        TableRows::ModalPeriods(modes) => lines.extend(modes.iter().map(|row| {
+            vec![
+                row.load_case.clone(),
+                row.mode.to_string(),
+                row.period.to_string(),
+                row.ux.to_string(),
+                row.uy.to_string(),
+                row.rz.to_string(),
+            ]
+        })),
+        TableRows::BaseReactions(reactions) => {
+            let force = units.force().to_si();
+            let moment = units.moment().to_si();
+            lines.extend(reactions.iter().map(|row| {
+                vec![
+                    row.load_case.clone(),
+                    (row.fx / force).to_string(),
+                    (row.fy / force).to_string(),
+                    (row.fz / force).to_string(),
+                    (row.mx / moment).to_string(),
+                    (row.my / moment).to_string(),
+                    (row.mz / moment).to_string(),
+                ]
+            }))
+        }
+        TableRows::LoadCombinations(combinations) => {
+            for combination in combinations {
+                lines.extend(combination.factors.iter().map(|(case, factor)| {
+                    vec![combination.name.clone(), case.clone(), factor.to_string()]
+                }));
+            }
+        }
+    }
+
+    let mut csv = String::new();
+    for line in lines {
+        let cells: Vec<String> = line.iter().map(|cell| csv_cell(cell)).collect();
+        csv.push_str(&cells.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Renders `rows` as a pretty-printed JSON array.
+pub fn render_json(rows: &TableRows) -> Result<String> {
+    let json = match rows {
+        TableRows::StoryDrifts(rows) => serde_json::to_string_pretty(rows),
+        TableRows::ModalPeriods(rows) => serde_json::to_string_pretty(rows),
+        TableRows::BaseReactions(rows) => serde_json::to_string_pretty(rows),
+        TableRows::LoadCombinations(rows) => serde_json::to_string_pretty(rows),
+    };
+    json.map_err(|e| AppError::Internal(format!("Failed to serialize table: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::{Direction, split_csv_line};
+
+    /// Header and data cells of an exported CSV
+    fn read_csv(csv: &str) -> (Vec<String>, Vec<Vec<String>>) {
+        let mut lines = csv.lines().map(split_csv_line);
+        let header = lines.next().expect("CSV has no header row");
+        (header, lines.collect())
+    }
+
+    fn number(cell: &str) -> f64 {
+        cell.parse().unwrap()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        let tolerance = 1e-9 * expected.abs().max(1.0);
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} vs {expected}"
+        );
+    }
+
+    fn drifts() -> Vec<StoryDrift> {
+        vec![
+            StoryDrift {
+                story: "Roof".to_string(),
+                load_case: "EQX, 5% ecc".to_string(),
+                direction: Direction::X,
+                drift: 0.004_213_7,
+            },
+            StoryDrift {
+                story: "Level \"2\"".to_string(),
+                load_case: " EQY".to_string(),
+                direction: Direction::Y,
+                drift: 3.1e-3,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_story_drifts_csv_round_trip() {
+        let source = drifts();
+        let csv = render_csv(&TableRows::StoryDrifts(source.clone()), UnitSystem::Metric);
+        let (header, rows) = read_csv(&csv);
+        assert_eq!(header, ["Story", "Output Case", "Direction", "Drift"]);
+
+        let parsed: Vec<StoryDrift> = rows
+            .iter()
+            .map(|cells| StoryDrift {
+                story: cells[0].clone(),
+                load_case: cells[1].clone(),
+                direction: Direction::parse(&cells[2]).unwrap(),
+                drift: number(&cells[3]),
+            })
+            .collect();
+        assert_eq!(parsed, source);
+    }
+
+    #[test]
+    fn test_modal_periods_csv_round_trip() {
+        let source = vec![
+            ModalPeriod {
+                load_case: "Modal".to_string(),
+                mode: 1,
+                period: 1.234_567_891,
+                ux: 0.71,
+                uy: 0.0,
+                rz: 0.02,
+            },
+            ModalPeriod {
+                load_case: "Modal".to_string(),
+                mode: 2,
+                period: 0.9,
+                ux: 1e-5,
+                uy: 0.68,
+                rz: 0.1,
+            },
+        ];
+        let csv = render_csv(
+            &TableRows::ModalPeriods(source.clone()),
+            UnitSystem::Imperial,
+        );
+        let (header, rows) = read_csv(&csv);
+        assert_eq!(header[2], "Period (s)");
+
+        let parsed: Vec<ModalPeriod> = rows
+            .iter()
+            .map(|cells| ModalPeriod {
+                load_case: cells[0].clone(),
+                mode: cells[1].parse().unwrap(),
+                period: number(&cells[2]),
+                ux: number(&cells[3]),
+                uy: number(&cells[4]),
+                rz: number(&cells[5]),
+            })
+            .collect();
+        assert_eq!(parsed, source);
+    }
+
+    #[test]
+    fn test_base_reactions_csv_round_trip_in_user_units() {
+        let source = vec![BaseReaction {
+            load_case: "Dead".to_string(),
+            fx: 1_250.0,
+            fy: -3.5e4,
+            fz: 8.2e6,
+            mx: 1.5e7,
+            my: -2.25e7,
+            mz: 4.0e3,
+        }];
+
+        for units in [UnitSystem::Metric, UnitSystem::Imperial] {
+            let csv = render_csv(&TableRows::BaseReactions(source.clone()), units);
+            let (header, rows) = read_csv(&csv);
+            let force = units.force().to_si();
+            let moment = units.moment().to_si();
+            match units {
+                UnitSystem::Metric => assert_eq!(header[1], "FX (kN)"),
+                UnitSystem::Imperial => assert_eq!(header[4], "MX (kip-ft)"),
+            }
+
+            assert_eq!(rows.len(), 1);
+            let cells = &rows[0];
+            assert_eq!(cells[0], "Dead");
+            let expected = &source[0];
+            assert_close(number(&cells[1]) * force, expected.fx);
+            assert_close(number(&cells[2]) * force, expected.fy);
+            assert_close(number(&cells[3]) * force, expected.fz);
+            assert_close(number(&cells[4]) * moment, expected.mx);
+            assert_close(number(&cells[5]) * moment, expected.my);
+            assert_close(number(&cells[6]) * moment, expected.mz);
+        }
+    }
+
+    #[test]
+    fn test_load_combinations_csv_round_trip() {
+        let source = vec![
+            LoadCombination {
+                name: "1.2D + 1.6L".to_string(),
+                factors: vec![("Dead".to_string(), 1.2), ("Live".to_string(), 1.6)],
+            },
+            LoadCombination {
+                name: "0.9D - 1.0E, X".to_string(),
+                factors: vec![("Dead".to_string(), 0.9), ("EQX".to_string(), -1.0)],
+            },
+        ];
+        let csv = render_csv(
+            &TableRows::LoadCombinations(source.clone()),
+            UnitSystem::Metric,
+        );
+        let (header, rows) = read_csv(&csv);
+        assert_eq!(header, ["Combination", "Load Case", "Factor"]);
+
+        let mut parsed: Vec<LoadCombination> = Vec::new();
+        for cells in rows {
+            let factor = (cells[1].clone(), number(&cells[2]));
+            match parsed.last_mut() {
+                Some(last) if last.name == cells[0] => last.factors.push(factor),
+                _ => parsed.push(LoadCombination {
+                    name: cells[0].clone(),
+                    factors: vec![factor],
+                }),
+            }
+        }
+        assert_eq!(parsed, source);
+    }
+
+    #[test]
+    fn test_empty_table_has_only_headers() {
+        let empty = [
+            TableRows::StoryDrifts(Vec::new()),
+            TableRows::ModalPeriods(Vec::new()),
+            TableRows::BaseReactions(Vec::new()),
+            TableRows::LoadCombinations(Vec::new()),
+        ];
+        for rows in &empty {
+            let csv = render(rows, ExportFormat::Csv, UnitSystem::Metric).unwrap();
+            assert_eq!(csv.lines().count(), 1);
+            assert_eq!(
+                render(rows, ExportFormat::Json, UnitSystem::Metric).unwrap(),
+                "[]"
+            );
+        }
+    }
+
+    #[test]
+    fn test_json_matches_bindings_and_stays_in_si() {
+        let source = drifts();
+        let json = render_json(&TableRows::StoryDrifts(source.clone())).unwrap();
+        let parsed: Vec<StoryDrift> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, source);
+
+        let reaction = BaseReaction {
+            load_case: "Dead".to_string(),
+            fx: 1_250.0,
+            fy: 0.0,
+            fz: 0.0,
+            mx: 0.0,
+            my: 0.0,
+            mz: 0.0,
+        };
+        let rows = TableRows::BaseReactions(vec![reaction.clone()]);
+        let json = render(&rows, ExportFormat::Json, UnitSystem::Imperial).unwrap();
+        let parsed: Vec<BaseReaction> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, vec![reaction]);
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        ExportTable::export(&Default::default()).expect("Failed to export ExportTable");
+        ExportFormat::export(&Default::default()).expect("Failed to export ExportFormat");
+    }
+}
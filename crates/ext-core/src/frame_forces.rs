@@ -0,0 +1,214 @@
+//! Frame member forces (ETABS "Element Forces - Frames").
+//!
+//! A large model has hundreds of thousands of rows, one per frame, output
+//! station and load case or combination, so they are stored per run and
+//! read back one filtered page at a time rather than as a whole table.
+
+use crate::results::{self, AnalysisRun};
+use crate::units::{ForceUnit, LengthUnit, MomentUnit};
+use ext_error::{AppError, Result};
+use polars::prelude::{DataFrame, PolarsError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use ts_rs::TS;
+
+/// Most rows one page of frame forces can hold
+pub const MAX_FRAME_FORCE_PAGE_SIZE: usize = 1000;
+
+/// Forces at one output station of a frame member, in N and N·m
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct FrameForce {
+    pub story: String,
+    /// Element label, e.g. "C12"
+    pub frame: String,
+    /// Load case or combination
+    pub load_case: String,
+    /// Distance from the member's I-end, in m
+    pub station: f64,
+    pub p: f64,
+    pub v2: f64,
+    pub v3: f64,
+    pub t: f64,
+    pub m2: f64,
+    pub m3: f64,
+}
+
+/// Narrows a frame force query; each field that is set must match exactly
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+#[serde(default)]
+pub struct FrameForceFilter {
+    pub story: Option<String>,
+    /// Load case or combination
+    pub load_case: Option<String>,
+    /// Element label
+    pub frame: Option<String>,
+}
+
+/// One page of frame forces matching a filter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct FrameForcePage {
+    pub rows: Vec<FrameForce>,
+    /// Rows matching the filter over all pages
+    #[ts(type = "number")]
+    pub total: u64,
+    pub page: usize,
+    pub page_size: usize,
+}
+
+/// Frame forces saved from ETABS: the run holding them and how many rows
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct FrameForceImport {
+    pub run: AnalysisRun,
+    pub rows: usize,
+}
+
+/// Fails unless `page_size` is between 1 and [`MAX_FRAME_FORCE_PAGE_SIZE`].
+pub fn validate_page_size(page_size: usize) -> Result<()> {
+    if !(1..=MAX_FRAME_FORCE_PAGE_SIZE).contains(&page_size) {
+        return Err(AppError::Validation(format!(
+            "page_size must be between 1 and {MAX_FRAME_FORCE_PAGE_SIZE} (got {page_size})"
+        )));
+    }
+    Ok(())
+}
+
+/// Reads the frame forces the sidecar's `extract-results` writes to
+/// `frame_forces.parquet`.
+pub fn read_frame_forces_parquet(path: &Path) -> Result<Vec<FrameForce>> {
+    frame_forces_from_frame(&results::read_parquet(path)?)
+}
+
+/// Frame forces from the `story`, `frame`, `loadCase`, `station`, `p`,
+/// `v2`, `v3`, `t`, `m2` and `m3` columns of an extracted results table,
+/// which the sidecar writes in kN, kN·m and m.
+pub fn frame_forces_from_frame(frame: &DataFrame) -> Result<Vec<FrameForce>> {
+    let invalid = |e: PolarsError| AppError::Etabs(format!("Invalid frame force table: {e}"));
+    let [stories, frames, load_cases] = ["story", "frame", "loadCase"]
+        .map(|name| frame.column(name).and_then(|c| c.str()).map_err(invalid));
+    let (stories, frames, load_cases) = (stories?, frames?, load_cases?);
+    let [stations, p, v2, v3, t, m2, m3] = ["station", "p", "v2", "v3", "t", "m2", "m3"]
+        .map(|name| frame.column(name).and_then(|c| c.f64()).map_err(invalid));
+    let (stations, p, v2, v3, t, m2, m3) = (stations?, p?, v2?, v3?, t?, m2?, m3?);
+
+    let force = ForceUnit::Kilonewton.to_si();
+    let moment = MomentUnit {
+        force: ForceUnit::Kilonewton,
+        length: LengthUnit::Meter,
+    }
+    .to_si();
+    let mut forces = Vec::with_capacity(frame.height());
+    for row in 0..frame.height() {
+        let (
+            Some(story),
+            Some(member),
+            Some(load_case),
+            Some(station),
+            Some(p),
+            Some(v2),
+            Some(v3),
+            Some(t),
+            Some(m2),
+            Some(m3),
+        ) = (
+            stories.get(row),
+            frames.get(row),
+            load_cases.get(row),
+            stations.get(row),
+            p.get(row),
+            v2.get(row),
+            v3.get(row),
+            t.get(row),
+            m2.get(row),
+            m3.get(row),
+        )
+        else {
+            return Err(AppError::Etabs(format!(
+                "Frame force row {} has empty cells",
+                row + 1
+            )));
+        };
+        forces.push(FrameForce {
+            story: story.to_string(),
+            frame: member.to_string(),
+            load_case: load_case.to_string(),
+            station,
+            p: p * force,
+            v2: v2 * force,
+            v3: v3 * force,
+            t: t * moment,
+            m2: m2 * moment,
+            m3: m3 * moment,
+        });
+    }
+    Ok(forces)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_forces_from_extracted_table() {
+        let frame = polars::df!(
+            "story" => ["Level 1", "Level 1"],
+            "frame" => ["C1", "C1"],
+            "loadCase" => ["1.2D+1.6L", "1.2D+1.6L"],
+            "station" => [0.0, 3.5],
+            "p" => [-1520.0, -1498.5],
+            "v2" => [12.0, 12.0],
+            "v3" => [-3.0, -3.0],
+            "t" => [0.25, 0.25],
+            "m2" => [4.0, -6.5],
+            "m3" => [21.0, -21.0],
+        )
+        .unwrap();
+
+        let forces = frame_forces_from_frame(&frame).unwrap();
+        assert_eq!(forces.len(), 2);
+        assert_eq!(forces[1].frame, "C1");
+        assert_eq!(forces[1].station, 3.5);
+        assert_eq!(forces[0].p, -1_520_000.0);
+        assert_eq!(forces[1].m3, -21_000.0);
+
+        let without_station = frame.drop("station").unwrap();
+        assert!(matches!(
+            frame_forces_from_frame(&without_station),
+            Err(AppError::Etabs(_))
+        ));
+    }
+
+    #[test]
+    fn test_page_size_is_bounded() {
+        assert!(validate_page_size(1).is_ok());
+        assert!(validate_page_size(MAX_FRAME_FORCE_PAGE_SIZE).is_ok());
+        assert!(matches!(
+            validate_page_size(0),
+            Err(AppError::Validation(_))
+        ));
+        assert!(validate_page_size(MAX_FRAME_FORCE_PAGE_SIZE + 1).is_err());
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        FrameForce::export(&Default::default()).expect("Failed to export FrameForce");
+        FrameForceFilter::export(&Default::default()).expect("Failed to export FrameForceFilter");
+        FrameForcePage::export(&Default::default()).expect("Failed to export FrameForcePage");
+        FrameForceImport::export(&Default::default()).expect("Failed to export FrameForceImport");
+    }
+}
@@ -1,9 +1,81 @@
+pub mod audit;
+pub mod bulk_import;
+pub mod combinations;
+pub mod deep_link;
+pub mod diagnostics;
+pub mod drift;
+pub mod duplicates;
+pub mod e2k;
+pub mod events;
+pub mod export;
+pub mod estimate;
+pub mod frame_forces;
+pub mod modal;
+pub mod model_diff;
+pub mod notes;
+pub mod project_lock;
+pub mod rebar;
+pub mod relocation;
+pub mod report;
+pub mod results;
+pub mod search;
+pub mod seismic;
+pub mod sections;
+pub mod settings;
+pub mod sidecar;
+pub mod tasks;
+pub mod templates;
+pub mod time;
+pub mod units;
+pub mod wind;
+
+use ext_error::{AppError, Result};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use ts_rs::TS;
 use std::collections::HashMap;
 
+/// Where a project sits in its lifecycle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum ProjectStatus {
+    #[default]
+    Active,
+    /// Finished work, hidden from the default project list
+    Archived,
+    /// Pending deletion; removed for good by emptying the trash
+    Trashed,
+}
+
+impl ProjectStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProjectStatus::Active => "Active",
+            ProjectStatus::Archived => "Archived",
+            ProjectStatus::Trashed => "Trashed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "Active" => Ok(ProjectStatus::Active),
+            "Archived" => Ok(ProjectStatus::Archived),
+            "Trashed" => Ok(ProjectStatus::Trashed),
+            other => Err(AppError::Validation(format!("Unknown project status: '{other}'"))),
+        }
+    }
+}
+
+/// Days a trashed project is kept before emptying the trash removes it
+pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+
+/// Most projects the recent list can return
+pub const MAX_RECENT_PROJECTS: usize = 50;
+
 /// Main project domain model
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(
@@ -23,8 +95,32 @@ pub struct Project {
 
     #[ts(type = "string")]
     pub updated_at: DateTime<Utc>,
+
+    /// Missing from project.json files written before archiving existed
+    #[serde(default)]
+    pub status: ProjectStatus,
+
+    /// When the project was moved to the trash
+    #[serde(default)]
+    #[ts(type = "string | null")]
+    pub trashed_at: Option<DateTime<Utc>>,
+
+    /// When the project was last opened; `None` if it never was
+    #[serde(default)]
+    #[ts(type = "string | null")]
+    pub last_opened_at: Option<DateTime<Utc>>,
 }
 
+/// Maximum project name length, in characters
+pub const MAX_PROJECT_NAME_LEN: usize = 200;
+
+/// Maximum project description length, in characters
+pub const MAX_PROJECT_DESCRIPTION_LEN: usize = 2000;
+
+/// Characters rejected in project names because they are illegal in
+/// Windows file names
+const ILLEGAL_NAME_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
 impl Project {
     pub fn new(name: String, description: String) -> Self {
         let now = Utc::now();
@@ -34,8 +130,185 @@ impl Project {
             description,
             created_at: now,
             updated_at: now,
+            status: ProjectStatus::Active,
+            trashed_at: None,
+            last_opened_at: None,
         }
     }
+
+    /// Moves the project to `status`, stamping `trashed_at` when trashing.
+    pub fn set_status(&mut self, status: ProjectStatus) {
+        self.trashed_at = match status {
+            ProjectStatus::Trashed => self.trashed_at.or_else(|| Some(Utc::now())),
+            _ => None,
+        };
+        self.status = status;
+    }
+
+    pub fn validate_name(name: &str) -> Result<()> {
+        let trimmed = name.trim();
+        if trimmed.is_empty() {
+            return Err(AppError::Validation("name must not be empty".to_string()));
+        }
+
+        let length = trimmed.chars().count();
+        if length > MAX_PROJECT_NAME_LEN {
+            return Err(AppError::Validation(format!(
+                "name must be at most {MAX_PROJECT_NAME_LEN} characters (got {length})"
+            )));
+        }
+
+        if let Some(c) = trimmed
+            .chars()
+            .find(|c| ILLEGAL_NAME_CHARS.contains(c) || c.is_control())
+        {
+            return Err(AppError::Validation(format!(
+                "name contains a character that is not allowed: {c:?}"
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_description(description: &str) -> Result<()> {
+        let length = description.chars().count();
+        if length > MAX_PROJECT_DESCRIPTION_LEN {
+            return Err(AppError::Validation(format!(
+                "description must be at most {MAX_PROJECT_DESCRIPTION_LEN} characters (got {length})"
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        Self::validate_name(&self.name)?;
+        Self::validate_description(&self.description)
+    }
+}
+
+/// File attached to a project, copied into the project's `files/` folder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct ProjectFile {
+    #[ts(type = "string")]
+    pub id: Uuid,
+
+    #[ts(type = "string")]
+    pub project_id: Uuid,
+
+    /// File name as it was when the user added it
+    pub original_name: String,
+
+    /// Location of the copy, relative to the project directory
+    pub stored_path: String,
+
+    /// Size in bytes
+    #[ts(type = "number")]
+    pub size: u64,
+
+    /// Lowercase hex SHA-256 of the contents
+    pub sha256: String,
+
+    #[ts(type = "string")]
+    pub added_at: DateTime<Utc>,
+
+    /// The stored copy was deleted outside the app
+    #[serde(default)]
+    pub missing: bool,
+}
+
+/// Snapshot of a project's metadata and attached-file list
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct ProjectBackup {
+    /// Sortable timestamp-based id, also the backup's file name
+    pub id: String,
+
+    #[ts(type = "string")]
+    pub created_at: DateTime<Utc>,
+
+    pub project: Project,
+
+    pub files: Vec<ProjectFile>,
+}
+
+/// Number of backups kept per project unless configured otherwise
+pub const DEFAULT_BACKUP_RETENTION: usize = 10;
+
+/// Label used to group projects, e.g. by client or code version
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct Tag {
+    #[ts(type = "string")]
+    pub id: Uuid,
+
+    pub name: String,
+
+    /// Hex color such as "#1e90ff" or "#09f"
+    pub color: String,
+}
+
+/// Maximum tag name length, in characters
+pub const MAX_TAG_NAME_LEN: usize = 50;
+
+impl Tag {
+    pub fn new(name: String, color: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            color,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err(AppError::Validation("tag name must not be empty".to_string()));
+        }
+
+        let length = name.chars().count();
+        if length > MAX_TAG_NAME_LEN {
+            return Err(AppError::Validation(format!(
+                "tag name must be at most {MAX_TAG_NAME_LEN} characters (got {length})"
+            )));
+        }
+
+        Self::validate_color(&self.color)
+    }
+
+    /// Accepts `#rgb` and `#rrggbb`, in either case.
+    pub fn validate_color(color: &str) -> Result<()> {
+        let valid = color
+            .strip_prefix('#')
+            .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()));
+        if !valid {
+            return Err(AppError::Validation(format!(
+                "tag color must be a hex color like #1e90ff (got '{color}')"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Options for creating a project
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct CreateProjectOptions {
+    /// Append " (2)", " (3)", ... instead of rejecting a name that is taken
+    #[serde(default)]
+    pub auto_suffix_name: bool,
 }
 
 /// Complete project state including all branches
@@ -208,6 +481,50 @@ pub struct GeometryDiffResult {
     pub total_changes: usize,
 }
 
+/// Cloud sync client detected from well-known folder markers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum SyncProvider {
+    OneDrive,
+    Dropbox,
+    GoogleDrive,
+    ICloud,
+    Box,
+}
+
+/// Writability and sync state of the projects directory
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct StorageStatus {
+    pub projects_dir: String,
+    pub read_only: bool,
+    pub sync_provider: Option<SyncProvider>,
+    pub advisories: Vec<String>,
+}
+
+/// Whether storage opened at startup. Until it has, only settings and
+/// retrying work; everything else fails with "storage not initialized".
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+#[serde(tag = "state")]
+pub enum InitStatus {
+    Ready,
+    Failed {
+        /// Directory that couldn't be opened
+        data_dir: String,
+        error: AppError,
+    },
+}
+
 /// Request to create a new branch
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(
@@ -272,10 +589,97 @@ mod tests {
         assert_eq!(project.name, "Test Project");
     }
 
+    #[test]
+    fn test_legacy_project_json_defaults_to_active() {
+        let json = r#"{
+            "id": "6f1c2a9e-4b7d-4c1e-9a43-2f5d8b0c7e11",
+            "name": "Legacy",
+            "description": "",
+            "created_at": "2025-01-26T06:40:00+00:00",
+            "updated_at": "2025-01-26T06:40:00+00:00"
+        }"#;
+        let project: Project = serde_json::from_str(json).unwrap();
+        assert_eq!(project.status, ProjectStatus::Active);
+        assert!(project.trashed_at.is_none());
+    }
+
+    #[test]
+    fn test_set_status_stamps_trashed_at() {
+        let mut project = Project::new("Tower A".to_string(), String::new());
+        project.set_status(ProjectStatus::Trashed);
+        assert!(project.trashed_at.is_some());
+
+        project.set_status(ProjectStatus::Active);
+        assert!(project.trashed_at.is_none());
+    }
+
+    #[test]
+    fn test_tag_color_must_be_hex() {
+        assert!(Tag::validate_color("#1E90ff").is_ok());
+        assert!(Tag::validate_color("#09f").is_ok());
+        for color in ["1e90ff", "#1e90f", "#ggg", "blue", ""] {
+            assert!(
+                matches!(Tag::validate_color(color), Err(AppError::Validation(_))),
+                "{color:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_tag_name_must_not_be_blank() {
+        let tag = Tag::new("  ".to_string(), "#fff".to_string());
+        assert!(matches!(tag.validate(), Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_project_status_round_trips_as_str() {
+        for status in [ProjectStatus::Active, ProjectStatus::Archived, ProjectStatus::Trashed] {
+            assert_eq!(ProjectStatus::parse(status.as_str()).unwrap(), status);
+        }
+        assert!(ProjectStatus::parse("Deleted").is_err());
+    }
+
+    #[test]
+    fn test_validate_name_boundaries() {
+        assert!(Project::validate_name(&"a".repeat(MAX_PROJECT_NAME_LEN)).is_ok());
+        assert!(Project::validate_name(&"a".repeat(MAX_PROJECT_NAME_LEN + 1)).is_err());
+        // Length is counted in characters, not bytes
+        assert!(Project::validate_name(&"Đ".repeat(MAX_PROJECT_NAME_LEN)).is_ok());
+    }
+
+    #[test]
+    fn test_validate_name_rejects_blank() {
+        for name in ["", "   ", "\t\n"] {
+            match Project::validate_name(name) {
+                Err(AppError::Validation(message)) => assert!(message.starts_with("name")),
+                other => panic!("expected validation error for {name:?}, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_name_rejects_path_characters() {
+        for name in ["Tower/A", "Tower\\A", "Tower: A", "Tower*", "Tower?", "A<B>", "a|b", "\"A\""] {
+            assert!(Project::validate_name(name).is_err(), "{name:?} should be rejected");
+        }
+        assert!(Project::validate_name("Tower A (Phase 2) - Tháp Đông").is_ok());
+    }
+
+    #[test]
+    fn test_validate_description_boundaries() {
+        assert!(Project::validate_description("").is_ok());
+        assert!(Project::validate_description(&"x".repeat(MAX_PROJECT_DESCRIPTION_LEN)).is_ok());
+        match Project::validate_description(&"x".repeat(MAX_PROJECT_DESCRIPTION_LEN + 1)) {
+            Err(AppError::Validation(message)) => assert!(message.starts_with("description")),
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_export_typescript_bindings() {
         // Export all types
         Project::export(&Default::default()).expect("Failed to export Project");
+        ProjectStatus::export(&Default::default()).expect("Failed to export ProjectStatus");
         ProjectState::export(&Default::default()).expect("Failed to export ProjectState");
         BranchData::export(&Default::default()).expect("Failed to export BranchData");
         VersionInfo::export(&Default::default()).expect("Failed to export VersionInfo");
@@ -290,5 +694,12 @@ mod tests {
         SaveVersionRequest::export(&Default::default()).expect("Failed to export SaveVersionRequest");
         CompareVersionsRequest::export(&Default::default()).expect("Failed to export CompareVersionsRequest");
         VersionIdentifier::export(&Default::default()).expect("Failed to export VersionIdentifier");
+        SyncProvider::export(&Default::default()).expect("Failed to export SyncProvider");
+        StorageStatus::export(&Default::default()).expect("Failed to export StorageStatus");
+        InitStatus::export(&Default::default()).expect("Failed to export InitStatus");
+        CreateProjectOptions::export(&Default::default()).expect("Failed to export CreateProjectOptions");
+        ProjectFile::export(&Default::default()).expect("Failed to export ProjectFile");
+        Tag::export(&Default::default()).expect("Failed to export Tag");
+        ProjectBackup::export(&Default::default()).expect("Failed to export ProjectBackup");
     }
 }
\ No newline at end of file
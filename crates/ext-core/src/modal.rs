@@ -0,0 +1,223 @@
+//! Modal results: periods, frequencies and cumulative mass participation,
+//! with the ASCE 7-16 §12.9.1.1 check that the modes included reach 90% of
+//! the actual mass in each orthogonal horizontal direction.
+//!
+//! Modes come from the ETABS "Modal Participating Mass Ratios" table of one
+//! modal load case, either imported or pulled from a connected ETABS.
+
+use crate::results::{Direction, ModalPeriod};
+use ext_error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Combined modal mass participation required in each horizontal
+/// direction: ASCE 7-16 §12.9.1.1
+pub const MIN_MASS_PARTICIPATION: f64 = 0.90;
+
+/// ETABS rounds its ratios; a sum this close to the minimum reaches it
+const PARTICIPATION_TOLERANCE: f64 = 1e-6;
+
+/// One vibration mode with the participation accumulated up to it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct ModalMode {
+    pub mode: u32,
+    /// Period in s
+    pub period: f64,
+    /// Frequency in Hz
+    pub frequency: f64,
+    /// Participating mass ratios of this mode
+    pub ux: f64,
+    pub uy: f64,
+    pub rz: f64,
+    /// Participating mass ratios of this mode and all before it
+    pub sum_ux: f64,
+    pub sum_uy: f64,
+    pub sum_rz: f64,
+}
+
+/// Modes of one modal load case and the 90% mass participation check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct ModalResults {
+    pub load_case: String,
+    /// Ordered by mode number
+    pub modes: Vec<ModalMode>,
+    /// First mode at which the cumulative UX ratio reaches 90%, if any
+    pub mode_reaching_90_x: Option<u32>,
+    /// First mode at which the cumulative UY ratio reaches 90%, if any
+    pub mode_reaching_90_y: Option<u32>,
+    /// Both horizontal directions reach 90%
+    pub meets_mass_participation: bool,
+}
+
+/// Builds the modal results of `load_case`, which may be left out when the
+/// periods cover a single modal case.
+pub fn modal_results(periods: &[ModalPeriod], load_case: Option<&str>) -> Result<ModalResults> {
+    let load_case = match load_case {
+        Some(case) => case.to_string(),
+        None => {
+            let mut cases: Vec<&str> = Vec::new();
+            for period in periods {
+                if !cases.contains(&period.load_case.as_str()) {
+                    cases.push(&period.load_case);
+                }
+            }
+            match cases.as_slice() {
+                [] => {
+                    return Err(AppError::Validation(
+                        "The analysis run has no modal periods".to_string(),
+                    ));
+                }
+                [case] => case.to_string(),
+                _ => {
+                    return Err(AppError::Validation(format!(
+                        "Modal periods cover several load cases ({}); choose one",
+                        cases.join(", ")
+                    )));
+                }
+            }
+        }
+    };
+
+    let mut selected: Vec<&ModalPeriod> = periods
+        .iter()
+        .filter(|period| period.load_case == load_case)
+        .collect();
+    if selected.is_empty() {
+        return Err(AppError::Validation(format!(
+            "No modal periods for load case '{load_case}'"
+        )));
+    }
+    selected.sort_by_key(|period| period.mode);
+
+    let mut modes = Vec::with_capacity(selected.len());
+    let (mut sum_ux, mut sum_uy, mut sum_rz) = (0.0, 0.0, 0.0);
+    for period in selected {
+        if !(period.period.is_finite() && period.period > 0.0) {
+            return Err(AppError::Validation(format!(
+                "Mode {} of '{load_case}' has no positive period (got {})",
+                period.mode, period.period
+            )));
+        }
+        sum_ux += period.ux;
+        sum_uy += period.uy;
+        sum_rz += period.rz;
+        modes.push(ModalMode {
+            mode: period.mode,
+            period: period.period,
+            frequency: 1.0 / period.period,
+            ux: period.ux,
+            uy: period.uy,
+            rz: period.rz,
+            sum_ux,
+            sum_uy,
+            sum_rz,
+        });
+    }
+
+    let mode_reaching_90_x = mode_reaching_participation(&modes, Direction::X);
+    let mode_reaching_90_y = mode_reaching_participation(&modes, Direction::Y);
+    Ok(ModalResults {
+        load_case,
+        modes,
+        mode_reaching_90_x,
+        mode_reaching_90_y,
+        meets_mass_participation: mode_reaching_90_x.is_some() && mode_reaching_90_y.is_some(),
+    })
+}
+
+/// First of `modes` at which the cumulative participation in `direction`
+/// reaches [`MIN_MASS_PARTICIPATION`], or `None` if it never does.
+pub fn mode_reaching_participation(modes: &[ModalMode], direction: Direction) -> Option<u32> {
+    modes
+        .iter()
+        .find(|mode| {
+            let sum = match direction {
+                Direction::X => mode.sum_ux,
+                Direction::Y => mode.sum_uy,
+            };
+            sum >= MIN_MASS_PARTICIPATION - PARTICIPATION_TOLERANCE
+        })
+        .map(|mode| mode.mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn period(mode: u32, period: f64, ux: f64, uy: f64) -> ModalPeriod {
+        ModalPeriod {
+            load_case: "Modal".to_string(),
+            mode,
+            period,
+            ux,
+            uy,
+            rz: 0.01,
+        }
+    }
+
+    #[test]
+    fn test_modal_results_accumulate_participation() {
+        // Out of order, as a pasted table might be
+        let periods = vec![
+            period(3, 0.25, 0.10, 0.02),
+            period(1, 1.25, 0.72, 0.01),
+            period(2, 1.0, 0.01, 0.80),
+            period(4, 0.2, 0.0, 0.08),
+        ];
+
+        let results = modal_results(&periods, None).unwrap();
+        assert_eq!(results.load_case, "Modal");
+        assert_eq!(
+            results.modes.iter().map(|m| m.mode).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+        assert!((results.modes[0].frequency - 0.8).abs() < 1e-12);
+        assert!((results.modes[2].sum_ux - 0.83).abs() < 1e-12);
+        assert_eq!(results.mode_reaching_90_x, None);
+        assert_eq!(results.mode_reaching_90_y, Some(4));
+        assert!(!results.meets_mass_participation);
+    }
+
+    #[test]
+    fn test_rounded_sum_reaches_ninety_percent() {
+        let periods = vec![period(1, 1.0, 0.5, 0.6), period(2, 0.5, 0.3999999, 0.3)];
+        let results = modal_results(&periods, Some("Modal")).unwrap();
+        assert_eq!(results.mode_reaching_90_x, Some(2));
+        assert_eq!(results.mode_reaching_90_y, Some(2));
+        assert!(results.meets_mass_participation);
+    }
+
+    #[test]
+    fn test_several_modal_cases_need_a_choice() {
+        let mut ritz = period(1, 1.1, 0.9, 0.9);
+        ritz.load_case = "Ritz".to_string();
+        let periods = vec![period(1, 1.0, 0.9, 0.9), ritz];
+
+        match modal_results(&periods, None) {
+            Err(AppError::Validation(message)) => assert!(message.contains("Modal, Ritz")),
+            other => panic!("expected validation error, got {other:?}"),
+        }
+        assert_eq!(
+            modal_results(&periods, Some("Ritz")).unwrap().modes[0].period,
+            1.1
+        );
+        assert!(matches!(
+            modal_results(&periods, Some("Eigen")),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        ModalMode::export(&Default::default()).expect("Failed to export ModalMode");
+        ModalResults::export(&Default::default()).expect("Failed to export ModalResults");
+    }
+}
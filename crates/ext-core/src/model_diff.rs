@@ -0,0 +1,404 @@
+//! Differences between two revisions of an ETABS model.
+//!
+//! Stories, materials, frame sections and line objects are matched by name
+//! and compared field by field. Lengths are compared in metres and
+//! stresses in pascals, with a relative tolerance so that values rounded
+//! differently by two exports are not reported as changes.
+
+use crate::e2k::{EtabsModel, FrameSection, LineObject, Material, Story};
+use crate::units::{ForceUnit, Length, LengthUnit, Stress, StressUnit};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Numbers closer than this, relative to the larger one, are equal
+pub const RELATIVE_TOLERANCE: f64 = 1e-6;
+
+/// Kind of model object a change applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum ModelObjectKind {
+    Story,
+    Material,
+    FrameSection,
+    Line,
+}
+
+/// How an object differs between the two models
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// Value of one property; lengths in metres, stresses in pascals
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+#[serde(untagged)]
+pub enum PropertyValue {
+    Number(f64),
+    Text(String),
+    Flag(bool),
+}
+
+/// One property that differs on a modified object
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct PropertyChange {
+    /// Field name as in the parsed model, e.g. "height"
+    pub property: String,
+    /// `None` when the first model does not set the property
+    pub before: Option<PropertyValue>,
+    /// `None` when the second model does not set the property
+    pub after: Option<PropertyValue>,
+}
+
+/// An added, removed or modified object
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct ModelChange {
+    pub object: ModelObjectKind,
+    pub name: String,
+    pub change: ChangeKind,
+    /// Differing properties; empty for added and removed objects
+    pub properties: Vec<PropertyChange>,
+}
+
+/// Changes from one model to another, stories first, then materials,
+/// frame sections and lines
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct ModelDiff {
+    pub changes: Vec<ModelChange>,
+}
+
+impl ModelDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Whether `a` and `b` are equal within [`RELATIVE_TOLERANCE`].
+pub fn nearly_equal(a: f64, b: f64) -> bool {
+    a == b || (a - b).abs() <= RELATIVE_TOLERANCE * a.abs().max(b.abs())
+}
+
+/// Changes that turn model `a` into model `b`.
+pub fn diff_models(a: &EtabsModel, b: &EtabsModel) -> ModelDiff {
+    let mut changes = Vec::new();
+    diff_objects(
+        ModelObjectKind::Story,
+        &a.stories,
+        &b.stories,
+        |s| &s.name,
+        diff_story,
+        &mut changes,
+    );
+    diff_objects(
+        ModelObjectKind::Material,
+        &a.materials,
+        &b.materials,
+        |m| &m.name,
+        diff_material,
+        &mut changes,
+    );
+    diff_objects(
+        ModelObjectKind::FrameSection,
+        &a.frame_sections,
+        &b.frame_sections,
+        |s| &s.name,
+        diff_frame_section,
+        &mut changes,
+    );
+    diff_objects(
+        ModelObjectKind::Line,
+        &a.lines,
+        &b.lines,
+        |l| &l.name,
+        diff_line,
+        &mut changes,
+    );
+    ModelDiff { changes }
+}
+
+/// Removed and modified objects in the order of `a`, then added ones in
+/// the order of `b`.
+fn diff_objects<T>(
+    object: ModelObjectKind,
+    a: &[T],
+    b: &[T],
+    name: impl Fn(&T) -> &String,
+    compare: impl Fn(&T, &T) -> Vec<PropertyChange>,
+    changes: &mut Vec<ModelChange>,
+) {
+    for before in a {
+        let change = match b.iter().find(|after| name(after) == name(before)) {
+            None => (ChangeKind::Removed, Vec::new()),
+            Some(after) => match compare(before, after) {
+                properties if properties.is_empty() => continue,
+                properties => (ChangeKind::Modified, properties),
+            },
+        };
+        changes.push(ModelChange {
+            object,
+            name: name(before).clone(),
+            change: change.0,
+            properties: change.1,
+        });
+    }
+
+    for after in b {
+        if !a.iter().any(|before| name(before) == name(after)) {
+            changes.push(ModelChange {
+                object,
+                name: name(after).clone(),
+                change: ChangeKind::Added,
+                properties: Vec::new(),
+            });
+        }
+    }
+}
+
+/// Collects the differing properties of one object.
+#[derive(Default)]
+struct Properties(Vec<PropertyChange>);
+
+impl Properties {
+    fn push(
+        &mut self,
+        property: &str,
+        before: Option<PropertyValue>,
+        after: Option<PropertyValue>,
+    ) {
+        let same = match (&before, &after) {
+            (Some(PropertyValue::Number(a)), Some(PropertyValue::Number(b))) => {
+                nearly_equal(*a, *b)
+            }
+            _ => before == after,
+        };
+        if !same {
+            self.0.push(PropertyChange {
+                property: property.to_string(),
+                before,
+                after,
+            });
+        }
+    }
+
+    fn number(&mut self, property: &str, before: Option<f64>, after: Option<f64>) {
+        self.push(
+            property,
+            before.map(PropertyValue::Number),
+            after.map(PropertyValue::Number),
+        );
+    }
+
+    fn length(&mut self, property: &str, before: Option<Length>, after: Option<Length>) {
+        self.number(property, before.map(metres), after.map(metres));
+    }
+
+    fn stress(&mut self, property: &str, before: Option<Stress>, after: Option<Stress>) {
+        self.number(property, before.map(pascals), after.map(pascals));
+    }
+
+    fn text(&mut self, property: &str, before: Option<&str>, after: Option<&str>) {
+        let text = |value: &str| PropertyValue::Text(value.to_string());
+        self.push(property, before.map(text), after.map(text));
+    }
+
+    fn flag(&mut self, property: &str, before: bool, after: bool) {
+        self.push(
+            property,
+            Some(PropertyValue::Flag(before)),
+            Some(PropertyValue::Flag(after)),
+        );
+    }
+}
+
+fn metres(length: Length) -> f64 {
+    length.to(LengthUnit::Meter)
+}
+
+fn pascals(stress: Stress) -> f64 {
+    stress.to(StressUnit {
+        force: ForceUnit::Newton,
+        length: LengthUnit::Meter,
+    })
+}
+
+fn diff_story(a: &Story, b: &Story) -> Vec<PropertyChange> {
+    let mut properties = Properties::default();
+    properties.length("height", Some(a.height), Some(b.height));
+    properties.length("elevation", Some(a.elevation), Some(b.elevation));
+    properties.flag("master_story", a.master_story, b.master_story);
+    properties.text(
+        "similar_to",
+        a.similar_to.as_deref(),
+        b.similar_to.as_deref(),
+    );
+    properties.0
+}
+
+fn diff_material(a: &Material, b: &Material) -> Vec<PropertyChange> {
+    let mut properties = Properties::default();
+    properties.text(
+        "material_type",
+        a.material_type.as_deref(),
+        b.material_type.as_deref(),
+    );
+    properties.number(
+        "weight_per_volume",
+        a.weight_per_volume,
+        b.weight_per_volume,
+    );
+    properties.stress("elastic_modulus", a.elastic_modulus, b.elastic_modulus);
+    properties.number("poisson_ratio", a.poisson_ratio, b.poisson_ratio);
+    properties.stress("fc", a.fc, b.fc);
+    properties.stress("fy", a.fy, b.fy);
+    properties.0
+}
+
+fn diff_frame_section(a: &FrameSection, b: &FrameSection) -> Vec<PropertyChange> {
+    let mut properties = Properties::default();
+    properties.text("material", a.material.as_deref(), b.material.as_deref());
+    properties.text("shape", a.shape.as_deref(), b.shape.as_deref());
+    properties.length("depth", a.depth, b.depth);
+    properties.length("width", a.width, b.width);
+    properties.0
+}
+
+fn diff_line(a: &LineObject, b: &LineObject) -> Vec<PropertyChange> {
+    let mut properties = Properties::default();
+    properties.text(
+        "kind",
+        Some(format!("{:?}", a.kind).as_str()),
+        Some(format!("{:?}", b.kind).as_str()),
+    );
+    properties.text(
+        "point_i",
+        Some(a.point_i.as_str()),
+        Some(b.point_i.as_str()),
+    );
+    properties.text(
+        "point_j",
+        Some(a.point_j.as_str()),
+        Some(b.point_j.as_str()),
+    );
+    properties.number(
+        "stories_spanned",
+        Some(a.stories_spanned as f64),
+        Some(b.stories_spanned as f64),
+    );
+    properties.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::e2k::parse_e2k;
+    use std::path::Path;
+
+    fn fixture(name: &str) -> EtabsModel {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name);
+        parse_e2k(&path).unwrap()
+    }
+
+    fn change<'a>(diff: &'a ModelDiff, object: ModelObjectKind, name: &str) -> &'a ModelChange {
+        diff.changes
+            .iter()
+            .find(|c| c.object == object && c.name == name)
+            .unwrap_or_else(|| panic!("no change for {object:?} {name}"))
+    }
+
+    #[test]
+    fn test_identical_models_have_no_changes() {
+        let model = fixture("two_story.e2k");
+        assert!(diff_models(&model, &model).is_empty());
+    }
+
+    #[test]
+    fn test_story_height_and_section_swap() {
+        let diff = diff_models(&fixture("two_story.e2k"), &fixture("two_story_rev.e2k"));
+
+        // Story1 grows from 3.5 to 4.0 m, which lifts Story2 with it
+        let story1 = change(&diff, ModelObjectKind::Story, "Story1");
+        assert_eq!(story1.change, ChangeKind::Modified);
+        let properties: Vec<&str> = story1
+            .properties
+            .iter()
+            .map(|p| p.property.as_str())
+            .collect();
+        assert_eq!(properties, vec!["height", "elevation"]);
+        assert_eq!(
+            story1.properties[0].before,
+            Some(PropertyValue::Number(3.5))
+        );
+        assert_eq!(story1.properties[0].after, Some(PropertyValue::Number(4.0)));
+        let story2 = change(&diff, ModelObjectKind::Story, "Story2");
+        assert_eq!(story2.properties.len(), 1);
+        assert_eq!(story2.properties[0].property, "elevation");
+
+        let removed = change(&diff, ModelObjectKind::FrameSection, "B300X600");
+        assert_eq!(removed.change, ChangeKind::Removed);
+        let added = change(&diff, ModelObjectKind::FrameSection, "B350X700");
+        assert_eq!(added.change, ChangeKind::Added);
+
+        // Everything else, including materials and lines, is unchanged
+        assert_eq!(diff.changes.len(), 4);
+    }
+
+    #[test]
+    fn test_small_float_differences_are_ignored() {
+        let a = fixture("two_story.e2k");
+        let mut b = a.clone();
+        b.stories[0].height = Length::from(3.500_000_1, LengthUnit::Meter);
+        b.materials[1].poisson_ratio = Some(0.200_000_01);
+        assert!(diff_models(&a, &b).is_empty());
+
+        b.stories[0].height = Length::from(3.51, LengthUnit::Meter);
+        assert_eq!(diff_models(&a, &b).changes.len(), 1);
+    }
+
+    #[test]
+    fn test_unset_property_is_a_change() {
+        let a = fixture("two_story.e2k");
+        let mut b = a.clone();
+        b.frame_sections[0].width = None;
+
+        let diff = diff_models(&a, &b);
+        let section = change(&diff, ModelObjectKind::FrameSection, "C500X500");
+        assert_eq!(section.properties[0].property, "width");
+        assert_eq!(section.properties[0].after, None);
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        ModelObjectKind::export(&Default::default()).expect("Failed to export ModelObjectKind");
+        ChangeKind::export(&Default::default()).expect("Failed to export ChangeKind");
+        PropertyValue::export(&Default::default()).expect("Failed to export PropertyValue");
+        PropertyChange::export(&Default::default()).expect("Failed to export PropertyChange");
+        ModelChange::export(&Default::default()).expect("Failed to export ModelChange");
+        ModelDiff::export(&Default::default()).expect("Failed to export ModelDiff");
+    }
+}
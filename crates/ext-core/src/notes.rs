@@ -0,0 +1,165 @@
+//! Free-form project notes, such as design assumptions, written in
+//! markdown. Every edit keeps the previous version as a revision.
+
+use chrono::{DateTime, Utc};
+use ext_error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Longest note title, in characters
+pub const MAX_NOTE_TITLE_LEN: usize = 200;
+
+/// Largest note body, in bytes of UTF-8
+pub const MAX_NOTE_BODY_BYTES: usize = 1024 * 1024;
+
+/// Markdown note attached to a project
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct Note {
+    #[ts(type = "string")]
+    pub id: Uuid,
+    #[ts(type = "string")]
+    pub project_id: Uuid,
+    pub title: String,
+    pub body_markdown: String,
+    #[ts(type = "string")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "string")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Earlier version of a note, kept when it was edited or rolled back
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct NoteRevision {
+    #[ts(type = "string")]
+    pub id: Uuid,
+    #[ts(type = "string")]
+    pub note_id: Uuid,
+    pub title: String,
+    pub body_markdown: String,
+    /// When this version was written
+    #[ts(type = "string")]
+    pub saved_at: DateTime<Utc>,
+}
+
+impl Note {
+    pub fn new(project_id: Uuid, title: String, body_markdown: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            project_id,
+            title: title.trim().to_string(),
+            body_markdown,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// The note with a new title and body, as of now.
+    pub fn edited(&self, title: String, body_markdown: String) -> Self {
+        Self {
+            title: title.trim().to_string(),
+            body_markdown,
+            updated_at: Utc::now(),
+            ..self.clone()
+        }
+    }
+
+    /// The current version, to keep before the note is edited.
+    pub fn to_revision(&self) -> NoteRevision {
+        NoteRevision {
+            id: Uuid::new_v4(),
+            note_id: self.id,
+            title: self.title.clone(),
+            body_markdown: self.body_markdown.clone(),
+            saved_at: self.updated_at,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.title.trim().is_empty() {
+            return Err(AppError::Validation(
+                "note title must not be empty".to_string(),
+            ));
+        }
+        let length = self.title.chars().count();
+        if length > MAX_NOTE_TITLE_LEN {
+            return Err(AppError::Validation(format!(
+                "note title must be at most {MAX_NOTE_TITLE_LEN} characters (got {length})"
+            )));
+        }
+        let size = self.body_markdown.len();
+        if size > MAX_NOTE_BODY_BYTES {
+            return Err(AppError::Validation(format!(
+                "note body must be at most {MAX_NOTE_BODY_BYTES} bytes (got {size})"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_body_is_capped_in_bytes() {
+        let project_id = Uuid::new_v4();
+        let at_limit = Note::new(
+            project_id,
+            "Loads".to_string(),
+            "x".repeat(MAX_NOTE_BODY_BYTES),
+        );
+        assert!(at_limit.validate().is_ok());
+
+        // 2 bytes per character, so half as many fit
+        let wide = "é".repeat(MAX_NOTE_BODY_BYTES / 2 + 1);
+        match Note::new(project_id, "Loads".to_string(), wide).validate() {
+            Err(AppError::Validation(message)) => assert!(message.contains("note body")),
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_title_is_required() {
+        let note = Note::new(Uuid::new_v4(), "  ".to_string(), String::new());
+        assert!(matches!(note.validate(), Err(AppError::Validation(_))));
+
+        let long = "a".repeat(MAX_NOTE_TITLE_LEN + 1);
+        let note = Note::new(Uuid::new_v4(), long, String::new());
+        assert!(matches!(note.validate(), Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_edit_keeps_identity_and_revision_keeps_old_version() {
+        let note = Note::new(
+            Uuid::new_v4(),
+            " Loads ".to_string(),
+            "SDL 1.5 kPa".to_string(),
+        );
+        assert_eq!(note.title, "Loads");
+
+        let revision = note.to_revision();
+        let edited = note.edited("Loads".to_string(), "SDL 2.0 kPa".to_string());
+        assert_eq!(edited.id, note.id);
+        assert_eq!(edited.created_at, note.created_at);
+        assert!(edited.updated_at >= note.updated_at);
+        assert_eq!(revision.note_id, note.id);
+        assert_eq!(revision.body_markdown, "SDL 1.5 kPa");
+        assert_eq!(revision.saved_at, note.updated_at);
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        Note::export(&Default::default()).expect("Failed to export Note");
+        NoteRevision::export(&Default::default()).expect("Failed to export NoteRevision");
+    }
+}
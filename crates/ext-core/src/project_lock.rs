@@ -0,0 +1,88 @@
+//! Advisory locks that keep two app instances from writing the same project
+//! at once, e.g. two users sharing a data directory.
+//!
+//! A lock records who holds it and which process took it. Another instance
+//! may take a lock over once it is stale: its process is no longer running,
+//! or it is older than the configured timeout. Only the timeout applies to
+//! locks taken on another machine, whose processes can't be checked.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Hours before a lock counts as abandoned unless configured otherwise
+pub const DEFAULT_LOCK_TIMEOUT_HOURS: u32 = 12;
+
+/// Longest configurable lock timeout, one week
+pub const MAX_LOCK_TIMEOUT_HOURS: u32 = 7 * 24;
+
+/// Who is editing a project; also the contents of its lock file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectLock {
+    pub project_id: Uuid,
+    /// Shown to other users, e.g. "jane@WS-042"
+    pub holder: String,
+    /// Process id of the app instance holding the lock
+    pub pid: u32,
+    /// Machine that process runs on
+    pub host: String,
+    pub acquired_at: DateTime<Utc>,
+}
+
+impl ProjectLock {
+    pub fn new(project_id: Uuid, holder: String, pid: u32, host: String) -> Self {
+        Self {
+            project_id,
+            holder,
+            pid,
+            host,
+            acquired_at: Utc::now(),
+        }
+    }
+
+    /// Whether another instance may take the lock over. `process_running`
+    /// is only asked about locks taken on `host`, the machine checking.
+    pub fn is_stale(
+        &self,
+        host: &str,
+        now: DateTime<Utc>,
+        timeout: Duration,
+        process_running: impl FnOnce(u32) -> bool,
+    ) -> bool {
+        now - self.acquired_at > timeout || (self.host == host && !process_running(self.pid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lock(host: &str) -> ProjectLock {
+        ProjectLock::new(
+            Uuid::new_v4(),
+            "jane@WS-042".to_string(),
+            4242,
+            host.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_lock_of_exited_process_is_stale() {
+        let lock = lock("WS-042");
+        let now = lock.acquired_at;
+        let timeout = Duration::hours(12);
+
+        assert!(!lock.is_stale("WS-042", now, timeout, |_| true));
+        assert!(lock.is_stale("WS-042", now, timeout, |pid| pid != 4242));
+    }
+
+    #[test]
+    fn test_lock_from_another_host_expires_by_age_only() {
+        let lock = lock("WS-017");
+        let timeout = Duration::hours(12);
+        let later = lock.acquired_at + Duration::hours(13);
+
+        assert!(!lock.is_stale("WS-042", lock.acquired_at, timeout, |_| false));
+        assert!(lock.is_stale("WS-042", later, timeout, |_| true));
+    }
+}
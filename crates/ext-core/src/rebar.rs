@@ -0,0 +1,470 @@
+//! Development and lap splice lengths of straight deformed bars in tension,
+//! ACI 318-19 Chapter 25.
+//!
+//! Uses the general equation of 25.4.2.4 rather than the simplified table,
+//! so cover, spacing and transverse reinforcement are accounted for. The
+//! equation is in inch-pound units; metric inputs are converted, which
+//! lands within 1% of the 318M coefficient (1/1.1 instead of 3/40 × √145).
+
+use crate::units::{ForceUnit, Length, LengthUnit, Stress, StressUnit};
+use ext_error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+const PSI: StressUnit = StressUnit {
+    force: ForceUnit::Pound,
+    length: LengthUnit::Inch,
+};
+
+/// Upper limit on √fc' in psi, 25.4.1.4
+const MAX_SQRT_FC_PSI: f64 = 100.0;
+
+/// Upper limit on (cb + Ktr) / db, 25.4.2.4
+const MAX_CONFINEMENT: f64 = 2.5;
+
+/// Upper limit on ψt × ψe, Table 25.4.2.5
+const MAX_PSI_T_PSI_E: f64 = 1.7;
+
+/// Neither ld nor a lap splice is taken less than 12 in, 25.4.2.1 and 25.5.2.1
+const MIN_LENGTH_IN: f64 = 12.0;
+
+/// Metric grades sit a little above their inch-pound counterparts
+/// (420 MPa = 60.9 ksi), so grade limits allow this much extra
+const GRADE_TOLERANCE: f64 = 1.02;
+
+/// Deformed bar size: ASTM A615 inch-pound sizes and common metric bars
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum RebarSize {
+    #[serde(rename = "#3")]
+    No3,
+    #[serde(rename = "#4")]
+    No4,
+    #[serde(rename = "#5")]
+    No5,
+    #[serde(rename = "#6")]
+    No6,
+    #[serde(rename = "#7")]
+    No7,
+    #[serde(rename = "#8")]
+    No8,
+    #[serde(rename = "#9")]
+    No9,
+    #[serde(rename = "#10")]
+    No10,
+    #[serde(rename = "#11")]
+    No11,
+    #[serde(rename = "#14")]
+    No14,
+    #[serde(rename = "#18")]
+    No18,
+    D10,
+    D12,
+    D16,
+    D20,
+    D25,
+    D28,
+    D32,
+    D36,
+    D40,
+}
+
+impl RebarSize {
+    /// Nominal diameter, db
+    pub fn diameter(self) -> Length {
+        let (value, unit) = match self {
+            RebarSize::No3 => (0.375, LengthUnit::Inch),
+            RebarSize::No4 => (0.5, LengthUnit::Inch),
+            RebarSize::No5 => (0.625, LengthUnit::Inch),
+            RebarSize::No6 => (0.75, LengthUnit::Inch),
+            RebarSize::No7 => (0.875, LengthUnit::Inch),
+            RebarSize::No8 => (1.0, LengthUnit::Inch),
+            RebarSize::No9 => (1.128, LengthUnit::Inch),
+            RebarSize::No10 => (1.27, LengthUnit::Inch),
+            RebarSize::No11 => (1.41, LengthUnit::Inch),
+            RebarSize::No14 => (1.693, LengthUnit::Inch),
+            RebarSize::No18 => (2.257, LengthUnit::Inch),
+            RebarSize::D10 => (10.0, LengthUnit::Millimeter),
+            RebarSize::D12 => (12.0, LengthUnit::Millimeter),
+            RebarSize::D16 => (16.0, LengthUnit::Millimeter),
+            RebarSize::D20 => (20.0, LengthUnit::Millimeter),
+            RebarSize::D25 => (25.0, LengthUnit::Millimeter),
+            RebarSize::D28 => (28.0, LengthUnit::Millimeter),
+            RebarSize::D32 => (32.0, LengthUnit::Millimeter),
+            RebarSize::D36 => (36.0, LengthUnit::Millimeter),
+            RebarSize::D40 => (40.0, LengthUnit::Millimeter),
+        };
+        Length::from(value, unit)
+    }
+
+    /// #14 and #18 bars can't be lap spliced in tension, 25.5.1.1
+    pub fn can_lap_splice(self) -> bool {
+        !matches!(self, RebarSize::No14 | RebarSize::No18)
+    }
+}
+
+/// Epoxy coating condition for ψe
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum BarCoating {
+    /// Uncoated or zinc-coated (galvanized), ψe = 1.0
+    #[default]
+    Uncoated,
+    /// Epoxy or zinc and epoxy dual-coated, ψe = 1.2
+    Epoxy,
+    /// Coated with clear cover under 3db or clear spacing under 6db, ψe = 1.5
+    EpoxyLowCover,
+}
+
+/// Site conditions that modify the basic development length
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct DevLengthModifiers {
+    /// Lightweight concrete, λ = 0.75
+    pub lightweight: bool,
+    pub coating: BarCoating,
+    /// More than 12 in of fresh concrete cast below the bar, ψt = 1.3
+    pub top_bar: bool,
+    /// Smaller of the cover to the bar centre and half the centre-to-centre
+    /// spacing
+    pub cb: Length,
+    /// Transverse reinforcement index; zero is always conservative
+    pub ktr: Length,
+}
+
+/// Input of the development length calculator
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct DevelopmentLengthInput {
+    pub bar: RebarSize,
+    pub fc: Stress,
+    pub fy: Stress,
+    pub modifiers: DevLengthModifiers,
+}
+
+/// Development and lap splice lengths, with the factors that produced them
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct DevelopmentLength {
+    pub ld: Length,
+    /// `None` for bars that can't be lap spliced
+    pub class_a_splice: Option<Length>,
+    pub class_b_splice: Option<Length>,
+    pub factors: DevLengthFactors,
+}
+
+/// Modification factors of Table 25.4.2.5 as applied
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct DevLengthFactors {
+    pub lambda: f64,
+    /// ψt × ψe, capped at 1.7
+    pub psi_t_psi_e: f64,
+    pub psi_s: f64,
+    pub psi_g: f64,
+    /// (cb + Ktr) / db, capped at 2.5
+    pub confinement: f64,
+    /// √fc' in psi, capped at 100
+    pub sqrt_fc: f64,
+}
+
+/// Tension lap splice class, Table 25.5.2.1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum SpliceClass {
+    /// At least twice the required steel and at most half of it spliced
+    A,
+    B,
+}
+
+impl DevLengthFactors {
+    pub fn new(bar: RebarSize, fc: Stress, fy: Stress, modifiers: &DevLengthModifiers) -> Self {
+        let db = bar.diameter();
+        let psi_e = match modifiers.coating {
+            BarCoating::Uncoated => 1.0,
+            BarCoating::Epoxy => 1.2,
+            BarCoating::EpoxyLowCover => 1.5,
+        };
+        let psi_t = if modifiers.top_bar { 1.3 } else { 1.0 };
+        let fy_ksi = fy.to(PSI) / 1000.0;
+        Self {
+            lambda: if modifiers.lightweight { 0.75 } else { 1.0 },
+            psi_t_psi_e: (psi_t * psi_e).min(MAX_PSI_T_PSI_E),
+            // No. 6 (19 mm) and smaller
+            psi_s: if db.to(LengthUnit::Millimeter) <= 19.1 {
+                0.8
+            } else {
+                1.0
+            },
+            psi_g: if fy_ksi <= 60.0 * GRADE_TOLERANCE {
+                1.0
+            } else if fy_ksi <= 80.0 * GRADE_TOLERANCE {
+                1.15
+            } else {
+                1.3
+            },
+            confinement: ((modifiers.cb + modifiers.ktr).to(LengthUnit::Inch)
+                / db.to(LengthUnit::Inch))
+            .min(MAX_CONFINEMENT),
+            sqrt_fc: fc.to(PSI).sqrt().min(MAX_SQRT_FC_PSI),
+        }
+    }
+}
+
+/// Tension development length of a straight bar, 25.4.2.4:
+/// ld = 3/40 × fy / (λ√fc') × ψt ψe ψs ψg / ((cb + Ktr) / db) × db,
+/// and at least 12 in. Inputs are assumed valid; see
+/// [`DevelopmentLengthInput::compute`].
+pub fn development_length(
+    bar: RebarSize,
+    fc: Stress,
+    fy: Stress,
+    modifiers: DevLengthModifiers,
+) -> Length {
+    let f = DevLengthFactors::new(bar, fc, fy, &modifiers);
+    let ld = 3.0 / 40.0 * fy.to(PSI) / (f.lambda * f.sqrt_fc) * f.psi_t_psi_e * f.psi_s * f.psi_g
+        / f.confinement
+        * bar.diameter().to(LengthUnit::Inch);
+    Length::from(ld.max(MIN_LENGTH_IN), LengthUnit::Inch)
+}
+
+/// Tension lap splice length, 25.5.2.1: 1.0 ld for class A, 1.3 ld for
+/// class B, and at least 12 in.
+pub fn lap_splice_length(class: SpliceClass, ld: Length) -> Length {
+    let factor = match class {
+        SpliceClass::A => 1.0,
+        SpliceClass::B => 1.3,
+    };
+    let lst = factor * ld.to(LengthUnit::Inch);
+    Length::from(lst.max(MIN_LENGTH_IN), LengthUnit::Inch)
+}
+
+impl DevelopmentLengthInput {
+    /// Rejects non-positive strengths and cb, negative Ktr and fy above
+    /// Grade 100, naming every offending field.
+    pub fn validate(&self) -> Result<()> {
+        let mut errors = Vec::new();
+        let modifiers = &self.modifiers;
+        for (field, value) in [
+            ("fc", self.fc.to(PSI)),
+            ("fy", self.fy.to(PSI)),
+            ("cb", modifiers.cb.to(LengthUnit::Inch)),
+        ] {
+            if !(value.is_finite() && value > 0.0) {
+                errors.push(format!("{field} must be positive"));
+            }
+        }
+        let ktr = modifiers.ktr.to(LengthUnit::Inch);
+        if !(ktr.is_finite() && ktr >= 0.0) {
+            errors.push("ktr must not be negative".to_string());
+        }
+        if self.fy.to(PSI) > 100_000.0 * GRADE_TOLERANCE {
+            errors.push("fy above 100,000 psi (690 MPa) is not covered by ACI 318-19".to_string());
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::Validation(errors.join("; ")))
+        }
+    }
+
+    pub fn compute(&self) -> Result<DevelopmentLength> {
+        self.validate()?;
+
+        let ld = development_length(self.bar, self.fc, self.fy, self.modifiers);
+        let splice = |class| {
+            self.bar
+                .can_lap_splice()
+                .then(|| lap_splice_length(class, ld))
+        };
+        Ok(DevelopmentLength {
+            ld,
+            class_a_splice: splice(SpliceClass::A),
+            class_b_splice: splice(SpliceClass::B),
+            factors: DevLengthFactors::new(self.bar, self.fc, self.fy, &self.modifiers),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn psi(value: f64) -> Stress {
+        Stress::from(value, PSI)
+    }
+
+    fn inches(value: f64) -> Length {
+        Length::from(value, LengthUnit::Inch)
+    }
+
+    fn bottom_bar(cb: Length) -> DevLengthModifiers {
+        DevLengthModifiers {
+            lightweight: false,
+            coating: BarCoating::Uncoated,
+            top_bar: false,
+            cb,
+            ktr: Length::default(),
+        }
+    }
+
+    fn assert_close(actual: Length, expected_in: f64) {
+        let actual = actual.to(LengthUnit::Inch);
+        assert!(
+            (actual - expected_in).abs() < 0.01,
+            "{actual} in vs {expected_in} in"
+        );
+    }
+
+    #[test]
+    fn test_no8_bottom_bar_with_confinement_capped() {
+        // 3/40 × 60000 / √4000 = 71.15; (cb + Ktr) / db = 3.0 → 2.5
+        let ld = development_length(
+            RebarSize::No8,
+            psi(4000.0),
+            psi(60_000.0),
+            bottom_bar(inches(3.0)),
+        );
+        assert_close(ld, 71.151 / 2.5);
+        assert_close(lap_splice_length(SpliceClass::B, ld), 1.3 * 71.151 / 2.5);
+    }
+
+    #[test]
+    fn test_epoxy_top_bar_factor_is_capped() {
+        // ψt ψe = 1.3 × 1.5 = 1.95 → 1.7; ψs = 0.8 for #6
+        // 3/40 × 60000 / √5000 × 1.7 × 0.8 / (1.5 / 0.75) × 0.75 = 32.456
+        let modifiers = DevLengthModifiers {
+            coating: BarCoating::EpoxyLowCover,
+            top_bar: true,
+            ..bottom_bar(inches(1.5))
+        };
+        let ld = development_length(RebarSize::No6, psi(5000.0), psi(60_000.0), modifiers);
+        assert_close(ld, 32.456);
+
+        let factors = DevLengthFactors::new(RebarSize::No6, psi(5000.0), psi(60_000.0), &modifiers);
+        assert_eq!(factors.psi_t_psi_e, 1.7);
+        // Below the cap the factors simply multiply
+        let epoxy = DevLengthModifiers {
+            coating: BarCoating::Epoxy,
+            ..modifiers
+        };
+        let factors = DevLengthFactors::new(RebarSize::No6, psi(5000.0), psi(60_000.0), &epoxy);
+        assert!((factors.psi_t_psi_e - 1.56).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_lightweight_high_strength_grade_80() {
+        // √12000 = 109.5 → 100; λ = 0.75; ψg = 1.15; ψs = 0.8
+        // 3/40 × 80000 / 75 × 1.15 × 0.8 / ((1.0 + 0.5) / 0.625) × 0.625 = 19.167
+        let modifiers = DevLengthModifiers {
+            lightweight: true,
+            ktr: inches(0.5),
+            ..bottom_bar(inches(1.0))
+        };
+        let ld = development_length(RebarSize::No5, psi(12_000.0), psi(80_000.0), modifiers);
+        assert_close(ld, 19.167);
+    }
+
+    #[test]
+    fn test_short_lengths_use_12_inch_minimum() {
+        let ld = development_length(
+            RebarSize::No3,
+            psi(8000.0),
+            psi(60_000.0),
+            bottom_bar(inches(2.0)),
+        );
+        assert_close(ld, 12.0);
+        assert_close(lap_splice_length(SpliceClass::A, inches(8.0)), 12.0);
+    }
+
+    #[test]
+    fn test_metric_bar_matches_318m_equation() {
+        // 318M: ld = fy / (1.1 λ √fc') × ψ / ((cb + Ktr) / db) × db
+        //       = 420 / (1.1 × √30) / 2.5 × 20 = 557.7 mm
+        let mpa = StressUnit {
+            force: ForceUnit::Newton,
+            length: LengthUnit::Millimeter,
+        };
+        let input = DevelopmentLengthInput {
+            bar: RebarSize::D20,
+            fc: Stress::from(30.0, mpa),
+            fy: Stress::from(420.0, mpa),
+            modifiers: bottom_bar(Length::from(50.0, LengthUnit::Millimeter)),
+        };
+        let result = input.compute().unwrap();
+        assert_eq!(result.factors.psi_g, 1.0, "420 MPa is Grade 60");
+        assert_eq!(result.factors.psi_s, 1.0, "20 mm is larger than No. 6");
+        let ld = result.ld.to(LengthUnit::Millimeter);
+        assert!((ld - 557.7).abs() / 557.7 < 0.01, "{ld} mm");
+    }
+
+    #[test]
+    fn test_large_bars_have_no_lap_splice() {
+        let input = DevelopmentLengthInput {
+            bar: RebarSize::No14,
+            fc: psi(5000.0),
+            fy: psi(60_000.0),
+            modifiers: bottom_bar(inches(3.0)),
+        };
+        let result = input.compute().unwrap();
+        assert!(result.class_a_splice.is_none());
+        assert!(result.class_b_splice.is_none());
+    }
+
+    #[test]
+    fn test_bad_inputs_are_named() {
+        let input = DevelopmentLengthInput {
+            bar: RebarSize::No5,
+            fc: psi(0.0),
+            fy: psi(120_000.0),
+            modifiers: DevLengthModifiers {
+                ktr: inches(-1.0),
+                ..bottom_bar(inches(1.0))
+            },
+        };
+        match input.compute() {
+            Err(AppError::Validation(message)) => {
+                assert!(message.contains("fc must be positive"));
+                assert!(message.contains("ktr must not be negative"));
+                assert!(message.contains("fy above"));
+                assert!(!message.contains("cb"));
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        RebarSize::export(&Default::default()).expect("Failed to export RebarSize");
+        BarCoating::export(&Default::default()).expect("Failed to export BarCoating");
+        DevLengthModifiers::export(&Default::default())
+            .expect("Failed to export DevLengthModifiers");
+        DevelopmentLengthInput::export(&Default::default())
+            .expect("Failed to export DevelopmentLengthInput");
+        DevelopmentLength::export(&Default::default()).expect("Failed to export DevelopmentLength");
+        DevLengthFactors::export(&Default::default()).expect("Failed to export DevLengthFactors");
+        SpliceClass::export(&Default::default()).expect("Failed to export SpliceClass");
+    }
+}
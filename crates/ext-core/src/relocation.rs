@@ -0,0 +1,32 @@
+//! Moving the projects directory to another location, project folders and
+//! all. Every folder is copied and verified before the app switches over,
+//! so a failed move leaves it on the old directory.
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Outcome of moving the projects directory; result of its task
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct RelocationReport {
+    /// The projects directory now in use
+    pub projects_dir: String,
+    /// Project folders copied there
+    pub copied: usize,
+    /// Folders still in the old directory, kept on request or because they
+    /// couldn't be removed
+    pub left_behind: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        RelocationReport::export(&Default::default()).expect("Failed to export RelocationReport");
+    }
+}
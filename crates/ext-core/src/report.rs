@@ -0,0 +1,678 @@
+//! Project summary report, the cover sheet of a calc package: project
+//! details, attached files with their hashes, load combinations, the
+//! latest drift check and the project's notes.
+//!
+//! Both formats are rendered from the same sections and contain nothing
+//! time-dependent beyond the project's own timestamps, so the same project
+//! and timezone policy always produce the same bytes. The PDF is plain text in the standard
+//! Courier fonts, which every viewer has, so no font is embedded.
+
+use crate::combinations::LoadCombination;
+use crate::drift::DriftCheckReport;
+use crate::notes::Note;
+use crate::time::{TimezonePolicy, format_timestamp};
+use crate::{Project, ProjectFile};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Output format of the project summary report
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum ReportFormat {
+    #[default]
+    Html,
+    Pdf,
+}
+
+impl ReportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Html => "html",
+            ReportFormat::Pdf => "pdf",
+        }
+    }
+}
+
+/// Drift check of the project's latest analysis run
+#[derive(Debug, Clone)]
+pub struct DriftSummary {
+    pub run_label: String,
+    pub limit_ratio: f64,
+    pub rows: Vec<DriftCheckReport>,
+}
+
+/// Everything the report shows; sections without data are rendered as
+/// "not available".
+#[derive(Debug, Clone)]
+pub struct ProjectSummary {
+    pub project: Project,
+    pub files: Vec<ProjectFile>,
+    pub combinations: Vec<LoadCombination>,
+    pub drift: Option<DriftSummary>,
+    pub notes: Vec<Note>,
+    /// How the project's timestamps are printed, from the settings
+    pub timezone: TimezonePolicy,
+}
+
+enum Body {
+    Fields(Vec<(&'static str, String)>),
+    Table {
+        headers: &'static [&'static str],
+        rows: Vec<Vec<String>>,
+    },
+    /// One group of fields per item, for rows too wide for a table
+    Records(Vec<Vec<(&'static str, String)>>),
+    /// (title, markdown) pairs, shown as written
+    Notes(Vec<(String, String)>),
+    NotAvailable(&'static str),
+}
+
+struct Section {
+    title: &'static str,
+    body: Body,
+}
+
+fn sections(summary: &ProjectSummary) -> Vec<Section> {
+    let project = &summary.project;
+    let timestamp = |time| format_timestamp(&time, summary.timezone);
+
+    let mut sections = vec![Section {
+        title: "Project",
+        body: Body::Fields(vec![
+            ("Name", project.name.clone()),
+            ("Description", project.description.clone()),
+            ("Project ID", project.id.to_string()),
+            ("Status", project.status.as_str().to_string()),
+            ("Created", timestamp(project.created_at)),
+            ("Last modified", timestamp(project.updated_at)),
+        ]),
+    }];
+
+    sections.push(Section {
+        title: "Attached files",
+        body: if summary.files.is_empty() {
+            Body::NotAvailable("No files are attached to this project.")
+        } else {
+            // A hash next to a long file name does not fit the PDF's width
+            Body::Records(
+                summary
+                    .files
+                    .iter()
+                    .map(|file| {
+                        let name = if file.missing {
+                            format!("{} (missing)", file.original_name)
+                        } else {
+                            file.original_name.clone()
+                        };
+                        vec![
+                            ("File", name),
+                            ("Size (bytes)", file.size.to_string()),
+                            ("SHA-256", file.sha256.clone()),
+                        ]
+                    })
+                    .collect(),
+            )
+        },
+    });
+
+    sections.push(Section {
+        title: "Load combinations",
+        body: if summary.combinations.is_empty() {
+            Body::NotAvailable("No load combinations have been generated.")
+        } else {
+            Body::Table {
+                headers: &["Combination", "Definition"],
+                rows: summary
+                    .combinations
+                    .iter()
+                    .map(|combination| vec![combination.name.clone(), formula(combination)])
+                    .collect(),
+            }
+        },
+    });
+
+    match &summary.drift {
+        Some(drift) => {
+            sections.push(Section {
+                title: "Drift check",
+                body: Body::Fields(vec![
+                    ("Analysis run", drift.run_label.clone()),
+                    ("Allowable drift", format!("{} hsx", drift.limit_ratio)),
+                ]),
+            });
+            sections.push(Section {
+                title: "Drift check results",
+                body: Body::Table {
+                    headers: &[
+                        "Story",
+                        "Dir",
+                        "Load case",
+                        "Drift (mm)",
+                        "Limit (mm)",
+                        "Ratio",
+                        "Result",
+                    ],
+                    rows: drift
+                        .rows
+                        .iter()
+                        .map(|row| {
+                            vec![
+                                row.story.clone(),
+                                row.direction.as_str().to_string(),
+                                row.load_case.clone(),
+                                format!("{:.1}", row.drift * 1000.0),
+                                format!("{:.1}", row.limit * 1000.0),
+                                format!("{:.3}", row.utilization),
+                                match (row.pass, row.governing) {
+                                    (true, true) => "PASS (governs)",
+                                    (true, false) => "PASS",
+                                    (false, true) => "FAIL (governs)",
+                                    (false, false) => "FAIL",
+                                }
+                                .to_string(),
+                            ]
+                        })
+                        .collect(),
+                },
+            });
+        }
+        None => sections.push(Section {
+            title: "Drift check",
+            body: Body::NotAvailable(
+                "Not available: import analysis results and the ETABS model (.e2k) first.",
+            ),
+        }),
+    }
+
+    sections.push(Section {
+        title: "Notes",
+        body: if summary.notes.is_empty() {
+            Body::NotAvailable("No notes have been written for this project.")
+        } else {
+            Body::Notes(
+                summary
+                    .notes
+                    .iter()
+                    .map(|note| (note.title.clone(), note.body_markdown.clone()))
+                    .collect(),
+            )
+        },
+    });
+
+    sections
+}
+
+/// "1.2 D + 1.6 L - 1 WX"
+fn formula(combination: &LoadCombination) -> String {
+    let mut formula = String::new();
+    for (i, (case, factor)) in combination.factors.iter().enumerate() {
+        let magnitude = factor.abs();
+        match (i, *factor < 0.0) {
+            (0, false) => formula.push_str(&format!("{magnitude} {case}")),
+            (0, true) => formula.push_str(&format!("-{magnitude} {case}")),
+            (_, false) => formula.push_str(&format!(" + {magnitude} {case}")),
+            (_, true) => formula.push_str(&format!(" - {magnitude} {case}")),
+        }
+    }
+    formula
+}
+
+/// Renders the report as a standalone HTML page.
+pub fn render_html(summary: &ProjectSummary) -> String {
+    let title = escape_html(&summary.project.name);
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title} - Project summary</title>\n<style>\n{HTML_STYLE}</style>\n</head>\n\
+         <body>\n<h1>{title}</h1>\n<p class=\"subtitle\">Project summary</p>\n"
+    );
+
+    for section in sections(summary) {
+        html.push_str(&format!("<h2>{}</h2>\n", section.title));
+        match section.body {
+            Body::Fields(fields) => {
+                html.push_str("<table class=\"fields\">\n");
+                for (label, value) in fields {
+                    html.push_str(&format!(
+                        "<tr><th>{label}</th><td>{}</td></tr>\n",
+                        escape_html(&value)
+                    ));
+                }
+                html.push_str("</table>\n");
+            }
+            Body::Records(records) => {
+                for fields in records {
+                    html.push_str("<table class=\"fields\">\n");
+                    for (label, value) in fields {
+                        html.push_str(&format!(
+                            "<tr><th>{label}</th><td>{}</td></tr>\n",
+                            escape_html(&value)
+                        ));
+                    }
+                    html.push_str("</table>\n");
+                }
+            }
+            Body::Table { headers, rows } => {
+                html.push_str("<table>\n<tr>");
+                for header in headers {
+                    html.push_str(&format!("<th>{header}</th>"));
+                }
+                html.push_str("</tr>\n");
+                for row in rows {
+                    html.push_str("<tr>");
+                    for cell in row {
+                        html.push_str(&format!("<td>{}</td>", escape_html(&cell)));
+                    }
+                    html.push_str("</tr>\n");
+                }
+                html.push_str("</table>\n");
+            }
+            Body::Notes(notes) => {
+                for (title, body) in notes {
+                    html.push_str(&format!(
+                        "<h3>{}</h3>\n<pre class=\"note\">{}</pre>\n",
+                        escape_html(&title),
+                        escape_html(&body)
+                    ));
+                }
+            }
+            Body::NotAvailable(message) => {
+                html.push_str(&format!("<p class=\"not-available\">{message}</p>\n"));
+            }
+        }
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+const HTML_STYLE: &str = "body { font-family: sans-serif; margin: 2em; color: #222; }
+h1 { margin-bottom: 0; }
+.subtitle { color: #666; margin-top: 0.2em; }
+h2 { border-bottom: 1px solid #ccc; padding-bottom: 0.2em; }
+table { border-collapse: collapse; margin-bottom: 1em; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }
+td { font-family: monospace; }
+table.fields th { background: #f4f4f4; }
+pre.note { font-family: inherit; white-space: pre-wrap; }
+.not-available { color: #666; font-style: italic; border: 1px dashed #ccc; padding: 0.6em; }
+";
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// US Letter in points
+const PAGE_WIDTH: f64 = 612.0;
+const PAGE_HEIGHT: f64 = 792.0;
+const MARGIN: f64 = 50.0;
+const FONT_SIZE: f64 = 8.0;
+const HEADING_SIZE: f64 = 11.0;
+const LEADING: f64 = 10.0;
+/// Courier glyphs are 0.6 em wide
+const LINE_CHARS: usize = ((PAGE_WIDTH - 2.0 * MARGIN) / (FONT_SIZE * 0.6)) as usize;
+const PAGE_LINES: usize = ((PAGE_HEIGHT - 2.0 * MARGIN) / LEADING) as usize;
+
+struct Line {
+    text: String,
+    heading: bool,
+}
+
+/// Renders the report as a PDF of monospaced text.
+pub fn render_pdf(summary: &ProjectSummary) -> Vec<u8> {
+    let mut lines = vec![
+        Line {
+            text: summary.project.name.clone(),
+            heading: true,
+        },
+        Line {
+            text: "Project summary".to_string(),
+            heading: false,
+        },
+    ];
+    let text = |text: String| Line {
+        text,
+        heading: false,
+    };
+
+    for section in sections(summary) {
+        lines.push(text(String::new()));
+        lines.push(Line {
+            text: section.title.to_string(),
+            heading: true,
+        });
+        match section.body {
+            Body::Fields(fields) => lines.extend(field_lines(&fields).into_iter().map(text)),
+            Body::Records(records) => {
+                for (i, fields) in records.iter().enumerate() {
+                    if i > 0 {
+                        lines.push(text(String::new()));
+                    }
+                    lines.extend(field_lines(fields).into_iter().map(text));
+                }
+            }
+            Body::Table { headers, rows } => {
+                let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+                for row in &rows {
+                    for (width, cell) in widths.iter_mut().zip(row) {
+                        *width = (*width).max(cell.chars().count());
+                    }
+                }
+                let format_row = |cells: Vec<String>| {
+                    cells
+                        .iter()
+                        .zip(&widths)
+                        .map(|(cell, &width)| format!("{cell:<width$}"))
+                        .collect::<Vec<_>>()
+                        .join("  ")
+                        .trim_end()
+                        .to_string()
+                };
+                lines.push(text(format_row(
+                    headers.iter().map(|h| h.to_string()).collect(),
+                )));
+                let rule = widths.iter().sum::<usize>() + 2 * widths.len().saturating_sub(1);
+                lines.push(text("-".repeat(rule.min(LINE_CHARS))));
+                for row in rows {
+                    lines.push(text(format_row(row)));
+                }
+            }
+            Body::Notes(notes) => {
+                for (title, body) in notes {
+                    lines.push(text(String::new()));
+                    lines.push(text(title));
+                    for line in body.lines() {
+                        lines.extend(wrap(line, LINE_CHARS).into_iter().map(text));
+                    }
+                }
+            }
+            Body::NotAvailable(message) => lines.push(text(message.to_string())),
+        }
+    }
+
+    write_pdf(&lines)
+}
+
+/// "Label  value" lines with the values aligned. Long values wrap under
+/// their first line rather than being cut at the page edge.
+fn field_lines(fields: &[(&'static str, String)]) -> Vec<String> {
+    let width = fields.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    let mut lines = Vec::new();
+    for (label, value) in fields {
+        for (i, piece) in wrap(value, LINE_CHARS - width - 2).into_iter().enumerate() {
+            let label = if i == 0 { label } else { "" };
+            lines.push(format!("{label:<width$}  {piece}"));
+        }
+    }
+    lines
+}
+
+/// Splits `line` into pieces of at most `width` characters; the PDF has no
+/// line wrapping of its own.
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    if chars.is_empty() {
+        return vec![String::new()];
+    }
+    chars.chunks(width).map(|piece| piece.iter().collect()).collect()
+}
+
+/// Writes `lines` as a minimal PDF 1.4 file, breaking pages as needed.
+fn write_pdf(lines: &[Line]) -> Vec<u8> {
+    let pages: Vec<&[Line]> = lines.chunks(PAGE_LINES).collect();
+
+    // 1: catalog, 2: page tree, 3 and 4: fonts, then a page and its
+    // content stream per page
+    let page_ids: Vec<usize> = (0..pages.len()).map(|i| 5 + 2 * i).collect();
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids
+                .iter()
+                .map(|id| format!("{id} 0 R"))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len()
+        )
+        .into_bytes(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Courier-Bold /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+    ];
+
+    for (page, &page_id) in pages.iter().zip(&page_ids) {
+        let mut content = format!(
+            "BT\n{LEADING} TL\n{MARGIN} {} Td\n",
+            PAGE_HEIGHT - MARGIN - LEADING
+        )
+        .into_bytes();
+        for line in page.iter() {
+            let (font, size) = if line.heading {
+                ("F2", HEADING_SIZE)
+            } else {
+                ("F1", FONT_SIZE)
+            };
+            content.extend_from_slice(format!("/{font} {size} Tf (").as_bytes());
+            content.extend(pdf_string(&line.text));
+            content.extend_from_slice(b") Tj T*\n");
+        }
+        content.extend_from_slice(b"ET");
+
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                page_id + 1
+            )
+            .into_bytes(),
+        );
+        let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+        stream.extend(content);
+        stream.extend_from_slice(b"\nendstream");
+        objects.push(stream);
+    }
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+        pdf.extend_from_slice(object);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+
+    let xref = pdf.len();
+    let xref_header = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    pdf.extend_from_slice(xref_header.as_bytes());
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1
+        )
+        .as_bytes(),
+    );
+    pdf
+}
+
+/// Encodes a line for a PDF string literal in WinAnsi, cut to the page
+/// width. Characters outside Latin-1 become '?'.
+fn pdf_string(text: &str) -> Vec<u8> {
+    let mut chars: Vec<char> = text.chars().collect();
+    if chars.len() > LINE_CHARS {
+        chars.truncate(LINE_CHARS - 3);
+        chars.extend("...".chars());
+    }
+
+    let mut encoded = Vec::with_capacity(chars.len());
+    for c in chars {
+        match c {
+            '(' | ')' | '\\' => {
+                encoded.push(b'\\');
+                encoded.push(c as u8);
+            }
+            ' '..='~' => encoded.push(c as u8),
+            '\u{a0}'..='\u{ff}' => encoded.extend(format!("\\{:03o}", c as u32).into_bytes()),
+            _ => encoded.push(b'?'),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::Direction;
+    use uuid::Uuid;
+
+    fn summary() -> ProjectSummary {
+        let project = Project::new("Tower <A>".to_string(), "Core (walls)".to_string());
+        let file = ProjectFile {
+            id: Uuid::new_v4(),
+            project_id: project.id,
+            original_name: "Tower.e2k".to_string(),
+            stored_path: "files/Tower.e2k".to_string(),
+            size: 42,
+            sha256: "ab".repeat(32),
+            added_at: project.created_at,
+            missing: false,
+        };
+        ProjectSummary {
+            project,
+            files: vec![file],
+            combinations: vec![LoadCombination {
+                name: "LRFD3".to_string(),
+                factors: vec![
+                    ("D".to_string(), 1.2),
+                    ("W".to_string(), -1.0),
+                    ("L".to_string(), 1.0),
+                ],
+            }],
+            drift: Some(DriftSummary {
+                run_label: "Run 1".to_string(),
+                limit_ratio: 0.02,
+                rows: vec![DriftCheckReport {
+                    story: "Roof".to_string(),
+                    direction: Direction::X,
+                    load_case: "EQX".to_string(),
+                    drift: 0.045,
+                    limit: 0.06,
+                    utilization: 0.75,
+                    pass: true,
+                    governing: true,
+                }],
+            }),
+            notes: vec![Note {
+                title: "Design basis".to_string(),
+                body_markdown: "- SDL <1.5 kPa>\n- Wind per ASCE 7-16".to_string(),
+                ..Note::new(Uuid::nil(), String::new(), String::new())
+            }],
+            timezone: TimezonePolicy::FixedOffset { minutes: 420 },
+        }
+    }
+
+    #[test]
+    fn test_html_escapes_and_lists_every_section() {
+        let html = render_html(&summary());
+        assert!(html.contains("<h1>Tower &lt;A&gt;</h1>"));
+        assert!(html.contains(&"ab".repeat(32)));
+        assert!(html.contains("1.2 D - 1 W + 1 L"));
+        assert!(html.contains("<td>45.0</td>"));
+        assert!(html.contains("PASS (governs)"));
+        assert!(html.contains("<h3>Design basis</h3>"));
+        assert!(html.contains("- SDL &lt;1.5 kPa&gt;\n- Wind per ASCE 7-16</pre>"));
+    }
+
+    #[test]
+    fn test_timestamps_follow_the_timezone_policy() {
+        let mut summary = summary();
+        summary.project.created_at = "2025-01-25T23:40:00Z".parse().unwrap();
+
+        let html = render_html(&summary);
+        assert!(html.contains("<td>2025-01-26 06:40 (UTC+07:00)</td>"));
+        summary.timezone = TimezonePolicy::FixedOffset { minutes: 0 };
+        assert!(render_html(&summary).contains("<td>2025-01-25 23:40 (UTC)</td>"));
+    }
+
+    #[test]
+    fn test_missing_sections_render_as_not_available() {
+        let mut summary = summary();
+        summary.files.clear();
+        summary.combinations.clear();
+        summary.drift = None;
+        summary.notes.clear();
+
+        let html = render_html(&summary);
+        assert_eq!(html.matches("class=\"not-available\"").count(), 4);
+        let pdf = String::from_utf8_lossy(&render_pdf(&summary)).to_string();
+        assert!(pdf.contains("Not available: import analysis results"));
+    }
+
+    #[test]
+    fn test_pdf_is_well_formed_and_deterministic() {
+        let summary = summary();
+        let pdf = render_pdf(&summary);
+        assert_eq!(pdf, render_pdf(&summary));
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("Core \\(walls\\)"));
+        let startxref: usize = text
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|tail| tail.lines().next())
+            .and_then(|offset| offset.parse().ok())
+            .unwrap();
+        assert!(pdf[startxref..].starts_with(b"xref\n"));
+    }
+
+    #[test]
+    fn test_pdf_never_cuts_file_hashes() {
+        let mut summary = summary();
+        summary.files[0].original_name =
+            format!("{}.edb", "Tower A - level 3 transfer slab ".repeat(6));
+        summary.files[0].sha256 = "0123456789abcdef".repeat(4);
+
+        let pdf = String::from_utf8_lossy(&render_pdf(&summary)).to_string();
+        assert!(pdf.contains(&"0123456789abcdef".repeat(4)));
+        // The name wraps onto further lines instead of being cut short
+        assert!(pdf.contains(".edb"));
+        assert!(!pdf.contains("..."));
+    }
+
+    #[test]
+    fn test_long_reports_break_pages() {
+        let mut summary = summary();
+        summary.combinations = (0..200)
+            .map(|i| LoadCombination {
+                name: format!("LRFD{i}"),
+                factors: vec![("D".to_string(), 1.4)],
+            })
+            .collect();
+        let pdf = String::from_utf8_lossy(&render_pdf(&summary)).to_string();
+        assert_eq!(pdf.matches("/Type /Page ").count(), 4);
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        ReportFormat::export(&Default::default()).expect("Failed to export ReportFormat");
+    }
+}
@@ -0,0 +1,1065 @@
+//! Analysis result tables exported from ETABS.
+//!
+//! ETABS exports its display tables as CSV; [`parse_results_csv`] reads the
+//! story drift, modal period and base reaction tables by column name, so
+//! column order and extra columns do not matter. [`parse_pasted_table`]
+//! reads the same tables as copied from the ETABS tables window.
+//! Drifts, modal periods and base reactions pulled from a connected ETABS
+//! arrive as the Parquet files the sidecar extracts; see
+//! [`read_story_drifts_parquet`], [`read_modal_periods_parquet`] and
+//! [`read_base_reactions_parquet`].
+
+use crate::units::{ForceUnit, LengthUnit, MomentUnit};
+use chrono::{DateTime, Utc};
+use ext_error::{AppError, Result};
+use polars::prelude::{DataFrame, ParquetReader, PolarsError, SerReader};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Row errors listed in full before the rest are only counted
+const MAX_REPORTED_ROW_ERRORS: usize = 20;
+
+/// Global lateral direction of a result
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum Direction {
+    X,
+    Y,
+}
+
+impl Direction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Direction::X => "X",
+            Direction::Y => "Y",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim() {
+            "X" | "x" => Ok(Direction::X),
+            "Y" | "y" => Ok(Direction::Y),
+            other => Err(AppError::Validation(format!(
+                "Invalid direction '{other}', expected X or Y"
+            ))),
+        }
+    }
+}
+
+/// One imported set of results, identified by a label such as "Rev B"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct AnalysisRun {
+    #[ts(type = "string")]
+    pub id: Uuid,
+    #[ts(type = "string")]
+    pub project_id: Uuid,
+    pub label: String,
+    #[ts(type = "string")]
+    pub imported_at: DateTime<Utc>,
+}
+
+impl AnalysisRun {
+    pub fn new(project_id: Uuid, label: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            project_id,
+            label,
+            imported_at: Utc::now(),
+        }
+    }
+}
+
+/// Inter-story drift ratio of one story (ETABS "Story Drifts")
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct StoryDrift {
+    pub story: String,
+    pub load_case: String,
+    pub direction: Direction,
+    /// Drift divided by story height
+    pub drift: f64,
+}
+
+/// One vibration mode (ETABS "Modal Participating Mass Ratios")
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct ModalPeriod {
+    pub load_case: String,
+    pub mode: u32,
+    /// Period in s
+    pub period: f64,
+    /// Participating mass ratios
+    pub ux: f64,
+    pub uy: f64,
+    pub rz: f64,
+}
+
+/// Base reactions for one load case (ETABS "Base Reactions")
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct BaseReaction {
+    pub load_case: String,
+    pub fx: f64,
+    pub fy: f64,
+    pub fz: f64,
+    pub mx: f64,
+    pub my: f64,
+    pub mz: f64,
+}
+
+/// Result tables of one run
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+#[serde(default)]
+pub struct AnalysisResults {
+    pub story_drifts: Vec<StoryDrift>,
+    pub modal_periods: Vec<ModalPeriod>,
+    pub base_reactions: Vec<BaseReaction>,
+}
+
+/// An ETABS table that can be imported from CSV
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum ResultTable {
+    StoryDrifts,
+    ModalPeriods,
+    BaseReactions,
+}
+
+impl ResultTable {
+    pub const ALL: [ResultTable; 3] = [
+        ResultTable::StoryDrifts,
+        ResultTable::ModalPeriods,
+        ResultTable::BaseReactions,
+    ];
+
+    /// Column headers this table needs, as ETABS names them
+    pub fn required_columns(&self) -> &'static [&'static str] {
+        match self {
+            ResultTable::StoryDrifts => &["Story", "Output Case", "Direction", "Drift"],
+            ResultTable::ModalPeriods => &["Case", "Mode", "Period", "UX", "UY", "RZ"],
+            ResultTable::BaseReactions => &["Output Case", "FX", "FY", "FZ", "MX", "MY", "MZ"],
+        }
+    }
+
+    /// Name as serialized, so the frontend can read it back from messages
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResultTable::StoryDrifts => "StoryDrifts",
+            ResultTable::ModalPeriods => "ModalPeriods",
+            ResultTable::BaseReactions => "BaseReactions",
+        }
+    }
+}
+
+/// A pasted row that could not be imported
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct RowError {
+    /// Line in the pasted text, from 1
+    pub line: usize,
+    pub message: String,
+}
+
+/// Outcome of pasting a table into an analysis run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct PasteImportReport {
+    pub run: AnalysisRun,
+    /// Table the text was detected, or hinted, to be
+    pub table: ResultTable,
+    pub imported_rows: usize,
+    /// Rows left out of the import
+    pub failed_rows: Vec<RowError>,
+}
+
+/// Table text read by [`parse_pasted_table`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PastedTable {
+    pub table: ResultTable,
+    /// Only `table` is filled, in SI units
+    pub results: AnalysisResults,
+    pub failed_rows: Vec<RowError>,
+}
+
+impl PastedTable {
+    pub fn row_count(&self) -> usize {
+        match self.table {
+            ResultTable::StoryDrifts => self.results.story_drifts.len(),
+            ResultTable::ModalPeriods => self.results.modal_periods.len(),
+            ResultTable::BaseReactions => self.results.base_reactions.len(),
+        }
+    }
+}
+
+/// Reads one ETABS table export. See [`parse_results_csv`].
+pub fn import_results_csv(table: ResultTable, path: &Path) -> Result<AnalysisResults> {
+    let bytes = std::fs::read(path).map_err(|e| {
+        AppError::FileSystem(format!("Failed to read '{}': {e}", path.display()))
+    })?;
+    parse_results_csv(table, &String::from_utf8_lossy(&bytes))
+}
+
+/// Reads the story drifts the sidecar's `extract-results` writes to
+/// `story_drifts.parquet`.
+pub fn read_story_drifts_parquet(path: &Path) -> Result<Vec<StoryDrift>> {
+    story_drifts_from_frame(&read_parquet(path)?)
+}
+
+/// Reads the modal participating mass ratios the sidecar's
+/// `extract-results` writes to `modal.parquet`.
+pub fn read_modal_periods_parquet(path: &Path) -> Result<Vec<ModalPeriod>> {
+    modal_periods_from_frame(&read_parquet(path)?)
+}
+
+/// Reads the base reactions the sidecar's `extract-results` writes to
+/// `base_reactions.parquet`.
+pub fn read_base_reactions_parquet(path: &Path) -> Result<Vec<BaseReaction>> {
+    base_reactions_from_frame(&read_parquet(path)?)
+}
+
+pub(crate) fn read_parquet(path: &Path) -> Result<DataFrame> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| AppError::FileSystem(format!("Failed to read '{}': {e}", path.display())))?;
+    ParquetReader::new(file)
+        .finish()
+        .map_err(|e| AppError::Etabs(format!("Failed to read '{}': {e}", path.display())))
+}
+
+/// Story drifts from the `story`, `loadCase`, `direction` and `drift`
+/// columns of an extracted results table.
+pub fn story_drifts_from_frame(frame: &DataFrame) -> Result<Vec<StoryDrift>> {
+    let invalid = |e: PolarsError| AppError::Etabs(format!("Invalid story drift table: {e}"));
+    let stories = frame
+        .column("story")
+        .and_then(|c| c.str())
+        .map_err(invalid)?;
+    let load_cases = frame
+        .column("loadCase")
+        .and_then(|c| c.str())
+        .map_err(invalid)?;
+    let directions = frame
+        .column("direction")
+        .and_then(|c| c.str())
+        .map_err(invalid)?;
+    let values = frame
+        .column("drift")
+        .and_then(|c| c.f64())
+        .map_err(invalid)?;
+
+    let rows = stories
+        .into_iter()
+        .zip(load_cases)
+        .zip(directions)
+        .zip(values);
+    let mut drifts = Vec::with_capacity(frame.height());
+    for (row, (((story, load_case), direction), drift)) in rows.enumerate() {
+        let (Some(story), Some(load_case), Some(direction), Some(drift)) =
+            (story, load_case, direction, drift)
+        else {
+            return Err(AppError::Etabs(format!(
+                "Story drift row {} has empty cells",
+                row + 1
+            )));
+        };
+        drifts.push(StoryDrift {
+            story: story.to_string(),
+            load_case: load_case.to_string(),
+            direction: Direction::parse(direction)?,
+            drift,
+        });
+    }
+    Ok(drifts)
+}
+
+/// Base reactions from the `loadCase`, `fx`, `fy`, `fz`, `mx`, `my` and
+/// `mz` columns of an extracted results table, which the sidecar writes in
+/// kN and kN·m.
+pub fn base_reactions_from_frame(frame: &DataFrame) -> Result<Vec<BaseReaction>> {
+    let invalid = |e: PolarsError| AppError::Etabs(format!("Invalid base reaction table: {e}"));
+    let load_cases = frame
+        .column("loadCase")
+        .and_then(|c| c.str())
+        .map_err(invalid)?;
+    let [fx, fy, fz, mx, my, mz] = ["fx", "fy", "fz", "mx", "my", "mz"]
+        .map(|name| frame.column(name).and_then(|c| c.f64()).map_err(invalid));
+    let (fx, fy, fz, mx, my, mz) = (fx?, fy?, fz?, mx?, my?, mz?);
+
+    let force = ForceUnit::Kilonewton.to_si();
+    let moment = MomentUnit {
+        force: ForceUnit::Kilonewton,
+        length: LengthUnit::Meter,
+    }
+    .to_si();
+    let mut reactions = Vec::with_capacity(frame.height());
+    for row in 0..frame.height() {
+        let (Some(load_case), Some(fx), Some(fy), Some(fz), Some(mx), Some(my), Some(mz)) = (
+            load_cases.get(row),
+            fx.get(row),
+            fy.get(row),
+            fz.get(row),
+            mx.get(row),
+            my.get(row),
+            mz.get(row),
+        ) else {
+            return Err(AppError::Etabs(format!(
+                "Base reaction row {} has empty cells",
+                row + 1
+            )));
+        };
+        reactions.push(BaseReaction {
+            load_case: load_case.to_string(),
+            fx: fx * force,
+            fy: fy * force,
+            fz: fz * force,
+            mx: mx * moment,
+            my: my * moment,
+            mz: mz * moment,
+        });
+    }
+    Ok(reactions)
+}
+
+/// Modal periods from the `loadCase`, `modeNumber`, `period`, `ux`, `uy`
+/// and `rz` columns of an extracted results table.
+pub fn modal_periods_from_frame(frame: &DataFrame) -> Result<Vec<ModalPeriod>> {
+    let invalid = |e: PolarsError| AppError::Etabs(format!("Invalid modal table: {e}"));
+    let load_cases = frame
+        .column("loadCase")
+        .and_then(|c| c.str())
+        .map_err(invalid)?;
+    let modes = frame
+        .column("modeNumber")
+        .and_then(|c| c.i32())
+        .map_err(invalid)?;
+    let [periods, ux, uy, rz] = ["period", "ux", "uy", "rz"]
+        .map(|name| frame.column(name).and_then(|c| c.f64()).map_err(invalid));
+    let (periods, ux, uy, rz) = (periods?, ux?, uy?, rz?);
+
+    let mut rows = Vec::with_capacity(frame.height());
+    for row in 0..frame.height() {
+        let (Some(load_case), Some(mode), Some(period), Some(ux), Some(uy), Some(rz)) = (
+            load_cases.get(row),
+            modes.get(row),
+            periods.get(row),
+            ux.get(row),
+            uy.get(row),
+            rz.get(row),
+        ) else {
+            return Err(AppError::Etabs(format!(
+                "Modal row {} has empty cells",
+                row + 1
+            )));
+        };
+        let mode = u32::try_from(mode).map_err(|_| {
+            AppError::Etabs(format!("Modal row {} has mode number {mode}", row + 1))
+        })?;
+        rows.push(ModalPeriod {
+            load_case: load_case.to_string(),
+            mode,
+            period,
+            ux,
+            uy,
+            rz,
+        });
+    }
+    Ok(rows)
+}
+
+/// A result row of one load case or combination
+pub trait LoadCaseRow {
+    /// What the rows are called in messages, e.g. "story drifts"
+    const NAME: &'static str;
+
+    fn load_case(&self) -> &str;
+}
+
+impl LoadCaseRow for StoryDrift {
+    const NAME: &'static str = "story drifts";
+
+    fn load_case(&self) -> &str {
+        &self.load_case
+    }
+}
+
+impl LoadCaseRow for BaseReaction {
+    const NAME: &'static str = "base reactions";
+
+    fn load_case(&self) -> &str {
+        &self.load_case
+    }
+}
+
+/// Keeps the rows of `load_cases`, or all of them when it is empty.
+/// Fails if a requested case has no rows, e.g. a misspelled name.
+pub fn select_load_cases<T: LoadCaseRow>(rows: Vec<T>, load_cases: &[String]) -> Result<Vec<T>> {
+    if load_cases.is_empty() {
+        return Ok(rows);
+    }
+
+    let missing: Vec<&str> = load_cases
+        .iter()
+        .filter(|case| !rows.iter().any(|row| row.load_case() == case.as_str()))
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        return Err(AppError::Validation(format!(
+            "No {} for load case(s): {}",
+            T::NAME,
+            missing.join(", ")
+        )));
+    }
+
+    Ok(rows
+        .into_iter()
+        .filter(|row| load_cases.iter().any(|case| case == row.load_case()))
+        .collect())
+}
+
+/// Parses CSV text for `table` into results with only that table filled.
+///
+/// A leading `TABLE: …` title line, as ETABS writes it, is skipped. Missing
+/// columns fail the whole import; bad rows are reported together by line.
+pub fn parse_results_csv(table: ResultTable, text: &str) -> Result<AnalysisResults> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim_start_matches('\u{feff}')))
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let mut header = lines
+        .next()
+        .ok_or_else(|| AppError::Validation("CSV file is empty".to_string()))?;
+    if split_csv_line(header.1)
+        .first()
+        .is_some_and(|cell| cell.trim().to_uppercase().starts_with("TABLE:"))
+    {
+        header = lines
+            .next()
+            .ok_or_else(|| AppError::Validation("CSV file has no header row".to_string()))?;
+    }
+
+    let headers: Vec<String> = split_csv_line(header.1)
+        .into_iter()
+        .map(|cell| cell.trim().to_lowercase())
+        .collect();
+    let columns = find_columns(table, &headers).map_err(|missing| {
+        AppError::Validation(format!(
+            "CSV is missing required column(s) {}",
+            missing.join(", ")
+        ))
+    })?;
+
+    let scales = vec![1.0; columns.len()];
+    let mut results = AnalysisResults::default();
+    let mut errors = Vec::new();
+    for (line_number, line) in lines {
+        let cells = split_csv_line(line);
+        let row = Row {
+            cells: &cells,
+            columns: &columns,
+            names: table.required_columns(),
+            scales: &scales,
+        };
+        if let Err(message) = row.read_into(table, &mut results) {
+            errors.push(format!("line {line_number}: {message}"));
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(invalid_rows(errors));
+    }
+
+    Ok(results)
+}
+
+/// Parses table text copied from the ETABS tables window: tab-separated,
+/// with a header row, optionally a `TABLE: …` title line above it and a
+/// units row below it. Comma-separated text is read as well.
+///
+/// Without `hint` the table is detected from the header; a header that
+/// fits more than one table fails with the candidates named, so the user
+/// can pick one. Base reactions are converted to N and N·m from the units
+/// in the units row or in the header, as in `FX (kN)`, and taken as N and
+/// N·m when neither gives one. Bad rows are left out and listed, and the
+/// paste only fails when no row could be read.
+pub fn parse_pasted_table(text: &str, hint: Option<ResultTable>) -> Result<PastedTable> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim_start_matches('\u{feff}')))
+        .filter(|(_, line)| !line.trim().is_empty())
+        .peekable();
+
+    let mut header = lines
+        .next()
+        .ok_or_else(|| AppError::Validation("Pasted text is empty".to_string()))?;
+    if header.1.trim().to_uppercase().starts_with("TABLE:") {
+        header = lines
+            .next()
+            .ok_or_else(|| AppError::Validation("Pasted text has no header row".to_string()))?;
+    }
+    let split: fn(&str) -> Vec<String> = if header.1.contains('\t') {
+        split_tsv_line
+    } else {
+        split_csv_line
+    };
+
+    let (headers, header_units): (Vec<String>, Vec<Option<String>>) =
+        split(header.1).iter().map(|cell| header_cell(cell)).unzip();
+    let table = match hint {
+        Some(table) => table,
+        None => detect_table(&headers)?,
+    };
+    let columns = find_columns(table, &headers).map_err(|missing| {
+        AppError::Validation(format!(
+            "Pasted {} table is missing required column(s) {}",
+            table.as_str(),
+            missing.join(", ")
+        ))
+    })?;
+
+    let mut units: Vec<Option<String>> = columns.iter().map(|&i| header_units[i].clone()).collect();
+    if let Some(&(_, line)) = lines.peek()
+        && let Some(row_units) = units_row(&split(line))
+    {
+        lines.next();
+        for (unit, &i) in units.iter_mut().zip(&columns) {
+            if let Some(cell) = row_units.get(i).filter(|cell| !cell.is_empty()) {
+                *unit = Some(cell.clone());
+            }
+        }
+    }
+    let scales = units
+        .iter()
+        .enumerate()
+        .map(|(n, unit)| match unit {
+            Some(unit) => column_scale(table, n, unit),
+            None => Ok(1.0),
+        })
+        .collect::<Result<Vec<f64>>>()?;
+
+    let mut results = AnalysisResults::default();
+    let mut failed_rows = Vec::new();
+    for (line, text) in lines {
+        let cells = split(text);
+        let row = Row {
+            cells: &cells,
+            columns: &columns,
+            names: table.required_columns(),
+            scales: &scales,
+        };
+        if let Err(message) = row.read_into(table, &mut results) {
+            failed_rows.push(RowError { line, message });
+        }
+    }
+
+    let pasted = PastedTable {
+        table,
+        results,
+        failed_rows,
+    };
+    if pasted.row_count() == 0 {
+        if pasted.failed_rows.is_empty() {
+            return Err(AppError::Validation(
+                "Pasted table has no data rows".to_string(),
+            ));
+        }
+        let errors = pasted
+            .failed_rows
+            .iter()
+            .map(|row| format!("line {}: {}", row.line, row.message))
+            .collect();
+        return Err(invalid_rows(errors));
+    }
+    Ok(pasted)
+}
+
+/// Positions of `table`'s required columns in the lowercase `headers`, or
+/// the quoted names of those missing
+fn find_columns(
+    table: ResultTable,
+    headers: &[String],
+) -> std::result::Result<Vec<usize>, Vec<String>> {
+    let mut columns = Vec::new();
+    let mut missing = Vec::new();
+    for &name in table.required_columns() {
+        match headers.iter().position(|h| *h == name.to_lowercase()) {
+            Some(index) => columns.push(index),
+            None => missing.push(format!("'{name}'")),
+        }
+    }
+    if missing.is_empty() {
+        Ok(columns)
+    } else {
+        Err(missing)
+    }
+}
+
+/// The one table whose required columns are all in `headers`
+fn detect_table(headers: &[String]) -> Result<ResultTable> {
+    let candidates: Vec<ResultTable> = ResultTable::ALL
+        .into_iter()
+        .filter(|table| find_columns(*table, headers).is_ok())
+        .collect();
+    match candidates[..] {
+        [table] => Ok(table),
+        [] => Err(AppError::Validation(
+            "Could not tell which table was pasted: the header has the columns of \
+             none of StoryDrifts, ModalPeriods or BaseReactions"
+                .to_string(),
+        )),
+        _ => {
+            let names: Vec<&str> = candidates.iter().map(ResultTable::as_str).collect();
+            Err(AppError::Validation(format!(
+                "Pasted header fits more than one table: {}",
+                names.join(", ")
+            )))
+        }
+    }
+}
+
+/// Lowercase column name of a header cell and the unit in parentheses after
+/// it, as in `FX (kN)`
+fn header_cell(cell: &str) -> (String, Option<String>) {
+    let cell = cell.trim();
+    if let Some(open) = cell.rfind('(')
+        && let Some(unit) = cell[open + 1..].strip_suffix(')')
+    {
+        let unit = unit.trim();
+        let name = cell[..open].trim().to_lowercase();
+        return (name, (!unit.is_empty()).then(|| unit.to_string()));
+    }
+    (cell.to_lowercase(), None)
+}
+
+/// The trimmed cells of an ETABS units row: one with a filled cell, where
+/// every filled cell is a unit
+fn units_row(cells: &[String]) -> Option<Vec<String>> {
+    let cells: Vec<String> = cells.iter().map(|cell| cell.trim().to_string()).collect();
+    let mut filled = cells.iter().filter(|cell| !cell.is_empty()).peekable();
+    let is_units = filled.peek().is_some() && filled.all(|cell| ColumnUnit::parse(cell).is_some());
+    is_units.then_some(cells)
+}
+
+/// Factor to SI for the `n`th required column of `table` given in `unit`
+fn column_scale(table: ResultTable, n: usize, unit: &str) -> Result<f64> {
+    let scale = match (table, n, ColumnUnit::parse(unit)) {
+        (ResultTable::BaseReactions, 1..=3, Some(ColumnUnit::Force(force))) => force.to_si(),
+        (ResultTable::BaseReactions, 4..=6, Some(ColumnUnit::Moment(moment))) => moment.to_si(),
+        (ResultTable::ModalPeriods, 2, Some(ColumnUnit::Seconds)) => 1.0,
+        _ => {
+            return Err(AppError::Validation(format!(
+                "Unit '{unit}' does not fit column '{}'",
+                table.required_columns()[n]
+            )));
+        }
+    };
+    Ok(scale)
+}
+
+/// Unit of a pasted column, as ETABS names it
+enum ColumnUnit {
+    Force(ForceUnit),
+    Moment(MomentUnit),
+    Length,
+    Seconds,
+}
+
+impl ColumnUnit {
+    fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if matches!(text.to_ascii_lowercase().as_str(), "s" | "sec") {
+            return Some(ColumnUnit::Seconds);
+        }
+        if let Some((force, length)) = text.split_once(['-', '·', '*']) {
+            return Some(ColumnUnit::Moment(MomentUnit {
+                force: ForceUnit::from_etabs(force)?,
+                length: LengthUnit::from_etabs(length)?,
+            }));
+        }
+        ForceUnit::from_etabs(text)
+            .map(ColumnUnit::Force)
+            .or_else(|| LengthUnit::from_etabs(text).map(|_| ColumnUnit::Length))
+    }
+}
+
+/// Validation error listing row errors, the first few in full
+fn invalid_rows(mut errors: Vec<String>) -> AppError {
+    let total = errors.len();
+    errors.truncate(MAX_REPORTED_ROW_ERRORS);
+    if total > MAX_REPORTED_ROW_ERRORS {
+        errors.push(format!("and {} more", total - MAX_REPORTED_ROW_ERRORS));
+    }
+    AppError::Validation(format!("{total} invalid row(s): {}", errors.join("; ")))
+}
+
+/// One data row, read through the positions of the required columns
+struct Row<'a> {
+    cells: &'a [String],
+    columns: &'a [usize],
+    names: &'static [&'static str],
+    /// Factor to SI of each required column
+    scales: &'a [f64],
+}
+
+impl Row<'_> {
+    /// Parses this row as `table` and adds it to `results`
+    fn read_into(
+        &self,
+        table: ResultTable,
+        results: &mut AnalysisResults,
+    ) -> std::result::Result<(), String> {
+        match table {
+            ResultTable::StoryDrifts => results.story_drifts.push(self.story_drift()?),
+            ResultTable::ModalPeriods => results.modal_periods.push(self.modal_period()?),
+            ResultTable::BaseReactions => results.base_reactions.push(self.base_reaction()?),
+        }
+        Ok(())
+    }
+
+    /// The `n`th required column of this row
+    fn text(&self, n: usize) -> std::result::Result<&str, String> {
+        let value = self
+            .cells
+            .get(self.columns[n])
+            .map(|cell| cell.trim())
+            .unwrap_or_default();
+        if value.is_empty() {
+            return Err(format!("'{}' is empty", self.names[n]));
+        }
+        Ok(value)
+    }
+
+    fn number(&self, n: usize) -> std::result::Result<f64, String> {
+        let value = self.text(n)?;
+        value
+            .parse::<f64>()
+            .ok()
+            .filter(|v| v.is_finite())
+            .map(|v| v * self.scales[n])
+            .ok_or_else(|| format!("'{}' is not a number: '{value}'", self.names[n]))
+    }
+
+    fn story_drift(&self) -> std::result::Result<StoryDrift, String> {
+        let direction = self.text(2)?;
+        Ok(StoryDrift {
+            story: self.text(0)?.to_string(),
+            load_case: self.text(1)?.to_string(),
+            direction: Direction::parse(direction)
+                .map_err(|_| format!("'Direction' must be X or Y: '{direction}'"))?,
+            drift: self.number(3)?,
+        })
+    }
+
+    fn modal_period(&self) -> std::result::Result<ModalPeriod, String> {
+        let mode = self.text(1)?;
+        Ok(ModalPeriod {
+            load_case: self.text(0)?.to_string(),
+            mode: mode
+                .parse()
+                .map_err(|_| format!("'Mode' is not a mode number: '{mode}'"))?,
+            period: self.number(2)?,
+            ux: self.number(3)?,
+            uy: self.number(4)?,
+            rz: self.number(5)?,
+        })
+    }
+
+    fn base_reaction(&self) -> std::result::Result<BaseReaction, String> {
+        Ok(BaseReaction {
+            load_case: self.text(0)?.to_string(),
+            fx: self.number(1)?,
+            fy: self.number(2)?,
+            fz: self.number(3)?,
+            mx: self.number(4)?,
+            my: self.number(5)?,
+            mz: self.number(6)?,
+        })
+    }
+}
+
+/// Splits one CSV line, honouring double-quoted cells and `""` escapes.
+pub(crate) fn split_csv_line(line: &str) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => cells.push(std::mem::take(&mut cell)),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell);
+    cells
+}
+
+/// Splits one line of tab-separated text, as ETABS copies tables.
+fn split_tsv_line(line: &str) -> Vec<String> {
+    line.split('\t').map(str::to_string).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_story_drifts_with_title_line() {
+        let csv = "TABLE:  Story Drifts\n\
+                   Story,Output Case,Case Type,Direction,Drift,Label\n\
+                   Roof,EQX,LinStatic,X,0.0042,1\n\
+                   \"Level 2\",EQY,LinStatic,Y,3.1E-03,1\n";
+
+        let results = parse_results_csv(ResultTable::StoryDrifts, csv).unwrap();
+        assert_eq!(
+            results.story_drifts,
+            vec![
+                StoryDrift {
+                    story: "Roof".to_string(),
+                    load_case: "EQX".to_string(),
+                    direction: Direction::X,
+                    drift: 0.0042,
+                },
+                StoryDrift {
+                    story: "Level 2".to_string(),
+                    load_case: "EQY".to_string(),
+                    direction: Direction::Y,
+                    drift: 0.0031,
+                },
+            ]
+        );
+        assert!(results.modal_periods.is_empty());
+    }
+
+    #[test]
+    fn test_missing_columns_are_named() {
+        let csv = "Case,Mode,Period,UX\nModal,1,1.2,0.7\n";
+        match parse_results_csv(ResultTable::ModalPeriods, csv) {
+            Err(AppError::Validation(message)) => {
+                assert!(message.contains("'UY'"));
+                assert!(message.contains("'RZ'"));
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_bad_rows_are_reported_by_line() {
+        let csv = "Output Case,FX,FY,FZ,MX,MY,MZ\n\
+                   Dead,0,0,1500,0,0,0\n\
+                   Live,0,0,abc,0,0,0\n\
+                   ,0,0,10,0,0,0\n";
+        match parse_results_csv(ResultTable::BaseReactions, csv) {
+            Err(AppError::Validation(message)) => {
+                assert!(message.starts_with("2 invalid row(s)"));
+                assert!(message.contains("line 3: 'FZ' is not a number: 'abc'"));
+                assert!(message.contains("line 4: 'Output Case' is empty"));
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_split_csv_line_handles_quotes() {
+        assert_eq!(
+            split_csv_line(r#"a,"b, c","say ""hi""""#),
+            vec!["a", "b, c", r#"say "hi""#]
+        );
+    }
+
+    #[test]
+    fn test_paste_detects_table_and_reads_units_row() {
+        let text = "TABLE:  Base Reactions\n\
+                    Output Case\tCase Type\tFX\tFY\tFZ\tMX\tMY\tMZ\n\
+                    \t\tkN\tkN\tkN\tkN-m\tkN-m\tkN-m\n\
+                    Dead\tLinStatic\t0\t0\t1500\t12.5\t0\t0\n\
+                    Live\tLinStatic\t0\t0\tabc\t0\t0\t0\n";
+
+        let pasted = parse_pasted_table(text, None).unwrap();
+        assert_eq!(pasted.table, ResultTable::BaseReactions);
+        assert_eq!(pasted.row_count(), 1);
+        assert_eq!(pasted.results.base_reactions[0].fz, 1.5e6);
+        assert_eq!(pasted.results.base_reactions[0].mx, 12_500.0);
+        assert_eq!(
+            pasted.failed_rows,
+            vec![RowError {
+                line: 5,
+                message: "'FZ' is not a number: 'abc'".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_paste_ambiguous_header_names_candidates() {
+        let text = "Story\tOutput Case\tDirection\tDrift\tFX\tFY\tFZ\tMX\tMY\tMZ\n\
+                    Roof\tEQX\tX\t0.004\t1\t0\t0\t0\t0\t0\n";
+        match parse_pasted_table(text, None) {
+            Err(AppError::Validation(message)) => {
+                assert!(message.contains("StoryDrifts, BaseReactions"));
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+
+        let pasted = parse_pasted_table(text, Some(ResultTable::StoryDrifts)).unwrap();
+        assert_eq!(pasted.results.story_drifts[0].drift, 0.004);
+        assert!(pasted.results.base_reactions.is_empty());
+    }
+
+    #[test]
+    fn test_paste_reads_units_from_header() {
+        let text = "Output Case,FX (kip),FY (kip),FZ (kip),MX (kip-ft),MY (kip-ft),MZ (kip-ft)\n\
+                    Dead,1,0,0,1,0,0\n";
+        let reaction = &parse_pasted_table(text, None).unwrap().results.base_reactions[0];
+        let kip = ForceUnit::Kip.to_si();
+        assert!((reaction.fx - kip).abs() < 1e-9);
+        assert!((reaction.mx - kip * 0.3048).abs() < 1e-9);
+
+        let text = "Output Case,FX (kN-m),FY,FZ,MX,MY,MZ\nDead,1,0,0,1,0,0\n";
+        match parse_pasted_table(text, None) {
+            Err(AppError::Validation(message)) => {
+                assert!(message.contains("'kN-m' does not fit column 'FX'"));
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_story_drifts_from_extracted_table() {
+        use polars::df;
+
+        let frame = df!(
+            "story" => ["Roof", "Roof", "Level 2"],
+            "loadCase" => ["EQX", "EQY", "EQX"],
+            "direction" => ["X", "Y", "X"],
+            "drift" => [0.0042, 0.0038, 0.0031],
+            "label" => [1, 1, 1],
+        )
+        .unwrap();
+
+        let drifts = story_drifts_from_frame(&frame).unwrap();
+        assert_eq!(drifts.len(), 3);
+        assert_eq!(drifts[1].direction, Direction::Y);
+
+        let selected = select_load_cases(drifts.clone(), &["EQX".to_string()]).unwrap();
+        assert_eq!(selected.len(), 2);
+        assert!(selected.iter().all(|drift| drift.load_case == "EQX"));
+        assert_eq!(select_load_cases(drifts.clone(), &[]).unwrap(), drifts);
+        match select_load_cases(drifts, &["EQX".to_string(), "Wind".to_string()]) {
+            Err(AppError::Validation(message)) => assert!(message.ends_with(": Wind")),
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_story_drift_table_without_drift_column_fails() {
+        let frame = polars::df!("story" => ["Roof"], "loadCase" => ["EQX"]).unwrap();
+        assert!(matches!(
+            story_drifts_from_frame(&frame),
+            Err(AppError::Etabs(_))
+        ));
+    }
+
+    #[test]
+    fn test_modal_periods_from_extracted_table() {
+        let frame = polars::df!(
+            "loadCase" => ["Modal", "Modal"],
+            "modeNumber" => [1i32, 2],
+            "period" => [1.21, 0.98],
+            "ux" => [0.71, 0.02],
+            "uy" => [0.01, 0.68],
+            "uz" => [0.0, 0.0],
+            "rz" => [0.03, 0.05],
+        )
+        .unwrap();
+
+        let periods = modal_periods_from_frame(&frame).unwrap();
+        assert_eq!(periods.len(), 2);
+        assert_eq!(periods[1].mode, 2);
+        assert_eq!(periods[1].uy, 0.68);
+
+        let without_rz = frame.drop("rz").unwrap();
+        assert!(matches!(
+            modal_periods_from_frame(&without_rz),
+            Err(AppError::Etabs(_))
+        ));
+    }
+
+    #[test]
+    fn test_base_reactions_from_extracted_table() {
+        let frame = polars::df!(
+            "loadCase" => ["Dead", "EQX"],
+            "fx" => [0.0, -1250.0],
+            "fy" => [0.0, 3.5],
+            "fz" => [18400.0, 0.0],
+            "mx" => [92000.0, -14.0],
+            "my" => [-131000.0, -42100.0],
+            "mz" => [0.0, 8800.0],
+        )
+        .unwrap();
+
+        let reactions = base_reactions_from_frame(&frame).unwrap();
+        assert_eq!(reactions.len(), 2);
+        assert_eq!(reactions[0].fz, 18_400_000.0);
+        assert_eq!(reactions[1].my, -42_100_000.0);
+
+        let selected = select_load_cases(reactions.clone(), &["EQX".to_string()]).unwrap();
+        assert_eq!(selected, vec![reactions[1].clone()]);
+        match select_load_cases(reactions, &["1.2D+1.6L".to_string()]) {
+            Err(AppError::Validation(message)) => {
+                assert_eq!(message, "No base reactions for load case(s): 1.2D+1.6L")
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        Direction::export(&Default::default()).expect("Failed to export Direction");
+        AnalysisRun::export(&Default::default()).expect("Failed to export AnalysisRun");
+        StoryDrift::export(&Default::default()).expect("Failed to export StoryDrift");
+        ModalPeriod::export(&Default::default()).expect("Failed to export ModalPeriod");
+        BaseReaction::export(&Default::default()).expect("Failed to export BaseReaction");
+        AnalysisResults::export(&Default::default()).expect("Failed to export AnalysisResults");
+        ResultTable::export(&Default::default()).expect("Failed to export ResultTable");
+        RowError::export(&Default::default()).expect("Failed to export RowError");
+        PasteImportReport::export(&Default::default())
+            .expect("Failed to export PasteImportReport");
+    }
+}
@@ -0,0 +1,129 @@
+use crate::Project;
+use ext_error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Project field that a search query matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum SearchField {
+    Name,
+    Description,
+}
+
+/// One project found by a search, best matches first
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct SearchHit {
+    pub project: Project,
+    /// Relevance; higher is better. Only comparable within one result list
+    pub score: f64,
+    pub matched_field: SearchField,
+}
+
+/// Splits a query into lowercase terms, rejecting queries with none.
+pub fn search_terms(query: &str) -> Result<Vec<String>> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if terms.is_empty() {
+        return Err(AppError::Validation("search query must not be empty".to_string()));
+    }
+    Ok(terms)
+}
+
+/// The field to report for a hit: the name if any term appears in it.
+pub fn matched_field(project: &Project, terms: &[String]) -> SearchField {
+    let name = project.name.to_lowercase();
+    if terms.iter().any(|term| name.contains(term.as_str())) {
+        SearchField::Name
+    } else {
+        SearchField::Description
+    }
+}
+
+/// Case-insensitive substring search, used when full-text search is not
+/// available. Every term must appear in the name or the description; name
+/// matches weigh twice as much, with a bonus when the name starts with the
+/// first term.
+pub fn substring_search(projects: &[Project], terms: &[String]) -> Vec<SearchHit> {
+    let mut hits: Vec<SearchHit> = projects
+        .iter()
+        .filter_map(|project| {
+            let name = project.name.to_lowercase();
+            let description = project.description.to_lowercase();
+
+            let mut score = 0.0;
+            for term in terms {
+                if name.contains(term.as_str()) {
+                    score += 2.0;
+                } else if description.contains(term.as_str()) {
+                    score += 1.0;
+                } else {
+                    return None;
+                }
+            }
+            if terms.first().is_some_and(|first| name.starts_with(first.as_str())) {
+                score += 1.0;
+            }
+
+            Some(SearchHit {
+                project: project.clone(),
+                score,
+                matched_field: matched_field(project, terms),
+            })
+        })
+        .collect();
+
+    hits.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.project.name.cmp(&b.project.name))
+    });
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str, description: &str) -> Project {
+        Project::new(name.to_string(), description.to_string())
+    }
+
+    #[test]
+    fn test_blank_query_is_rejected() {
+        assert!(matches!(search_terms("   "), Err(AppError::Validation(_))));
+        assert_eq!(search_terms(" Tower  A ").unwrap(), vec!["tower", "a"]);
+    }
+
+    #[test]
+    fn test_name_matches_rank_above_description_matches() {
+        let projects = vec![
+            project("Podium", "Transfer slab below the tower"),
+            project("Tower A", "Core wall system"),
+        ];
+        let hits = substring_search(&projects, &search_terms("tower").unwrap());
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].project.name, "Tower A");
+        assert_eq!(hits[0].matched_field, SearchField::Name);
+        assert_eq!(hits[1].matched_field, SearchField::Description);
+    }
+
+    #[test]
+    fn test_all_terms_must_match() {
+        let projects = vec![project("Tower A", "Steel moment frame")];
+        assert_eq!(substring_search(&projects, &search_terms("tower steel").unwrap()).len(), 1);
+        assert!(substring_search(&projects, &search_terms("tower concrete").unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        SearchField::export(&Default::default()).expect("Failed to export SearchField");
+        SearchHit::export(&Default::default()).expect("Failed to export SearchHit");
+    }
+}
@@ -0,0 +1,302 @@
+//! Geometric properties of common structural cross-sections.
+//!
+//! Shapes are idealised: I-shape fillets and tube corner radii are ignored,
+//! so results run a few percent below tabulated AISC values. Properties are
+//! in the units of the dimensions (in → in², in⁴, in³); use
+//! [`SectionProperties::convert`] to report them in another length unit.
+
+use crate::units::LengthUnit;
+use ext_error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+use ts_rs::TS;
+
+/// Cross-section shape; x is the strong (depth) axis
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+#[serde(tag = "kind")]
+pub enum SectionShape {
+    /// Solid rectangle, width `b` and depth `h`
+    Rectangle { b: f64, h: f64 },
+    /// Solid circle of diameter `d`
+    Circle { d: f64 },
+    /// Doubly symmetric I-shape (W, S, HP)
+    IShape { d: f64, bf: f64, tf: f64, tw: f64 },
+    /// Rectangular hollow section, depth `h`, width `b`, wall `t`
+    Tube { h: f64, b: f64, t: f64 },
+    /// Circular hollow section, outside diameter `od`, wall `t`
+    Pipe { od: f64, t: f64 },
+}
+
+/// Section properties about the centroidal axes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct SectionProperties {
+    pub area: f64,
+    /// Moments of inertia
+    pub ix: f64,
+    pub iy: f64,
+    /// Elastic section moduli
+    pub sx: f64,
+    pub sy: f64,
+    /// Plastic section moduli
+    pub zx: f64,
+    pub zy: f64,
+    /// Radii of gyration
+    pub rx: f64,
+    pub ry: f64,
+}
+
+impl SectionProperties {
+    /// Rescales properties computed from dimensions in `from` to `to`.
+    pub fn convert(&self, from: LengthUnit, to: LengthUnit) -> Self {
+        let scale = from.to_si() / to.to_si();
+        Self {
+            area: self.area * scale.powi(2),
+            ix: self.ix * scale.powi(4),
+            iy: self.iy * scale.powi(4),
+            sx: self.sx * scale.powi(3),
+            sy: self.sy * scale.powi(3),
+            zx: self.zx * scale.powi(3),
+            zy: self.zy * scale.powi(3),
+            rx: self.rx * scale,
+            ry: self.ry * scale,
+        }
+    }
+}
+
+impl SectionShape {
+    /// Rejects zero, negative or non-finite dimensions and walls or flanges
+    /// too thick for the shape, naming every offending field.
+    pub fn validate(&self) -> Result<()> {
+        let mut errors = Vec::new();
+        let mut positive = |field: &str, value: f64| {
+            if !(value.is_finite() && value > 0.0) {
+                errors.push(format!("{field} must be positive"));
+                false
+            } else {
+                true
+            }
+        };
+
+        match *self {
+            SectionShape::Rectangle { b, h } => {
+                positive("b", b);
+                positive("h", h);
+            }
+            SectionShape::Circle { d } => {
+                positive("d", d);
+            }
+            SectionShape::IShape { d, bf, tf, tw } => {
+                let sizes = positive("d", d) & positive("bf", bf);
+                let thicknesses = positive("tf", tf) & positive("tw", tw);
+                if sizes && thicknesses {
+                    if 2.0 * tf >= d {
+                        errors.push("tf must be less than half of d".to_string());
+                    }
+                    if tw >= bf {
+                        errors.push("tw must be less than bf".to_string());
+                    }
+                }
+            }
+            SectionShape::Tube { h, b, t } => {
+                if positive("h", h) & positive("b", b) & positive("t", t)
+                    && 2.0 * t >= h.min(b)
+                {
+                    errors.push("t must be less than half of h and b".to_string());
+                }
+            }
+            SectionShape::Pipe { od, t } => {
+                if positive("od", od) & positive("t", t) && 2.0 * t >= od {
+                    errors.push("t must be less than half of od".to_string());
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::Validation(errors.join("; ")))
+        }
+    }
+
+    pub fn properties(&self) -> Result<SectionProperties> {
+        self.validate()?;
+
+        let (area, ix, iy, sx, sy, zx, zy) = match *self {
+            SectionShape::Rectangle { b, h } => (
+                b * h,
+                b * h.powi(3) / 12.0,
+                h * b.powi(3) / 12.0,
+                b * h * h / 6.0,
+                h * b * b / 6.0,
+                b * h * h / 4.0,
+                h * b * b / 4.0,
+            ),
+            SectionShape::Circle { d } => {
+                let i = PI * d.powi(4) / 64.0;
+                let s = PI * d.powi(3) / 32.0;
+                let z = d.powi(3) / 6.0;
+                (PI * d * d / 4.0, i, i, s, s, z, z)
+            }
+            SectionShape::IShape { d, bf, tf, tw } => {
+                let web = d - 2.0 * tf;
+                let ix = (bf * d.powi(3) - (bf - tw) * web.powi(3)) / 12.0;
+                let iy = (2.0 * tf * bf.powi(3) + web * tw.powi(3)) / 12.0;
+                (
+                    2.0 * bf * tf + web * tw,
+                    ix,
+                    iy,
+                    2.0 * ix / d,
+                    2.0 * iy / bf,
+                    bf * tf * (d - tf) + tw * web * web / 4.0,
+                    bf * bf * tf / 2.0 + web * tw * tw / 4.0,
+                )
+            }
+            SectionShape::Tube { h, b, t } => {
+                let (hi, bi) = (h - 2.0 * t, b - 2.0 * t);
+                let ix = (b * h.powi(3) - bi * hi.powi(3)) / 12.0;
+                let iy = (h * b.powi(3) - hi * bi.powi(3)) / 12.0;
+                (
+                    b * h - bi * hi,
+                    ix,
+                    iy,
+                    2.0 * ix / h,
+                    2.0 * iy / b,
+                    (b * h * h - bi * hi * hi) / 4.0,
+                    (h * b * b - hi * bi * bi) / 4.0,
+                )
+            }
+            SectionShape::Pipe { od, t } => {
+                let id = od - 2.0 * t;
+                let i = PI * (od.powi(4) - id.powi(4)) / 64.0;
+                let z = (od.powi(3) - id.powi(3)) / 6.0;
+                (PI * (od * od - id * id) / 4.0, i, i, 2.0 * i / od, 2.0 * i / od, z, z)
+            }
+        };
+
+        Ok(SectionProperties {
+            area,
+            ix,
+            iy,
+            sx,
+            sy,
+            zx,
+            zy,
+            rx: (ix / area).sqrt(),
+            ry: (iy / area).sqrt(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64, what: &str) {
+        let error = (actual - expected).abs() / expected;
+        assert!(error <= tolerance, "{what}: {actual} vs {expected} ({:.1}%)", error * 100.0);
+    }
+
+    #[test]
+    fn test_w14x90_matches_aisc_within_fillet_tolerance() {
+        // AISC Manual Table 1-1: d = 14.0, bf = 14.5, tf = 0.710, tw = 0.440
+        let w14x90 = SectionShape::IShape {
+            d: 14.0,
+            bf: 14.5,
+            tf: 0.710,
+            tw: 0.440,
+        };
+        let p = w14x90.properties().unwrap();
+
+        // Published values include the web-flange fillets, worth about 2%
+        for (actual, expected, what) in [
+            (p.area, 26.5, "A"),
+            (p.ix, 999.0, "Ix"),
+            (p.sx, 143.0, "Sx"),
+            (p.zx, 157.0, "Zx"),
+            (p.rx, 6.14, "rx"),
+            (p.iy, 362.0, "Iy"),
+            (p.sy, 49.9, "Sy"),
+            (p.zy, 75.6, "Zy"),
+            (p.ry, 3.70, "ry"),
+        ] {
+            assert_close(actual, expected, 0.025, what);
+        }
+    }
+
+    #[test]
+    fn test_rectangle_closed_form() {
+        let p = SectionShape::Rectangle { b: 300.0, h: 600.0 }.properties().unwrap();
+        assert_eq!(p.area, 180_000.0);
+        assert_eq!(p.ix, 300.0 * 600.0_f64.powi(3) / 12.0);
+        assert_eq!(p.zx / p.sx, 1.5);
+    }
+
+    #[test]
+    fn test_thin_pipe_approaches_circle_ring() {
+        let p = SectionShape::Pipe { od: 10.0, t: 0.01 }.properties().unwrap();
+        // r ≈ mean radius / √2 for a thin ring
+        assert_close(p.rx, 4.995 / 2.0_f64.sqrt(), 1e-4, "rx");
+        assert_close(p.zx / p.sx, 4.0 / PI, 1e-2, "shape factor");
+    }
+
+    #[test]
+    fn test_tube_is_solid_minus_hole() {
+        let tube = SectionShape::Tube { h: 8.0, b: 4.0, t: 0.5 }.properties().unwrap();
+        let outer = SectionShape::Rectangle { b: 4.0, h: 8.0 }.properties().unwrap();
+        let inner = SectionShape::Rectangle { b: 3.0, h: 7.0 }.properties().unwrap();
+        assert!((tube.ix - (outer.ix - inner.ix)).abs() < 1e-9);
+        assert!((tube.zy - (outer.zy - inner.zy)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_convert_matches_properties_of_converted_shape() {
+        let inches = SectionShape::Tube { h: 8.0, b: 4.0, t: 0.5 }.properties().unwrap();
+        let millimeters = SectionShape::Tube {
+            h: 203.2,
+            b: 101.6,
+            t: 12.7,
+        }
+        .properties()
+        .unwrap();
+        let converted = inches.convert(LengthUnit::Inch, LengthUnit::Millimeter);
+        assert_close(converted.area, millimeters.area, 1e-12, "A");
+        assert_close(converted.ix, millimeters.ix, 1e-12, "Ix");
+        assert_close(converted.zy, millimeters.zy, 1e-12, "Zy");
+        assert_close(converted.ry, millimeters.ry, 1e-12, "ry");
+    }
+
+    #[test]
+    fn test_bad_dimensions_are_named() {
+        let shape = SectionShape::IShape {
+            d: 14.0,
+            bf: -1.0,
+            tf: 0.0,
+            tw: 0.44,
+        };
+        match shape.properties() {
+            Err(AppError::Validation(message)) => {
+                assert!(message.contains("bf must be positive"));
+                assert!(message.contains("tf must be positive"));
+                assert!(!message.contains("d must"));
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+
+        let pipe = SectionShape::Pipe { od: 4.0, t: 2.0 };
+        assert!(matches!(pipe.properties(), Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        SectionShape::export(&Default::default()).expect("Failed to export SectionShape");
+        SectionProperties::export(&Default::default()).expect("Failed to export SectionProperties");
+    }
+}
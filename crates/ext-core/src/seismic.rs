@@ -0,0 +1,409 @@
+//! Seismic base shear by the equivalent lateral force procedure, ASCE 7-16
+//! §12.8.
+//!
+//! Spectral accelerations are in g, periods in s, weights and forces in kN
+//! and heights in m, as in [`crate::estimate`], whose vertical distribution
+//! is reused here.
+
+use crate::estimate::{StoryForceRow, distribution_exponent, vertical_distribution};
+use chrono::{DateTime, Utc};
+use ext_error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Site and building parameters of the ELF procedure
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct SeismicParams {
+    pub sds: f64,
+    pub sd1: f64,
+    /// Mapped 1 s acceleration; Eq. 12.8-6 applies when it's 0.6g or more
+    pub s1: f64,
+    /// Response modification coefficient
+    pub r: f64,
+    /// Importance factor
+    pub ie: f64,
+    /// Fundamental period
+    pub t: f64,
+    /// Long-period transition period
+    pub tl: f64,
+    /// Effective seismic weight in kN
+    pub building_weight: f64,
+}
+
+/// Equation of §12.8.1.1 that sets Cs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum CsEquation {
+    /// SDS / (R/Ie), Eq. 12.8-2
+    Spectral,
+    /// SD1 / (T·R/Ie) for T ≤ TL, Eq. 12.8-3
+    VelocityCap,
+    /// SD1·TL / (T²·R/Ie) for T > TL, Eq. 12.8-4
+    LongPeriodCap,
+    /// 0.044·SDS·Ie, and at least 0.01, Eq. 12.8-5
+    Minimum,
+    /// 0.5·S1 / (R/Ie) where S1 ≥ 0.6g, Eq. 12.8-6
+    NearFaultMinimum,
+}
+
+impl CsEquation {
+    /// Equation number in ASCE 7-16
+    pub fn equation(self) -> &'static str {
+        match self {
+            CsEquation::Spectral => "12.8-2",
+            CsEquation::VelocityCap => "12.8-3",
+            CsEquation::LongPeriodCap => "12.8-4",
+            CsEquation::Minimum => "12.8-5",
+            CsEquation::NearFaultMinimum => "12.8-6",
+        }
+    }
+}
+
+/// Seismic response coefficient and base shear, Eq. 12.8-1
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct BaseShearResult {
+    pub cs: f64,
+    pub governing_equation: CsEquation,
+    /// V in kN
+    pub base_shear: f64,
+}
+
+/// One story for the vertical distribution, listed from the bottom up
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct SeismicStory {
+    pub name: String,
+    /// Floor-to-floor height in m
+    pub height: f64,
+    /// Seismic weight in kN
+    pub weight: f64,
+}
+
+/// Base shear and story forces saved with a project
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct SeismicBaseShear {
+    pub params: SeismicParams,
+    pub result: BaseShearResult,
+    pub story_forces: Vec<StoryForceRow>,
+
+    #[ts(type = "string")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl SeismicParams {
+    /// Rejects zero, negative or non-finite parameters, naming every
+    /// offending field. S1 may be zero.
+    pub fn validate(&self) -> Result<()> {
+        let mut errors = Vec::new();
+        for (field, value) in [
+            ("sds", self.sds),
+            ("sd1", self.sd1),
+            ("r", self.r),
+            ("ie", self.ie),
+            ("t", self.t),
+            ("tl", self.tl),
+            ("building_weight", self.building_weight),
+        ] {
+            if !(value.is_finite() && value > 0.0) {
+                errors.push(format!("{field} must be positive"));
+            }
+        }
+        if !(self.s1.is_finite() && self.s1 >= 0.0) {
+            errors.push("s1 must not be negative".to_string());
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::Validation(errors.join("; ")))
+        }
+    }
+}
+
+/// Cs from Eq. 12.8-2, capped by Eq. 12.8-3 or 12.8-4 and raised to the
+/// minimums of Eq. 12.8-5 and 12.8-6; the minimums win over the caps.
+pub fn compute_base_shear(params: &SeismicParams) -> Result<BaseShearResult> {
+    params.validate()?;
+
+    let r_ie = params.r / params.ie;
+    let mut cs = params.sds / r_ie;
+    let mut governing = CsEquation::Spectral;
+
+    let (cap, cap_equation) = if params.t <= params.tl {
+        (params.sd1 / (params.t * r_ie), CsEquation::VelocityCap)
+    } else {
+        (
+            params.sd1 * params.tl / (params.t.powi(2) * r_ie),
+            CsEquation::LongPeriodCap,
+        )
+    };
+    if cap < cs {
+        cs = cap;
+        governing = cap_equation;
+    }
+
+    let minimum = (0.044 * params.sds * params.ie).max(0.01);
+    if minimum > cs {
+        cs = minimum;
+        governing = CsEquation::Minimum;
+    }
+    if params.s1 >= 0.6 {
+        let near_fault = 0.5 * params.s1 / r_ie;
+        if near_fault > cs {
+            cs = near_fault;
+            governing = CsEquation::NearFaultMinimum;
+        }
+    }
+
+    Ok(BaseShearResult {
+        cs,
+        governing_equation: governing,
+        base_shear: cs * params.building_weight,
+    })
+}
+
+/// Distributes `base_shear` over the stories with Fx = Cvx·V, Eq. 12.8-11,
+/// using the k exponent for `period`.
+pub fn distribute_story_forces(
+    base_shear: f64,
+    period: f64,
+    stories: &[SeismicStory],
+) -> Result<Vec<StoryForceRow>> {
+    let mut errors = Vec::new();
+    if !(base_shear.is_finite() && base_shear >= 0.0) {
+        errors.push("base_shear must not be negative".to_string());
+    }
+    if !(period.is_finite() && period > 0.0) {
+        errors.push("period must be positive".to_string());
+    }
+    if stories.is_empty() {
+        errors.push("stories must not be empty".to_string());
+    }
+    for (i, story) in stories.iter().enumerate() {
+        if !(story.height.is_finite() && story.height > 0.0) {
+            errors.push(format!("stories[{i}].height must be positive"));
+        }
+        if !(story.weight.is_finite() && story.weight > 0.0) {
+            errors.push(format!("stories[{i}].weight must be positive"));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(AppError::Validation(errors.join("; ")));
+    }
+
+    let elevations: Vec<f64> = stories
+        .iter()
+        .scan(0.0, |elevation, story| {
+            *elevation += story.height;
+            Some(*elevation)
+        })
+        .collect();
+    let weights: Vec<f64> = stories.iter().map(|story| story.weight).collect();
+    let cvx = vertical_distribution(&elevations, &weights, distribution_exponent(period));
+
+    Ok(stories
+        .iter()
+        .enumerate()
+        .map(|(i, story)| StoryForceRow {
+            story: story.name.clone(),
+            elevation: elevations[i],
+            weight: story.weight,
+            cvx: cvx[i],
+            force: Some(base_shear * cvx[i]),
+        })
+        .collect())
+}
+
+/// Base shear and its distribution, ready to save with a project.
+pub fn seismic_base_shear(
+    params: &SeismicParams,
+    stories: &[SeismicStory],
+) -> Result<SeismicBaseShear> {
+    let result = compute_base_shear(params)?;
+    let story_forces = distribute_story_forces(result.base_shear, params.t, stories)?;
+    Ok(SeismicBaseShear {
+        params: *params,
+        result,
+        story_forces,
+        created_at: Utc::now(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Risk category II site with SDS = 1.0, SD1 = 0.6 and a special
+    /// moment frame
+    fn params(t: f64) -> SeismicParams {
+        SeismicParams {
+            sds: 1.0,
+            sd1: 0.6,
+            s1: 0.5,
+            r: 8.0,
+            ie: 1.0,
+            t,
+            tl: 8.0,
+            building_weight: 10_000.0,
+        }
+    }
+
+    fn assert_governs(params: SeismicParams, equation: CsEquation, cs: f64) {
+        let result = compute_base_shear(&params).unwrap();
+        assert_eq!(result.governing_equation, equation);
+        assert!((result.cs - cs).abs() < 1e-9, "{} vs {cs}", result.cs);
+        assert!((result.base_shear - cs * params.building_weight).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_short_period_uses_spectral_plateau() {
+        // 1.0 / 8 = 0.125 against 0.6 / (0.5 × 8) = 0.15
+        assert_governs(params(0.5), CsEquation::Spectral, 0.125);
+    }
+
+    #[test]
+    fn test_velocity_cap() {
+        // 0.6 / (1.0 × 8) = 0.075
+        assert_governs(params(1.0), CsEquation::VelocityCap, 0.075);
+    }
+
+    #[test]
+    fn test_long_period_cap() {
+        // Low seismicity, R = 1.5, T > TL: 0.1 × 4 / (25 × 1.5) = 0.01067,
+        // above both 0.044 × 0.15 and 0.01
+        let params = SeismicParams {
+            sds: 0.15,
+            sd1: 0.1,
+            s1: 0.05,
+            r: 1.5,
+            t: 5.0,
+            tl: 4.0,
+            ..params(5.0)
+        };
+        assert_governs(params, CsEquation::LongPeriodCap, 0.4 / 37.5);
+    }
+
+    #[test]
+    fn test_minimum_governs_over_caps() {
+        // 0.6 / (3 × 8) = 0.025 is below 0.044 × 1.0
+        assert_governs(params(3.0), CsEquation::Minimum, 0.044);
+
+        // 0.044 × 0.1 = 0.0044 is below the 0.01 floor
+        let low = SeismicParams {
+            sds: 0.1,
+            sd1: 0.08,
+            s1: 0.04,
+            ..params(2.0)
+        };
+        assert_governs(low, CsEquation::Minimum, 0.01);
+    }
+
+    #[test]
+    fn test_near_fault_minimum_only_from_s1_of_0_6g() {
+        // 0.5 × 1.0 / 8 = 0.0625 above 0.044 × 1.2 = 0.0528
+        let near_fault = SeismicParams {
+            sds: 1.2,
+            sd1: 1.0,
+            s1: 1.0,
+            ..params(4.0)
+        };
+        assert_governs(near_fault, CsEquation::NearFaultMinimum, 0.0625);
+
+        let below = SeismicParams {
+            s1: 0.59,
+            ..near_fault
+        };
+        assert_governs(below, CsEquation::Minimum, 0.0528);
+    }
+
+    #[test]
+    fn test_importance_factor_scales_cs() {
+        let essential = SeismicParams {
+            ie: 1.5,
+            ..params(0.5)
+        };
+        assert_governs(essential, CsEquation::Spectral, 1.5 / 8.0);
+    }
+
+    fn stories() -> Vec<SeismicStory> {
+        ["L1", "L2", "Roof"]
+            .into_iter()
+            .map(|name| SeismicStory {
+                name: name.to_string(),
+                height: 4.0,
+                weight: 1000.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_story_forces_with_k_between_0_5_and_2_5_s() {
+        // Equal weights at 4, 8 and 12 m
+        for (period, k) in [(0.4, 1.0), (0.5, 1.0), (1.5, 1.5), (2.5, 2.0), (3.0, 2.0)] {
+            let rows = distribute_story_forces(300.0, period, &stories()).unwrap();
+            let sum: f64 = [4.0_f64, 8.0, 12.0].iter().map(|h| h.powf(k)).sum();
+            let roof = 300.0 * 12.0_f64.powf(k) / sum;
+            assert!((rows[2].force.unwrap() - roof).abs() < 1e-9, "T = {period}");
+
+            let total: f64 = rows.iter().filter_map(|row| row.force).sum();
+            assert!((total - 300.0).abs() < 1e-9);
+        }
+
+        // T = 0.5 s: forces proportional to height, 50, 100 and 150
+        let rows = distribute_story_forces(300.0, 0.5, &stories()).unwrap();
+        assert!((rows[0].force.unwrap() - 50.0).abs() < 1e-9);
+        assert_eq!(rows[2].elevation, 12.0);
+    }
+
+    #[test]
+    fn test_validation_names_fields() {
+        let bad = SeismicParams {
+            r: 0.0,
+            s1: -0.1,
+            ..params(f64::NAN)
+        };
+        match compute_base_shear(&bad) {
+            Err(AppError::Validation(message)) => {
+                assert!(message.contains("r must be positive"));
+                assert!(message.contains("t must be positive"));
+                assert!(message.contains("s1 must not be negative"));
+                assert!(!message.contains("sds"));
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+
+        let mut stories = stories();
+        stories[1].weight = 0.0;
+        assert!(matches!(
+            distribute_story_forces(300.0, 1.0, &stories),
+            Err(AppError::Validation(message)) if message.contains("stories[1].weight")
+        ));
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        SeismicParams::export(&Default::default()).expect("Failed to export SeismicParams");
+        CsEquation::export(&Default::default()).expect("Failed to export CsEquation");
+        BaseShearResult::export(&Default::default()).expect("Failed to export BaseShearResult");
+        SeismicStory::export(&Default::default()).expect("Failed to export SeismicStory");
+        SeismicBaseShear::export(&Default::default()).expect("Failed to export SeismicBaseShear");
+    }
+}
@@ -0,0 +1,241 @@
+use crate::project_lock::{DEFAULT_LOCK_TIMEOUT_HOURS, MAX_LOCK_TIMEOUT_HOURS};
+use crate::time::TimezonePolicy;
+use crate::units::UnitSystem;
+use crate::DEFAULT_BACKUP_RETENTION;
+use ext_error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use ts_rs::TS;
+
+/// Largest number of backups that can be kept per project
+pub const MAX_BACKUP_RETENTION: usize = 1000;
+
+/// Minimum severity written to the application log
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum LogLevel {
+    #[default]
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+/// Application settings. Every field has a default, so settings files
+/// written by older versions keep loading as fields are added.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+#[serde(default)]
+pub struct Settings {
+    /// Overrides the default projects directory; applied on next start
+    pub projects_dir: Option<String>,
+    pub units: UnitSystem,
+    /// Backups kept per project
+    pub backup_retention: usize,
+    /// Folder containing ETABS.exe
+    pub etabs_install_path: Option<String>,
+    pub log_level: LogLevel,
+    pub timezone: TimezonePolicy,
+    /// Hours after which another instance's project lock counts as abandoned
+    pub lock_timeout_hours: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            projects_dir: None,
+            units: UnitSystem::default(),
+            backup_retention: DEFAULT_BACKUP_RETENTION,
+            etabs_install_path: None,
+            log_level: LogLevel::default(),
+            timezone: TimezonePolicy::default(),
+            lock_timeout_hours: DEFAULT_LOCK_TIMEOUT_HOURS,
+        }
+    }
+}
+
+/// Partial settings update; fields left out keep their current value.
+/// For the optional paths an empty string clears the setting.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+#[serde(default)]
+pub struct SettingsPatch {
+    pub projects_dir: Option<String>,
+    pub units: Option<UnitSystem>,
+    pub backup_retention: Option<usize>,
+    pub etabs_install_path: Option<String>,
+    pub log_level: Option<LogLevel>,
+    pub timezone: Option<TimezonePolicy>,
+    pub lock_timeout_hours: Option<u32>,
+}
+
+impl Settings {
+    pub fn apply(&mut self, patch: SettingsPatch) {
+        if let Some(dir) = patch.projects_dir {
+            self.projects_dir = non_empty(dir);
+        }
+        if let Some(units) = patch.units {
+            self.units = units;
+        }
+        if let Some(retention) = patch.backup_retention {
+            self.backup_retention = retention;
+        }
+        if let Some(path) = patch.etabs_install_path {
+            self.etabs_install_path = non_empty(path);
+        }
+        if let Some(level) = patch.log_level {
+            self.log_level = level;
+        }
+        if let Some(timezone) = patch.timezone {
+            self.timezone = timezone;
+        }
+        if let Some(hours) = patch.lock_timeout_hours {
+            self.lock_timeout_hours = hours;
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if !(1..=MAX_BACKUP_RETENTION).contains(&self.backup_retention) {
+            return Err(AppError::Validation(format!(
+                "backup_retention must be between 1 and {MAX_BACKUP_RETENTION} (got {})",
+                self.backup_retention
+            )));
+        }
+        if !(1..=MAX_LOCK_TIMEOUT_HOURS).contains(&self.lock_timeout_hours) {
+            return Err(AppError::Validation(format!(
+                "lock_timeout_hours must be between 1 and {MAX_LOCK_TIMEOUT_HOURS} (got {})",
+                self.lock_timeout_hours
+            )));
+        }
+
+        for (field, value) in [
+            ("projects_dir", &self.projects_dir),
+            ("etabs_install_path", &self.etabs_install_path),
+        ] {
+            if let Some(path) = value
+                && !Path::new(path).is_absolute()
+            {
+                return Err(AppError::Validation(format!(
+                    "{field} must be an absolute path (got '{path}')"
+                )));
+            }
+        }
+
+        self.timezone.validate()
+    }
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn absolute(path: &str) -> String {
+        if cfg!(windows) {
+            format!("C:\\{path}")
+        } else {
+            format!("/{path}")
+        }
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let settings: Settings = serde_json::from_str(r#"{ "units": "Imperial" }"#).unwrap();
+        assert_eq!(settings.units, UnitSystem::Imperial);
+        assert_eq!(settings.backup_retention, DEFAULT_BACKUP_RETENTION);
+        assert_eq!(settings.log_level, LogLevel::Error);
+    }
+
+    #[test]
+    fn test_partial_update_keeps_other_fields() {
+        let mut settings = Settings {
+            projects_dir: Some(absolute("Etabs")),
+            backup_retention: 25,
+            ..Default::default()
+        };
+
+        settings.apply(SettingsPatch {
+            units: Some(UnitSystem::Imperial),
+            ..Default::default()
+        });
+
+        assert_eq!(settings.units, UnitSystem::Imperial);
+        assert_eq!(settings.projects_dir, Some(absolute("Etabs")));
+        assert_eq!(settings.backup_retention, 25);
+    }
+
+    #[test]
+    fn test_empty_string_clears_path() {
+        let mut settings = Settings {
+            etabs_install_path: Some(absolute("ETABS 21")),
+            ..Default::default()
+        };
+        settings.apply(SettingsPatch {
+            etabs_install_path: Some("  ".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(settings.etabs_install_path, None);
+    }
+
+    #[test]
+    fn test_relative_path_is_rejected() {
+        let settings = Settings {
+            projects_dir: Some("projects".to_string()),
+            ..Default::default()
+        };
+        match settings.validate() {
+            Err(AppError::Validation(message)) => assert!(message.starts_with("projects_dir")),
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_zero_backup_retention_is_rejected() {
+        let settings = Settings {
+            backup_retention: 0,
+            ..Default::default()
+        };
+        assert!(matches!(settings.validate(), Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_lock_timeout_out_of_range_is_rejected() {
+        for hours in [0, MAX_LOCK_TIMEOUT_HOURS + 1] {
+            let settings = Settings {
+                lock_timeout_hours: hours,
+                ..Default::default()
+            };
+            match settings.validate() {
+                Err(AppError::Validation(message)) => {
+                    assert!(message.starts_with("lock_timeout_hours"))
+                }
+                other => panic!("expected validation error, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        LogLevel::export(&Default::default()).expect("Failed to export LogLevel");
+        Settings::export(&Default::default()).expect("Failed to export Settings");
+        SettingsPatch::export(&Default::default()).expect("Failed to export SettingsPatch");
+    }
+}
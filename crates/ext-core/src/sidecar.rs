@@ -0,0 +1,611 @@
+//! Client for `etab-cli`, the .NET sidecar that owns all ETABS COM access.
+//!
+//! Rust never calls ETABS COM itself: every request spawns the sidecar once,
+//! which writes a single `CliResult` JSON object to stdout and exits. See
+//! `crates/ext/skill/references/sidecar.md` for the command contracts.
+
+use crate::combinations::LoadCombination;
+use crate::frame_forces::{self, FrameForce};
+use crate::results::{self, BaseReaction, ModalPeriod, StoryDrift};
+use crate::{CliResult, EtabsStatus};
+use ext_error::{AppError, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Environment variable that overrides where the sidecar is looked up
+pub const SIDECAR_PATH_ENV: &str = "ETAB_CLI_PATH";
+
+const SIDECAR_FILE_NAME: &str = "etab-cli.exe";
+
+/// Story drift table written by `etab-cli extract-results`
+const STORY_DRIFTS_FILE: &str = "story_drifts.parquet";
+
+/// Modal participating mass ratio table written by `etab-cli extract-results`
+const MODAL_FILE: &str = "modal.parquet";
+
+/// Base reaction table written by `etab-cli extract-results`
+const BASE_REACTIONS_FILE: &str = "base_reactions.parquet";
+
+/// Frame member force table written by `etab-cli extract-results`
+const FRAME_FORCES_FILE: &str = "frame_forces.parquet";
+
+/// Returned on every platform where the sidecar cannot run
+pub const WINDOWS_ONLY_MESSAGE: &str = "ETABS COM is only available on Windows";
+
+/// `data` of `etab-cli get-status`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SidecarStatus {
+    pub is_running: bool,
+    pub pid: Option<u32>,
+    pub etabs_version: Option<String>,
+    pub open_file_path: Option<String>,
+    pub is_model_open: bool,
+    pub is_locked: Option<bool>,
+    pub is_analyzed: Option<bool>,
+    pub messages: Vec<String>,
+}
+
+/// What is currently open in the attached ETABS instance
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct EtabsModelInfo {
+    pub etabs_version: Option<String>,
+    pub model_filename: Option<String>,
+    pub story_names: Vec<String>,
+    /// `None` when the sidecar could not read the analysis state
+    pub is_analyzed: Option<bool>,
+    pub is_locked: Option<bool>,
+}
+
+/// `data` of `etab-cli get-stories`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GetStoriesData {
+    pub file_path: String,
+    /// Top down, as in the `.e2k` stories table
+    pub story_names: Vec<String>,
+    pub messages: Vec<String>,
+}
+
+/// `data` of `etab-cli open-model`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OpenModelData {
+    pub file_path: String,
+    pub pid: Option<u32>,
+    pub messages: Vec<String>,
+}
+
+/// `data` of `etab-cli run-analysis`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RunAnalysisData {
+    pub file_path: String,
+    pub analysis_time_ms: u64,
+    pub case_count: u32,
+    pub finished_case_count: u32,
+    pub messages: Vec<String>,
+}
+
+/// `data` of `etab-cli add-load-combinations`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AddLoadCombinationsData {
+    pub file_path: String,
+    pub added: Vec<String>,
+    pub replaced: Vec<String>,
+    pub messages: Vec<String>,
+}
+
+/// One combination in the `--input` file of `etab-cli add-load-combinations`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CombinationInput<'a> {
+    name: &'a str,
+    factors: Vec<CombinationFactor<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CombinationFactor<'a> {
+    load_case: &'a str,
+    factor: f64,
+}
+
+/// Combinations written into the open model by
+/// [`EtabsConnection::add_load_combinations`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct LoadCombinationsReport {
+    pub model_filename: String,
+    /// Combinations the model did not have yet
+    pub added: Vec<String>,
+    /// Combinations of the same name that were overwritten
+    pub replaced: Vec<String>,
+    pub messages: Vec<String>,
+}
+
+/// Outcome of opening a model with [`SidecarClient::open_model`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct OpenModelReport {
+    pub file_path: String,
+    /// Opened in a hidden instance, which has exited again
+    pub hidden: bool,
+    /// ETABS process the model is open in; `None` after a hidden run
+    pub pid: Option<u32>,
+    /// Analysis time; `None` when no analysis ran
+    #[ts(type = "number | null")]
+    pub analysis_time_ms: Option<u64>,
+    pub case_count: Option<u32>,
+    /// Load cases the analysis finished
+    pub finished_case_count: Option<u32>,
+    pub messages: Vec<String>,
+}
+
+/// Spawns `etab-cli` and decodes its result.
+#[derive(Debug, Clone)]
+pub struct SidecarClient {
+    path: PathBuf,
+}
+
+impl SidecarClient {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Finds the sidecar from `ETAB_CLI_PATH`, or next to the running
+    /// executable where the installer puts it.
+    pub fn locate() -> Result<Self> {
+        if !cfg!(windows) {
+            return Err(AppError::Etabs(WINDOWS_ONLY_MESSAGE.to_string()));
+        }
+
+        let path = match std::env::var_os(SIDECAR_PATH_ENV) {
+            Some(path) => PathBuf::from(path),
+            None => std::env::current_exe()
+                .map_err(|e| AppError::Etabs(format!("Failed to locate etab-cli: {e}")))?
+                .with_file_name(SIDECAR_FILE_NAME),
+        };
+
+        if !path.is_file() {
+            return Err(AppError::Etabs(format!(
+                "etab-cli was not found at '{}'. Reinstall the app or set {SIDECAR_PATH_ENV}.",
+                path.display()
+            )));
+        }
+
+        Ok(Self::new(path))
+    }
+
+    /// Runs one sidecar command and returns its `data`.
+    pub fn run<T: DeserializeOwned>(&self, command: &str, args: &[&str]) -> Result<T> {
+        let output = Command::new(&self.path)
+            .arg(command)
+            .args(args)
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| {
+                AppError::Etabs(format!("Failed to start '{}': {e}", self.path.display()))
+            })?;
+
+        parse_output(command, &output.stdout)
+    }
+
+    pub fn status(&self) -> Result<SidecarStatus> {
+        self.run("get-status", &[])
+    }
+
+    /// Opens an `.edb` model. Hidden, a new ETABS instance opens it, runs
+    /// every load case, saves the results into the file and exits, which
+    /// can take minutes. Otherwise it opens in the ETABS the user has
+    /// running and is left there for them; analysis never runs in the
+    /// user's instance.
+    pub fn open_model(&self, model: &Path, hidden: bool) -> Result<OpenModelReport> {
+        let is_edb = model
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("edb"));
+        if !is_edb {
+            return Err(AppError::Validation(format!(
+                "'{}' is not an ETABS model (.edb)",
+                model.display()
+            )));
+        }
+        if !model.is_file() {
+            return Err(AppError::NotFound(format!(
+                "Model '{}' not found",
+                model.display()
+            )));
+        }
+
+        let file = model.to_string_lossy();
+        if hidden {
+            let data: RunAnalysisData = self.run("run-analysis", &["--file", &file])?;
+            Ok(OpenModelReport {
+                file_path: data.file_path,
+                hidden,
+                pid: None,
+                analysis_time_ms: Some(data.analysis_time_ms),
+                case_count: Some(data.case_count),
+                finished_case_count: Some(data.finished_case_count),
+                messages: data.messages,
+            })
+        } else {
+            let data: OpenModelData = self.run("open-model", &["--file", &file])?;
+            Ok(OpenModelReport {
+                file_path: data.file_path,
+                hidden,
+                pid: data.pid,
+                analysis_time_ms: None,
+                case_count: None,
+                finished_case_count: None,
+                messages: data.messages,
+            })
+        }
+    }
+}
+
+/// The `--input` JSON of `etab-cli add-load-combinations`.
+fn combinations_input(combinations: &[LoadCombination]) -> Result<String> {
+    let input: Vec<CombinationInput> = combinations
+        .iter()
+        .map(|combination| CombinationInput {
+            name: &combination.name,
+            factors: combination
+                .factors
+                .iter()
+                .map(|(load_case, factor)| CombinationFactor {
+                    load_case,
+                    factor: *factor,
+                })
+                .collect(),
+        })
+        .collect();
+    serde_json::to_string(&input)
+        .map_err(|e| AppError::Internal(format!("Failed to encode load combinations: {e}")))
+}
+
+/// Decodes the single JSON object the sidecar writes to stdout.
+pub fn parse_output<T: DeserializeOwned>(command: &str, stdout: &[u8]) -> Result<T> {
+    let result: CliResult<T> = serde_json::from_slice(stdout).map_err(|e| {
+        AppError::Etabs(format!("etab-cli {command} returned invalid output: {e}"))
+    })?;
+
+    if !result.success {
+        return Err(AppError::Etabs(
+            result
+                .error
+                .unwrap_or_else(|| format!("etab-cli {command} failed")),
+        ));
+    }
+
+    result
+        .data
+        .ok_or_else(|| AppError::Etabs(format!("etab-cli {command} returned no data")))
+}
+
+/// Attachment to the ETABS instance the user already has open.
+///
+/// The sidecar is single-shot, so no COM object is held between calls; the
+/// connection remembers which ETABS process it attached to and refuses to
+/// silently switch to another one.
+#[derive(Debug, Clone)]
+pub struct EtabsConnection {
+    client: SidecarClient,
+    pid: Option<u32>,
+}
+
+impl EtabsConnection {
+    pub fn attach_to_running_instance(client: SidecarClient) -> Result<Self> {
+        let status = client.status()?;
+        if !status.is_running {
+            return Err(AppError::Etabs(
+                "ETABS is not running. Start ETABS and try again.".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            client,
+            pid: status.pid,
+        })
+    }
+
+    fn current_status(&self) -> Result<SidecarStatus> {
+        let status = self.client.status()?;
+        if !status.is_running || status.pid != self.pid {
+            return Err(AppError::Etabs(
+                "The ETABS instance this connection attached to has closed. Reconnect to continue."
+                    .to_string(),
+            ));
+        }
+        Ok(status)
+    }
+
+    pub fn status(&self) -> Result<EtabsStatus> {
+        let status = self.current_status()?;
+        Ok(EtabsStatus {
+            is_running: status.is_running,
+            version: status.etabs_version,
+            current_file: status.open_file_path,
+        })
+    }
+
+    pub fn get_model_filename(&self) -> Result<Option<String>> {
+        Ok(self.current_status()?.open_file_path)
+    }
+
+    pub fn get_analysis_run_status(&self) -> Result<Option<bool>> {
+        Ok(self.current_status()?.is_analyzed)
+    }
+
+    /// Story names, top down, read from the model open in the attached
+    /// instance.
+    pub fn get_story_names(&self) -> Result<Vec<String>> {
+        let model = self
+            .get_model_filename()?
+            .ok_or_else(|| AppError::Etabs("No model is open in ETABS".to_string()))?;
+        self.story_names_of(&model)
+    }
+
+    fn story_names_of(&self, model: &str) -> Result<Vec<String>> {
+        let data: GetStoriesData = self.client.run("get-stories", &["--file", model])?;
+        Ok(data.story_names)
+    }
+
+    /// Story drifts of the open model, from the analysis results saved in
+    /// its file, with the path of that model.
+    pub fn get_story_drifts(&self) -> Result<(String, Vec<StoryDrift>)> {
+        self.extract_results(STORY_DRIFTS_FILE, results::read_story_drifts_parquet)
+    }
+
+    /// Modal periods and participating mass ratios of the open model, from
+    /// the analysis results saved in its file, with the path of that model.
+    pub fn get_modal_periods(&self) -> Result<(String, Vec<ModalPeriod>)> {
+        self.extract_results(MODAL_FILE, results::read_modal_periods_parquet)
+    }
+
+    /// Base reactions of every load case and combination of the open
+    /// model, from the analysis results saved in its file, with the path of
+    /// that model.
+    pub fn get_base_reactions(&self) -> Result<(String, Vec<BaseReaction>)> {
+        self.extract_results(BASE_REACTIONS_FILE, results::read_base_reactions_parquet)
+    }
+
+    /// Forces at every output station of every frame member of the open
+    /// model, for all load cases and combinations, with the path of that
+    /// model.
+    pub fn get_frame_forces(&self) -> Result<(String, Vec<FrameForce>)> {
+        self.extract_results(FRAME_FORCES_FILE, frame_forces::read_frame_forces_parquet)
+    }
+
+    /// Writes `combinations` into the open model as linear additive load
+    /// combinations, replacing any of the same name. The model is not
+    /// saved; that is left to the user.
+    pub fn add_load_combinations(
+        &self,
+        combinations: &[LoadCombination],
+    ) -> Result<LoadCombinationsReport> {
+        if combinations.is_empty() {
+            return Err(AppError::Validation(
+                "There are no load combinations to write".to_string(),
+            ));
+        }
+        let model = self
+            .get_model_filename()?
+            .ok_or_else(|| AppError::Etabs("No model is open in ETABS".to_string()))?;
+
+        let input = std::env::temp_dir().join(format!("etabext-{}.json", Uuid::new_v4()));
+        std::fs::write(&input, combinations_input(combinations)?).map_err(|e| {
+            AppError::FileSystem(format!("Failed to write '{}': {e}", input.display()))
+        })?;
+        let input_arg = input.to_string_lossy().to_string();
+        let written = self.client.run::<AddLoadCombinationsData>(
+            "add-load-combinations",
+            &["--file", &model, "--input", &input_arg],
+        );
+        let _ = std::fs::remove_file(&input);
+
+        let data = written?;
+        Ok(LoadCombinationsReport {
+            model_filename: model,
+            added: data.added,
+            replaced: data.replaced,
+            messages: data.messages,
+        })
+    }
+
+    /// Runs `extract-results` on the open model and reads `table` with
+    /// `read`, returning the model's path along with it. The sidecar
+    /// extracts every results table into a temporary folder, which is
+    /// removed once the table is read.
+    fn extract_results<T>(
+        &self,
+        table: &str,
+        read: impl FnOnce(&Path) -> Result<T>,
+    ) -> Result<(String, T)> {
+        let model = self
+            .get_model_filename()?
+            .ok_or_else(|| AppError::Etabs("No model is open in ETABS".to_string()))?;
+        let output = std::env::temp_dir().join(format!("etabext-{}", Uuid::new_v4()));
+        let output_arg = output.to_string_lossy().to_string();
+
+        let extracted = self
+            .client
+            .run::<serde_json::Value>(
+                "extract-results",
+                &["--file", &model, "--output-dir", &output_arg],
+            )
+            .and_then(|_| read(&output.join(table)));
+        let _ = std::fs::remove_dir_all(&output);
+        Ok((model, extracted?))
+    }
+
+    /// What the attached instance has open. Only Mode A commands are used,
+    /// so this never starts a hidden ETABS.
+    pub fn get_model_info(&self) -> Result<EtabsModelInfo> {
+        let status = self.current_status()?;
+        let story_names = match &status.open_file_path {
+            Some(model) => self.story_names_of(model)?,
+            None => Vec::new(),
+        };
+
+        Ok(EtabsModelInfo {
+            etabs_version: status.etabs_version,
+            model_filename: status.open_file_path,
+            story_names,
+            is_analyzed: status.is_analyzed,
+            is_locked: status.is_locked,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status_output() {
+        let stdout = br#"{
+            "success": true,
+            "error": null,
+            "timestamp": "2024-02-05T14:30:00Z",
+            "data": {
+                "isRunning": true,
+                "pid": 12345,
+                "etabsVersion": "22.0.0.1234",
+                "openFilePath": "C:\\Projects\\main\\working\\model.edb",
+                "isModelOpen": true,
+                "isLocked": false,
+                "isAnalyzed": true,
+                "messages": ["ETABS 22.0.0 is running"]
+            }
+        }"#;
+
+        let status: SidecarStatus = parse_output("get-status", stdout).unwrap();
+        assert!(status.is_running);
+        assert_eq!(status.pid, Some(12345));
+        assert_eq!(status.is_analyzed, Some(true));
+    }
+
+    #[test]
+    fn test_failure_maps_to_etabs_error() {
+        let stdout = br#"{
+            "success": false,
+            "error": "ETABS is not running",
+            "timestamp": "2024-02-05T14:30:00Z",
+            "data": { "isRunning": false }
+        }"#;
+
+        match parse_output::<SidecarStatus>("open-model", stdout) {
+            Err(AppError::Etabs(message)) => assert_eq!(message, "ETABS is not running"),
+            other => panic!("expected ETABS error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_garbage_output_is_etabs_error() {
+        let result = parse_output::<SidecarStatus>("get-status", b"Unhandled exception");
+        assert!(matches!(result, Err(AppError::Etabs(_))));
+    }
+
+    #[test]
+    fn test_parse_get_stories_output() {
+        let stdout = br#"{
+            "success": true,
+            "error": null,
+            "timestamp": "2024-02-05T14:30:00Z",
+            "data": {
+                "filePath": "C:\\Projects\\main\\working\\model.edb",
+                "storyNames": ["Roof", "L2", "L1"],
+                "messages": ["Read 3 stories"]
+            }
+        }"#;
+
+        let data: GetStoriesData = parse_output("get-stories", stdout).unwrap();
+        assert_eq!(data.story_names, ["Roof", "L2", "L1"]);
+    }
+
+    #[test]
+    fn test_parse_run_analysis_output() {
+        let stdout = br#"{
+            "success": true,
+            "error": null,
+            "timestamp": "2024-02-05T14:30:00Z",
+            "data": {
+                "filePath": "C:\\Projects\\v3\\model.edb",
+                "analysisTimeMs": 134210,
+                "caseCount": 12,
+                "finishedCaseCount": 11,
+                "messages": ["Analysis complete (2m 14s)"]
+            }
+        }"#;
+
+        let data: RunAnalysisData = parse_output("run-analysis", stdout).unwrap();
+        assert_eq!(data.file_path, "C:\\Projects\\v3\\model.edb");
+        assert_eq!(data.analysis_time_ms, 134210);
+        assert_eq!(data.finished_case_count, 11);
+    }
+
+    #[test]
+    fn test_combinations_input_lists_factors_by_case() {
+        let combinations = vec![LoadCombination {
+            name: "LRFD2".to_string(),
+            factors: vec![("Dead".to_string(), 1.2), ("Live".to_string(), 1.6)],
+        }];
+
+        let input: serde_json::Value =
+            serde_json::from_str(&combinations_input(&combinations).unwrap()).unwrap();
+        assert_eq!(
+            input,
+            serde_json::json!([{
+                "name": "LRFD2",
+                "factors": [
+                    { "loadCase": "Dead", "factor": 1.2 },
+                    { "loadCase": "Live", "factor": 1.6 }
+                ]
+            }])
+        );
+    }
+
+    #[test]
+    fn test_open_model_requires_existing_edb() {
+        let client = SidecarClient::new(PathBuf::from("etab-cli.exe"));
+        let missing = std::env::temp_dir().join(Uuid::new_v4().to_string());
+
+        let result = client.open_model(&missing.join("Tower.e2k"), true);
+        assert!(matches!(result, Err(AppError::Validation(_))), "{result:?}");
+        let result = client.open_model(&missing.join("Tower.EDB"), false);
+        assert!(matches!(result, Err(AppError::NotFound(_))), "{result:?}");
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_locate_is_windows_only() {
+        match SidecarClient::locate() {
+            Err(AppError::Etabs(message)) => assert_eq!(message, WINDOWS_ONLY_MESSAGE),
+            other => panic!("expected ETABS error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        EtabsModelInfo::export(&Default::default()).expect("Failed to export EtabsModelInfo");
+        OpenModelReport::export(&Default::default()).expect("Failed to export OpenModelReport");
+        LoadCombinationsReport::export(&Default::default())
+            .expect("Failed to export LoadCombinationsReport");
+    }
+}
@@ -0,0 +1,98 @@
+//! Long-running operations that run in the background and report progress,
+//! so commands can return at once with a task id.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Event sent when a running task's progress changes
+pub const TASK_PROGRESS: &str = "task:progress";
+
+/// Event sent once a task is done, failed or cancelled
+pub const TASK_COMPLETED: &str = "task:completed";
+
+/// Finished tasks kept for the session; the oldest are dropped first
+pub const MAX_FINISHED_TASKS: usize = 50;
+
+/// Operation a task runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum TaskKind {
+    ImportEtabsModel,
+    BulkImport,
+    ProjectReport,
+    RelocateProjects,
+    OpenModel,
+    ExtractFrameForces,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum TaskStatus {
+    Running,
+    Done,
+    Failed,
+    Cancelled,
+}
+
+/// A background task; payload of `task:progress` and `task:completed`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct TaskInfo {
+    #[ts(type = "string")]
+    pub id: Uuid,
+    pub kind: TaskKind,
+    /// Fraction done, from 0 to 1
+    pub progress: f32,
+    pub status: TaskStatus,
+    #[ts(type = "string")]
+    pub started_at: DateTime<Utc>,
+    #[ts(type = "string | null")]
+    pub finished_at: Option<DateTime<Utc>>,
+    /// What the operation returned, once done
+    #[ts(type = "unknown")]
+    pub result: Option<serde_json::Value>,
+    /// Why the task failed
+    pub error: Option<String>,
+}
+
+impl TaskInfo {
+    pub fn new(kind: TaskKind) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            kind,
+            progress: 0.0,
+            status: TaskStatus::Running,
+            started_at: Utc::now(),
+            finished_at: None,
+            result: None,
+            error: None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status != TaskStatus::Running
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        TaskKind::export(&Default::default()).expect("Failed to export TaskKind");
+        TaskStatus::export(&Default::default()).expect("Failed to export TaskStatus");
+        TaskInfo::export(&Default::default()).expect("Failed to export TaskInfo");
+    }
+}
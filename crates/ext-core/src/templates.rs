@@ -0,0 +1,248 @@
+//! Starting points for new projects: the load cases and tags a building
+//! type usually needs. Built-in templates ship with the app; user templates
+//! are saved from existing projects.
+
+use crate::combinations::{self, DesignCode, LoadCase, LoadCaseKind};
+use crate::{Project, Tag};
+use ext_error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Tag given to projects created from a template, by name so templates
+/// don't depend on tag ids
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct TemplateTag {
+    pub name: String,
+    /// Used when no tag with this name exists yet
+    pub color: String,
+}
+
+/// Project settings a template starts from
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+#[serde(default)]
+pub struct TemplateSettings {
+    /// Combinations for the template's load cases are generated with this
+    /// code when set
+    pub design_code: Option<DesignCode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct ProjectTemplate {
+    #[ts(type = "string")]
+    pub id: Uuid,
+    pub name: String,
+    /// Also the description of projects created from the template
+    pub description: String,
+    pub default_tags: Vec<TemplateTag>,
+    pub default_load_cases: Vec<LoadCase>,
+    pub default_settings: TemplateSettings,
+    /// Ships with the app and cannot be deleted
+    pub built_in: bool,
+}
+
+impl ProjectTemplate {
+    /// Captures what another project of the same kind would start from.
+    /// Attached files, results and the model are project-specific and left
+    /// out.
+    pub fn from_project(
+        project: &Project,
+        name: String,
+        tags: &[Tag],
+        load_cases: Vec<LoadCase>,
+        design_code: Option<DesignCode>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            description: project.description.clone(),
+            default_tags: tags
+                .iter()
+                .map(|tag| TemplateTag {
+                    name: tag.name.clone(),
+                    color: tag.color.clone(),
+                })
+                .collect(),
+            default_load_cases: load_cases,
+            default_settings: TemplateSettings { design_code },
+            built_in: false,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        Project::validate_name(&self.name)?;
+        Project::validate_description(&self.description)?;
+        for tag in &self.default_tags {
+            Tag::new(tag.name.clone(), tag.color.clone()).validate()?;
+        }
+        // A template may leave load cases to the project
+        if !self.default_load_cases.is_empty() {
+            combinations::validate_cases(&self.default_load_cases)?;
+        }
+        Ok(())
+    }
+
+    pub fn is_built_in(id: Uuid) -> bool {
+        built_in_templates().iter().any(|template| template.id == id)
+    }
+}
+
+/// Rejects deleting a template that ships with the app.
+pub fn ensure_not_built_in(id: Uuid) -> Result<()> {
+    if ProjectTemplate::is_built_in(id) {
+        return Err(AppError::Validation(
+            "built-in templates cannot be deleted".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Templates compiled into the app. Their ids are fixed so projects and
+/// the frontend can refer to them across versions.
+pub fn built_in_templates() -> Vec<ProjectTemplate> {
+    vec![
+        built_in(
+            0x0001,
+            "Concrete Shear Wall Building",
+            "Cast-in-place concrete core and shear walls resisting wind and seismic loads",
+            &[("Concrete", "#808080"), ("Shear Wall", "#4682b4")],
+            &[
+                ("Dead", LoadCaseKind::Dead),
+                ("SDL", LoadCaseKind::Dead),
+                ("Live", LoadCaseKind::Live),
+                ("Roof Live", LoadCaseKind::RoofLive),
+                ("EQX", LoadCaseKind::Seismic),
+                ("EQY", LoadCaseKind::Seismic),
+                ("WX", LoadCaseKind::Wind),
+                ("WY", LoadCaseKind::Wind),
+            ],
+        ),
+        built_in(
+            0x0002,
+            "Steel Moment Frame",
+            "Structural steel special moment frames with a composite deck",
+            &[("Steel", "#b22222"), ("Moment Frame", "#daa520")],
+            &[
+                ("Dead", LoadCaseKind::Dead),
+                ("SDL", LoadCaseKind::Dead),
+                ("Live", LoadCaseKind::Live),
+                ("Roof Live", LoadCaseKind::RoofLive),
+                ("Snow", LoadCaseKind::Snow),
+                ("EQX", LoadCaseKind::Seismic),
+                ("EQY", LoadCaseKind::Seismic),
+                ("WX", LoadCaseKind::Wind),
+                ("WY", LoadCaseKind::Wind),
+            ],
+        ),
+        built_in(
+            0x0003,
+            "Steel Braced Frame",
+            "Structural steel concentrically braced frames with a composite deck",
+            &[("Steel", "#b22222"), ("Braced Frame", "#2e8b57")],
+            &[
+                ("Dead", LoadCaseKind::Dead),
+                ("SDL", LoadCaseKind::Dead),
+                ("Live", LoadCaseKind::Live),
+                ("Roof Live", LoadCaseKind::RoofLive),
+                ("Snow", LoadCaseKind::Snow),
+                ("EQX", LoadCaseKind::Seismic),
+                ("EQY", LoadCaseKind::Seismic),
+                ("WX", LoadCaseKind::Wind),
+                ("WY", LoadCaseKind::Wind),
+            ],
+        ),
+    ]
+}
+
+fn built_in(
+    id: u128,
+    name: &str,
+    description: &str,
+    tags: &[(&str, &str)],
+    cases: &[(&str, LoadCaseKind)],
+) -> ProjectTemplate {
+    ProjectTemplate {
+        id: Uuid::from_u128(id),
+        name: name.to_string(),
+        description: description.to_string(),
+        default_tags: tags
+            .iter()
+            .map(|&(name, color)| TemplateTag {
+                name: name.to_string(),
+                color: color.to_string(),
+            })
+            .collect(),
+        default_load_cases: cases
+            .iter()
+            .map(|&(name, kind)| LoadCase {
+                name: name.to_string(),
+                kind,
+            })
+            .collect(),
+        default_settings: TemplateSettings {
+            design_code: Some(DesignCode::Asce7Lrfd),
+        },
+        built_in: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_built_in_templates_are_valid_and_unique() {
+        let templates = built_in_templates();
+        for template in &templates {
+            template.validate().unwrap();
+            assert!(template.built_in);
+            assert!(ProjectTemplate::is_built_in(template.id));
+        }
+        for (i, template) in templates.iter().enumerate() {
+            assert!(templates[i + 1..].iter().all(|other| other.id != template.id));
+        }
+    }
+
+    #[test]
+    fn test_template_from_project_keeps_tags_and_cases_only() {
+        let project = Project::new("Tower A".to_string(), "Podium and tower".to_string());
+        let tags = vec![Tag::new("Steel".to_string(), "#b22222".to_string())];
+        let cases = vec![LoadCase {
+            name: "Dead".to_string(),
+            kind: LoadCaseKind::Dead,
+        }];
+
+        let template = ProjectTemplate::from_project(
+            &project,
+            "Tower".to_string(),
+            &tags,
+            cases,
+            Some(DesignCode::Asce7Asd),
+        );
+        assert_eq!(template.description, "Podium and tower");
+        assert_eq!(template.default_tags[0].name, "Steel");
+        assert_eq!(template.default_load_cases.len(), 1);
+        assert!(!template.built_in);
+        assert!(ensure_not_built_in(template.id).is_ok());
+        assert!(ensure_not_built_in(built_in_templates()[0].id).is_err());
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        TemplateTag::export(&Default::default()).expect("Failed to export TemplateTag");
+        TemplateSettings::export(&Default::default()).expect("Failed to export TemplateSettings");
+        ProjectTemplate::export(&Default::default()).expect("Failed to export ProjectTemplate");
+    }
+}
@@ -0,0 +1,152 @@
+use chrono::{DateTime, FixedOffset, Local, TimeZone, Utc};
+use ext_error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Largest offset accepted for a fixed timezone, in minutes (UTC±18:00).
+pub const MAX_OFFSET_MINUTES: i32 = 18 * 60;
+
+/// How timestamps are presented to users. Storage is always UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+#[serde(tag = "kind")]
+pub enum TimezonePolicy {
+    /// Use the operating system's local timezone, including DST.
+    #[default]
+    SystemLocal,
+    /// Use a fixed offset from UTC, in minutes east of Greenwich.
+    FixedOffset { minutes: i32 },
+}
+
+impl TimezonePolicy {
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            TimezonePolicy::SystemLocal => Ok(()),
+            TimezonePolicy::FixedOffset { minutes } => {
+                if minutes.abs() > MAX_OFFSET_MINUTES {
+                    Err(AppError::Validation(format!(
+                        "timezone offset must be within ±{MAX_OFFSET_MINUTES} minutes, got {minutes}"
+                    )))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+/// Formats a stored UTC timestamp for display according to `policy`.
+///
+/// The result always carries its UTC offset, e.g. `2025-01-26 06:40 (UTC+07:00)`,
+/// so a reader never has to guess which timezone a report was printed in.
+pub fn format_timestamp(timestamp: &DateTime<Utc>, policy: TimezonePolicy) -> String {
+    match policy {
+        TimezonePolicy::SystemLocal => format_in(timestamp, &Local),
+        TimezonePolicy::FixedOffset { minutes } => match FixedOffset::east_opt(minutes * 60) {
+            Some(offset) => format_in(timestamp, &offset),
+            None => format_in(timestamp, &Utc),
+        },
+    }
+}
+
+fn format_in<Tz: TimeZone>(timestamp: &DateTime<Utc>, tz: &Tz) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let local = timestamp.with_timezone(tz);
+    let offset = local.format("%:z").to_string();
+    let designation = if offset == "+00:00" {
+        "UTC".to_string()
+    } else {
+        format!("UTC{offset}")
+    };
+    format!("{} ({designation})", local.format("%Y-%m-%d %H:%M"))
+}
+
+/// Parses an RFC 3339 timestamp supplied by the frontend (e.g. a filter bound)
+/// and normalizes it to UTC.
+pub fn parse_timestamp(value: &str) -> Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value.trim())
+        .map(|parsed| parsed.with_timezone(&Utc))
+        .map_err(|e| AppError::Validation(format!("invalid timestamp '{value}': {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        parse_timestamp(value).unwrap()
+    }
+
+    #[test]
+    fn test_fixed_offset_rolls_over_date() {
+        let timestamp = utc("2025-01-25T23:40:00Z");
+        let formatted = format_timestamp(&timestamp, TimezonePolicy::FixedOffset { minutes: 420 });
+        assert_eq!(formatted, "2025-01-26 06:40 (UTC+07:00)");
+    }
+
+    #[test]
+    fn test_zero_offset_is_labelled_utc() {
+        let timestamp = utc("2025-01-25T23:40:00Z");
+        let formatted = format_timestamp(&timestamp, TimezonePolicy::FixedOffset { minutes: 0 });
+        assert_eq!(formatted, "2025-01-25 23:40 (UTC)");
+    }
+
+    #[test]
+    fn test_negative_fractional_offset() {
+        let timestamp = utc("2025-07-01T12:00:00Z");
+        let formatted = format_timestamp(&timestamp, TimezonePolicy::FixedOffset { minutes: -210 });
+        assert_eq!(formatted, "2025-07-01 08:30 (UTC-03:30)");
+    }
+
+    #[test]
+    fn test_system_local_includes_designation() {
+        let timestamp = utc("2025-07-01T12:00:00Z");
+        let formatted = format_timestamp(&timestamp, TimezonePolicy::SystemLocal);
+        assert!(formatted.contains("(UTC"), "missing designation: {formatted}");
+    }
+
+    #[test]
+    fn test_parse_normalizes_to_utc() {
+        assert_eq!(utc("2025-01-26T06:40:00+07:00"), utc("2025-01-25T23:40:00Z"));
+    }
+
+    #[test]
+    fn test_parse_repeated_wall_time_at_dst_fall_back() {
+        // 01:30 happens twice in New York on 2024-11-03: once in EDT, once in EST.
+        let first = utc("2024-11-03T01:30:00-04:00");
+        let second = utc("2024-11-03T01:30:00-05:00");
+        assert_eq!(first, utc("2024-11-03T05:30:00Z"));
+        assert_eq!(second, utc("2024-11-03T06:30:00Z"));
+        assert_eq!((second - first).num_minutes(), 60);
+    }
+
+    #[test]
+    fn test_parse_across_dst_spring_forward() {
+        // 01:59 EST and 03:00 EDT on 2024-03-10 are one minute apart.
+        let before = utc("2024-03-10T01:59:00-05:00");
+        let after = utc("2024-03-10T03:00:00-04:00");
+        assert_eq!((after - before).num_minutes(), 1);
+    }
+
+    #[test]
+    fn test_parse_rejects_missing_offset() {
+        let err = parse_timestamp("2025-01-26T06:40:00").unwrap_err();
+        assert!(matches!(err, AppError::Validation(message) if message.contains("2025-01-26T06:40:00")));
+    }
+
+    #[test]
+    fn test_validate_offset_bounds() {
+        assert!(TimezonePolicy::FixedOffset { minutes: MAX_OFFSET_MINUTES }.validate().is_ok());
+        assert!(TimezonePolicy::FixedOffset { minutes: MAX_OFFSET_MINUTES + 1 }.validate().is_err());
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        TimezonePolicy::export(&Default::default()).expect("Failed to export TimezonePolicy");
+    }
+}
@@ -0,0 +1,495 @@
+//! Engineering quantities and unit conversion.
+//!
+//! Quantities are stored in SI (m, N, N·m, Pa, m/s) and converted at the
+//! edges: when an `.e2k` file is parsed and when the frontend asks for a
+//! value in the user's unit system. Factors are the exact definitions (1 in =
+//! 25.4 mm, 1 lbf = 4.4482216152605 N), so conversions round-trip to
+//! floating point precision.
+
+use ext_error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Sub};
+use ts_rs::TS;
+
+/// Unit system for displaying and entering engineering quantities
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum UnitSystem {
+    /// kN, m, kN·m, MPa
+    #[default]
+    Metric,
+    /// kip, ft, kip·ft, ksi
+    Imperial,
+}
+
+impl UnitSystem {
+    pub fn length(self) -> LengthUnit {
+        match self {
+            UnitSystem::Metric => LengthUnit::Meter,
+            UnitSystem::Imperial => LengthUnit::Foot,
+        }
+    }
+
+    pub fn force(self) -> ForceUnit {
+        match self {
+            UnitSystem::Metric => ForceUnit::Kilonewton,
+            UnitSystem::Imperial => ForceUnit::Kip,
+        }
+    }
+
+    pub fn moment(self) -> MomentUnit {
+        MomentUnit {
+            force: self.force(),
+            length: self.length(),
+        }
+    }
+
+    /// Stresses use the customary small length: N/mm² (MPa) and kip/in² (ksi)
+    pub fn stress(self) -> StressUnit {
+        StressUnit {
+            force: match self {
+                UnitSystem::Metric => ForceUnit::Newton,
+                UnitSystem::Imperial => ForceUnit::Kip,
+            },
+            length: match self {
+                UnitSystem::Metric => LengthUnit::Millimeter,
+                UnitSystem::Imperial => LengthUnit::Inch,
+            },
+        }
+    }
+}
+
+/// Length unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum LengthUnit {
+    Millimeter,
+    Centimeter,
+    Meter,
+    Inch,
+    Foot,
+}
+
+impl LengthUnit {
+    /// Metres per unit
+    pub fn to_si(self) -> f64 {
+        match self {
+            LengthUnit::Millimeter => 0.001,
+            LengthUnit::Centimeter => 0.01,
+            LengthUnit::Meter => 1.0,
+            LengthUnit::Inch => 0.0254,
+            LengthUnit::Foot => 0.3048,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            LengthUnit::Millimeter => "mm",
+            LengthUnit::Centimeter => "cm",
+            LengthUnit::Meter => "m",
+            LengthUnit::Inch => "in",
+            LengthUnit::Foot => "ft",
+        }
+    }
+
+    /// Parses the length name ETABS writes in `UNITS` records
+    pub fn from_etabs(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "MM" => Some(LengthUnit::Millimeter),
+            "CM" => Some(LengthUnit::Centimeter),
+            "M" => Some(LengthUnit::Meter),
+            "IN" | "INCH" => Some(LengthUnit::Inch),
+            "FT" | "FEET" => Some(LengthUnit::Foot),
+            _ => None,
+        }
+    }
+}
+
+/// Force unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum ForceUnit {
+    Newton,
+    Kilonewton,
+    /// Pound-force
+    Pound,
+    Kip,
+    KilogramForce,
+    TonneForce,
+}
+
+impl ForceUnit {
+    /// Newtons per unit
+    pub fn to_si(self) -> f64 {
+        const POUND: f64 = 4.448_221_615_260_5;
+        const STANDARD_GRAVITY: f64 = 9.806_65;
+        match self {
+            ForceUnit::Newton => 1.0,
+            ForceUnit::Kilonewton => 1000.0,
+            ForceUnit::Pound => POUND,
+            ForceUnit::Kip => 1000.0 * POUND,
+            ForceUnit::KilogramForce => STANDARD_GRAVITY,
+            ForceUnit::TonneForce => 1000.0 * STANDARD_GRAVITY,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            ForceUnit::Newton => "N",
+            ForceUnit::Kilonewton => "kN",
+            ForceUnit::Pound => "lbf",
+            ForceUnit::Kip => "kip",
+            ForceUnit::KilogramForce => "kgf",
+            ForceUnit::TonneForce => "tonf",
+        }
+    }
+
+    /// Parses the force name ETABS writes in `UNITS` records
+    pub fn from_etabs(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "N" => Some(ForceUnit::Newton),
+            "KN" => Some(ForceUnit::Kilonewton),
+            "LB" | "LBF" => Some(ForceUnit::Pound),
+            "KIP" => Some(ForceUnit::Kip),
+            "KGF" => Some(ForceUnit::KilogramForce),
+            "TONF" => Some(ForceUnit::TonneForce),
+            _ => None,
+        }
+    }
+}
+
+/// Moment unit, force times length
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct MomentUnit {
+    pub force: ForceUnit,
+    pub length: LengthUnit,
+}
+
+impl MomentUnit {
+    /// Newton-metres per unit
+    pub fn to_si(self) -> f64 {
+        self.force.to_si() * self.length.to_si()
+    }
+
+    /// ASCII symbol such as `kN-m`, safe in CSV headers
+    pub fn symbol(self) -> String {
+        format!("{}-{}", self.force.symbol(), self.length.symbol())
+    }
+}
+
+/// Stress unit, force per length squared
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct StressUnit {
+    pub force: ForceUnit,
+    pub length: LengthUnit,
+}
+
+impl StressUnit {
+    /// Pascals per unit
+    pub fn to_si(self) -> f64 {
+        self.force.to_si() / self.length.to_si().powi(2)
+    }
+}
+
+/// Speed unit, for wind speeds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum SpeedUnit {
+    MetersPerSecond,
+    KilometersPerHour,
+    MilesPerHour,
+}
+
+impl SpeedUnit {
+    /// Metres per second per unit
+    pub fn to_si(self) -> f64 {
+        match self {
+            SpeedUnit::MetersPerSecond => 1.0,
+            SpeedUnit::KilometersPerHour => 1.0 / 3.6,
+            SpeedUnit::MilesPerHour => 0.447_04,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            SpeedUnit::MetersPerSecond => "m/s",
+            SpeedUnit::KilometersPerHour => "km/h",
+            SpeedUnit::MilesPerHour => "mph",
+        }
+    }
+}
+
+/// Force and length units of an ETABS model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct UnitSet {
+    pub force: ForceUnit,
+    pub length: LengthUnit,
+}
+
+impl Default for UnitSet {
+    fn default() -> Self {
+        Self {
+            force: ForceUnit::Kilonewton,
+            length: LengthUnit::Meter,
+        }
+    }
+}
+
+impl UnitSet {
+    pub fn moment(self) -> MomentUnit {
+        MomentUnit {
+            force: self.force,
+            length: self.length,
+        }
+    }
+
+    pub fn stress(self) -> StressUnit {
+        StressUnit {
+            force: self.force,
+            length: self.length,
+        }
+    }
+}
+
+macro_rules! quantity {
+    ($(#[$doc:meta])* $name:ident, $unit:ty) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize, TS)]
+        #[ts(
+            export,
+            export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+        )]
+        #[serde(transparent)]
+        pub struct $name(f64);
+
+        impl $name {
+            pub fn from(value: f64, unit: $unit) -> Self {
+                Self(value * unit.to_si())
+            }
+
+            pub fn to(self, unit: $unit) -> f64 {
+                self.0 / unit.to_si()
+            }
+
+            pub fn is_finite(self) -> bool {
+                self.0.is_finite()
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self(self.0 + other.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self(self.0 - other.0)
+            }
+        }
+    };
+}
+
+quantity!(
+    /// Length, serialized in metres
+    Length,
+    LengthUnit
+);
+quantity!(
+    /// Force, serialized in newtons
+    Force,
+    ForceUnit
+);
+quantity!(
+    /// Moment, serialized in newton-metres
+    Moment,
+    MomentUnit
+);
+quantity!(
+    /// Stress, serialized in pascals
+    Stress,
+    StressUnit
+);
+quantity!(
+    /// Speed, serialized in metres per second
+    Speed,
+    SpeedUnit
+);
+
+/// Unit of any supported quantity, for conversions requested by the frontend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+#[serde(tag = "kind")]
+pub enum Unit {
+    Length { unit: LengthUnit },
+    Force { unit: ForceUnit },
+    Moment { force: ForceUnit, length: LengthUnit },
+    Stress { force: ForceUnit, length: LengthUnit },
+    Speed { unit: SpeedUnit },
+}
+
+impl Unit {
+    fn quantity(self) -> &'static str {
+        match self {
+            Unit::Length { .. } => "length",
+            Unit::Force { .. } => "force",
+            Unit::Moment { .. } => "moment",
+            Unit::Stress { .. } => "stress",
+            Unit::Speed { .. } => "speed",
+        }
+    }
+
+    fn to_si(self) -> f64 {
+        match self {
+            Unit::Length { unit } => unit.to_si(),
+            Unit::Force { unit } => unit.to_si(),
+            Unit::Moment { force, length } => MomentUnit { force, length }.to_si(),
+            Unit::Stress { force, length } => StressUnit { force, length }.to_si(),
+            Unit::Speed { unit } => unit.to_si(),
+        }
+    }
+}
+
+/// Converts `value` between two units of the same quantity.
+pub fn convert_quantity(value: f64, from: Unit, to: Unit) -> Result<f64> {
+    if from.quantity() != to.quantity() {
+        return Err(AppError::Validation(format!(
+            "Cannot convert {} to {}",
+            from.quantity(),
+            to.quantity()
+        )));
+    }
+    if !value.is_finite() {
+        return Err(AppError::Validation(format!(
+            "value must be a finite number (got {value})"
+        )));
+    }
+    Ok(value * from.to_si() / to.to_si())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_relative(actual: f64, expected: f64, tolerance: f64) {
+        let error = ((actual - expected) / expected).abs();
+        assert!(error <= tolerance, "{actual} vs {expected} (relative error {error:e})");
+    }
+
+    #[test]
+    fn test_kip_ft_round_trip_through_kn_m() {
+        let kip_ft = UnitSystem::Imperial.moment();
+        let kn_m = UnitSystem::Metric.moment();
+
+        for value in [1.0, 123.456, 98_765.4321, 1e-6] {
+            let metric = Moment::from(value, kip_ft).to(kn_m);
+            let back = Moment::from(metric, kn_m).to(kip_ft);
+            assert_relative(back, value, 1e-9);
+        }
+
+        // 1 kip·ft = 4.448222 kN × 0.3048 m
+        assert_relative(Moment::from(1.0, kip_ft).to(kn_m), 1.355_817_948_331_400_4, 1e-12);
+    }
+
+    #[test]
+    fn test_convert_quantity_round_trip() {
+        let kip_ft = Unit::Moment {
+            force: ForceUnit::Kip,
+            length: LengthUnit::Foot,
+        };
+        let kn_m = Unit::Moment {
+            force: ForceUnit::Kilonewton,
+            length: LengthUnit::Meter,
+        };
+        let metric = convert_quantity(250.0, kip_ft, kn_m).unwrap();
+        assert_relative(convert_quantity(metric, kn_m, kip_ft).unwrap(), 250.0, 1e-9);
+    }
+
+    #[test]
+    fn test_stress_and_length_factors() {
+        let ksi = UnitSystem::Imperial.stress();
+        let mpa = UnitSystem::Metric.stress();
+        assert_relative(Stress::from(50.0, ksi).to(mpa), 344.737_864_658_4, 1e-9);
+        assert_relative(Length::from(12.0, LengthUnit::Inch).to(LengthUnit::Foot), 1.0, 1e-15);
+        assert_eq!(Force::from(2.0, ForceUnit::Kilonewton).to(ForceUnit::Newton), 2000.0);
+        assert_relative(
+            Speed::from(115.0, SpeedUnit::MilesPerHour).to(SpeedUnit::KilometersPerHour),
+            185.074_56,
+            1e-9,
+        );
+    }
+
+    #[test]
+    fn test_mismatched_quantities_are_rejected() {
+        let result = convert_quantity(
+            1.0,
+            Unit::Length {
+                unit: LengthUnit::Foot,
+            },
+            Unit::Force {
+                unit: ForceUnit::Kip,
+            },
+        );
+        match result {
+            Err(AppError::Validation(message)) => {
+                assert_eq!(message, "Cannot convert length to force");
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_etabs_unit_names() {
+        assert_eq!(ForceUnit::from_etabs("kip"), Some(ForceUnit::Kip));
+        assert_eq!(LengthUnit::from_etabs("FT"), Some(LengthUnit::Foot));
+        assert_eq!(LengthUnit::from_etabs("yd"), None);
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        UnitSystem::export(&Default::default()).expect("Failed to export UnitSystem");
+        LengthUnit::export(&Default::default()).expect("Failed to export LengthUnit");
+        ForceUnit::export(&Default::default()).expect("Failed to export ForceUnit");
+        MomentUnit::export(&Default::default()).expect("Failed to export MomentUnit");
+        StressUnit::export(&Default::default()).expect("Failed to export StressUnit");
+        UnitSet::export(&Default::default()).expect("Failed to export UnitSet");
+        Length::export(&Default::default()).expect("Failed to export Length");
+        Force::export(&Default::default()).expect("Failed to export Force");
+        Moment::export(&Default::default()).expect("Failed to export Moment");
+        Stress::export(&Default::default()).expect("Failed to export Stress");
+        SpeedUnit::export(&Default::default()).expect("Failed to export SpeedUnit");
+        Speed::export(&Default::default()).expect("Failed to export Speed");
+        Unit::export(&Default::default()).expect("Failed to export Unit");
+    }
+}
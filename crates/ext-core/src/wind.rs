@@ -0,0 +1,405 @@
+//! Wind pressures on the main wind force resisting system by the
+//! directional procedure, ASCE 7-16 Chapter 27 Part 1.
+//!
+//! Only the external wall pressures p = q·G·Cp of an enclosed rectangular
+//! building are computed; internal pressure cancels out of the net story
+//! load. The equations are in inch-pound units and inputs are converted
+//! through [`crate::units`], so metric input gives the same result as the
+//! SI form of Eq. 26.10-1 (0.613·Kz·Kzt·Kd·Ke·V²).
+
+use crate::units::{ForceUnit, Length, LengthUnit, Speed, SpeedUnit, Stress, StressUnit};
+use chrono::{DateTime, Utc};
+use ext_error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+const PSF: StressUnit = StressUnit {
+    force: ForceUnit::Pound,
+    length: LengthUnit::Foot,
+};
+
+/// Kz is constant below this height in ft, Table 26.10-1 note
+const MIN_KZ_HEIGHT_FT: f64 = 15.0;
+
+/// Cp of the windward wall, Figure 27.3-1
+pub const WINDWARD_CP: f64 = 0.8;
+
+/// Surface roughness category, §26.7.3
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub enum Exposure {
+    /// Urban and suburban areas
+    B,
+    /// Open terrain with scattered obstructions
+    C,
+    /// Flat, unobstructed areas and water surfaces
+    D,
+}
+
+impl Exposure {
+    /// Terrain exposure constants (α, zg in ft), Table 26.11-1
+    pub fn constants(self) -> (f64, f64) {
+        match self {
+            Exposure::B => (7.0, 1200.0),
+            Exposure::C => (9.5, 900.0),
+            Exposure::D => (11.5, 700.0),
+        }
+    }
+}
+
+/// One story level where pressures are reported
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct WindStory {
+    pub name: String,
+    /// Height above ground
+    pub elevation: Length,
+}
+
+/// Inputs of the MWFRS wall pressure calculation
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct WindInput {
+    /// Basic wind speed V
+    pub v: Speed,
+    pub exposure: Exposure,
+    /// Topographic factor, 1.0 on flat ground
+    pub kzt: f64,
+    /// Directionality factor, 0.85 for buildings
+    pub kd: f64,
+    /// Ground elevation factor, 1.0 at sea level
+    pub ke: f64,
+    /// Gust-effect factor, 0.85 for rigid buildings
+    pub gust_factor: f64,
+    /// Plan dimension parallel to the wind, L
+    pub length: Length,
+    /// Plan dimension normal to the wind, B
+    pub width: Length,
+    /// The highest elevation is taken as the mean roof height
+    pub stories: Vec<WindStory>,
+}
+
+/// Velocity and wall pressures at one story
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct WindPressureRow {
+    pub story: String,
+    pub elevation: Length,
+    pub kz: f64,
+    pub qz: Stress,
+    /// qz·G·Cp, positive towards the wall
+    pub windward: Stress,
+    /// qh·G·Cp, negative (suction) and the same at every story
+    pub leeward: Stress,
+    /// Windward minus leeward, the pressure on the building as a whole
+    pub net: Stress,
+}
+
+/// Wall pressures of a building, stored with the project
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = concat!(env!("CARGO_MANIFEST_DIR"), "/../../packages/shared/src/types/")
+)]
+pub struct WindPressures {
+    pub input: WindInput,
+    /// Velocity pressure at the mean roof height
+    pub qh: Stress,
+    pub windward_cp: f64,
+    pub leeward_cp: f64,
+    pub rows: Vec<WindPressureRow>,
+
+    #[ts(type = "string")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl WindInput {
+    /// Rejects zero, negative or non-finite speeds, factors, dimensions and
+    /// elevations, naming every offending field.
+    pub fn validate(&self) -> Result<()> {
+        let mut errors = Vec::new();
+        let mut positive = |field: &str, value: f64| {
+            if !(value.is_finite() && value > 0.0) {
+                errors.push(format!("{field} must be positive"));
+            }
+        };
+
+        positive("v", self.v.to(SpeedUnit::MetersPerSecond));
+        positive("kzt", self.kzt);
+        positive("kd", self.kd);
+        positive("ke", self.ke);
+        positive("gust_factor", self.gust_factor);
+        positive("length", self.length.to(LengthUnit::Meter));
+        positive("width", self.width.to(LengthUnit::Meter));
+        for (i, story) in self.stories.iter().enumerate() {
+            positive(
+                &format!("stories[{i}].elevation"),
+                story.elevation.to(LengthUnit::Meter),
+            );
+        }
+        if self.stories.is_empty() {
+            errors.push("stories must not be empty".to_string());
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::Validation(errors.join("; ")))
+        }
+    }
+}
+
+/// Velocity pressure exposure coefficient, Table 26.10-1:
+/// Kz = 2.01·(z/zg)^(2/α), constant below 15 ft and at most 2.01 above zg.
+pub fn kz(exposure: Exposure, z: Length) -> f64 {
+    let (alpha, zg) = exposure.constants();
+    let z = z.to(LengthUnit::Foot).clamp(MIN_KZ_HEIGHT_FT, zg);
+    2.01 * (z / zg).powf(2.0 / alpha)
+}
+
+/// Velocity pressure qz = 0.00256·Kz·Kzt·Kd·Ke·V² psf with V in mph,
+/// Eq. 26.10-1.
+pub fn velocity_pressure(v: Speed, kz: f64, kzt: f64, kd: f64, ke: f64) -> Stress {
+    let v = v.to(SpeedUnit::MilesPerHour);
+    Stress::from(0.00256 * kz * kzt * kd * ke * v * v, PSF)
+}
+
+/// Leeward wall Cp for the plan ratio L/B, Figure 27.3-1: −0.5 up to 1,
+/// −0.3 at 2 and −0.2 from 4, linear in between.
+pub fn leeward_cp(l_over_b: f64) -> f64 {
+    if l_over_b <= 1.0 {
+        -0.5
+    } else if l_over_b <= 2.0 {
+        -0.5 + 0.2 * (l_over_b - 1.0)
+    } else if l_over_b <= 4.0 {
+        -0.3 + 0.05 * (l_over_b - 2.0)
+    } else {
+        -0.2
+    }
+}
+
+/// Windward and leeward wall pressures at every story. Windward pressure
+/// varies with height; leeward pressure uses qh at the highest story.
+pub fn compute_wind_pressures(input: &WindInput) -> Result<WindPressures> {
+    input.validate()?;
+
+    let q = |z: Length| {
+        let kz = kz(input.exposure, z);
+        let qz = velocity_pressure(input.v, kz, input.kzt, input.kd, input.ke);
+        (kz, qz)
+    };
+    let h = input
+        .stories
+        .iter()
+        .map(|story| story.elevation)
+        .fold(Length::default(), |h, z| if z > h { z } else { h });
+    let (_, qh) = q(h);
+
+    let windward_cp = WINDWARD_CP;
+    let leeward_cp =
+        leeward_cp(input.length.to(LengthUnit::Meter) / input.width.to(LengthUnit::Meter));
+    let pressure = |q: Stress, cp: f64| Stress::from(q.to(PSF) * input.gust_factor * cp, PSF);
+    let leeward = pressure(qh, leeward_cp);
+
+    let rows = input
+        .stories
+        .iter()
+        .map(|story| {
+            let (kz, qz) = q(story.elevation);
+            let windward = pressure(qz, windward_cp);
+            WindPressureRow {
+                story: story.name.clone(),
+                elevation: story.elevation,
+                kz,
+                qz,
+                windward,
+                leeward,
+                net: windward - leeward,
+            }
+        })
+        .collect();
+
+    Ok(WindPressures {
+        input: input.clone(),
+        qh,
+        windward_cp,
+        leeward_cp,
+        rows,
+        created_at: Utc::now(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feet(value: f64) -> Length {
+        Length::from(value, LengthUnit::Foot)
+    }
+
+    fn assert_within_1_percent(actual: f64, expected: f64, what: &str) {
+        let error = ((actual - expected) / expected).abs();
+        assert!(
+            error <= 0.01,
+            "{what}: {actual} vs {expected} ({:.2}%)",
+            error * 100.0
+        );
+    }
+
+    /// Four stories at 15 ft, 115 mph, exposure C, square plan
+    fn office() -> WindInput {
+        WindInput {
+            v: Speed::from(115.0, SpeedUnit::MilesPerHour),
+            exposure: Exposure::C,
+            kzt: 1.0,
+            kd: 0.85,
+            ke: 1.0,
+            gust_factor: 0.85,
+            length: feet(100.0),
+            width: feet(100.0),
+            stories: (1..=4)
+                .map(|i| WindStory {
+                    name: format!("L{}", i + 1),
+                    elevation: feet(15.0 * i as f64),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_kz_matches_table_26_10_1() {
+        for (exposure, z, tabulated) in [
+            (Exposure::B, 30.0, 0.70),
+            (Exposure::B, 60.0, 0.85),
+            (Exposure::B, 100.0, 0.99),
+            (Exposure::C, 15.0, 0.85),
+            (Exposure::C, 30.0, 0.98),
+            (Exposure::C, 60.0, 1.13),
+            (Exposure::C, 100.0, 1.26),
+            (Exposure::D, 15.0, 1.03),
+            (Exposure::D, 60.0, 1.31),
+        ] {
+            assert_within_1_percent(
+                kz(exposure, feet(z)),
+                tabulated,
+                &format!("{exposure:?} {z} ft"),
+            );
+        }
+    }
+
+    #[test]
+    fn test_kz_is_constant_below_15_ft() {
+        let at_15 = kz(Exposure::B, feet(15.0));
+        assert_eq!(kz(Exposure::B, feet(10.0)), at_15);
+        assert_eq!(kz(Exposure::B, feet(1.0)), at_15);
+        assert!(kz(Exposure::B, feet(16.0)) > at_15);
+        assert_eq!(kz(Exposure::C, feet(5000.0)), 2.01);
+    }
+
+    #[test]
+    fn test_worked_example_wall_pressures() {
+        // Hand calculation with tabulated Kz: q = 0.00256 × Kz × 0.85 × 115²
+        //   30 ft: Kz = 0.98, qz = 28.20 psf, windward 28.20 × 0.85 × 0.8 = 19.18
+        //   60 ft: Kz = 1.13, qh = 32.52 psf, leeward 32.52 × 0.85 × −0.5 = −13.82
+        let result = compute_wind_pressures(&office()).unwrap();
+        assert_within_1_percent(result.qh.to(PSF), 32.52, "qh");
+        assert_eq!(result.leeward_cp, -0.5);
+
+        let at_30 = &result.rows[1];
+        assert_eq!(at_30.story, "L3");
+        assert_within_1_percent(at_30.qz.to(PSF), 28.20, "qz at 30 ft");
+        assert_within_1_percent(at_30.windward.to(PSF), 19.18, "windward at 30 ft");
+        assert_within_1_percent(at_30.leeward.to(PSF), -13.82, "leeward");
+        assert_within_1_percent(at_30.net.to(PSF), 19.18 + 13.82, "net at 30 ft");
+        assert!(result.rows.iter().all(|row| row.leeward == at_30.leeward));
+    }
+
+    #[test]
+    fn test_metric_input_matches_si_equation() {
+        // 0.613 × Kz × Kd × V² with V = 50 m/s at 10 m, exposure C:
+        // Kz = 2.01 × (32.81 / 900)^(2 / 9.5) = 1.0009, qz = 1303.9 Pa
+        let input = WindInput {
+            v: Speed::from(50.0, SpeedUnit::MetersPerSecond),
+            stories: vec![WindStory {
+                name: "Roof".to_string(),
+                elevation: Length::from(10.0, LengthUnit::Meter),
+            }],
+            ..office()
+        };
+        let result = compute_wind_pressures(&input).unwrap();
+        let pascals = StressUnit {
+            force: ForceUnit::Newton,
+            length: LengthUnit::Meter,
+        };
+        assert_within_1_percent(result.rows[0].qz.to(pascals), 1303.9, "qz");
+    }
+
+    #[test]
+    fn test_leeward_cp_interpolates_on_l_over_b() {
+        for (ratio, cp) in [
+            (0.5, -0.5),
+            (1.0, -0.5),
+            (1.5, -0.4),
+            (2.0, -0.3),
+            (3.0, -0.25),
+            (6.0, -0.2),
+        ] {
+            assert!((leeward_cp(ratio) - cp).abs() < 1e-12, "L/B = {ratio}");
+        }
+
+        let long = WindInput {
+            length: feet(300.0),
+            ..office()
+        };
+        assert!((compute_wind_pressures(&long).unwrap().leeward_cp + 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_validation_names_fields() {
+        let mut input = WindInput {
+            v: Speed::default(),
+            width: feet(-10.0),
+            ..office()
+        };
+        input.stories[2].elevation = feet(f64::NAN);
+        match compute_wind_pressures(&input) {
+            Err(AppError::Validation(message)) => {
+                assert!(message.contains("v must be positive"));
+                assert!(message.contains("width must be positive"));
+                assert!(message.contains("stories[2].elevation must be positive"));
+                assert!(!message.contains("length"));
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+
+        let empty = WindInput {
+            stories: Vec::new(),
+            ..office()
+        };
+        assert!(matches!(
+            compute_wind_pressures(&empty),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_export_typescript_bindings() {
+        Exposure::export(&Default::default()).expect("Failed to export Exposure");
+        WindStory::export(&Default::default()).expect("Failed to export WindStory");
+        WindInput::export(&Default::default()).expect("Failed to export WindInput");
+        WindPressureRow::export(&Default::default()).expect("Failed to export WindPressureRow");
+        WindPressures::export(&Default::default()).expect("Failed to export WindPressures");
+    }
+}
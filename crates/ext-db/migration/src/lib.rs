@@ -1,12 +1,38 @@
 pub use sea_orm_migration::prelude::*;
 
-mod m20220101_000001_create_table;
+mod m20250126_000001_create_projects_table;
+mod m20250202_000001_create_project_files_table;
+mod m20250203_000001_add_project_status;
+mod m20250204_000001_create_projects_fts;
+mod m20250205_000001_create_tags_tables;
+mod m20250206_000001_create_analysis_results_tables;
+mod m20250207_000001_add_project_file_missing;
+mod m20250208_000001_add_project_last_opened;
+mod m20250209_000001_create_audit_log_table;
+mod m20250210_000001_create_project_templates_table;
+mod m20250211_000001_create_notes_tables;
+mod m20250212_000001_create_project_locks_table;
+mod m20250213_000001_create_frame_forces_table;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20220101_000001_create_table::Migration)]
+        vec![
+            Box::new(m20250126_000001_create_projects_table::Migration),
+            Box::new(m20250202_000001_create_project_files_table::Migration),
+            Box::new(m20250203_000001_add_project_status::Migration),
+            Box::new(m20250204_000001_create_projects_fts::Migration),
+            Box::new(m20250205_000001_create_tags_tables::Migration),
+            Box::new(m20250206_000001_create_analysis_results_tables::Migration),
+            Box::new(m20250207_000001_add_project_file_missing::Migration),
+            Box::new(m20250208_000001_add_project_last_opened::Migration),
+            Box::new(m20250209_000001_create_audit_log_table::Migration),
+            Box::new(m20250210_000001_create_project_templates_table::Migration),
+            Box::new(m20250211_000001_create_notes_tables::Migration),
+            Box::new(m20250212_000001_create_project_locks_table::Migration),
+            Box::new(m20250213_000001_create_frame_forces_table::Migration),
+        ]
     }
 }
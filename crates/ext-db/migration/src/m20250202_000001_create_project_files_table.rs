@@ -0,0 +1,57 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProjectFiles::Table)
+                    .if_not_exists()
+                    .col(string(ProjectFiles::Id).primary_key())
+                    .col(string(ProjectFiles::ProjectId))
+                    .col(string(ProjectFiles::OriginalName))
+                    .col(string(ProjectFiles::StoredPath))
+                    .col(big_integer(ProjectFiles::Size))
+                    .col(string(ProjectFiles::Sha256))
+                    .col(timestamp_with_time_zone(ProjectFiles::AddedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        // One copy of any given content per project
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_project_files_project_sha256")
+                    .table(ProjectFiles::Table)
+                    .col(ProjectFiles::ProjectId)
+                    .col(ProjectFiles::Sha256)
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProjectFiles::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum ProjectFiles {
+    Table,
+    Id,
+    ProjectId,
+    OriginalName,
+    StoredPath,
+    Size,
+    Sha256,
+    AddedAt,
+}
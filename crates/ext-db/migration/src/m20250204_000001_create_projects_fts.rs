@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // SQLite builds without FTS5 reject the virtual table; search then
+        // falls back to substring matching, so this is not an error
+        if db
+            .execute_unprepared(
+                "CREATE VIRTUAL TABLE IF NOT EXISTS projects_fts \
+                 USING fts5(project_id UNINDEXED, name, description)",
+            )
+            .await
+            .is_err()
+        {
+            return Ok(());
+        }
+
+        db.execute_unprepared(
+            "INSERT INTO projects_fts (project_id, name, description) \
+             SELECT id, name, description FROM projects",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS projects_fts")
+            .await?;
+
+        Ok(())
+    }
+}
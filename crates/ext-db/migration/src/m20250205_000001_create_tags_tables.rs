@@ -0,0 +1,73 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Tags::Table)
+                    .if_not_exists()
+                    .col(string(Tags::Id).primary_key())
+                    .col(string(Tags::Name))
+                    .col(string(Tags::Color))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProjectTags::Table)
+                    .if_not_exists()
+                    .col(string(ProjectTags::ProjectId))
+                    .col(string(ProjectTags::TagId))
+                    .primary_key(
+                        Index::create()
+                            .col(ProjectTags::ProjectId)
+                            .col(ProjectTags::TagId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_project_tags_tag_id")
+                    .table(ProjectTags::Table)
+                    .col(ProjectTags::TagId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProjectTags::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Tags::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Tags {
+    Table,
+    Id,
+    Name,
+    Color,
+}
+
+#[derive(DeriveIden)]
+enum ProjectTags {
+    Table,
+    ProjectId,
+    TagId,
+}
@@ -0,0 +1,184 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AnalysisRuns::Table)
+                    .if_not_exists()
+                    .col(string(AnalysisRuns::Id).primary_key())
+                    .col(string(AnalysisRuns::ProjectId))
+                    .col(string(AnalysisRuns::Label))
+                    .col(timestamp_with_time_zone(AnalysisRuns::ImportedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Re-importing a label replaces the run instead of adding another
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_analysis_runs_project_label")
+                    .table(AnalysisRuns::Table)
+                    .col(AnalysisRuns::ProjectId)
+                    .col(AnalysisRuns::Label)
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(StoryDrifts::Table)
+                    .if_not_exists()
+                    .col(pk_auto(StoryDrifts::Id))
+                    .col(string(StoryDrifts::RunId))
+                    .col(string(StoryDrifts::Story))
+                    .col(string(StoryDrifts::LoadCase))
+                    .col(string(StoryDrifts::Direction))
+                    .col(double(StoryDrifts::Drift))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModalPeriods::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ModalPeriods::Id))
+                    .col(string(ModalPeriods::RunId))
+                    .col(string(ModalPeriods::LoadCase))
+                    .col(integer(ModalPeriods::Mode))
+                    .col(double(ModalPeriods::Period))
+                    .col(double(ModalPeriods::Ux))
+                    .col(double(ModalPeriods::Uy))
+                    .col(double(ModalPeriods::Rz))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(BaseReactions::Table)
+                    .if_not_exists()
+                    .col(pk_auto(BaseReactions::Id))
+                    .col(string(BaseReactions::RunId))
+                    .col(string(BaseReactions::LoadCase))
+                    .col(double(BaseReactions::Fx))
+                    .col(double(BaseReactions::Fy))
+                    .col(double(BaseReactions::Fz))
+                    .col(double(BaseReactions::Mx))
+                    .col(double(BaseReactions::My))
+                    .col(double(BaseReactions::Mz))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_story_drifts_run_id")
+                    .table(StoryDrifts::Table)
+                    .col(StoryDrifts::RunId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_modal_periods_run_id")
+                    .table(ModalPeriods::Table)
+                    .col(ModalPeriods::RunId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_base_reactions_run_id")
+                    .table(BaseReactions::Table)
+                    .col(BaseReactions::RunId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BaseReactions::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ModalPeriods::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(StoryDrifts::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(AnalysisRuns::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AnalysisRuns {
+    Table,
+    Id,
+    ProjectId,
+    Label,
+    ImportedAt,
+}
+
+#[derive(DeriveIden)]
+enum StoryDrifts {
+    Table,
+    Id,
+    RunId,
+    Story,
+    LoadCase,
+    Direction,
+    Drift,
+}
+
+#[derive(DeriveIden)]
+enum ModalPeriods {
+    Table,
+    Id,
+    RunId,
+    LoadCase,
+    Mode,
+    Period,
+    Ux,
+    Uy,
+    Rz,
+}
+
+#[derive(DeriveIden)]
+enum BaseReactions {
+    Table,
+    Id,
+    RunId,
+    LoadCase,
+    Fx,
+    Fy,
+    Fz,
+    Mx,
+    My,
+    Mz,
+}
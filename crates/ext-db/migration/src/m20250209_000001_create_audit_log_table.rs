@@ -0,0 +1,52 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditLog::Table)
+                    .if_not_exists()
+                    .col(string(AuditLog::Id).primary_key())
+                    .col(string(AuditLog::ProjectId))
+                    .col(string(AuditLog::Action))
+                    .col(string(AuditLog::Detail))
+                    .col(timestamp_with_time_zone(AuditLog::Timestamp))
+                    .to_owned(),
+            )
+            .await?;
+
+        // The log is always read one project at a time, newest first
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_log_project_timestamp")
+                    .table(AuditLog::Table)
+                    .col(AuditLog::ProjectId)
+                    .col(AuditLog::Timestamp)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AuditLog::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AuditLog {
+    Table,
+    Id,
+    ProjectId,
+    Action,
+    Detail,
+    Timestamp,
+}
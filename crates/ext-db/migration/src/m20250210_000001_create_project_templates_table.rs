@@ -0,0 +1,42 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Only user templates are stored; built-in ones ship with the app
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProjectTemplates::Table)
+                    .if_not_exists()
+                    .col(string(ProjectTemplates::Id).primary_key())
+                    .col(string(ProjectTemplates::Name))
+                    .col(string(ProjectTemplates::Description))
+                    .col(json(ProjectTemplates::DefaultTags))
+                    .col(json(ProjectTemplates::DefaultLoadCases))
+                    .col(json(ProjectTemplates::DefaultSettings))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProjectTemplates::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ProjectTemplates {
+    Table,
+    Id,
+    Name,
+    Description,
+    DefaultTags,
+    DefaultLoadCases,
+    DefaultSettings,
+}
@@ -0,0 +1,92 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Notes::Table)
+                    .if_not_exists()
+                    .col(string(Notes::Id).primary_key())
+                    .col(string(Notes::ProjectId))
+                    .col(string(Notes::Title))
+                    .col(text(Notes::BodyMarkdown))
+                    .col(timestamp_with_time_zone(Notes::CreatedAt))
+                    .col(timestamp_with_time_zone(Notes::UpdatedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notes_project_id")
+                    .table(Notes::Table)
+                    .col(Notes::ProjectId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        // One row per edit, holding the version the edit replaced
+        manager
+            .create_table(
+                Table::create()
+                    .table(NoteRevisions::Table)
+                    .if_not_exists()
+                    .col(string(NoteRevisions::Id).primary_key())
+                    .col(string(NoteRevisions::NoteId))
+                    .col(string(NoteRevisions::Title))
+                    .col(text(NoteRevisions::BodyMarkdown))
+                    .col(timestamp_with_time_zone(NoteRevisions::SavedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_note_revisions_note_saved_at")
+                    .table(NoteRevisions::Table)
+                    .col(NoteRevisions::NoteId)
+                    .col(NoteRevisions::SavedAt)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(NoteRevisions::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Notes::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Notes {
+    Table,
+    Id,
+    ProjectId,
+    Title,
+    BodyMarkdown,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum NoteRevisions {
+    Table,
+    Id,
+    NoteId,
+    Title,
+    BodyMarkdown,
+    SavedAt,
+}
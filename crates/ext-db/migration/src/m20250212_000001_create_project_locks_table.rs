@@ -0,0 +1,40 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // At most one lock per project, mirrored by the lock file in its folder
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProjectLocks::Table)
+                    .if_not_exists()
+                    .col(string(ProjectLocks::ProjectId).primary_key())
+                    .col(string(ProjectLocks::Holder))
+                    .col(big_integer(ProjectLocks::Pid))
+                    .col(string(ProjectLocks::Host))
+                    .col(timestamp_with_time_zone(ProjectLocks::AcquiredAt))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProjectLocks::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ProjectLocks {
+    Table,
+    ProjectId,
+    Holder,
+    Pid,
+    Host,
+    AcquiredAt,
+}
@@ -0,0 +1,74 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(FrameForces::Table)
+                    .if_not_exists()
+                    .col(pk_auto(FrameForces::Id))
+                    .col(string(FrameForces::RunId))
+                    .col(string(FrameForces::Story))
+                    .col(string(FrameForces::Frame))
+                    .col(string(FrameForces::LoadCase))
+                    .col(double(FrameForces::Station))
+                    .col(double(FrameForces::P))
+                    .col(double(FrameForces::V2))
+                    .col(double(FrameForces::V3))
+                    .col(double(FrameForces::T))
+                    .col(double(FrameForces::M2))
+                    .col(double(FrameForces::M3))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Pages are filtered by story, load case or frame within a run
+        for (name, column) in [
+            ("idx_frame_forces_run_story", FrameForces::Story),
+            ("idx_frame_forces_run_load_case", FrameForces::LoadCase),
+            ("idx_frame_forces_run_frame", FrameForces::Frame),
+        ] {
+            manager
+                .create_index(
+                    Index::create()
+                        .name(name)
+                        .table(FrameForces::Table)
+                        .col(FrameForces::RunId)
+                        .col(column)
+                        .if_not_exists()
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(FrameForces::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum FrameForces {
+    Table,
+    Id,
+    RunId,
+    Story,
+    Frame,
+    LoadCase,
+    Station,
+    P,
+    V2,
+    V3,
+    T,
+    M2,
+    M3,
+}
@@ -0,0 +1,313 @@
+//! Portable `.etabx` project archives.
+//!
+//! An archive is a zip of the project folder under `project/`, plus a
+//! `manifest.json` with the format version and the attached-file metadata,
+//! notes and analysis runs that live in the database rather than on disk.
+//! The results of each run are stored under `results/<run id>.json`;
+//! archives written before runs were exported simply have none.
+
+use crate::locks::LOCK_FILE;
+use crate::paths::{fs_error, long_path};
+use crate::FILES_DIR;
+use chrono::{DateTime, Utc};
+use ext_core::frame_forces::FrameForce;
+use ext_core::notes::Note;
+use ext_core::results::{AnalysisResults, AnalysisRun};
+use ext_core::ProjectFile;
+use ext_error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Component, Path};
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Bumped whenever the archive layout changes incompatibly
+pub const ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+/// Conventional extension for project archives
+pub const ARCHIVE_EXTENSION: &str = "etabx";
+
+const MANIFEST_NAME: &str = "manifest.json";
+
+/// Folder inside the archive that holds the project directory
+const PROJECT_PREFIX: &str = "project";
+
+/// Folder inside the archive that holds the results of each run
+const RESULTS_PREFIX: &str = "results";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub format_version: u32,
+    pub project_id: Uuid,
+    pub exported_at: DateTime<Utc>,
+    #[serde(default)]
+    pub files: Vec<ProjectFile>,
+    #[serde(default)]
+    pub notes: Vec<Note>,
+    #[serde(default)]
+    pub runs: Vec<AnalysisRun>,
+}
+
+/// Results of one analysis run in the archive
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArchivedResults {
+    pub results: AnalysisResults,
+    pub frame_forces: Vec<FrameForce>,
+}
+
+/// Zips `project_dir` into `dest`, with `results` holding the results of
+/// each of `manifest.runs` in the same order. The archive is written next
+/// to `dest` under a temporary name first, so a failed export never leaves
+/// a truncated archive behind.
+pub fn write_archive(
+    project_dir: &Path,
+    manifest: &ArchiveManifest,
+    results: &[ArchivedResults],
+    dest: &Path,
+) -> Result<()> {
+    if results.len() != manifest.runs.len() {
+        return Err(AppError::Internal(format!(
+            "{} runs in the manifest but {} result sets",
+            manifest.runs.len(),
+            results.len()
+        )));
+    }
+
+    let mut partial = dest.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = Path::new(&partial);
+
+    let written = (|| -> zip::result::ZipResult<()> {
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        let mut zip = ZipWriter::new(File::create(long_path(partial))?);
+
+        zip.start_file(MANIFEST_NAME, options)?;
+        zip.write_all(&serde_json::to_vec_pretty(manifest).map_err(io::Error::other)?)?;
+
+        add_directory(&mut zip, project_dir, PROJECT_PREFIX, options)?;
+        for (run, results) in manifest.runs.iter().zip(results) {
+            zip.start_file(results_entry(run), options)?;
+            serde_json::to_writer(&mut zip, results).map_err(io::Error::other)?;
+        }
+        zip.finish()?;
+        Ok(())
+    })();
+
+    if let Err(e) = written {
+        let _ = fs::remove_file(long_path(partial));
+        return Err(AppError::FileSystem(format!(
+            "Failed to write archive '{}': {e}",
+            dest.display()
+        )));
+    }
+
+    fs::rename(long_path(partial), long_path(dest)).map_err(|e| fs_error("write archive", dest, e))
+}
+
+fn add_directory(
+    zip: &mut ZipWriter<File>,
+    dir: &Path,
+    prefix: &str,
+    options: SimpleFileOptions,
+) -> zip::result::ZipResult<()> {
+    let mut entries = fs::read_dir(long_path(dir))?.collect::<io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        // The lock belongs to whoever has the project open, not the project
+        if prefix == PROJECT_PREFIX && entry.file_name() == LOCK_FILE {
+            continue;
+        }
+        let name = format!("{prefix}/{}", entry.file_name().to_string_lossy());
+        if entry.file_type()?.is_dir() {
+            zip.add_directory(name.as_str(), options)?;
+            add_directory(zip, &entry.path(), &name, options)?;
+        } else {
+            zip.start_file(name.as_str(), options)?;
+            io::copy(&mut File::open(entry.path())?, zip)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `stored_path` names a file directly inside the project's files
+/// folder
+fn is_stored_file_path(stored_path: &str) -> bool {
+    let mut components = Path::new(stored_path).components();
+    matches!(
+        (components.next(), components.next(), components.next()),
+        (Some(Component::Normal(dir)), Some(Component::Normal(_)), None) if dir == FILES_DIR
+    )
+}
+
+fn results_entry(run: &AnalysisRun) -> String {
+    format!("{RESULTS_PREFIX}/{}.json", run.id)
+}
+
+/// Unpacks the project folder from `archive` into `dest`, which must not
+/// exist yet, and reads the results of each of the manifest's runs in the
+/// same order. On error `dest` may be partly written; the caller removes
+/// it.
+pub fn extract_archive(
+    archive: &Path,
+    dest: &Path,
+) -> Result<(ArchiveManifest, Vec<ArchivedResults>)> {
+    let corrupt = |reason: &dyn Display| {
+        AppError::Validation(format!(
+            "'{}' is not a valid project archive: {reason}",
+            archive.display()
+        ))
+    };
+
+    let file = File::open(long_path(archive)).map_err(|e| fs_error("open archive", archive, e))?;
+    let mut zip = ZipArchive::new(file).map_err(|e| corrupt(&e))?;
+
+    let manifest: ArchiveManifest = {
+        let entry = zip.by_name(MANIFEST_NAME).map_err(|e| corrupt(&e))?;
+        serde_json::from_reader(entry).map_err(|e| corrupt(&e))?
+    };
+    if manifest.format_version > ARCHIVE_FORMAT_VERSION {
+        return Err(AppError::Validation(format!(
+            "Archive format version {} is newer than this version of the app supports ({})",
+            manifest.format_version, ARCHIVE_FORMAT_VERSION
+        )));
+    }
+    // The paths are joined onto the project folder and later renamed and
+    // deleted, so they must not reach into another project
+    if let Some(file) = manifest
+        .files
+        .iter()
+        .find(|f| !is_stored_file_path(&f.stored_path))
+    {
+        return Err(corrupt(&format!("unsafe file path '{}'", file.stored_path)));
+    }
+
+    fs::create_dir_all(long_path(dest)).map_err(|e| fs_error("create import directory", dest, e))?;
+
+    for index in 0..zip.len() {
+        let mut entry = zip.by_index(index).map_err(|e| corrupt(&e))?;
+
+        // Rejects absolute paths and `..` so entries cannot escape `dest`
+        let Some(path) = entry.enclosed_name() else {
+            return Err(corrupt(&format!("unsafe entry path '{}'", entry.name())));
+        };
+        let Ok(relative) = path.strip_prefix(PROJECT_PREFIX) else {
+            continue;
+        };
+        let target = dest.join(relative);
+
+        if entry.is_dir() {
+            fs::create_dir_all(long_path(&target))
+                .map_err(|e| fs_error("create directory", &target, e))?;
+            continue;
+        }
+
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(long_path(parent))
+                .map_err(|e| fs_error("create directory", parent, e))?;
+        }
+        let mut out =
+            File::create(long_path(&target)).map_err(|e| fs_error("extract file", &target, e))?;
+        // Truncated or damaged entries fail here with a CRC or EOF error
+        io::copy(&mut entry, &mut out).map_err(|e| corrupt(&e))?;
+    }
+
+    let mut results = Vec::with_capacity(manifest.runs.len());
+    for run in &manifest.runs {
+        let entry = zip.by_name(&results_entry(run)).map_err(|e| corrupt(&e))?;
+        results.push(serde_json::from_reader(entry).map_err(|e| corrupt(&e))?);
+    }
+
+    Ok((manifest, results))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> ArchiveManifest {
+        ArchiveManifest {
+            format_version: ARCHIVE_FORMAT_VERSION,
+            project_id: Uuid::new_v4(),
+            exported_at: Utc::now(),
+            files: Vec::new(),
+            notes: Vec::new(),
+            runs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_write_and_extract_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("source");
+        fs::create_dir_all(source.join("files")).unwrap();
+        fs::write(source.join("project.json"), "{}").unwrap();
+        fs::write(source.join("files").join("model.e2k"), "$ STORIES").unwrap();
+
+        let archive = temp.path().join("tower.etabx");
+        let mut written = manifest();
+        written.runs.push(AnalysisRun::new(written.project_id, "Rev A".to_string()));
+        let frame_force = FrameForce {
+            story: "Story1".to_string(),
+            frame: "C1".to_string(),
+            load_case: "Dead".to_string(),
+            station: 0.0,
+            p: -1000.0,
+            v2: 0.0,
+            v3: 0.0,
+            t: 0.0,
+            m2: 0.0,
+            m3: 0.0,
+        };
+        let results = ArchivedResults {
+            results: AnalysisResults::default(),
+            frame_forces: vec![frame_force],
+        };
+        write_archive(&source, &written, std::slice::from_ref(&results), &archive).unwrap();
+
+        let dest = temp.path().join("dest");
+        let (read, read_results) = extract_archive(&archive, &dest).unwrap();
+        assert_eq!(read.project_id, written.project_id);
+        assert_eq!(read.runs, written.runs);
+        assert_eq!(read_results[0].frame_forces, results.frame_forces);
+        assert_eq!(fs::read_to_string(dest.join("files").join("model.e2k")).unwrap(), "$ STORIES");
+        assert!(!dest.join(RESULTS_PREFIX).exists());
+        assert!(!temp.path().join("tower.etabx.partial").exists());
+    }
+
+    #[test]
+    fn test_truncated_archive_is_validation_error() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("source");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("project.json"), "{}".repeat(1000)).unwrap();
+
+        let archive = temp.path().join("tower.etabx");
+        write_archive(&source, &manifest(), &[], &archive).unwrap();
+        let bytes = fs::read(&archive).unwrap();
+        fs::write(&archive, &bytes[..bytes.len() / 2]).unwrap();
+
+        let result = extract_archive(&archive, &temp.path().join("dest"));
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_newer_format_version_is_rejected() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("source");
+        fs::create_dir_all(&source).unwrap();
+
+        let mut newer = manifest();
+        newer.format_version = ARCHIVE_FORMAT_VERSION + 1;
+        let archive = temp.path().join("tower.etabx");
+        write_archive(&source, &newer, &[], &archive).unwrap();
+
+        let result = extract_archive(&archive, &temp.path().join("dest"));
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+}
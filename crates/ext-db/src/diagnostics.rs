@@ -0,0 +1,232 @@
+//! Storage diagnostics for support requests. Gathering them only reads;
+//! nothing changes unless [`Database::repair_storage`] is called.
+
+use crate::entities::prelude::*;
+use crate::entities::project_file;
+use crate::paths::{fs_error, long_path};
+use crate::storage::free_space;
+use crate::{hash_file, Database, FILES_DIR, REMOVING_SUFFIX};
+use chrono::Utc;
+use ext_core::audit::{AuditAction, AuditEntry};
+use ext_core::diagnostics::{Diagnostics, OrphanedRowAction, RepairReport};
+use ext_core::duplicates::next_available_name;
+use ext_core::{Project, ProjectFile, ProjectStatus};
+use ext_error::{AppError, Result};
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, ConnectionTrait, DbBackend, EntityTrait, QueryFilter, Statement};
+use std::collections::HashSet;
+use std::path::Path;
+use tokio::fs;
+use tracing::instrument;
+use uuid::Uuid;
+
+impl Database {
+    #[instrument(level = "debug", skip_all)]
+    pub async fn diagnostics(&self) -> Result<Diagnostics> {
+        let database_path = self.database_path().await?;
+        let database_size = match &database_path {
+            Some(path) => fs::metadata(long_path(Path::new(path)))
+                .await
+                .map(|metadata| metadata.len())
+                .unwrap_or(0),
+            None => 0,
+        };
+
+        let projects = self.list_projects().await?;
+        let (orphaned_folders, orphaned_rows) = self.find_orphans(&projects).await?;
+
+        Ok(Diagnostics {
+            database_path,
+            database_size,
+            integrity_check: self.integrity_check().await?,
+            projects_dir: self.projects_dir.to_string_lossy().to_string(),
+            free_space: free_space(&self.projects_dir),
+            project_count: projects.len() as u64,
+            orphaned_folders,
+            orphaned_rows,
+            log_dir: None,
+        })
+    }
+
+    /// File behind the main SQLite database; `None` when it is in memory.
+    async fn database_path(&self) -> Result<Option<String>> {
+        let row = self
+            .db
+            .query_one(Statement::from_string(DbBackend::Sqlite, "PRAGMA database_list"))
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to read database path: {e}")))?;
+
+        let file: Option<String> = row.and_then(|row| row.try_get("", "file").ok());
+        Ok(file.filter(|file| !file.is_empty()))
+    }
+
+    async fn integrity_check(&self) -> Result<String> {
+        let problems: Vec<String> = self
+            .db
+            .query_all(Statement::from_string(DbBackend::Sqlite, "PRAGMA integrity_check"))
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to check database integrity: {e}")))?
+            .iter()
+            .filter_map(|row| row.try_get::<String>("", "integrity_check").ok())
+            .collect();
+        Ok(problems.join("; "))
+    }
+
+    /// Project folders without a row, and projects without a folder.
+    async fn find_orphans(&self, projects: &[Project]) -> Result<(Vec<Uuid>, Vec<Uuid>)> {
+        let registered: HashSet<Uuid> = projects.iter().map(|project| project.id).collect();
+
+        let folders = self
+            .project_folders()
+            .await?
+            .into_iter()
+            .filter(|id| !registered.contains(id))
+            .collect();
+
+        let rows = projects
+            .iter()
+            .filter(|project| !long_path(&self.projects_dir.join(project.id.to_string())).is_dir())
+            .map(|project| project.id)
+            .collect();
+
+        Ok((folders, rows))
+    }
+
+    /// Registers orphaned folders again from their project.json, and moves
+    /// projects whose folder is missing to the trash or removes them.
+    ///
+    /// Trashing rewrites the folder's project.json from the registry and
+    /// flags the project's attached files as missing.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn repair_storage(&self, orphaned_rows: OrphanedRowAction) -> Result<RepairReport> {
+        self.ensure_writable()?;
+
+        let projects = self.list_projects().await?;
+        let (folders, rows) = self.find_orphans(&projects).await?;
+        let mut names: Vec<String> = projects.iter().map(|project| project.name.clone()).collect();
+        let mut report = RepairReport::default();
+
+        for id in folders {
+            match self.register_orphaned_folder(id, &names).await {
+                Ok(project) => {
+                    names.push(project.name);
+                    report.registered.push(id);
+                }
+                Err(e) => report.skipped.push(format!("{id}: {e}")),
+            }
+        }
+
+        for project in projects.into_iter().filter(|project| rows.contains(&project.id)) {
+            let project_id = project.id.to_string();
+            match orphaned_rows {
+                OrphanedRowAction::Remove => {
+                    self.delete_project(&project_id).await?;
+                    report.removed.push(project.id);
+                }
+                OrphanedRowAction::Trash => {
+                    ProjectFileEntity::update_many()
+                        .col_expr(project_file::Column::Missing, Expr::value(true))
+                        .filter(project_file::Column::ProjectId.eq(&project_id))
+                        .exec(&self.db)
+                        .await
+                        .map_err(|e| {
+                            AppError::Database(format!("Failed to flag project files: {e}"))
+                        })?;
+
+                    let mut trashed = project;
+                    trashed.set_status(ProjectStatus::Trashed);
+                    let entry = AuditEntry::new(
+                        trashed.id,
+                        AuditAction::Trashed,
+                        "Moved to trash by storage repair: project folder was missing".to_string(),
+                    );
+                    self.save_project_with_audit(&trashed, Some(&entry)).await?;
+                    report.trashed.push(trashed.id);
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn register_orphaned_folder(&self, id: Uuid, names: &[String]) -> Result<Project> {
+        let project_dir = self.projects_dir.join(id.to_string());
+        let metadata_path = project_dir.join("project.json");
+        let content = fs::read_to_string(long_path(&metadata_path))
+            .await
+            .map_err(|e| fs_error("read project file", &metadata_path, e))?;
+        let mut project: Project = serde_json::from_str(&content)
+            .map_err(|e| AppError::Validation(format!("Invalid project.json: {e}")))?;
+        if project.id != id {
+            return Err(AppError::Validation(format!(
+                "project.json belongs to project {}",
+                project.id
+            )));
+        }
+
+        project.name = next_available_name(&project.name, names);
+        let entry = AuditEntry::new(
+            id,
+            AuditAction::Imported,
+            "Registered again from its folder by storage repair".to_string(),
+        );
+        self.save_project_with_audit(&project, Some(&entry)).await?;
+        self.register_stored_files(&project, &project_dir).await?;
+
+        Ok(project)
+    }
+
+    /// Records stored copies in `files/` that have no row, recovering the
+    /// file id and original name from the `{id}_{name}` file name.
+    async fn register_stored_files(&self, project: &Project, project_dir: &Path) -> Result<()> {
+        let files_dir = project_dir.join(FILES_DIR);
+        let Ok(mut entries) = fs::read_dir(long_path(&files_dir)).await else {
+            return Ok(());
+        };
+
+        let known: HashSet<String> = ProjectFileEntity::find()
+            .filter(project_file::Column::ProjectId.eq(project.id.to_string()))
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to list project files: {e}")))?
+            .into_iter()
+            .map(|row| row.stored_path)
+            .collect();
+
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| fs_error("read files directory", &files_dir, e))?
+        {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let stored_path = format!("{FILES_DIR}/{name}");
+            if name.ends_with(REMOVING_SUFFIX) || known.contains(&stored_path) {
+                continue;
+            }
+            let Some((id, original_name)) = name.split_once('_') else {
+                continue;
+            };
+            let Ok(id) = Uuid::parse_str(id) else {
+                continue;
+            };
+
+            let (size, sha256) = hash_file(&entry.path()).await?;
+            let file = ProjectFile {
+                id,
+                project_id: project.id,
+                original_name: original_name.to_string(),
+                stored_path,
+                size,
+                sha256,
+                added_at: Utc::now(),
+                missing: false,
+            };
+            ProjectFileEntity::insert(project_file::ActiveModel::from(&file))
+                .exec(&self.db)
+                .await
+                .map_err(|e| AppError::Database(format!("Failed to record project file: {e}")))?;
+        }
+
+        Ok(())
+    }
+}
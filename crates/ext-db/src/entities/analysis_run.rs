@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use ext_core::results::AnalysisRun;
+use ext_error::{AppError, Result};
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "analysis_runs")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub project_id: String,
+    pub label: String,
+    pub imported_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl TryFrom<Model> for AnalysisRun {
+    type Error = AppError;
+
+    fn try_from(model: Model) -> Result<Self> {
+        let parse = |value: &str| {
+            Uuid::parse_str(value).map_err(|e| {
+                AppError::Database(format!("Invalid id '{}' in analysis_runs: {}", value, e))
+            })
+        };
+
+        Ok(AnalysisRun {
+            id: parse(&model.id)?,
+            project_id: parse(&model.project_id)?,
+            label: model.label,
+            imported_at: model.imported_at,
+        })
+    }
+}
+
+impl From<&AnalysisRun> for ActiveModel {
+    fn from(run: &AnalysisRun) -> Self {
+        ActiveModel {
+            id: Set(run.id.to_string()),
+            project_id: Set(run.project_id.to_string()),
+            label: Set(run.label.clone()),
+            imported_at: Set(run.imported_at),
+        }
+    }
+}
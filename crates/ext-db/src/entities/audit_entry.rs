@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use ext_core::audit::{AuditAction, AuditEntry};
+use ext_error::{AppError, Result};
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "audit_log")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub project_id: String,
+    pub action: String,
+    pub detail: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl TryFrom<Model> for AuditEntry {
+    type Error = AppError;
+
+    fn try_from(model: Model) -> Result<Self> {
+        let parse = |value: &str| {
+            Uuid::parse_str(value).map_err(|e| {
+                AppError::Database(format!("Invalid id '{value}' in audit_log: {e}"))
+            })
+        };
+
+        Ok(AuditEntry {
+            id: parse(&model.id)?,
+            project_id: parse(&model.project_id)?,
+            action: AuditAction::parse(&model.action)?,
+            detail: model.detail,
+            timestamp: model.timestamp,
+        })
+    }
+}
+
+impl From<&AuditEntry> for ActiveModel {
+    fn from(entry: &AuditEntry) -> Self {
+        ActiveModel {
+            id: Set(entry.id.to_string()),
+            project_id: Set(entry.project_id.to_string()),
+            action: Set(entry.action.as_str().to_string()),
+            detail: Set(entry.detail.clone()),
+            timestamp: Set(entry.timestamp),
+        }
+    }
+}
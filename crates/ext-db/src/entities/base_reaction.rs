@@ -0,0 +1,54 @@
+use ext_core::results::BaseReaction;
+use sea_orm::entity::prelude::*;
+use sea_orm::{NotSet, Set};
+
+/// One load case of a run's base reaction table
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "base_reactions")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub run_id: String,
+    pub load_case: String,
+    pub fx: f64,
+    pub fy: f64,
+    pub fz: f64,
+    pub mx: f64,
+    pub my: f64,
+    pub mz: f64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl From<Model> for BaseReaction {
+    fn from(model: Model) -> Self {
+        BaseReaction {
+            load_case: model.load_case,
+            fx: model.fx,
+            fy: model.fy,
+            fz: model.fz,
+            mx: model.mx,
+            my: model.my,
+            mz: model.mz,
+        }
+    }
+}
+
+impl ActiveModel {
+    pub fn for_run(run_id: &str, reaction: &BaseReaction) -> Self {
+        ActiveModel {
+            id: NotSet,
+            run_id: Set(run_id.to_string()),
+            load_case: Set(reaction.load_case.clone()),
+            fx: Set(reaction.fx),
+            fy: Set(reaction.fy),
+            fz: Set(reaction.fz),
+            mx: Set(reaction.mx),
+            my: Set(reaction.my),
+            mz: Set(reaction.mz),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use ext_core::frame_forces::FrameForce;
+use sea_orm::entity::prelude::*;
+use sea_orm::{NotSet, Set};
+
+/// One output station of a run's frame force table
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "frame_forces")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub run_id: String,
+    pub story: String,
+    pub frame: String,
+    pub load_case: String,
+    pub station: f64,
+    pub p: f64,
+    pub v2: f64,
+    pub v3: f64,
+    pub t: f64,
+    pub m2: f64,
+    pub m3: f64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl From<Model> for FrameForce {
+    fn from(model: Model) -> Self {
+        FrameForce {
+            story: model.story,
+            frame: model.frame,
+            load_case: model.load_case,
+            station: model.station,
+            p: model.p,
+            v2: model.v2,
+            v3: model.v3,
+            t: model.t,
+            m2: model.m2,
+            m3: model.m3,
+        }
+    }
+}
+
+impl ActiveModel {
+    pub fn for_run(run_id: &str, force: &FrameForce) -> Self {
+        ActiveModel {
+            id: NotSet,
+            run_id: Set(run_id.to_string()),
+            story: Set(force.story.clone()),
+            frame: Set(force.frame.clone()),
+            load_case: Set(force.load_case.clone()),
+            station: Set(force.station),
+            p: Set(force.p),
+            v2: Set(force.v2),
+            v3: Set(force.v3),
+            t: Set(force.t),
+            m2: Set(force.m2),
+            m3: Set(force.m3),
+        }
+    }
+}
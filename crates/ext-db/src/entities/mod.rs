@@ -0,0 +1,18 @@
+//! SeaORM entities backing the SQLite project registry.
+
+pub mod prelude;
+
+pub mod analysis_run;
+pub mod audit_entry;
+pub mod base_reaction;
+pub mod frame_force;
+pub mod modal_period;
+pub mod note;
+pub mod note_revision;
+pub mod project;
+pub mod project_file;
+pub mod project_lock;
+pub mod project_tag;
+pub mod project_template;
+pub mod story_drift;
+pub mod tag;
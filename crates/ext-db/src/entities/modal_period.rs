@@ -0,0 +1,51 @@
+use ext_core::results::ModalPeriod;
+use sea_orm::entity::prelude::*;
+use sea_orm::{NotSet, Set};
+
+/// One mode of a run's modal table
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "modal_periods")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub run_id: String,
+    pub load_case: String,
+    pub mode: i32,
+    pub period: f64,
+    pub ux: f64,
+    pub uy: f64,
+    pub rz: f64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl From<Model> for ModalPeriod {
+    fn from(model: Model) -> Self {
+        ModalPeriod {
+            load_case: model.load_case,
+            mode: model.mode.max(0) as u32,
+            period: model.period,
+            ux: model.ux,
+            uy: model.uy,
+            rz: model.rz,
+        }
+    }
+}
+
+impl ActiveModel {
+    pub fn for_run(run_id: &str, period: &ModalPeriod) -> Self {
+        ActiveModel {
+            id: NotSet,
+            run_id: Set(run_id.to_string()),
+            load_case: Set(period.load_case.clone()),
+            mode: Set(period.mode as i32),
+            period: Set(period.period),
+            ux: Set(period.ux),
+            uy: Set(period.uy),
+            rz: Set(period.rz),
+        }
+    }
+}
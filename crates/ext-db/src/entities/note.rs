@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use ext_core::notes::Note;
+use ext_error::{AppError, Result};
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "notes")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub project_id: String,
+    pub title: String,
+    pub body_markdown: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl TryFrom<Model> for Note {
+    type Error = AppError;
+
+    fn try_from(model: Model) -> Result<Self> {
+        let parse = |value: &str| {
+            Uuid::parse_str(value)
+                .map_err(|e| AppError::Database(format!("Invalid id '{value}' in notes: {e}")))
+        };
+
+        Ok(Note {
+            id: parse(&model.id)?,
+            project_id: parse(&model.project_id)?,
+            title: model.title,
+            body_markdown: model.body_markdown,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        })
+    }
+}
+
+impl From<&Note> for ActiveModel {
+    fn from(note: &Note) -> Self {
+        ActiveModel {
+            id: Set(note.id.to_string()),
+            project_id: Set(note.project_id.to_string()),
+            title: Set(note.title.clone()),
+            body_markdown: Set(note.body_markdown.clone()),
+            created_at: Set(note.created_at),
+            updated_at: Set(note.updated_at),
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use ext_core::notes::NoteRevision;
+use ext_error::{AppError, Result};
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "note_revisions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub note_id: String,
+    pub title: String,
+    pub body_markdown: String,
+    pub saved_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl TryFrom<Model> for NoteRevision {
+    type Error = AppError;
+
+    fn try_from(model: Model) -> Result<Self> {
+        let parse = |value: &str| {
+            Uuid::parse_str(value).map_err(|e| {
+                AppError::Database(format!("Invalid id '{value}' in note_revisions: {e}"))
+            })
+        };
+
+        Ok(NoteRevision {
+            id: parse(&model.id)?,
+            note_id: parse(&model.note_id)?,
+            title: model.title,
+            body_markdown: model.body_markdown,
+            saved_at: model.saved_at,
+        })
+    }
+}
+
+impl From<&NoteRevision> for ActiveModel {
+    fn from(revision: &NoteRevision) -> Self {
+        ActiveModel {
+            id: Set(revision.id.to_string()),
+            note_id: Set(revision.note_id.to_string()),
+            title: Set(revision.title.clone()),
+            body_markdown: Set(revision.body_markdown.clone()),
+            saved_at: Set(revision.saved_at),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+pub use super::analysis_run::Entity as AnalysisRunEntity;
+pub use super::audit_entry::Entity as AuditEntryEntity;
+pub use super::base_reaction::Entity as BaseReactionEntity;
+pub use super::frame_force::Entity as FrameForceEntity;
+pub use super::modal_period::Entity as ModalPeriodEntity;
+pub use super::note::Entity as NoteEntity;
+pub use super::note_revision::Entity as NoteRevisionEntity;
+pub use super::project::Entity as ProjectEntity;
+pub use super::project_file::Entity as ProjectFileEntity;
+pub use super::project_lock::Entity as ProjectLockEntity;
+pub use super::project_tag::Entity as ProjectTagEntity;
+pub use super::project_template::Entity as ProjectTemplateEntity;
+pub use super::story_drift::Entity as StoryDriftEntity;
+pub use super::tag::Entity as TagEntity;
@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use ext_core::{Project, ProjectStatus};
+use ext_error::{AppError, Result};
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "projects")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub name: String,
+    #[sea_orm(column_type = "Text")]
+    pub description: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub status: String,
+    pub trashed_at: Option<DateTime<Utc>>,
+    pub last_opened_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl TryFrom<Model> for Project {
+    type Error = AppError;
+
+    fn try_from(model: Model) -> Result<Self> {
+        let id = Uuid::parse_str(&model.id).map_err(|e| {
+            AppError::Database(format!("Invalid project id '{}' in database: {}", model.id, e))
+        })?;
+
+        Ok(Project {
+            id,
+            name: model.name,
+            description: model.description,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+            status: ProjectStatus::parse(&model.status)?,
+            trashed_at: model.trashed_at,
+            last_opened_at: model.last_opened_at,
+        })
+    }
+}
+
+impl From<&Project> for ActiveModel {
+    fn from(project: &Project) -> Self {
+        ActiveModel {
+            id: Set(project.id.to_string()),
+            name: Set(project.name.clone()),
+            description: Set(project.description.clone()),
+            created_at: Set(project.created_at),
+            updated_at: Set(project.updated_at),
+            status: Set(project.status.as_str().to_string()),
+            trashed_at: Set(project.trashed_at),
+            last_opened_at: Set(project.last_opened_at),
+        }
+    }
+}
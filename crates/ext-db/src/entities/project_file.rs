@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use ext_core::ProjectFile;
+use ext_error::{AppError, Result};
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "project_files")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub project_id: String,
+    pub original_name: String,
+    pub stored_path: String,
+    pub size: i64,
+    pub sha256: String,
+    pub added_at: DateTime<Utc>,
+    pub missing: bool,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl TryFrom<Model> for ProjectFile {
+    type Error = AppError;
+
+    fn try_from(model: Model) -> Result<Self> {
+        let parse = |value: &str| {
+            Uuid::parse_str(value).map_err(|e| {
+                AppError::Database(format!("Invalid id '{}' in project_files: {}", value, e))
+            })
+        };
+
+        Ok(ProjectFile {
+            id: parse(&model.id)?,
+            project_id: parse(&model.project_id)?,
+            original_name: model.original_name,
+            stored_path: model.stored_path,
+            size: model.size.max(0) as u64,
+            sha256: model.sha256,
+            added_at: model.added_at,
+            missing: model.missing,
+        })
+    }
+}
+
+impl From<&ProjectFile> for ActiveModel {
+    fn from(file: &ProjectFile) -> Self {
+        ActiveModel {
+            id: Set(file.id.to_string()),
+            project_id: Set(file.project_id.to_string()),
+            original_name: Set(file.original_name.clone()),
+            stored_path: Set(file.stored_path.clone()),
+            size: Set(file.size as i64),
+            sha256: Set(file.sha256.clone()),
+            added_at: Set(file.added_at),
+            missing: Set(file.missing),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use ext_core::project_lock::ProjectLock;
+use ext_error::{AppError, Result};
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "project_locks")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub project_id: String,
+    pub holder: String,
+    pub pid: i64,
+    pub host: String,
+    pub acquired_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl TryFrom<Model> for ProjectLock {
+    type Error = AppError;
+
+    fn try_from(model: Model) -> Result<Self> {
+        let project_id = Uuid::parse_str(&model.project_id).map_err(|e| {
+            AppError::Database(format!(
+                "Invalid id '{}' in project_locks: {e}",
+                model.project_id
+            ))
+        })?;
+        let pid = u32::try_from(model.pid).map_err(|_| {
+            AppError::Database(format!("Invalid pid {} in project_locks", model.pid))
+        })?;
+
+        Ok(ProjectLock {
+            project_id,
+            holder: model.holder,
+            pid,
+            host: model.host,
+            acquired_at: model.acquired_at,
+        })
+    }
+}
+
+impl From<&ProjectLock> for ActiveModel {
+    fn from(lock: &ProjectLock) -> Self {
+        ActiveModel {
+            project_id: Set(lock.project_id.to_string()),
+            holder: Set(lock.holder.clone()),
+            pid: Set(i64::from(lock.pid)),
+            host: Set(lock.host.clone()),
+            acquired_at: Set(lock.acquired_at),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use ext_core::templates::ProjectTemplate;
+use ext_error::{AppError, Result};
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde_json::json;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "project_templates")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub default_tags: Json,
+    pub default_load_cases: Json,
+    pub default_settings: Json,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl TryFrom<Model> for ProjectTemplate {
+    type Error = AppError;
+
+    fn try_from(model: Model) -> Result<Self> {
+        let id = Uuid::parse_str(&model.id).map_err(|e| {
+            AppError::Database(format!("Invalid template id '{}' in database: {}", model.id, e))
+        })?;
+        let invalid = |e: serde_json::Error| {
+            AppError::Database(format!("Invalid template {id} in database: {e}"))
+        };
+
+        Ok(ProjectTemplate {
+            id,
+            name: model.name,
+            description: model.description,
+            default_tags: serde_json::from_value(model.default_tags).map_err(invalid)?,
+            default_load_cases: serde_json::from_value(model.default_load_cases).map_err(invalid)?,
+            default_settings: serde_json::from_value(model.default_settings).map_err(invalid)?,
+            built_in: false,
+        })
+    }
+}
+
+impl From<&ProjectTemplate> for ActiveModel {
+    fn from(template: &ProjectTemplate) -> Self {
+        ActiveModel {
+            id: Set(template.id.to_string()),
+            name: Set(template.name.clone()),
+            description: Set(template.description.clone()),
+            default_tags: Set(json!(template.default_tags)),
+            default_load_cases: Set(json!(template.default_load_cases)),
+            default_settings: Set(json!(template.default_settings)),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use ext_core::results::{Direction, StoryDrift};
+use ext_error::{AppError, Result};
+use sea_orm::entity::prelude::*;
+use sea_orm::{NotSet, Set};
+
+/// One row of a run's story drift table
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "story_drifts")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub run_id: String,
+    pub story: String,
+    pub load_case: String,
+    pub direction: String,
+    pub drift: f64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl TryFrom<Model> for StoryDrift {
+    type Error = AppError;
+
+    fn try_from(model: Model) -> Result<Self> {
+        Ok(StoryDrift {
+            story: model.story,
+            load_case: model.load_case,
+            direction: Direction::parse(&model.direction).map_err(|_| {
+                AppError::Database(format!(
+                    "Invalid direction '{}' in story_drifts",
+                    model.direction
+                ))
+            })?,
+            drift: model.drift,
+        })
+    }
+}
+
+impl ActiveModel {
+    pub fn for_run(run_id: &str, drift: &StoryDrift) -> Self {
+        ActiveModel {
+            id: NotSet,
+            run_id: Set(run_id.to_string()),
+            story: Set(drift.story.clone()),
+            load_case: Set(drift.load_case.clone()),
+            direction: Set(drift.direction.as_str().to_string()),
+            drift: Set(drift.drift),
+        }
+    }
+}
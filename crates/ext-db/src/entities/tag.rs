@@ -0,0 +1,45 @@
+use ext_core::Tag;
+use ext_error::{AppError, Result};
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "tags")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub name: String,
+    pub color: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl TryFrom<Model> for Tag {
+    type Error = AppError;
+
+    fn try_from(model: Model) -> Result<Self> {
+        let id = Uuid::parse_str(&model.id).map_err(|e| {
+            AppError::Database(format!("Invalid tag id '{}' in database: {}", model.id, e))
+        })?;
+
+        Ok(Tag {
+            id,
+            name: model.name,
+            color: model.color,
+        })
+    }
+}
+
+impl From<&Tag> for ActiveModel {
+    fn from(tag: &Tag) -> Self {
+        ActiveModel {
+            id: Set(tag.id.to_string()),
+            name: Set(tag.name.clone()),
+            color: Set(tag.color.clone()),
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! Frame member forces of analysis runs. Tables run to hundreds of
+//! thousands of rows, so they are written in batches and read back one
+//! filtered page at a time. See [`ext_core::frame_forces`].
+
+use crate::Database;
+use crate::entities::frame_force;
+use crate::entities::prelude::*;
+use ext_core::frame_forces::{FrameForce, FrameForceFilter, FrameForcePage};
+use ext_core::results::AnalysisRun;
+use ext_error::{AppError, Result};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbBackend, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Statement, TransactionTrait,
+};
+use tracing::instrument;
+
+/// Rows per insert, well under SQLite's limit on bound parameters
+const INSERT_BATCH_ROWS: usize = 1000;
+
+impl Database {
+    /// Replaces the frame forces of the run labelled `run_label`, creating
+    /// the run if needed. The run's other tables are kept.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id, rows = forces.len()))]
+    pub async fn save_frame_forces(
+        &self,
+        project_id: &str,
+        run_label: &str,
+        forces: &[FrameForce],
+    ) -> Result<AnalysisRun> {
+        let (project, label) = self.run_target(project_id, run_label).await?;
+
+        // The run and its rows are written together, so a failed import
+        // leaves no empty run behind
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let run = Self::upsert_run(&txn, &project, label).await?;
+        let run_id = run.id.to_string();
+        Self::delete_frame_forces(&txn, std::slice::from_ref(&run_id)).await?;
+        for batch in forces.chunks(INSERT_BATCH_ROWS) {
+            FrameForceEntity::insert_many(
+                batch
+                    .iter()
+                    .map(|row| frame_force::ActiveModel::for_run(&run_id, row)),
+            )
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to save frame forces: {e}")))?;
+        }
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(run)
+    }
+
+    /// Page `page` (from 0) of a run's frame forces matching `filter`, in
+    /// the order they were extracted, with the number of matching rows.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id, run_id = %run_id))]
+    pub async fn query_frame_forces(
+        &self,
+        project_id: &str,
+        run_id: &str,
+        filter: &FrameForceFilter,
+        page: u64,
+        page_size: u64,
+    ) -> Result<FrameForcePage> {
+        self.find_analysis_run(project_id, run_id).await?;
+
+        let mut query = FrameForceEntity::find().filter(frame_force::Column::RunId.eq(run_id));
+        if let Some(story) = &filter.story {
+            query = query.filter(frame_force::Column::Story.eq(story.as_str()));
+        }
+        if let Some(load_case) = &filter.load_case {
+            query = query.filter(frame_force::Column::LoadCase.eq(load_case.as_str()));
+        }
+        if let Some(frame) = &filter.frame {
+            query = query.filter(frame_force::Column::Frame.eq(frame.as_str()));
+        }
+
+        let total = query
+            .clone()
+            .count(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to count frame forces: {e}")))?;
+        let rows = query
+            .order_by_asc(frame_force::Column::Id)
+            .offset(page.saturating_mul(page_size))
+            .limit(page_size)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to load frame forces: {e}")))?
+            .into_iter()
+            .map(FrameForce::from)
+            .collect();
+
+        Ok(FrameForcePage {
+            rows,
+            total,
+            page: page as usize,
+            page_size: page_size as usize,
+        })
+    }
+
+    /// Every frame force of run `run_id`, in the order they were extracted.
+    pub(crate) async fn list_frame_forces(&self, run_id: &str) -> Result<Vec<FrameForce>> {
+        Ok(FrameForceEntity::find()
+            .filter(frame_force::Column::RunId.eq(run_id))
+            .order_by_asc(frame_force::Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to load frame forces: {e}")))?
+            .into_iter()
+            .map(FrameForce::from)
+            .collect())
+    }
+
+    /// Copies the frame forces of run `from_run_id` to run `to_run_id`
+    /// without loading them.
+    pub(crate) async fn copy_frame_forces<C: ConnectionTrait>(
+        conn: &C,
+        from_run_id: &str,
+        to_run_id: &str,
+    ) -> Result<()> {
+        conn.execute(Statement::from_sql_and_values(
+            DbBackend::Sqlite,
+            "INSERT INTO frame_forces \
+             (run_id, story, frame, load_case, station, p, v2, v3, t, m2, m3) \
+             SELECT ?, story, frame, load_case, station, p, v2, v3, t, m2, m3 \
+             FROM frame_forces WHERE run_id = ? ORDER BY id",
+            [to_run_id.into(), from_run_id.into()],
+        ))
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to copy frame forces: {e}")))?;
+        Ok(())
+    }
+
+    /// Deletes the frame forces belonging to any of `run_ids`.
+    pub(crate) async fn delete_frame_forces<C: ConnectionTrait>(
+        conn: &C,
+        run_ids: &[String],
+    ) -> Result<()> {
+        FrameForceEntity::delete_many()
+            .filter(frame_force::Column::RunId.is_in(run_ids.iter().cloned()))
+            .exec(conn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to clear frame forces: {e}")))?;
+        Ok(())
+    }
+}
@@ -1,46 +1,209 @@
-pub use ext_core::Project;
+pub mod archive;
+mod diagnostics;
+pub mod entities;
+mod frame_forces;
+pub mod locks;
+mod merge;
+mod notes;
+pub mod paths;
+mod relocate;
+pub mod settings;
+pub mod storage;
+mod templates;
+
+use entities::prelude::*;
+use chrono::{DateTime, Utc};
+use entities::{
+    analysis_run, audit_entry, base_reaction, modal_period, note, project, project_file,
+    project_lock, project_tag, story_drift, tag,
+};
+pub use ext_core::{Project, ProjectBackup, ProjectFile, ProjectStatus, StorageStatus, Tag};
+use ext_core::DEFAULT_BACKUP_RETENTION;
+use ext_core::project_lock::DEFAULT_LOCK_TIMEOUT_HOURS;
+use archive::{ArchiveManifest, ArchivedResults, ARCHIVE_FORMAT_VERSION};
+use ext_core::audit::{AuditAction, AuditEntry};
+use ext_core::combinations::{LoadCase, LoadCombination};
+use ext_core::duplicates::{names_collide, next_available_name};
+use ext_core::e2k::EtabsModel;
+use ext_core::estimate::LateralEstimate;
+use ext_core::results::{
+    AnalysisResults, AnalysisRun, BaseReaction, ModalPeriod, ResultTable, StoryDrift,
+};
+use ext_core::search::{self, SearchHit};
+use ext_core::seismic::SeismicBaseShear;
+use ext_core::wind::WindPressures;
 use ext_error::{AppError, Result};
-use sea_orm::{Database as SeaOrmDatabase, DbConn};
+use migration::{Migrator, MigratorTrait};
+use paths::{fs_error, long_path};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, Database as SeaOrmDatabase, DbBackend, DbConn, EntityTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set, Statement, TransactionTrait,
+};
+use sha2::{Digest, Sha256};
+use std::io;
 use std::path::{Path, PathBuf};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use storage::{retry_transient, write_atomic};
 use tokio::fs;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// Folder inside each project directory that holds attached files
+const FILES_DIR: &str = "files";
+
+/// Folder inside each project directory that holds metadata backups
+const BACKUPS_DIR: &str = "backups";
+
+/// Folder inside each project directory that holds generated reports
+const REPORTS_DIR: &str = "reports";
+
+/// Suffix of a copy that is still being written
+const PARTIAL_SUFFIX: &str = ".partial";
+
+/// Suffix of a copy whose row is being deleted
+const REMOVING_SUFFIX: &str = ".removing";
 
 pub struct Database {
     db: DbConn,
     projects_dir: PathBuf,
+    storage: StorageStatus,
+    backup_retention: AtomicUsize,
+    /// Seconds before another instance's project lock may be taken over
+    lock_timeout_secs: AtomicI64,
+    /// Holder of each project lock this instance took, by project id
+    held_locks: Mutex<HashMap<String, String>>,
+    /// This machine's name, recorded in the locks it takes
+    host: String,
 }
 
 impl Database {
+    #[instrument(level = "debug", skip_all)]
     pub async fn new(db_url: &str, projects_dir: &str) -> Result<Self> {
         // Initialize database
         let db = SeaOrmDatabase::connect(db_url)
             .await
             .map_err(|e| AppError::Database(e.to_string()))?;
 
+        // Bring the schema up to date; already-applied migrations are skipped
+        Migrator::up(&db, None)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to run migrations: {}", e)))?;
+
         // Ensure projects directory exists
         let projects_path = PathBuf::from(projects_dir);
-        fs::create_dir_all(&projects_path)
+        fs::create_dir_all(long_path(&projects_path))
             .await
-            .map_err(|e| AppError::Database(format!("Failed to create projects directory: {}", e)))?;
+            .map_err(|e| fs_error("create projects directory", &projects_path, e))?;
+
+        // Detect read-only shares and cloud sync folders up front
+        let storage = storage::probe(&projects_path);
 
         Ok(Self {
             db,
             projects_dir: projects_path,
+            storage,
+            backup_retention: AtomicUsize::new(DEFAULT_BACKUP_RETENTION),
+            lock_timeout_secs: AtomicI64::new(i64::from(DEFAULT_LOCK_TIMEOUT_HOURS) * 3600),
+            held_locks: Mutex::new(HashMap::new()),
+            host: locks::host_name(),
         })
     }
 
+    pub fn storage_status(&self) -> &StorageStatus {
+        &self.storage
+    }
+
+    /// Sets how many backups are kept per project; older ones are pruned on
+    /// the next backup.
+    pub fn set_backup_retention(&self, retention: usize) {
+        self.backup_retention.store(retention.max(1), Ordering::Relaxed);
+    }
+
+    /// Fails fast when the projects directory cannot be written to, instead of
+    /// failing deep inside a multi-step write.
+    fn ensure_writable(&self) -> Result<()> {
+        if self.storage.read_only {
+            return Err(AppError::FileSystem(format!(
+                "Projects directory '{}' is read-only. Choose a writable projects directory to \
+                 create or modify projects.",
+                self.projects_dir.display()
+            )));
+        }
+        Ok(())
+    }
+
+    #[instrument(level = "debug", skip_all, fields(project_id = %project.id))]
     pub async fn save_project(&self, project: &Project) -> Result<()> {
+        self.save_project_with_audit(project, None).await
+    }
+
+    /// Saves a new project and records its creation in the audit log.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project.id))]
+    pub async fn create_project(&self, project: &Project) -> Result<()> {
+        let entry = AuditEntry::new(
+            project.id,
+            AuditAction::Created,
+            format!("Created \"{}\"", project.name),
+        );
+        self.save_project_with_audit(project, Some(&entry)).await
+    }
+
+    /// Saves the project and appends `audit` to its log in the same
+    /// transaction.
+    async fn save_project_with_audit(
+        &self,
+        project: &Project,
+        audit: Option<&AuditEntry>,
+    ) -> Result<()> {
+        self.ensure_project_writable(&project.id.to_string())
+            .await?;
+
+        // The row is only committed once the folder has been written, so a
+        // failed filesystem write leaves the database untouched
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
         // Save project metadata to database
-        self.save_project_to_db(project).await?;
+        Self::save_project_to_db(&txn, project).await?;
+        Self::index_project(&txn, project).await?;
+        if let Some(entry) = audit {
+            Self::append_audit(&txn, entry).await?;
+        }
 
         // Save project folder structure
         self.save_project_to_filesystem(project).await?;
 
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
         Ok(())
     }
 
-    async fn save_project_to_db(&self, project: &Project) -> Result<()> {
-        // TODO: Implement Sea-ORM entity operations here
-        // This will be done after setting up migrations and entities
+    async fn save_project_to_db<C: ConnectionTrait>(conn: &C, project: &Project) -> Result<()> {
+        ProjectEntity::insert(project::ActiveModel::from(project))
+            .on_conflict(
+                OnConflict::column(project::Column::Id)
+                    .update_columns([
+                        project::Column::Name,
+                        project::Column::Description,
+                        project::Column::UpdatedAt,
+                        project::Column::Status,
+                        project::Column::TrashedAt,
+                        project::Column::LastOpenedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(conn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to save project: {}", e)))?;
+
         Ok(())
     }
 
@@ -48,41 +211,45 @@ impl Database {
         let project_path = self.projects_dir.join(project.id.to_string());
         
         // Create project directory
-        fs::create_dir_all(&project_path)
+        fs::create_dir_all(long_path(&project_path))
             .await
-            .map_err(|e| AppError::Database(format!("Failed to create project directory: {}", e)))?;
+            .map_err(|e| fs_error("create project directory", &project_path, e))?;
 
         // Save project metadata as JSON
-        let metadata = serde_json::json!({
-            "id": project.id.to_string(),
-            "name": project.name,
-            "description": project.description,
-            "created_at": project.created_at.to_rfc3339(),
-            "updated_at": project.updated_at.to_rfc3339(),
-        });
-
         let metadata_path = project_path.join("project.json");
-        fs::write(
-            &metadata_path,
-            serde_json::to_string_pretty(&metadata)
-                .map_err(|e| AppError::Database(format!("Failed to serialize project: {}", e)))?,
-        )
-        .await
-        .map_err(|e| AppError::Database(format!("Failed to write project file: {}", e)))?;
+        let content = serde_json::to_string_pretty(project)
+            .map_err(|e| AppError::Database(format!("Failed to serialize project: {}", e)))?;
+        write_atomic(&metadata_path, &content)
+            .await
+            .map_err(|e| fs_error("write project file", &metadata_path, e))?;
 
         Ok(())
     }
 
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
     pub async fn load_project(&self, project_id: &str) -> Result<Option<Project>> {
+        let row = ProjectEntity::find_by_id(project_id.to_string())
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to load project: {}", e)))?;
+
+        match row {
+            Some(row) => Project::try_from(row).map(Some),
+            // Folders created before the registry existed only have project.json
+            None => self.load_project_from_filesystem(project_id).await,
+        }
+    }
+
+    async fn load_project_from_filesystem(&self, project_id: &str) -> Result<Option<Project>> {
         let project_path = self.projects_dir.join(project_id).join("project.json");
-        
-        if !project_path.exists() {
+
+        if !long_path(&project_path).exists() {
             return Ok(None);
         }
 
-        let content = fs::read_to_string(&project_path)
+        let content = retry_transient(|| fs::read_to_string(long_path(&project_path)))
             .await
-            .map_err(|e| AppError::Database(format!("Failed to read project file: {}", e)))?;
+            .map_err(|e| fs_error("read project file", &project_path, e))?;
 
         let project: Project = serde_json::from_str(&content)
             .map_err(|e| AppError::Database(format!("Failed to parse project: {}", e)))?;
@@ -90,29 +257,1963 @@ impl Database {
         Ok(Some(project))
     }
 
+    #[instrument(level = "debug", skip_all)]
     pub async fn list_projects(&self) -> Result<Vec<Project>> {
-        let mut projects = Vec::new();
-        let mut entries = fs::read_dir(&self.projects_dir)
+        ProjectEntity::find()
+            .order_by_asc(project::Column::CreatedAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to list projects: {}", e)))?
+            .into_iter()
+            .map(Project::try_from)
+            .collect()
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    pub async fn list_projects_with_status(&self, status: ProjectStatus) -> Result<Vec<Project>> {
+        ProjectEntity::find()
+            .filter(project::Column::Status.eq(status.as_str()))
+            .order_by_asc(project::Column::CreatedAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to list projects: {}", e)))?
+            .into_iter()
+            .map(Project::try_from)
+            .collect()
+    }
+
+    /// Projects that are not in the trash, most recently opened first.
+    /// Projects never opened follow, most recently updated first.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn list_recent_projects(&self, limit: usize) -> Result<Vec<Project>> {
+        // SQLite sorts NULL lowest, so never-opened projects come last
+        ProjectEntity::find()
+            .filter(project::Column::Status.ne(ProjectStatus::Trashed.as_str()))
+            .order_by_desc(project::Column::LastOpenedAt)
+            .order_by_desc(project::Column::UpdatedAt)
+            .limit(limit as u64)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to list recent projects: {e}")))?
+            .into_iter()
+            .map(Project::try_from)
+            .collect()
+    }
+
+    /// Finds a project whose name matches `name` case-insensitively.
+    ///
+    /// Names are compared in Rust because SQLite's LOWER() only folds ASCII.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn find_project_by_name(&self, name: &str) -> Result<Option<Project>> {
+        Ok(self
+            .list_projects()
+            .await?
+            .into_iter()
+            .find(|project| names_collide(&project.name, name)))
+    }
+
+    /// Persists changes to an existing project and bumps `updated_at`.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project.id))]
+    pub async fn update_project(&self, project: &Project) -> Result<Project> {
+        let Some(existing) = self.load_project(&project.id.to_string()).await? else {
+            return Err(AppError::NotFound(format!("Project {} not found", project.id)));
+        };
+
+        self.backup_project(&project.id.to_string()).await?;
+
+        let mut updated = project.clone();
+        updated.updated_at = Utc::now();
+        let entry = AuditEntry::for_update(&existing, &updated);
+        self.save_project_with_audit(&updated, Some(&entry)).await?;
+
+        Ok(updated)
+    }
+
+    /// Records that the project was opened. Unlike an edit this does not
+    /// bump `updated_at` or take a backup.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn touch_project_opened(&self, project_id: &str) -> Result<Project> {
+        let mut project = self
+            .load_project(project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project {project_id} not found")))?;
+
+        project.last_opened_at = Some(Utc::now());
+        self.save_project(&project).await?;
+
+        Ok(project)
+    }
+
+    /// Snapshots the project's metadata and file list into its `backups/`
+    /// folder, then prunes all but the newest `backup_retention` backups.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn backup_project(&self, project_id: &str) -> Result<ProjectBackup> {
+        self.ensure_project_writable(project_id).await?;
+
+        let backups_dir = self.projects_dir.join(project_id).join(BACKUPS_DIR);
+        let backup = self.write_backup(project_id, &backups_dir).await?;
+
+        let retention = self.backup_retention.load(Ordering::Relaxed);
+        for stale in self.backup_ids(project_id).await?.iter().skip(retention) {
+            let _ = fs::remove_file(long_path(&backups_dir.join(format!("{stale}.json")))).await;
+        }
+
+        Ok(backup)
+    }
+
+    /// Where the snapshot taken before a permanent delete goes: beside the
+    /// project folders rather than in the folder being removed.
+    fn deleted_backups_dir(&self, project_id: &str) -> PathBuf {
+        self.projects_dir.join(BACKUPS_DIR).join(project_id)
+    }
+
+    async fn write_backup(&self, project_id: &str, backups_dir: &Path) -> Result<ProjectBackup> {
+        let project = self
+            .load_project(project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+        let created_at = Utc::now();
+        // The random tail keeps ids unique when two backups share a timestamp
+        let random = Uuid::new_v4().simple().to_string();
+        let backup = ProjectBackup {
+            id: format!("{}-{}", created_at.format("%Y%m%dT%H%M%S%.6fZ"), &random[..8]),
+            created_at,
+            project,
+            files: self.list_project_files(project_id).await?,
+        };
+
+        fs::create_dir_all(long_path(backups_dir))
             .await
-            .map_err(|e| AppError::Database(format!("Failed to read projects directory: {}", e)))?;
+            .map_err(|e| fs_error("create backups directory", backups_dir, e))?;
+
+        let backup_path = backups_dir.join(format!("{}.json", backup.id));
+        let content = serde_json::to_string_pretty(&backup)
+            .map_err(|e| AppError::Database(format!("Failed to serialize backup: {}", e)))?;
+        write_atomic(&backup_path, &content)
+            .await
+            .map_err(|e| fs_error("write backup file", &backup_path, e))?;
+
+        Ok(backup)
+    }
+
+    /// The folder holding a project's backups: its own `backups/` folder,
+    /// or once the project has been deleted, the snapshot taken before.
+    fn backups_dir(&self, project_id: &str) -> PathBuf {
+        let project_dir = self.projects_dir.join(project_id);
+        if long_path(&project_dir).exists() {
+            project_dir.join(BACKUPS_DIR)
+        } else {
+            self.deleted_backups_dir(project_id)
+        }
+    }
+
+    /// Backup ids for a project, newest first.
+    async fn backup_ids(&self, project_id: &str) -> Result<Vec<String>> {
+        Self::backup_ids_in(&self.backups_dir(project_id)).await
+    }
+
+    async fn backup_ids_in(backups_dir: &Path) -> Result<Vec<String>> {
+        let mut entries = match fs::read_dir(long_path(backups_dir)).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(fs_error("read backups directory", backups_dir, e)),
+        };
 
+        let mut ids = Vec::new();
         while let Some(entry) = entries
             .next_entry()
             .await
-            .map_err(|e| AppError::Database(format!("Failed to read directory entry: {}", e)))?
+            .map_err(|e| fs_error("read backups directory", backups_dir, e))?
+        {
+            if let Some(id) = entry.file_name().to_string_lossy().strip_suffix(".json") {
+                ids.push(id.to_string());
+            }
+        }
+
+        // Ids start with a UTC timestamp, so they sort chronologically
+        ids.sort_unstable_by(|a, b| b.cmp(a));
+        Ok(ids)
+    }
+
+    /// Backups for a project, newest first.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn list_backups(&self, project_id: &str) -> Result<Vec<ProjectBackup>> {
+        let mut backups = Vec::new();
+        for id in self.backup_ids(project_id).await? {
+            backups.push(self.load_backup(project_id, &id).await?);
+        }
+        Ok(backups)
+    }
+
+    async fn load_backup(&self, project_id: &str, backup_id: &str) -> Result<ProjectBackup> {
+        // The id becomes a file name; only accept what backup_project generates
+        if backup_id.is_empty()
+            || !backup_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
         {
-            if let Ok(Some(project)) = self.load_project(
-                entry
-                    .file_name()
-                    .to_string_lossy()
-                    .as_ref(),
+            return Err(AppError::Validation(format!("Invalid backup id: '{}'", backup_id)));
+        }
+
+        let backup_path = self
+            .backups_dir(project_id)
+            .join(format!("{backup_id}.json"));
+        if !long_path(&backup_path).exists() {
+            return Err(AppError::NotFound(format!("Backup {} not found", backup_id)));
+        }
+
+        let content = retry_transient(|| fs::read_to_string(long_path(&backup_path)))
+            .await
+            .map_err(|e| fs_error("read backup file", &backup_path, e))?;
+
+        serde_json::from_str(&content)
+            .map_err(|e| AppError::Database(format!("Failed to parse backup {}: {}", backup_id, e)))
+    }
+
+    /// Rewrites the project's metadata from a backup, bumping `updated_at`.
+    /// Attached files are not touched, so a deleted project comes back
+    /// without them.
+    #[instrument(
+        level = "debug",
+        skip_all,
+        fields(project_id = %project_id, backup_id = %backup_id)
+    )]
+    pub async fn restore_backup(&self, project_id: &str, backup_id: &str) -> Result<Project> {
+        self.ensure_project_writable(project_id).await?;
+
+        let backup = self.load_backup(project_id, backup_id).await?;
+        if backup.project.id.to_string() != project_id {
+            return Err(AppError::Validation(format!(
+                "Backup {} belongs to a different project",
+                backup_id
+            )));
+        }
+
+        // Keep the state being replaced, in case the restore was a mistake
+        if self.load_project(project_id).await.ok().flatten().is_some() {
+            self.backup_project(project_id).await?;
+        }
+
+        let mut restored = backup.project;
+        restored.updated_at = Utc::now();
+        let entry = AuditEntry::new(
+            restored.id,
+            AuditAction::Updated,
+            format!("Restored backup {backup_id}"),
+        );
+        self.save_project_with_audit(&restored, Some(&entry)).await?;
+
+        Ok(restored)
+    }
+
+    /// Whether the FTS5 search index exists. SQLite builds without FTS5
+    /// skip it in the migration.
+    async fn has_search_index<C: ConnectionTrait>(conn: &C) -> bool {
+        conn.query_one(Statement::from_string(
+            DbBackend::Sqlite,
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name = 'projects_fts'",
+        ))
+        .await
+        .ok()
+        .flatten()
+        .is_some()
+    }
+
+    async fn index_project<C: ConnectionTrait>(conn: &C, project: &Project) -> Result<()> {
+        if !Self::has_search_index(conn).await {
+            return Ok(());
+        }
+
+        let id = project.id.to_string();
+        Self::unindex_project(conn, &id).await?;
+        conn.execute(Statement::from_sql_and_values(
+            DbBackend::Sqlite,
+            "INSERT INTO projects_fts (project_id, name, description) VALUES (?, ?, ?)",
+            [
+                id.into(),
+                project.name.clone().into(),
+                project.description.clone().into(),
+            ],
+        ))
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to index project: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn unindex_project<C: ConnectionTrait>(conn: &C, project_id: &str) -> Result<()> {
+        if !Self::has_search_index(conn).await {
+            return Ok(());
+        }
+
+        conn.execute(Statement::from_sql_and_values(
+            DbBackend::Sqlite,
+            "DELETE FROM projects_fts WHERE project_id = ?",
+            [project_id.into()],
+        ))
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to unindex project: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Searches names and descriptions of projects that are not in the
+    /// trash, best matches first.
+    ///
+    /// Uses the FTS5 index with prefix matching on every term, or a
+    /// case-insensitive substring match when the index does not exist.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn search_projects(&self, query: &str) -> Result<Vec<SearchHit>> {
+        let terms = search::search_terms(query)?;
+
+        let projects: Vec<Project> = self
+            .list_projects()
+            .await?
+            .into_iter()
+            .filter(|project| project.status != ProjectStatus::Trashed)
+            .collect();
+
+        if !Self::has_search_index(&self.db).await {
+            return Ok(search::substring_search(&projects, &terms));
+        }
+
+        // Quote each term so FTS5 operators in user input are taken literally
+        let expression = terms
+            .iter()
+            .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let rows = self
+            .db
+            .query_all(Statement::from_sql_and_values(
+                DbBackend::Sqlite,
+                "SELECT project_id, bm25(projects_fts, 0.0, 2.0, 1.0) AS rank \
+                 FROM projects_fts WHERE projects_fts MATCH ? ORDER BY rank",
+                [expression.into()],
+            ))
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to search projects: {}", e)))?;
+
+        let mut hits = Vec::new();
+        for row in rows {
+            let id: String = row
+                .try_get("", "project_id")
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            let rank: f64 = row
+                .try_get("", "rank")
+                .map_err(|e| AppError::Database(e.to_string()))?;
+
+            if let Some(project) = projects.iter().find(|p| p.id.to_string() == id) {
+                hits.push(SearchHit {
+                    project: project.clone(),
+                    // bm25 is negative, lower meaning more relevant
+                    score: -rank,
+                    matched_field: search::matched_field(project, &terms),
+                });
+            }
+        }
+
+        Ok(hits)
+    }
+
+    /// Rebuilds the search index from the projects table, creating it if a
+    /// newer SQLite now supports FTS5. Returns the number of indexed projects.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn reindex_projects(&self) -> Result<u64> {
+        self.db
+            .execute_unprepared(
+                "CREATE VIRTUAL TABLE IF NOT EXISTS projects_fts \
+                 USING fts5(project_id UNINDEXED, name, description)",
+            )
+            .await
+            .map_err(|e| AppError::Database(format!("Full-text search is not available: {}", e)))?;
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        txn.execute_unprepared("DELETE FROM projects_fts")
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to clear search index: {}", e)))?;
+
+        let indexed = txn
+            .execute_unprepared(
+                "INSERT INTO projects_fts (project_id, name, description) \
+                 SELECT id, name, description FROM projects",
             )
             .await
-            {
-                projects.push(project);
+            .map_err(|e| AppError::Database(format!("Failed to rebuild search index: {}", e)))?
+            .rows_affected();
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(indexed)
+    }
+
+    /// Moves a project between active, archived and trashed.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn set_project_status(
+        &self,
+        project_id: &str,
+        status: ProjectStatus,
+    ) -> Result<Project> {
+        let mut project = self
+            .load_project(project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+        project.set_status(status);
+        self.update_project(&project).await
+    }
+
+    /// Deletes every trashed project that has been in the trash for longer
+    /// than `retention`, returning the ids that were removed.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn purge_trashed_projects(&self, retention: chrono::Duration) -> Result<Vec<Uuid>> {
+        let cutoff = Utc::now() - retention;
+        let mut purged = Vec::new();
+
+        for project in self.list_projects_with_status(ProjectStatus::Trashed).await? {
+            // Rows trashed before trashed_at existed count from their last update
+            if project.trashed_at.unwrap_or(project.updated_at) <= cutoff {
+                self.delete_project(&project.id.to_string()).await?;
+                purged.push(project.id);
+            }
+        }
+        self.prune_deleted_backups(cutoff).await?;
+
+        Ok(purged)
+    }
+
+    /// Removes the backups of deleted projects whose newest backup was
+    /// taken before `cutoff`.
+    async fn prune_deleted_backups(&self, cutoff: DateTime<Utc>) -> Result<()> {
+        let deleted_dir = self.projects_dir.join(BACKUPS_DIR);
+        let mut entries = match fs::read_dir(long_path(&deleted_dir)).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(fs_error("read backups directory", &deleted_dir, e)),
+        };
+
+        // Backup ids start with the same timestamp format, so they compare as text
+        let cutoff = cutoff.format("%Y%m%dT%H%M%S%.6fZ").to_string();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| fs_error("read backups directory", &deleted_dir, e))?
+        {
+            let dir = entry.path();
+            if !entry.file_type().await.is_ok_and(|kind| kind.is_dir()) {
+                continue;
+            }
+            let newest = Self::backup_ids_in(&dir).await?.into_iter().next();
+            if newest.is_none_or(|id| id < cutoff) {
+                let _ = fs::remove_dir_all(long_path(&dir)).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the project row and its folder under `projects_dir`. A last
+    /// backup is kept under `projects_dir/backups/`, so the metadata can
+    /// still be restored until [`Self::purge_trashed_projects`] prunes it.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn delete_project(&self, project_id: &str) -> Result<()> {
+        self.ensure_project_writable(project_id).await?;
+
+        // The id becomes a path component below; never accept anything but a uuid
+        Uuid::parse_str(project_id).map_err(|_| {
+            AppError::Validation(format!("Invalid project id: '{}'", project_id))
+        })?;
+
+        if self.load_project(project_id).await?.is_some() {
+            self.write_backup(project_id, &self.deleted_backups_dir(project_id))
+                .await?;
+        }
+        self.discard_project(project_id).await
+    }
+
+    /// Removes the project row and its folder without keeping a backup, for
+    /// undoing a project that failed to be created.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn discard_project(&self, project_id: &str) -> Result<()> {
+        self.ensure_project_writable(project_id).await?;
+
+        // The id becomes a path component below; never accept anything but a uuid
+        Uuid::parse_str(project_id).map_err(|_| {
+            AppError::Validation(format!("Invalid project id: '{}'", project_id))
+        })?;
+
+        let project_path = self.projects_dir.join(project_id);
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        ProjectFileEntity::delete_many()
+            .filter(project_file::Column::ProjectId.eq(project_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to delete project files: {}", e)))?;
+
+        ProjectTagEntity::delete_many()
+            .filter(project_tag::Column::ProjectId.eq(project_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to detach project tags: {}", e)))?;
+
+        Self::delete_analysis_runs(&txn, project_id).await?;
+        Self::delete_notes(&txn, project_id).await?;
+
+        ProjectLockEntity::delete_many()
+            .filter(project_lock::Column::ProjectId.eq(project_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to delete project lock: {e}")))?;
+        Self::unindex_project(&txn, project_id).await?;
+
+        // The log outlives archiving and trashing, but not a hard delete
+        AuditEntryEntity::delete_many()
+            .filter(audit_entry::Column::ProjectId.eq(project_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to delete audit log: {e}")))?;
+
+        let deleted = ProjectEntity::delete_by_id(project_id.to_string())
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to delete project: {}", e)))?;
+
+        if deleted.rows_affected == 0 && !long_path(&project_path).exists() {
+            return Err(AppError::NotFound(format!("Project {} not found", project_id)));
+        }
+
+        // A folder that was already partly or entirely removed by hand is fine
+        match fs::remove_dir_all(long_path(&project_path)).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(fs_error("remove project directory", &project_path, e)),
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        self.forget_project_lock(project_id);
+
+        Ok(())
+    }
+
+    /// Writes the project folder, its attached-file metadata, notes and
+    /// analysis runs with their results to a portable archive at `dest`.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn export_project(&self, project_id: &str, dest: &Path) -> Result<()> {
+        let project = self
+            .load_project(project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+        // project.json may predate fields that only the registry has
+        if !self.storage.read_only {
+            self.save_project_to_filesystem(&project).await?;
+        }
+
+        let runs = self.list_analysis_runs(project_id).await?;
+        let mut results = Vec::with_capacity(runs.len());
+        for run in &runs {
+            let run_id = run.id.to_string();
+            results.push(ArchivedResults {
+                results: self.load_run_results(project_id, &run_id).await?,
+                frame_forces: self.list_frame_forces(&run_id).await?,
+            });
+        }
+
+        let manifest = ArchiveManifest {
+            format_version: ARCHIVE_FORMAT_VERSION,
+            project_id: project.id,
+            exported_at: Utc::now(),
+            files: self.list_project_files(project_id).await?,
+            notes: self.list_notes(project_id).await?,
+            runs,
+        };
+
+        let project_dir = self.projects_dir.join(project_id);
+        let dest = dest.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            archive::write_archive(&project_dir, &manifest, &results, &dest)
+        })
+        .await
+        .map_err(|e| AppError::Internal(format!("Export task failed: {}", e)))?
+    }
+
+    /// Unpacks an archive into `projects_dir` and registers the project.
+    ///
+    /// The archive is extracted to a hidden staging folder and only renamed
+    /// into place once it is complete. The project gets a fresh id if its id
+    /// is already taken locally, and a " (2)"-style name if its name is.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn import_project(&self, archive_path: &Path) -> Result<Project> {
+        self.ensure_writable()?;
+
+        let staging = self.projects_dir.join(format!(".import-{}", Uuid::new_v4()));
+        let extracted = {
+            let (archive_path, staging) = (archive_path.to_path_buf(), staging.clone());
+            tokio::task::spawn_blocking(move || archive::extract_archive(&archive_path, &staging))
+                .await
+                .map_err(|e| AppError::Internal(format!("Import task failed: {}", e)))?
+        };
+
+        let result = match extracted {
+            Ok((manifest, results)) => self.register_import(&staging, manifest, results).await,
+            Err(e) => Err(e),
+        };
+        if result.is_err() {
+            let _ = fs::remove_dir_all(long_path(&staging)).await;
+        }
+        result
+    }
+
+    async fn register_import(
+        &self,
+        staging: &Path,
+        manifest: ArchiveManifest,
+        results: Vec<ArchivedResults>,
+    ) -> Result<Project> {
+        let project_json = staging.join("project.json");
+        let content = fs::read_to_string(long_path(&project_json))
+            .await
+            .map_err(|_| AppError::Validation("Archive does not contain project.json".to_string()))?;
+        let mut project: Project = serde_json::from_str(&content).map_err(|e| {
+            AppError::Validation(format!("Archive contains an invalid project.json: {}", e))
+        })?;
+
+        let id_taken = self.load_project(&project.id.to_string()).await?.is_some()
+            || long_path(&self.projects_dir.join(project.id.to_string())).exists();
+        if id_taken {
+            project.id = Uuid::new_v4();
+        }
+
+        let existing: Vec<String> = self
+            .list_projects()
+            .await?
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        project.name = next_available_name(&project.name, &existing);
+
+        let target = self.projects_dir.join(project.id.to_string());
+        fs::rename(long_path(staging), long_path(&target))
+            .await
+            .map_err(|e| fs_error("move imported project into place", &target, e))?;
+
+        if let Err(e) = self.register_imported_project(&project, &manifest, &results).await {
+            let _ = self.discard_project(&project.id.to_string()).await;
+            let _ = fs::remove_dir_all(long_path(&target)).await;
+            return Err(e);
+        }
+
+        Ok(project)
+    }
+
+    async fn register_imported_project(
+        &self,
+        project: &Project,
+        manifest: &ArchiveManifest,
+        results: &[ArchivedResults],
+    ) -> Result<()> {
+        let entry = AuditEntry::new(
+            project.id,
+            AuditAction::Imported,
+            format!("Imported \"{}\" from an archive", project.name),
+        );
+        self.save_project_with_audit(project, Some(&entry)).await?;
+
+        let project_dir = self.projects_dir.join(project.id.to_string());
+        for file in &manifest.files {
+            // Skip metadata whose copy did not make it into the archive
+            if !long_path(&project_dir.join(&file.stored_path)).exists() {
+                continue;
+            }
+
+            // File ids must stay unique when the same archive is imported twice
+            let mut file = file.clone();
+            file.id = Uuid::new_v4();
+            file.project_id = project.id;
+            ProjectFileEntity::insert(project_file::ActiveModel::from(&file))
+                .exec(&self.db)
+                .await
+                .map_err(|e| AppError::Database(format!("Failed to record project file: {}", e)))?;
+        }
+
+        // Revisions are not exported, so each note starts a fresh history
+        for note in &manifest.notes {
+            let mut note = note.clone();
+            note.id = Uuid::new_v4();
+            note.project_id = project.id;
+            NoteEntity::insert(note::ActiveModel::from(&note))
+                .exec(&self.db)
+                .await
+                .map_err(|e| AppError::Database(format!("Failed to record note: {e}")))?;
+        }
+
+        let project_id = project.id.to_string();
+        for (run, archived) in manifest.runs.iter().zip(results) {
+            self.save_analysis_results(&project_id, &run.label, &archived.results)
+                .await?;
+            if !archived.frame_forces.is_empty() {
+                self.save_frame_forces(&project_id, &run.label, &archived.frame_forces)
+                    .await?;
             }
         }
 
-        Ok(projects)
+        Ok(())
+    }
+
+    /// Copies a project under a new id and `name`: its description, tags,
+    /// attached files, lateral estimate, ETABS model and load combinations.
+    /// Backups and reports stay with the original; analysis runs are copied
+    /// only with `include_results`. A failed copy is removed again.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn duplicate_project(
+        &self,
+        project_id: &str,
+        name: &str,
+        include_results: bool,
+    ) -> Result<Project> {
+        self.ensure_writable()?;
+
+        let source = self
+            .load_project(project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project {project_id} not found")))?;
+        let copy = Project::new(name.to_string(), source.description.clone());
+
+        if let Err(e) = self.copy_project_contents(&source, &copy, include_results).await {
+            let copy_dir = self.projects_dir.join(copy.id.to_string());
+            let _ = self.discard_project(&copy.id.to_string()).await;
+            let _ = fs::remove_dir_all(long_path(&copy_dir)).await;
+            return Err(e);
+        }
+
+        Ok(copy)
     }
-}
\ No newline at end of file
+
+    async fn copy_project_contents(
+        &self,
+        source: &Project,
+        copy: &Project,
+        include_results: bool,
+    ) -> Result<()> {
+        let entry = AuditEntry::new(
+            copy.id,
+            AuditAction::Created,
+            format!("Duplicated from \"{}\"", source.name),
+        );
+        self.save_project_with_audit(copy, Some(&entry)).await?;
+
+        let source_id = &source.id.to_string();
+        let copy_id = copy.id.to_string();
+        let source_dir = self.projects_dir.join(source_id);
+        let copy_dir = self.projects_dir.join(&copy_id);
+
+        let data_files = [
+            "estimate.json",
+            "seismic.json",
+            "wind.json",
+            "model.json",
+            "load_cases.json",
+            "combinations.json",
+        ];
+        for data_file in data_files {
+            let from = source_dir.join(data_file);
+            let to = copy_dir.join(data_file);
+            match retry_transient(|| fs::copy(long_path(&from), long_path(&to))).await {
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(fs_error("copy project data", &from, e)),
+            }
+        }
+
+        let files_dir = copy_dir.join(FILES_DIR);
+        fs::create_dir_all(long_path(&files_dir))
+            .await
+            .map_err(|e| fs_error("create files directory", &files_dir, e))?;
+        for file in self.list_project_files(source_id).await? {
+            // There is nothing to copy for a file deleted outside the app
+            if file.missing {
+                continue;
+            }
+
+            let id = Uuid::new_v4();
+            let stored_path = format!("{FILES_DIR}/{id}_{}", file.original_name);
+            let from = source_dir.join(&file.stored_path);
+            let to = copy_dir.join(&stored_path);
+            retry_transient(|| fs::copy(long_path(&from), long_path(&to)))
+                .await
+                .map_err(|e| fs_error("copy file", &from, e))?;
+
+            let copied = ProjectFile {
+                id,
+                project_id: copy.id,
+                stored_path,
+                added_at: Utc::now(),
+                ..file
+            };
+            ProjectFileEntity::insert(project_file::ActiveModel::from(&copied))
+                .exec(&self.db)
+                .await
+                .map_err(|e| AppError::Database(format!("Failed to record project file: {e}")))?;
+        }
+
+        let tag_ids: Vec<String> = self
+            .list_project_tags(source_id)
+            .await?
+            .iter()
+            .map(|tag| tag.id.to_string())
+            .collect();
+        if !tag_ids.is_empty() {
+            self.set_project_tags(&copy_id, &tag_ids).await?;
+        }
+
+        if include_results {
+            for run in self.list_analysis_runs(source_id).await? {
+                let run_id = run.id.to_string();
+                let results = self.load_run_results(source_id, &run_id).await?;
+                let copy = self
+                    .save_analysis_results(&copy_id, &run.label, &results)
+                    .await?;
+                Self::copy_frame_forces(&self.db, &run_id, &copy.id.to_string()).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn append_audit<C: ConnectionTrait>(conn: &C, entry: &AuditEntry) -> Result<()> {
+        AuditEntryEntity::insert(audit_entry::ActiveModel::from(entry))
+            .exec(conn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to record audit entry: {e}")))?;
+        Ok(())
+    }
+
+    /// Returns one page of a project's audit log, newest first, optionally
+    /// only entries with `action`. Pages are numbered from 0.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn list_audit_log(
+        &self,
+        project_id: &str,
+        action: Option<AuditAction>,
+        page: u64,
+        page_size: u64,
+    ) -> Result<Vec<AuditEntry>> {
+        let mut query = AuditEntryEntity::find()
+            .filter(audit_entry::Column::ProjectId.eq(project_id));
+        if let Some(action) = action {
+            query = query.filter(audit_entry::Column::Action.eq(action.as_str()));
+        }
+
+        query
+            .order_by_desc(audit_entry::Column::Timestamp)
+            .offset(page.saturating_mul(page_size))
+            .limit(page_size)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to list audit log: {e}")))?
+            .into_iter()
+            .map(AuditEntry::try_from)
+            .collect()
+    }
+
+    /// Stores a new tag. Tag names are unique regardless of case.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn create_tag(&self, tag: &Tag) -> Result<()> {
+        if self
+            .list_tags()
+            .await?
+            .iter()
+            .any(|existing| names_collide(&existing.name, &tag.name))
+        {
+            return Err(AppError::Validation("tag name already exists".to_string()));
+        }
+
+        TagEntity::insert(tag::ActiveModel::from(tag))
+            .exec(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to save tag: {}", e)))?;
+
+        Ok(())
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    pub async fn list_tags(&self) -> Result<Vec<Tag>> {
+        TagEntity::find()
+            .order_by_asc(tag::Column::Name)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to list tags: {}", e)))?
+            .into_iter()
+            .map(Tag::try_from)
+            .collect()
+    }
+
+    /// Deletes a tag and detaches it from every project that carried it.
+    #[instrument(level = "debug", skip_all, fields(tag_id = %tag_id))]
+    pub async fn delete_tag(&self, tag_id: &str) -> Result<()> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        ProjectTagEntity::delete_many()
+            .filter(project_tag::Column::TagId.eq(tag_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to detach tag: {}", e)))?;
+
+        let deleted = TagEntity::delete_by_id(tag_id.to_string())
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to delete tag: {}", e)))?;
+
+        if deleted.rows_affected == 0 {
+            return Err(AppError::NotFound(format!("Tag {} not found", tag_id)));
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Replaces the tags on a project with `tag_ids`.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn set_project_tags(&self, project_id: &str, tag_ids: &[String]) -> Result<Vec<Tag>> {
+        self.ensure_project_writable(project_id).await?;
+        if self.load_project(project_id).await?.is_none() {
+            return Err(AppError::NotFound(format!("Project {} not found", project_id)));
+        }
+
+        let tags = self.list_tags().await?;
+        let mut selected: Vec<Tag> = Vec::new();
+        for tag_id in tag_ids {
+            let tag = tags
+                .iter()
+                .find(|tag| &tag.id.to_string() == tag_id)
+                .ok_or_else(|| AppError::NotFound(format!("Tag {} not found", tag_id)))?;
+            if !selected.contains(tag) {
+                selected.push(tag.clone());
+            }
+        }
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        ProjectTagEntity::delete_many()
+            .filter(project_tag::Column::ProjectId.eq(project_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to clear project tags: {}", e)))?;
+
+        if !selected.is_empty() {
+            ProjectTagEntity::insert_many(selected.iter().map(|tag| project_tag::ActiveModel {
+                project_id: Set(project_id.to_string()),
+                tag_id: Set(tag.id.to_string()),
+            }))
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to tag project: {}", e)))?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(selected)
+    }
+
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn list_project_tags(&self, project_id: &str) -> Result<Vec<Tag>> {
+        let tag_ids: Vec<String> = ProjectTagEntity::find()
+            .filter(project_tag::Column::ProjectId.eq(project_id))
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to load project tags: {}", e)))?
+            .into_iter()
+            .map(|row| row.tag_id)
+            .collect();
+
+        Ok(self
+            .list_tags()
+            .await?
+            .into_iter()
+            .filter(|tag| tag_ids.contains(&tag.id.to_string()))
+            .collect())
+    }
+
+    #[instrument(level = "debug", skip_all, fields(tag_id = %tag_id))]
+    pub async fn list_projects_by_tag(&self, tag_id: &str) -> Result<Vec<Project>> {
+        let project_ids: Vec<String> = ProjectTagEntity::find()
+            .filter(project_tag::Column::TagId.eq(tag_id))
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to load tagged projects: {}", e)))?
+            .into_iter()
+            .map(|row| row.project_id)
+            .collect();
+
+        ProjectEntity::find()
+            .filter(project::Column::Id.is_in(project_ids))
+            .order_by_asc(project::Column::CreatedAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to list projects: {}", e)))?
+            .into_iter()
+            .map(Project::try_from)
+            .collect()
+    }
+
+    /// Stores every table of a run. Importing a label the project already
+    /// has replaces that run's rows rather than adding a second run.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn save_analysis_results(
+        &self,
+        project_id: &str,
+        run_label: &str,
+        results: &AnalysisResults,
+    ) -> Result<AnalysisRun> {
+        self.write_analysis_results(project_id, run_label, results, &ResultTable::ALL)
+            .await
+    }
+
+    /// Replaces one table of a run, creating the run if needed. The run's
+    /// other tables are kept.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn save_result_table(
+        &self,
+        project_id: &str,
+        run_label: &str,
+        table: ResultTable,
+        results: &AnalysisResults,
+    ) -> Result<AnalysisRun> {
+        self.write_analysis_results(project_id, run_label, results, &[table])
+            .await
+    }
+
+    /// Checks that a run labelled `run_label` can be written to the
+    /// project, returning the project and the trimmed label.
+    async fn run_target<'a>(
+        &self,
+        project_id: &str,
+        run_label: &'a str,
+    ) -> Result<(Project, &'a str)> {
+        self.ensure_project_writable(project_id).await?;
+
+        let label = run_label.trim();
+        if label.is_empty() {
+            return Err(AppError::Validation("run label must not be empty".to_string()));
+        }
+        let project = self
+            .load_project(project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project {project_id} not found")))?;
+        Ok((project, label))
+    }
+
+    /// Creates the run labelled `label`, or marks the existing one as
+    /// re-imported.
+    async fn upsert_run<C: ConnectionTrait>(
+        conn: &C,
+        project: &Project,
+        label: &str,
+    ) -> Result<AnalysisRun> {
+        let existing = AnalysisRunEntity::find()
+            .filter(analysis_run::Column::ProjectId.eq(project.id.to_string()))
+            .filter(analysis_run::Column::Label.eq(label))
+            .one(conn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to load analysis run: {e}")))?;
+
+        match existing {
+            Some(model) => {
+                let mut run = AnalysisRun::try_from(model)?;
+                run.imported_at = Utc::now();
+                AnalysisRunEntity::update(analysis_run::ActiveModel::from(&run))
+                    .exec(conn)
+                    .await
+                    .map_err(|e| {
+                        AppError::Database(format!("Failed to update analysis run: {e}"))
+                    })?;
+                Ok(run)
+            }
+            None => {
+                let run = AnalysisRun::new(project.id, label.to_string());
+                AnalysisRunEntity::insert(analysis_run::ActiveModel::from(&run))
+                    .exec(conn)
+                    .await
+                    .map_err(|e| {
+                        AppError::Database(format!("Failed to save analysis run: {e}"))
+                    })?;
+                Ok(run)
+            }
+        }
+    }
+
+    async fn write_analysis_results(
+        &self,
+        project_id: &str,
+        run_label: &str,
+        results: &AnalysisResults,
+        tables: &[ResultTable],
+    ) -> Result<AnalysisRun> {
+        let (project, label) = self.run_target(project_id, run_label).await?;
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let run = Self::upsert_run(&txn, &project, label).await?;
+        let run_id = run.id.to_string();
+        Self::delete_run_rows(&txn, tables, std::slice::from_ref(&run_id)).await?;
+
+        for table in tables {
+            let inserted = match table {
+                ResultTable::StoryDrifts if !results.story_drifts.is_empty() => {
+                    StoryDriftEntity::insert_many(
+                        results
+                            .story_drifts
+                            .iter()
+                            .map(|row| story_drift::ActiveModel::for_run(&run_id, row)),
+                    )
+                    .exec(&txn)
+                    .await
+                    .map(|_| ())
+                }
+                ResultTable::ModalPeriods if !results.modal_periods.is_empty() => {
+                    ModalPeriodEntity::insert_many(
+                        results
+                            .modal_periods
+                            .iter()
+                            .map(|row| modal_period::ActiveModel::for_run(&run_id, row)),
+                    )
+                    .exec(&txn)
+                    .await
+                    .map(|_| ())
+                }
+                ResultTable::BaseReactions if !results.base_reactions.is_empty() => {
+                    BaseReactionEntity::insert_many(
+                        results
+                            .base_reactions
+                            .iter()
+                            .map(|row| base_reaction::ActiveModel::for_run(&run_id, row)),
+                    )
+                    .exec(&txn)
+                    .await
+                    .map(|_| ())
+                }
+                _ => Ok(()),
+            };
+            inserted.map_err(|e| {
+                AppError::Database(format!("Failed to save {table:?} results: {e}"))
+            })?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(run)
+    }
+
+    /// Deletes the rows of `tables` belonging to any of `run_ids`.
+    async fn delete_run_rows<C: ConnectionTrait>(
+        conn: &C,
+        tables: &[ResultTable],
+        run_ids: &[String],
+    ) -> Result<()> {
+        for table in tables {
+            let deleted = match table {
+                ResultTable::StoryDrifts => StoryDriftEntity::delete_many()
+                    .filter(story_drift::Column::RunId.is_in(run_ids.iter().cloned()))
+                    .exec(conn)
+                    .await,
+                ResultTable::ModalPeriods => ModalPeriodEntity::delete_many()
+                    .filter(modal_period::Column::RunId.is_in(run_ids.iter().cloned()))
+                    .exec(conn)
+                    .await,
+                ResultTable::BaseReactions => BaseReactionEntity::delete_many()
+                    .filter(base_reaction::Column::RunId.is_in(run_ids.iter().cloned()))
+                    .exec(conn)
+                    .await,
+            };
+            deleted.map_err(|e| {
+                AppError::Database(format!("Failed to clear {table:?} results: {e}"))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Deletes every analysis run of a project with its result rows.
+    async fn delete_analysis_runs<C: ConnectionTrait>(conn: &C, project_id: &str) -> Result<()> {
+        let run_ids: Vec<String> = AnalysisRunEntity::find()
+            .filter(analysis_run::Column::ProjectId.eq(project_id))
+            .all(conn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to list analysis runs: {e}")))?
+            .into_iter()
+            .map(|run| run.id)
+            .collect();
+
+        Self::delete_run_rows(conn, &ResultTable::ALL, &run_ids).await?;
+        Self::delete_frame_forces(conn, &run_ids).await?;
+
+        AnalysisRunEntity::delete_many()
+            .filter(analysis_run::Column::ProjectId.eq(project_id))
+            .exec(conn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to delete analysis runs: {e}")))?;
+
+        Ok(())
+    }
+
+    /// Runs of a project, most recently imported first.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn list_analysis_runs(&self, project_id: &str) -> Result<Vec<AnalysisRun>> {
+        AnalysisRunEntity::find()
+            .filter(analysis_run::Column::ProjectId.eq(project_id))
+            .order_by_desc(analysis_run::Column::ImportedAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to list analysis runs: {e}")))?
+            .into_iter()
+            .map(AnalysisRun::try_from)
+            .collect()
+    }
+
+    /// Fails with `NotFound` unless the run belongs to the project.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id, run_id = %run_id))]
+    pub async fn find_analysis_run(&self, project_id: &str, run_id: &str) -> Result<AnalysisRun> {
+        AnalysisRunEntity::find_by_id(run_id.to_string())
+            .filter(analysis_run::Column::ProjectId.eq(project_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to load analysis run: {e}")))?
+            .ok_or_else(|| AppError::NotFound(format!("Analysis run {run_id} not found")))
+            .and_then(AnalysisRun::try_from)
+    }
+
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id, run_id = %run_id))]
+    pub async fn list_story_drifts(
+        &self,
+        project_id: &str,
+        run_id: &str,
+    ) -> Result<Vec<StoryDrift>> {
+        self.find_analysis_run(project_id, run_id).await?;
+
+        StoryDriftEntity::find()
+            .filter(story_drift::Column::RunId.eq(run_id))
+            .order_by_asc(story_drift::Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to load story drifts: {e}")))?
+            .into_iter()
+            .map(StoryDrift::try_from)
+            .collect()
+    }
+
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id, run_id = %run_id))]
+    pub async fn list_modal_periods(
+        &self,
+        project_id: &str,
+        run_id: &str,
+    ) -> Result<Vec<ModalPeriod>> {
+        self.find_analysis_run(project_id, run_id).await?;
+
+        Ok(ModalPeriodEntity::find()
+            .filter(modal_period::Column::RunId.eq(run_id))
+            .order_by_asc(modal_period::Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to load modal periods: {e}")))?
+            .into_iter()
+            .map(ModalPeriod::from)
+            .collect())
+    }
+
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id, run_id = %run_id))]
+    pub async fn list_base_reactions(
+        &self,
+        project_id: &str,
+        run_id: &str,
+    ) -> Result<Vec<BaseReaction>> {
+        self.find_analysis_run(project_id, run_id).await?;
+
+        Ok(BaseReactionEntity::find()
+            .filter(base_reaction::Column::RunId.eq(run_id))
+            .order_by_asc(base_reaction::Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to load base reactions: {e}")))?
+            .into_iter()
+            .map(BaseReaction::from)
+            .collect())
+    }
+
+    /// Every table of a run except the frame forces, which are kept apart.
+    async fn load_run_results(&self, project_id: &str, run_id: &str) -> Result<AnalysisResults> {
+        Ok(AnalysisResults {
+            story_drifts: self.list_story_drifts(project_id, run_id).await?,
+            modal_periods: self.list_modal_periods(project_id, run_id).await?,
+            base_reactions: self.list_base_reactions(project_id, run_id).await?,
+        })
+    }
+
+    /// Copies `source` into the project's `files/` folder and records it.
+    ///
+    /// The copy is written under a temporary name and only renamed into place
+    /// once complete; the row is inserted last, so a crash never leaves a row
+    /// without its file.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn add_file_to_project(&self, project_id: &str, source: &Path) -> Result<ProjectFile> {
+        self.ensure_project_writable(project_id).await?;
+
+        let project = self
+            .load_project(project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+        let original_name = source
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .ok_or_else(|| {
+                AppError::Validation(format!("'{}' is not a file", source.display()))
+            })?;
+
+        let files_dir = self.projects_dir.join(project_id).join(FILES_DIR);
+        fs::create_dir_all(long_path(&files_dir))
+            .await
+            .map_err(|e| fs_error("create files directory", &files_dir, e))?;
+        self.recover_files(project_id, &files_dir).await;
+
+        let id = Uuid::new_v4();
+        let partial = files_dir.join(format!("{id}{PARTIAL_SUFFIX}"));
+        retry_transient(|| fs::copy(long_path(source), long_path(&partial)))
+            .await
+            .map_err(|e| fs_error("copy file", source, e))?;
+
+        // Hash the copy rather than the source so the recorded hash always
+        // matches what is stored, even if the source changes meanwhile
+        let (size, sha256) = match hash_file(&partial).await {
+            Ok(hashed) => hashed,
+            Err(e) => {
+                let _ = fs::remove_file(long_path(&partial)).await;
+                return Err(e);
+            }
+        };
+
+        if let Some(existing) = self.find_file_by_hash(project_id, &sha256).await? {
+            let _ = fs::remove_file(long_path(&partial)).await;
+            return Err(AppError::Validation(format!(
+                "'{original_name}' is already attached to this project as '{}'",
+                existing.original_name
+            )));
+        }
+
+        let stored_path = format!("{FILES_DIR}/{id}_{original_name}");
+        let destination = self.projects_dir.join(project_id).join(&stored_path);
+        if let Err(e) = retry_transient(|| fs::rename(long_path(&partial), long_path(&destination))).await {
+            let _ = fs::remove_file(long_path(&partial)).await;
+            return Err(fs_error("store file", &destination, e));
+        }
+
+        let file = ProjectFile {
+            id,
+            project_id: project.id,
+            original_name,
+            stored_path,
+            size,
+            sha256,
+            added_at: Utc::now(),
+            missing: false,
+        };
+
+        let entry = AuditEntry::new(
+            project.id,
+            AuditAction::FileAttached,
+            format!("Attached \"{}\"", file.original_name),
+        );
+        if let Err(e) = self.record_attached_file(&file, &entry).await {
+            let _ = fs::remove_file(long_path(&destination)).await;
+            return Err(e);
+        }
+
+        Ok(file)
+    }
+
+    async fn record_attached_file(&self, file: &ProjectFile, audit: &AuditEntry) -> Result<()> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        ProjectFileEntity::insert(project_file::ActiveModel::from(file))
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to record project file: {e}")))?;
+        Self::append_audit(&txn, audit).await?;
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// Attaches a file the app generated, such as a report. Generating an
+    /// identical file again returns the existing attachment.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn attach_generated_file(&self, project_id: &str, path: &Path) -> Result<ProjectFile> {
+        let (_, sha256) = hash_file(path).await?;
+        if let Some(existing) = self.find_file_by_hash(project_id, &sha256).await? {
+            return Ok(existing);
+        }
+        self.add_file_to_project(project_id, path).await
+    }
+
+    /// An attached file, in any project, with the same contents as `path`.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn find_attached_copy(&self, path: &Path) -> Result<Option<ProjectFile>> {
+        let (_, sha256) = hash_file(path).await?;
+        ProjectFileEntity::find()
+            .filter(project_file::Column::Sha256.eq(sha256))
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to look up project file: {e}")))?
+            .map(ProjectFile::try_from)
+            .transpose()
+    }
+
+    async fn find_file_by_hash(&self, project_id: &str, sha256: &str) -> Result<Option<ProjectFile>> {
+        ProjectFileEntity::find()
+            .filter(project_file::Column::ProjectId.eq(project_id))
+            .filter(project_file::Column::Sha256.eq(sha256))
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to look up project file: {}", e)))?
+            .map(ProjectFile::try_from)
+            .transpose()
+    }
+
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn list_project_files(&self, project_id: &str) -> Result<Vec<ProjectFile>> {
+        if !self.storage.read_only {
+            let files_dir = self.projects_dir.join(project_id).join(FILES_DIR);
+            self.recover_files(project_id, &files_dir).await;
+        }
+
+        ProjectFileEntity::find()
+            .filter(project_file::Column::ProjectId.eq(project_id))
+            .order_by_asc(project_file::Column::AddedAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to list project files: {}", e)))?
+            .into_iter()
+            .map(ProjectFile::try_from)
+            .collect()
+    }
+
+    /// Folder holding a project's attached file copies
+    pub fn project_files_dir(&self, project_id: &str) -> PathBuf {
+        self.projects_dir.join(project_id).join(FILES_DIR)
+    }
+
+    /// An attached file and the path of its stored copy. Fails with
+    /// `NotFound` unless the file belongs to the project.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id, file_id = %file_id))]
+    pub async fn find_project_file(
+        &self,
+        project_id: &str,
+        file_id: &str,
+    ) -> Result<(ProjectFile, PathBuf)> {
+        let row = ProjectFileEntity::find_by_id(file_id.to_string())
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to load project file: {e}")))?
+            .filter(|row| row.project_id == project_id)
+            .ok_or_else(|| {
+                AppError::NotFound(format!("File {file_id} not found in project {project_id}"))
+            })?;
+        let file = ProjectFile::try_from(row)?;
+        let stored = self.projects_dir.join(project_id).join(&file.stored_path);
+        Ok((file, stored))
+    }
+
+    /// Re-reads an attached file's stored copy after it changed on disk,
+    /// updating its size and hash or flagging it as missing when the copy
+    /// is gone. Returns the updated file, or `None` if nothing changed.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id, file_id = %file_id))]
+    pub async fn refresh_file_metadata(
+        &self,
+        project_id: &str,
+        file_id: &str,
+    ) -> Result<Option<ProjectFile>> {
+        let row = ProjectFileEntity::find_by_id(file_id.to_string())
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to load project file: {e}")))?
+            .filter(|row| row.project_id == project_id)
+            .ok_or_else(|| {
+                AppError::NotFound(format!("File {file_id} not found in project {project_id}"))
+            })?;
+        let mut file = ProjectFile::try_from(row)?;
+
+        let stored = self.projects_dir.join(project_id).join(&file.stored_path);
+        match fs::metadata(long_path(&stored)).await {
+            Ok(_) => {
+                let (size, sha256) = hash_file(&stored).await?;
+                if !file.missing && size == file.size && sha256 == file.sha256 {
+                    return Ok(None);
+                }
+                file.size = size;
+                file.sha256 = sha256;
+                file.missing = false;
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                if file.missing {
+                    return Ok(None);
+                }
+                file.missing = true;
+            }
+            Err(e) => return Err(fs_error("read file metadata", &stored, e)),
+        }
+
+        ProjectFileEntity::update(project_file::ActiveModel::from(&file))
+            .exec(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to update project file: {e}")))?;
+
+        Ok(Some(file))
+    }
+
+    /// Deletes an attached file's row and its copy on disk.
+    ///
+    /// The copy is moved aside before the row is deleted and only removed
+    /// once the deletion has committed. If anything fails in between, the
+    /// copy is moved back (here, or by `recover_files` after a crash).
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id, file_id = %file_id))]
+    pub async fn remove_file_from_project(&self, project_id: &str, file_id: &str) -> Result<()> {
+        self.ensure_project_writable(project_id).await?;
+
+        let row = ProjectFileEntity::find_by_id(file_id.to_string())
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to load project file: {}", e)))?
+            .filter(|row| row.project_id == project_id)
+            .ok_or_else(|| {
+                AppError::NotFound(format!("File {} not found in project {}", file_id, project_id))
+            })?;
+
+        let stored = self.projects_dir.join(project_id).join(&row.stored_path);
+        let removing = removing_path(&stored);
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        ProjectFileEntity::delete_by_id(row.id.clone())
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to delete project file: {}", e)))?;
+        let project_uuid = Uuid::parse_str(project_id)
+            .map_err(|_| AppError::Validation(format!("Invalid project id: '{project_id}'")))?;
+        let entry = AuditEntry::new(
+            project_uuid,
+            AuditAction::FileRemoved,
+            format!("Removed \"{}\"", row.original_name),
+        );
+        Self::append_audit(&txn, &entry).await?;
+
+        // A copy that was already deleted by hand only leaves the row to drop
+        match retry_transient(|| fs::rename(long_path(&stored), long_path(&removing))).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(fs_error("remove project file", &stored, e)),
+        }
+
+        if let Err(e) = txn.commit().await {
+            let _ = fs::rename(long_path(&removing), long_path(&stored)).await;
+            return Err(AppError::Database(e.to_string()));
+        }
+
+        // Left behind on failure; recover_files deletes it later since the row is gone
+        let _ = fs::remove_file(long_path(&removing)).await;
+
+        Ok(())
+    }
+
+    /// Cleans up after an add or remove that was interrupted by a crash:
+    /// half-written copies are deleted, and copies set aside for removal are
+    /// restored if their row survived or deleted if it did not.
+    async fn recover_files(&self, project_id: &str, files_dir: &Path) {
+        let Ok(mut entries) = fs::read_dir(long_path(files_dir)).await else {
+            return;
+        };
+
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            if name.ends_with(PARTIAL_SUFFIX) {
+                let _ = fs::remove_file(entry.path()).await;
+            } else if let Some(original) = name.strip_suffix(REMOVING_SUFFIX) {
+                let stored_path = format!("{FILES_DIR}/{original}");
+                let row_exists = ProjectFileEntity::find()
+                    .filter(project_file::Column::ProjectId.eq(project_id))
+                    .filter(project_file::Column::StoredPath.eq(&stored_path))
+                    .one(&self.db)
+                    .await
+                    .ok()
+                    .flatten()
+                    .is_some();
+
+                if row_exists {
+                    let _ = fs::rename(entry.path(), long_path(&files_dir.join(original))).await;
+                } else {
+                    let _ = fs::remove_file(entry.path()).await;
+                }
+            }
+        }
+    }
+
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn save_lateral_estimate(
+        &self,
+        project_id: &str,
+        estimate: &LateralEstimate,
+    ) -> Result<()> {
+        self.ensure_project_writable(project_id).await?;
+
+        if self.load_project(project_id).await?.is_none() {
+            return Err(AppError::NotFound(format!("Project {} not found", project_id)));
+        }
+
+        let estimate_path = self.projects_dir.join(project_id).join("estimate.json");
+        let content = serde_json::to_string_pretty(estimate)
+            .map_err(|e| AppError::Database(format!("Failed to serialize estimate: {}", e)))?;
+        write_atomic(&estimate_path, &content)
+            .await
+            .map_err(|e| fs_error("write estimate file", &estimate_path, e))?;
+
+        Ok(())
+    }
+
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn load_lateral_estimate(&self, project_id: &str) -> Result<Option<LateralEstimate>> {
+        let estimate_path = self.projects_dir.join(project_id).join("estimate.json");
+
+        if !long_path(&estimate_path).exists() {
+            return Ok(None);
+        }
+
+        let content = retry_transient(|| fs::read_to_string(long_path(&estimate_path)))
+            .await
+            .map_err(|e| fs_error("read estimate file", &estimate_path, e))?;
+
+        let estimate = serde_json::from_str(&content)
+            .map_err(|e| AppError::Database(format!("Failed to parse estimate: {}", e)))?;
+
+        Ok(Some(estimate))
+    }
+
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn save_seismic_base_shear(
+        &self,
+        project_id: &str,
+        base_shear: &SeismicBaseShear,
+    ) -> Result<()> {
+        self.ensure_project_writable(project_id).await?;
+
+        if self.load_project(project_id).await?.is_none() {
+            return Err(AppError::NotFound(format!("Project {} not found", project_id)));
+        }
+
+        let seismic_path = self.projects_dir.join(project_id).join("seismic.json");
+        let content = serde_json::to_string_pretty(base_shear)
+            .map_err(|e| AppError::Database(format!("Failed to serialize base shear: {}", e)))?;
+        write_atomic(&seismic_path, &content)
+            .await
+            .map_err(|e| fs_error("write base shear file", &seismic_path, e))?;
+
+        Ok(())
+    }
+
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn load_seismic_base_shear(
+        &self,
+        project_id: &str,
+    ) -> Result<Option<SeismicBaseShear>> {
+        let seismic_path = self.projects_dir.join(project_id).join("seismic.json");
+
+        if !long_path(&seismic_path).exists() {
+            return Ok(None);
+        }
+
+        let content = retry_transient(|| fs::read_to_string(long_path(&seismic_path)))
+            .await
+            .map_err(|e| fs_error("read base shear file", &seismic_path, e))?;
+
+        let base_shear = serde_json::from_str(&content)
+            .map_err(|e| AppError::Database(format!("Failed to parse base shear: {}", e)))?;
+
+        Ok(Some(base_shear))
+    }
+
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn save_wind_pressures(
+        &self,
+        project_id: &str,
+        pressures: &WindPressures,
+    ) -> Result<()> {
+        self.ensure_project_writable(project_id).await?;
+
+        if self.load_project(project_id).await?.is_none() {
+            return Err(AppError::NotFound(format!("Project {} not found", project_id)));
+        }
+
+        let wind_path = self.projects_dir.join(project_id).join("wind.json");
+        let content = serde_json::to_string_pretty(pressures)
+            .map_err(|e| AppError::Database(format!("Failed to serialize wind pressures: {}", e)))?;
+        write_atomic(&wind_path, &content)
+            .await
+            .map_err(|e| fs_error("write wind pressure file", &wind_path, e))?;
+
+        Ok(())
+    }
+
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn load_wind_pressures(&self, project_id: &str) -> Result<Option<WindPressures>> {
+        let wind_path = self.projects_dir.join(project_id).join("wind.json");
+
+        if !long_path(&wind_path).exists() {
+            return Ok(None);
+        }
+
+        let content = retry_transient(|| fs::read_to_string(long_path(&wind_path)))
+            .await
+            .map_err(|e| fs_error("read wind pressure file", &wind_path, e))?;
+
+        let pressures = serde_json::from_str(&content)
+            .map_err(|e| AppError::Database(format!("Failed to parse wind pressures: {}", e)))?;
+
+        Ok(Some(pressures))
+    }
+
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn save_load_combinations(
+        &self,
+        project_id: &str,
+        combinations: &[LoadCombination],
+    ) -> Result<()> {
+        self.ensure_project_writable(project_id).await?;
+
+        if self.load_project(project_id).await?.is_none() {
+            return Err(AppError::NotFound(format!("Project {} not found", project_id)));
+        }
+
+        let combinations_path = self.projects_dir.join(project_id).join("combinations.json");
+        let content = serde_json::to_string_pretty(combinations)
+            .map_err(|e| AppError::Database(format!("Failed to serialize combinations: {}", e)))?;
+        write_atomic(&combinations_path, &content)
+            .await
+            .map_err(|e| fs_error("write combinations file", &combinations_path, e))?;
+
+        Ok(())
+    }
+
+    /// Returns the saved combinations, or an empty list if none were generated.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn load_load_combinations(&self, project_id: &str) -> Result<Vec<LoadCombination>> {
+        let combinations_path = self.projects_dir.join(project_id).join("combinations.json");
+
+        if !long_path(&combinations_path).exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = retry_transient(|| fs::read_to_string(long_path(&combinations_path)))
+            .await
+            .map_err(|e| fs_error("read combinations file", &combinations_path, e))?;
+
+        serde_json::from_str(&content)
+            .map_err(|e| AppError::Database(format!("Failed to parse combinations: {}", e)))
+    }
+
+    /// Saves the load cases the project's combinations are generated from.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn save_load_cases(&self, project_id: &str, cases: &[LoadCase]) -> Result<()> {
+        self.ensure_project_writable(project_id).await?;
+
+        if self.load_project(project_id).await?.is_none() {
+            return Err(AppError::NotFound(format!("Project {project_id} not found")));
+        }
+
+        let cases_path = self.projects_dir.join(project_id).join("load_cases.json");
+        let content = serde_json::to_string_pretty(cases)
+            .map_err(|e| AppError::Database(format!("Failed to serialize load cases: {e}")))?;
+        write_atomic(&cases_path, &content)
+            .await
+            .map_err(|e| fs_error("write load cases file", &cases_path, e))?;
+
+        Ok(())
+    }
+
+    /// Returns the saved load cases, or an empty list if none were saved.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn load_load_cases(&self, project_id: &str) -> Result<Vec<LoadCase>> {
+        let cases_path = self.projects_dir.join(project_id).join("load_cases.json");
+
+        if !long_path(&cases_path).exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = retry_transient(|| fs::read_to_string(long_path(&cases_path)))
+            .await
+            .map_err(|e| fs_error("read load cases file", &cases_path, e))?;
+
+        serde_json::from_str(&content)
+            .map_err(|e| AppError::Database(format!("Failed to parse load cases: {e}")))
+    }
+
+    /// Writes a generated report into the project's `reports/` folder,
+    /// replacing an earlier report of the same name.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn write_project_report(
+        &self,
+        project_id: &str,
+        file_name: &str,
+        content: &str,
+    ) -> Result<PathBuf> {
+        self.ensure_project_writable(project_id).await?;
+
+        if self.load_project(project_id).await?.is_none() {
+            return Err(AppError::NotFound(format!("Project {project_id} not found")));
+        }
+
+        let reports_dir = self.projects_dir.join(project_id).join(REPORTS_DIR);
+        fs::create_dir_all(long_path(&reports_dir))
+            .await
+            .map_err(|e| fs_error("create reports directory", &reports_dir, e))?;
+
+        let report_path = reports_dir.join(file_name);
+        write_atomic(&report_path, content)
+            .await
+            .map_err(|e| fs_error("write report", &report_path, e))?;
+
+        Ok(report_path)
+    }
+
+    /// Stores an imported ETABS model alongside the project.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn save_etabs_model(&self, project_id: &str, model: &EtabsModel) -> Result<()> {
+        self.ensure_project_writable(project_id).await?;
+
+        if self.load_project(project_id).await?.is_none() {
+            return Err(AppError::NotFound(format!("Project {} not found", project_id)));
+        }
+
+        let model_path = self.projects_dir.join(project_id).join("model.json");
+        let content = serde_json::to_string_pretty(model)
+            .map_err(|e| AppError::Database(format!("Failed to serialize ETABS model: {}", e)))?;
+        write_atomic(&model_path, &content)
+            .await
+            .map_err(|e| fs_error("write model file", &model_path, e))?;
+
+        Ok(())
+    }
+
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn load_etabs_model(&self, project_id: &str) -> Result<Option<EtabsModel>> {
+        let model_path = self.projects_dir.join(project_id).join("model.json");
+
+        if !long_path(&model_path).exists() {
+            return Ok(None);
+        }
+
+        let content = retry_transient(|| fs::read_to_string(long_path(&model_path)))
+            .await
+            .map_err(|e| fs_error("read model file", &model_path, e))?;
+
+        let model = serde_json::from_str(&content)
+            .map_err(|e| AppError::Database(format!("Failed to parse ETABS model: {}", e)))?;
+
+        Ok(Some(model))
+    }
+}
+
+fn removing_path(stored: &Path) -> PathBuf {
+    let name = stored
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    stored.with_file_name(format!("{name}{REMOVING_SUFFIX}"))
+}
+
+/// Returns the size and lowercase hex SHA-256 of a file.
+async fn hash_file(path: &Path) -> Result<(u64, String)> {
+    let target = long_path(path);
+    let hashed = tokio::task::spawn_blocking(move || -> io::Result<(u64, String)> {
+        let mut file = std::fs::File::open(target)?;
+        let mut hasher = Sha256::new();
+        let size = io::copy(&mut file, &mut hasher)?;
+        Ok((size, format!("{:x}", hasher.finalize())))
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Hashing task failed: {}", e)))?;
+
+    hashed.map_err(|e| fs_error("hash file", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_long_unicode_projects_dir_round_trip() {
+        let temp = tempfile::tempdir().expect("Failed to create temp dir");
+
+        let mut projects_dir = temp.path().join("OneDrive - Công ty Kết cấu");
+        while projects_dir.as_os_str().len() <= paths::MAX_PATH {
+            projects_dir = projects_dir.join("Dự án Tháp Đông");
+        }
+
+        let db = Database::new("sqlite::memory:", projects_dir.to_str().unwrap())
+            .await
+            .expect("Failed to open database");
+
+        let project = Project::new("Tòa nhà A".to_string(), "Kết cấu khung".to_string());
+        db.save_project(&project).await.expect("Failed to save project");
+
+        let loaded = db
+            .load_project(&project.id.to_string())
+            .await
+            .expect("Failed to load project")
+            .expect("Project should exist");
+        assert_eq!(loaded.name, "Tòa nhà A");
+
+        let projects = db.list_projects().await.expect("Failed to list projects");
+        assert_eq!(projects.len(), 1);
+    }
+}
@@ -0,0 +1,336 @@
+//! Advisory project locks, so app instances sharing a data directory don't
+//! write the same project at once. See [`ext_core::project_lock`].
+//!
+//! The lock file in the project folder is what's checked: instances with a
+//! registry of their own still share the project folders. The
+//! `project_locks` table mirrors it.
+
+use crate::entities::prelude::*;
+use crate::entities::project_lock;
+use crate::paths::{fs_error, long_path};
+use crate::storage::write_atomic;
+use crate::Database;
+use chrono::{DateTime, Duration, Utc};
+use ext_core::project_lock::ProjectLock;
+use ext_error::{AppError, Result};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use std::io;
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use std::sync::PoisonError;
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// Lock file inside each project directory
+pub(crate) const LOCK_FILE: &str = "project.lock";
+
+/// Holder shown for a lock file that can't be read
+const UNKNOWN_HOLDER: &str = "another instance";
+
+/// Name of this machine, as recorded in the locks it takes
+pub fn host_name() -> String {
+    System::host_name().unwrap_or_else(|| "unknown host".to_string())
+}
+
+/// Holder name for locks taken by this user on this machine, e.g.
+/// "jane@WS-042"
+pub fn default_lock_holder() -> String {
+    let user = std::env::var("USERNAME")
+        .or_else(|_| std::env::var("USER"))
+        .unwrap_or_else(|_| "unknown".to_string());
+    format!("{user}@{}", host_name())
+}
+
+impl Database {
+    /// Takes the lock on a project for `holder`, or renews it if this
+    /// instance holds it already. Fails with [`AppError::Locked`] while
+    /// another instance holds a lock that isn't stale; a stale one is taken
+    /// over.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn acquire_project_lock(
+        &self,
+        project_id: &str,
+        holder: String,
+    ) -> Result<ProjectLock> {
+        self.ensure_writable()?;
+
+        let project = self
+            .load_project(project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project {project_id} not found")))?;
+
+        let lock = ProjectLock::new(project.id, holder, std::process::id(), self.host.clone());
+        let lock_path = self.projects_dir.join(project_id).join(LOCK_FILE);
+        let content = serde_json::to_string_pretty(&lock)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize project lock: {e}")))?;
+
+        match self.read_lock_file(project_id).await? {
+            Some(current) if self.holds(&current) => {
+                write_atomic(&lock_path, &content)
+                    .await
+                    .map_err(|e| fs_error("write lock file", &lock_path, e))?;
+            }
+            current => {
+                if let Some(current) = current {
+                    if !self.is_stale(&current) {
+                        return Err(AppError::Locked(current.holder));
+                    }
+                    tracing::warn!(
+                        "Taking over stale lock on project {project_id} held by {} (pid {} on {})",
+                        current.holder,
+                        current.pid,
+                        current.host
+                    );
+                    remove_stale_lock(&lock_path, &current).await?;
+                }
+
+                // Of two instances taking a free project at once, only one
+                // creates the file
+                match create_lock_file(&lock_path, &content).await {
+                    Ok(()) => {}
+                    Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                        let holder = self
+                            .read_lock_file(project_id)
+                            .await?
+                            .map(|lock| lock.holder);
+                        return Err(AppError::Locked(
+                            holder.unwrap_or_else(|| UNKNOWN_HOLDER.to_string()),
+                        ));
+                    }
+                    Err(e) => return Err(fs_error("create lock file", &lock_path, e)),
+                }
+            }
+        }
+
+        let mirrored = ProjectLockEntity::insert(project_lock::ActiveModel::from(&lock))
+            .on_conflict(
+                OnConflict::column(project_lock::Column::ProjectId)
+                    .update_columns([
+                        project_lock::Column::Holder,
+                        project_lock::Column::Pid,
+                        project_lock::Column::Host,
+                        project_lock::Column::AcquiredAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(&self.db)
+            .await;
+        if let Err(e) = mirrored {
+            let _ = fs::remove_file(long_path(&lock_path)).await;
+            return Err(AppError::Database(format!(
+                "Failed to save project lock: {e}"
+            )));
+        }
+
+        self.held_locks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(project_id.to_string(), lock.holder.clone());
+        Ok(lock)
+    }
+
+    /// Releases this instance's lock on a project. A lock held by another
+    /// instance, or already taken over, is left alone.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn release_project_lock(&self, project_id: &str) -> Result<()> {
+        let holder = self
+            .held_locks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(project_id);
+        let Some(holder) = holder else {
+            return Ok(());
+        };
+
+        ProjectLockEntity::delete_many()
+            .filter(project_lock::Column::ProjectId.eq(project_id))
+            .filter(project_lock::Column::Holder.eq(holder.as_str()))
+            .filter(project_lock::Column::Pid.eq(i64::from(std::process::id())))
+            .filter(project_lock::Column::Host.eq(self.host.as_str()))
+            .exec(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to release project lock: {e}")))?;
+
+        let ours = self.read_lock_file(project_id).await?.is_some_and(|lock| {
+            lock.holder == holder && lock.pid == std::process::id() && lock.host == self.host
+        });
+        if !ours {
+            return Ok(());
+        }
+        let lock_path = self.projects_dir.join(project_id).join(LOCK_FILE);
+        match fs::remove_file(long_path(&lock_path)).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(fs_error("remove lock file", &lock_path, e)),
+        }
+    }
+
+    /// Releases every lock this instance holds, e.g. when the app exits.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn release_all_project_locks(&self) -> Result<()> {
+        let held: Vec<String> = self
+            .held_locks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .keys()
+            .cloned()
+            .collect();
+        for project_id in held {
+            self.release_project_lock(&project_id).await?;
+        }
+        Ok(())
+    }
+
+    /// The lock on a project, stale or not.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn load_project_lock(&self, project_id: &str) -> Result<Option<ProjectLock>> {
+        self.read_lock_file(project_id).await
+    }
+
+    /// Reads the project's lock file. One that can't be parsed, e.g. while
+    /// another instance is still writing it, counts as a lock by an
+    /// unknown holder, taken when the file was last modified.
+    async fn read_lock_file(&self, project_id: &str) -> Result<Option<ProjectLock>> {
+        let lock_path = self.projects_dir.join(project_id).join(LOCK_FILE);
+        read_lock(&lock_path, project_id).await
+    }
+
+    /// Sets how old another instance's lock gets before it may be taken
+    /// over.
+    pub fn set_lock_timeout(&self, timeout: Duration) {
+        self.lock_timeout_secs
+            .store(timeout.num_seconds().max(0), Ordering::Relaxed);
+    }
+
+    /// [`Database::ensure_writable`], and also fails while another instance
+    /// holds the project's lock.
+    pub(crate) async fn ensure_project_writable(&self, project_id: &str) -> Result<()> {
+        self.ensure_writable()?;
+        self.check_project_lock(project_id).await
+    }
+
+    /// Fails with [`AppError::Locked`] while another instance holds a lock
+    /// on the project that isn't stale.
+    pub(crate) async fn check_project_lock(&self, project_id: &str) -> Result<()> {
+        if let Some(lock) = self.load_project_lock(project_id).await?
+            && !self.holds(&lock)
+            && !self.is_stale(&lock)
+        {
+            return Err(AppError::Locked(lock.holder));
+        }
+        Ok(())
+    }
+
+    /// Forgets the lock of a deleted project; its row goes with the project.
+    pub(crate) fn forget_project_lock(&self, project_id: &str) {
+        self.held_locks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(project_id);
+    }
+
+    fn holds(&self, lock: &ProjectLock) -> bool {
+        let held = self
+            .held_locks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        held.get(&lock.project_id.to_string()) == Some(&lock.holder)
+            && lock.pid == std::process::id()
+            && lock.host == self.host
+    }
+
+    fn is_stale(&self, lock: &ProjectLock) -> bool {
+        let timeout = Duration::seconds(self.lock_timeout_secs.load(Ordering::Relaxed));
+        lock.is_stale(&self.host, Utc::now(), timeout, process_running)
+    }
+}
+
+/// Reads a lock file of project `project_id`; see
+/// `Database::read_lock_file`.
+async fn read_lock(path: &Path, project_id: &str) -> Result<Option<ProjectLock>> {
+    let content = match fs::read_to_string(long_path(path)).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(fs_error("read lock file", path, e)),
+    };
+    if let Ok(lock) = serde_json::from_str(&content) {
+        return Ok(Some(lock));
+    }
+
+    let project_id = Uuid::parse_str(project_id)
+        .map_err(|_| AppError::Validation(format!("Invalid project id: '{project_id}'")))?;
+    let modified = fs::metadata(long_path(path))
+        .await
+        .and_then(|metadata| metadata.modified())
+        .map(DateTime::<Utc>::from)
+        .unwrap_or_else(|_| Utc::now());
+    Ok(Some(ProjectLock {
+        acquired_at: modified,
+        ..ProjectLock::new(project_id, UNKNOWN_HOLDER.to_string(), 0, String::new())
+    }))
+}
+
+/// Moves the stale lock `stale` out of the way before it is taken over.
+///
+/// Deleting it outright would race: of two instances taking over the same
+/// lock, the second would delete the lock the first had just created. The
+/// file is renamed to a name of its own instead, which only one instance
+/// can do, and is only deleted if it still holds `stale`. A newer lock
+/// moved by mistake is put back, unless the project was locked again in
+/// the meantime.
+async fn remove_stale_lock(lock_path: &Path, stale: &ProjectLock) -> Result<()> {
+    let tombstone = lock_path.with_file_name(format!("{LOCK_FILE}.{}.stale", Uuid::new_v4()));
+    match fs::rename(long_path(lock_path), long_path(&tombstone)).await {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(fs_error("move stale lock file", lock_path, e)),
+    }
+
+    let moved = read_lock(&tombstone, &stale.project_id.to_string()).await?;
+    let still_stale = moved.is_some_and(|moved| {
+        moved.holder == stale.holder
+            && moved.pid == stale.pid
+            && moved.host == stale.host
+            && moved.acquired_at == stale.acquired_at
+    });
+    if !still_stale {
+        // Linking fails instead of replacing a lock taken since
+        match fs::hard_link(long_path(&tombstone), long_path(lock_path)).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(_) => {
+                return fs::rename(long_path(&tombstone), long_path(lock_path))
+                    .await
+                    .map_err(|e| fs_error("restore lock file", lock_path, e));
+            }
+        }
+    }
+
+    match fs::remove_file(long_path(&tombstone)).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(fs_error("remove lock file", &tombstone, e)),
+    }
+}
+
+/// Creates the lock file, failing with `AlreadyExists` when there is one.
+async fn create_lock_file(path: &Path, content: &str) -> io::Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(long_path(path))
+        .await?;
+    file.write_all(content.as_bytes()).await?;
+    file.sync_all().await
+}
+
+fn process_running(pid: u32) -> bool {
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+    system.process(pid).is_some()
+}
@@ -0,0 +1,212 @@
+//! Merging a duplicate project into the one that is kept. Attached files,
+//! notes, analysis runs and tags move over; project data the kept project
+//! already has is only replaced when [`MergeOptions`] asks for it. The
+//! absorbed project is moved to the trash, where its backups stay with it.
+
+use crate::entities::prelude::*;
+use crate::entities::{analysis_run, note, project_file, project_tag};
+use crate::paths::{fs_error, long_path};
+use crate::storage::retry_transient;
+use crate::{Database, FILES_DIR};
+use ext_core::audit::{AuditAction, AuditEntry};
+use ext_core::duplicates::{MergeOptions, next_available_name};
+use ext_core::{Project, ProjectStatus};
+use ext_error::{AppError, Result};
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, Set, TransactionTrait};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tracing::instrument;
+
+impl Database {
+    /// Moves the attached files, notes, analysis runs and tags of project
+    /// `absorb_id` into project `keep_id`, then trashes the absorbed
+    /// project. Both logs record the merge. Runs whose label the kept
+    /// project already uses get a " (2)"-style suffix.
+    #[instrument(level = "debug", skip_all, fields(keep_id = %keep_id, absorb_id = %absorb_id))]
+    pub async fn merge_projects(
+        &self,
+        keep_id: &str,
+        absorb_id: &str,
+        options: &MergeOptions,
+    ) -> Result<Project> {
+        if keep_id == absorb_id {
+            return Err(AppError::Validation(
+                "A project cannot be merged into itself".to_string(),
+            ));
+        }
+        self.ensure_project_writable(keep_id).await?;
+        self.ensure_project_writable(absorb_id).await?;
+
+        let keep = self
+            .load_project(keep_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project {keep_id} not found")))?;
+        let absorb = self
+            .load_project(absorb_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project {absorb_id} not found")))?;
+        if keep.status == ProjectStatus::Trashed {
+            return Err(AppError::Validation(format!(
+                "\"{}\" is in the trash; restore it before merging into it",
+                keep.name
+            )));
+        }
+
+        let keep_dir = self.projects_dir.join(keep_id);
+        let absorb_dir = self.projects_dir.join(absorb_id);
+        let moved = self.move_files(absorb_id, &absorb_dir, &keep_dir).await?;
+
+        let committed = self.merge_rows(&keep, &absorb).await;
+        if let Err(e) = committed {
+            for (from, to) in moved.iter().rev() {
+                if let Err(undo) = fs::rename(long_path(to), long_path(from)).await {
+                    tracing::warn!("Failed to move '{}' back: {undo}", to.display());
+                }
+            }
+            return Err(e);
+        }
+
+        let data_files = [
+            ("model.json", options.replace_model),
+            ("load_cases.json", options.replace_combinations),
+            ("combinations.json", options.replace_combinations),
+            ("estimate.json", options.replace_calculations),
+            ("seismic.json", options.replace_calculations),
+            ("wind.json", options.replace_calculations),
+        ];
+        for (data_file, replace) in data_files {
+            let from = absorb_dir.join(data_file);
+            let to = keep_dir.join(data_file);
+            if !long_path(&from).exists() || (long_path(&to).exists() && !replace) {
+                continue;
+            }
+            retry_transient(|| fs::copy(long_path(&from), long_path(&to)))
+                .await
+                .map_err(|e| fs_error("copy project data", &from, e))?;
+        }
+
+        self.set_project_status(absorb_id, ProjectStatus::Trashed)
+            .await?;
+        self.load_project(keep_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Project {keep_id} not found")))
+    }
+
+    /// Moves the stored copies of a project's attached files into another
+    /// project folder, returning the moves made. Stored names start with
+    /// the file's id, so they cannot clash in the kept project.
+    async fn move_files(
+        &self,
+        project_id: &str,
+        from_dir: &Path,
+        to_dir: &Path,
+    ) -> Result<Vec<(PathBuf, PathBuf)>> {
+        let files_dir = to_dir.join(FILES_DIR);
+        fs::create_dir_all(long_path(&files_dir))
+            .await
+            .map_err(|e| fs_error("create files directory", &files_dir, e))?;
+
+        let mut moved = Vec::new();
+        for file in self.list_project_files(project_id).await? {
+            // The row moves regardless; diagnostics already flag it as missing
+            if file.missing {
+                continue;
+            }
+            let from = from_dir.join(&file.stored_path);
+            let to = to_dir.join(&file.stored_path);
+            match retry_transient(|| fs::rename(long_path(&from), long_path(&to))).await {
+                Ok(()) => moved.push((from, to)),
+                Err(e) => {
+                    for (from, to) in moved.iter().rev() {
+                        let _ = fs::rename(long_path(to), long_path(from)).await;
+                    }
+                    return Err(fs_error("move file", &from, e));
+                }
+            }
+        }
+        Ok(moved)
+    }
+
+    /// Re-points the absorbed project's rows at the kept project and
+    /// records the merge in both audit logs, in one transaction.
+    async fn merge_rows(&self, keep: &Project, absorb: &Project) -> Result<()> {
+        let (keep_id, absorb_id) = (keep.id.to_string(), absorb.id.to_string());
+        let mut labels: Vec<String> = self
+            .list_analysis_runs(&keep_id)
+            .await?
+            .into_iter()
+            .map(|run| run.label)
+            .collect();
+        let runs = self.list_analysis_runs(&absorb_id).await?;
+        let keep_tags = self.list_project_tags(&keep_id).await?;
+        let new_tags: Vec<_> = self
+            .list_project_tags(&absorb_id)
+            .await?
+            .into_iter()
+            .filter(|tag| !keep_tags.contains(tag))
+            .collect();
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        ProjectFileEntity::update_many()
+            .col_expr(
+                project_file::Column::ProjectId,
+                Expr::value(keep_id.as_str()),
+            )
+            .filter(project_file::Column::ProjectId.eq(absorb_id.as_str()))
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to move project files: {e}")))?;
+        NoteEntity::update_many()
+            .col_expr(note::Column::ProjectId, Expr::value(keep_id.as_str()))
+            .filter(note::Column::ProjectId.eq(absorb_id.as_str()))
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to move notes: {e}")))?;
+
+        for mut run in runs {
+            run.project_id = keep.id;
+            run.label = next_available_name(&run.label, &labels);
+            labels.push(run.label.clone());
+            AnalysisRunEntity::update(analysis_run::ActiveModel::from(&run))
+                .exec(&txn)
+                .await
+                .map_err(|e| AppError::Database(format!("Failed to move analysis run: {e}")))?;
+        }
+
+        if !new_tags.is_empty() {
+            ProjectTagEntity::insert_many(new_tags.iter().map(|tag| project_tag::ActiveModel {
+                project_id: Set(keep_id.clone()),
+                tag_id: Set(tag.id.to_string()),
+            }))
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to tag project: {e}")))?;
+        }
+
+        let merged_in = AuditEntry::new(
+            keep.id,
+            AuditAction::Merged,
+            format!(
+                "Merged \"{}\" ({}) into this project",
+                absorb.name, absorb.id
+            ),
+        );
+        let merged_out = AuditEntry::new(
+            absorb.id,
+            AuditAction::Merged,
+            format!("Merged into \"{}\" ({})", keep.name, keep.id),
+        );
+        Self::append_audit(&txn, &merged_in).await?;
+        Self::append_audit(&txn, &merged_out).await?;
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+}
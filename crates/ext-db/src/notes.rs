@@ -0,0 +1,182 @@
+//! Project notes and the revisions kept each time one is edited.
+
+use crate::Database;
+use crate::entities::prelude::*;
+use crate::entities::{note, note_revision};
+use ext_core::notes::{Note, NoteRevision};
+use ext_error::{AppError, Result};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, TransactionTrait,
+};
+use tracing::instrument;
+
+impl Database {
+    #[instrument(level = "debug", skip_all, fields(project_id = %note.project_id))]
+    pub async fn create_note(&self, note: &Note) -> Result<()> {
+        self.ensure_project_writable(&note.project_id.to_string())
+            .await?;
+        note.validate()?;
+
+        let project_id = note.project_id.to_string();
+        if self.load_project(&project_id).await?.is_none() {
+            return Err(AppError::NotFound(format!(
+                "Project {project_id} not found"
+            )));
+        }
+
+        NoteEntity::insert(note::ActiveModel::from(note))
+            .exec(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to save note: {e}")))?;
+
+        Ok(())
+    }
+
+    #[instrument(level = "debug", skip_all, fields(note_id = %note_id))]
+    pub async fn load_note(&self, note_id: &str) -> Result<Option<Note>> {
+        NoteEntity::find_by_id(note_id.to_string())
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to load note: {e}")))?
+            .map(Note::try_from)
+            .transpose()
+    }
+
+    /// Notes of a project, most recently edited first.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project_id))]
+    pub async fn list_notes(&self, project_id: &str) -> Result<Vec<Note>> {
+        NoteEntity::find()
+            .filter(note::Column::ProjectId.eq(project_id))
+            .order_by_desc(note::Column::UpdatedAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to list notes: {e}")))?
+            .into_iter()
+            .map(Note::try_from)
+            .collect()
+    }
+
+    /// Replaces the stored note with `note`, keeping the stored version as a
+    /// revision.
+    #[instrument(level = "debug", skip_all, fields(note_id = %note.id))]
+    pub async fn update_note(&self, note: &Note) -> Result<()> {
+        self.ensure_writable()?;
+        note.validate()?;
+
+        let note_id = note.id.to_string();
+        let previous = self
+            .load_note(&note_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Note {note_id} not found")))?;
+        self.check_project_lock(&previous.project_id.to_string())
+            .await?;
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        NoteRevisionEntity::insert(note_revision::ActiveModel::from(&previous.to_revision()))
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to save note revision: {e}")))?;
+
+        NoteEntity::update(note::ActiveModel::from(note))
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to update note: {e}")))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Removes a note together with its revisions.
+    #[instrument(level = "debug", skip_all, fields(note_id = %note_id))]
+    pub async fn delete_note(&self, note_id: &str) -> Result<()> {
+        self.ensure_writable()?;
+        if let Some(note) = self.load_note(note_id).await? {
+            self.check_project_lock(&note.project_id.to_string())
+                .await?;
+        }
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        NoteRevisionEntity::delete_many()
+            .filter(note_revision::Column::NoteId.eq(note_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to delete note revisions: {e}")))?;
+
+        let deleted = NoteEntity::delete_by_id(note_id.to_string())
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to delete note: {e}")))?;
+
+        if deleted.rows_affected == 0 {
+            return Err(AppError::NotFound(format!("Note {note_id} not found")));
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn delete_notes<C: ConnectionTrait>(conn: &C, project_id: &str) -> Result<()> {
+        let note_ids: Vec<String> = NoteEntity::find()
+            .filter(note::Column::ProjectId.eq(project_id))
+            .all(conn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to list notes: {e}")))?
+            .into_iter()
+            .map(|note| note.id)
+            .collect();
+
+        NoteRevisionEntity::delete_many()
+            .filter(note_revision::Column::NoteId.is_in(note_ids))
+            .exec(conn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to delete note revisions: {e}")))?;
+
+        NoteEntity::delete_many()
+            .filter(note::Column::ProjectId.eq(project_id))
+            .exec(conn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to delete notes: {e}")))?;
+
+        Ok(())
+    }
+
+    /// Earlier versions of a note, newest first.
+    #[instrument(level = "debug", skip_all, fields(note_id = %note_id))]
+    pub async fn list_note_revisions(&self, note_id: &str) -> Result<Vec<NoteRevision>> {
+        NoteRevisionEntity::find()
+            .filter(note_revision::Column::NoteId.eq(note_id))
+            .order_by_desc(note_revision::Column::SavedAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to list note revisions: {e}")))?
+            .into_iter()
+            .map(NoteRevision::try_from)
+            .collect()
+    }
+
+    #[instrument(level = "debug", skip_all, fields(revision_id = %revision_id))]
+    pub async fn load_note_revision(&self, revision_id: &str) -> Result<Option<NoteRevision>> {
+        NoteRevisionEntity::find_by_id(revision_id.to_string())
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to load note revision: {e}")))?
+            .map(NoteRevision::try_from)
+            .transpose()
+    }
+}
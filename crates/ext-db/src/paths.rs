@@ -0,0 +1,151 @@
+use ext_error::AppError;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Classic Win32 `MAX_PATH`, including the terminating NUL.
+pub const MAX_PATH: usize = 260;
+
+/// Returns a path that the OS will accept even when it is longer than
+/// `MAX_PATH`.
+///
+/// On Windows absolute paths are given the extended-length `\\?\` prefix
+/// (`\\?\UNC\` for network shares). Extended-length paths bypass Win32
+/// normalization, so `.`/`..` components and forward slashes are resolved
+/// here first. Everywhere else the path is returned unchanged.
+pub fn long_path(path: &Path) -> PathBuf {
+    if cfg!(windows) {
+        to_extended_length(path)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+fn to_extended_length(path: &Path) -> PathBuf {
+    let raw = path.to_string_lossy().replace('/', "\\");
+
+    if raw.starts_with(r"\\?\") || raw.starts_with(r"\\.\") {
+        return PathBuf::from(raw);
+    }
+
+    if let Some(share) = raw.strip_prefix(r"\\") {
+        return PathBuf::from(format!(r"\\?\UNC\{}", normalize_components(share, 2)));
+    }
+
+    let bytes = raw.as_bytes();
+    let is_drive_absolute = bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && bytes[2] == b'\\';
+
+    if is_drive_absolute {
+        PathBuf::from(format!(r"\\?\{}", normalize_components(&raw, 1)))
+    } else {
+        // Relative paths cannot carry the prefix; leave them to the OS.
+        PathBuf::from(raw)
+    }
+}
+
+/// Resolves `.` and `..` in `path`, whose first `root_len` components are
+/// the drive (`C:`) or the share (`server\share`).
+fn normalize_components(path: &str, root_len: usize) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split('\\') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                // Never pop the drive or share root.
+                if parts.len() > root_len {
+                    parts.pop();
+                }
+            }
+            other => parts.push(other),
+        }
+    }
+    parts.join("\\")
+}
+
+/// Converts an `io::Error` raised while touching `path` into an `AppError`
+/// that names the path, and points at the projects directory when the
+/// failure looks like a path-length limit.
+pub fn fs_error(action: &str, path: &Path, err: io::Error) -> AppError {
+    let shown = path.display();
+    let length = path.as_os_str().len();
+
+    if is_path_too_long(&err, length) {
+        AppError::FileSystem(format!(
+            "Failed to {action} '{shown}': the path is {length} characters long, which exceeds \
+             the limit supported by this system. Consider moving the projects directory to a \
+             shorter location (e.g. C:\\Etabs)."
+        ))
+    } else {
+        AppError::FileSystem(format!("Failed to {action} '{shown}': {err}"))
+    }
+}
+
+fn is_path_too_long(err: &io::Error, length: usize) -> bool {
+    match err.raw_os_error() {
+        // ERROR_FILENAME_EXCED_RANGE
+        Some(206) if cfg!(windows) => true,
+        // ERROR_PATH_NOT_FOUND is what Win32 reports for over-long paths
+        // that were not given the extended-length prefix.
+        Some(3) if cfg!(windows) => length >= MAX_PATH,
+        // ENAMETOOLONG
+        Some(36) if cfg!(unix) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extended_length_drive_path() {
+        let path = to_extended_length(Path::new(r"C:\Users\Đức\OneDrive\Dự án\..\Tháp A"));
+        assert_eq!(path, PathBuf::from(r"\\?\C:\Users\Đức\OneDrive\Tháp A"));
+    }
+
+    #[test]
+    fn test_extended_length_forward_slashes() {
+        let path = to_extended_length(Path::new("C:/projects/./tower/project.json"));
+        assert_eq!(path, PathBuf::from(r"\\?\C:\projects\tower\project.json"));
+    }
+
+    #[test]
+    fn test_extended_length_unc_path() {
+        let path = to_extended_length(Path::new(r"\\server\share\projects"));
+        assert_eq!(path, PathBuf::from(r"\\?\UNC\server\share\projects"));
+    }
+
+    #[test]
+    fn test_extended_length_unc_path_stays_on_share() {
+        let path = to_extended_length(Path::new(r"\\srv\share\..\x"));
+        assert_eq!(path, PathBuf::from(r"\\?\UNC\srv\share\x"));
+    }
+
+    #[test]
+    fn test_extended_length_is_idempotent() {
+        let once = to_extended_length(Path::new(r"C:\projects"));
+        let twice = to_extended_length(&once);
+        assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn test_extended_length_leaves_relative_paths() {
+        let path = to_extended_length(Path::new(r"projects\tower"));
+        assert_eq!(path, PathBuf::from(r"projects\tower"));
+    }
+
+    #[test]
+    fn test_fs_error_names_path() {
+        let err = fs_error(
+            "read project file",
+            Path::new("/data/Dự án/project.json"),
+            io::Error::new(io::ErrorKind::NotFound, "missing"),
+        );
+        match err {
+            AppError::FileSystem(message) => assert!(message.contains("/data/Dự án/project.json")),
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+}
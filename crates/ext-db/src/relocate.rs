@@ -0,0 +1,287 @@
+//! Moving the projects directory. Project folders are copied to the new
+//! directory and verified one at a time; the caller switches to the new
+//! directory once every folder is there, and only then removes the
+//! originals. See [`ext_core::relocation`].
+
+use crate::paths::{fs_error, long_path};
+use crate::storage::{self, partial_path, retry_transient};
+use crate::{hash_file, Database};
+use ext_error::{AppError, Result};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicI64, AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+use tokio::fs;
+use tracing::instrument;
+use uuid::Uuid;
+
+impl Database {
+    /// Ids of the project folders in the projects directory, registered or
+    /// not. Other folders, such as interrupted import staging, are not
+    /// projects.
+    pub async fn project_folders(&self) -> Result<Vec<Uuid>> {
+        let mut folders = Vec::new();
+        let mut entries = fs::read_dir(long_path(&self.projects_dir))
+            .await
+            .map_err(|e| fs_error("read projects directory", &self.projects_dir, e))?;
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| fs_error("read projects directory", &self.projects_dir, e))?
+        {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let is_dir = entry.file_type().await.is_ok_and(|kind| kind.is_dir());
+            if let Ok(id) = Uuid::parse_str(&name)
+                && id.to_string() == name
+                && is_dir
+            {
+                folders.push(id);
+            }
+        }
+        folders.sort();
+        Ok(folders)
+    }
+
+    /// Checks that `destination` can take over as the projects directory
+    /// for `folders`, creating it if needed. It must be absolute, must not
+    /// be the current directory or inside it, must be writable, must not
+    /// hold any of the folders yet, and its volume needs room for them.
+    #[instrument(level = "debug", skip_all, fields(destination = %destination.display()))]
+    pub async fn check_relocation_target(
+        &self,
+        destination: &Path,
+        folders: &[Uuid],
+    ) -> Result<()> {
+        if !destination.is_absolute() {
+            return Err(AppError::Validation(format!(
+                "Projects directory must be an absolute path (got '{}')",
+                destination.display()
+            )));
+        }
+        if destination.to_str().is_none() {
+            return Err(AppError::Validation(format!(
+                "Projects directory '{}' is not valid UTF-8",
+                destination.display()
+            )));
+        }
+
+        fs::create_dir_all(long_path(destination))
+            .await
+            .map_err(|e| fs_error("create projects directory", destination, e))?;
+        let current = canonical(&self.projects_dir).await?;
+        if canonical(destination).await?.starts_with(&current) {
+            return Err(AppError::Validation(format!(
+                "'{}' is inside the current projects directory '{}'",
+                destination.display(),
+                self.projects_dir.display()
+            )));
+        }
+
+        if storage::probe(destination).read_only {
+            return Err(AppError::FileSystem(format!(
+                "Projects directory '{}' is read-only",
+                destination.display()
+            )));
+        }
+
+        let mut needed = 0;
+        for id in folders {
+            let name = id.to_string();
+            if long_path(&destination.join(&name)).exists() {
+                return Err(AppError::Validation(format!(
+                    "'{}' already contains a folder for project {name}",
+                    destination.display()
+                )));
+            }
+            needed += folder_size(&self.projects_dir.join(&name)).await?;
+        }
+        if let Some(available) = storage::free_space(destination)
+            && available < needed
+        {
+            return Err(AppError::FileSystem(format!(
+                "Not enough space in '{}': the projects need {needed} bytes, {available} are \
+                 free",
+                destination.display()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Copies `folders` into `destination`, checking every copied file
+    /// against the SHA-256 of its original. `on_copied` is told how many
+    /// folders are done after each one; an error from it stops the copy.
+    /// On any failure the copies made so far are removed.
+    #[instrument(level = "debug", skip_all, fields(destination = %destination.display()))]
+    pub async fn copy_project_folders(
+        &self,
+        destination: &Path,
+        folders: &[Uuid],
+        mut on_copied: impl FnMut(usize) -> Result<()>,
+    ) -> Result<()> {
+        let mut copied: Vec<PathBuf> = Vec::new();
+        for id in folders {
+            let source = self.projects_dir.join(id.to_string());
+            let target = destination.join(id.to_string());
+            let outcome = match copy_verified(&source, &target).await {
+                Ok(()) => {
+                    copied.push(target);
+                    on_copied(copied.len())
+                }
+                Err(e) => Err(e),
+            };
+            if let Err(e) = outcome {
+                tracing::warn!("Moving the projects directory failed at project {id}: {e}");
+                for folder in &copied {
+                    if let Err(cleanup) = remove_folder(folder).await {
+                        tracing::warn!("Failed to remove copy '{}': {cleanup}", folder.display());
+                    }
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// This database with `projects_dir` as its projects directory. Settings
+    /// and the project locks this instance holds carry over.
+    pub fn with_projects_dir(&self, projects_dir: &Path) -> Database {
+        let held_locks = self
+            .held_locks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        Database {
+            db: self.db.clone(),
+            projects_dir: projects_dir.to_path_buf(),
+            storage: storage::probe(projects_dir),
+            backup_retention: AtomicUsize::new(self.backup_retention.load(Ordering::Relaxed)),
+            lock_timeout_secs: AtomicI64::new(self.lock_timeout_secs.load(Ordering::Relaxed)),
+            held_locks: Mutex::new(held_locks),
+            host: self.host.clone(),
+        }
+    }
+
+    /// Removes the given project folders, leaving registry rows alone.
+    /// Returns the folders that couldn't be removed; each failure is
+    /// logged.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn remove_project_folders(&self, folders: &[Uuid]) -> Vec<PathBuf> {
+        let mut left = Vec::new();
+        for id in folders {
+            let folder = self.projects_dir.join(id.to_string());
+            if let Err(e) = remove_folder(&folder).await {
+                tracing::warn!("Failed to remove '{}': {e}", folder.display());
+                left.push(folder);
+            }
+        }
+        left
+    }
+}
+
+/// Copies `source` to `target` through a partial folder that is renamed
+/// once every file in it matches its original.
+async fn copy_verified(source: &Path, target: &Path) -> Result<()> {
+    let partial = partial_path(target);
+    // Left over from an earlier move that was interrupted
+    let _ = fs::remove_dir_all(long_path(&partial)).await;
+
+    let renamed = match copy_tree(source, &partial).await {
+        Ok(()) => retry_transient(|| fs::rename(long_path(&partial), long_path(target)))
+            .await
+            .map_err(|e| fs_error("rename copied project", &partial, e)),
+        Err(e) => Err(e),
+    };
+    if renamed.is_err() {
+        let _ = fs::remove_dir_all(long_path(&partial)).await;
+    }
+    renamed
+}
+
+async fn copy_tree(source: &Path, target: &Path) -> Result<()> {
+    let mut pending = vec![PathBuf::new()];
+    while let Some(relative) = pending.pop() {
+        let from_dir = source.join(&relative);
+        let to_dir = target.join(&relative);
+        fs::create_dir_all(long_path(&to_dir))
+            .await
+            .map_err(|e| fs_error("create folder", &to_dir, e))?;
+
+        let mut entries = fs::read_dir(long_path(&from_dir))
+            .await
+            .map_err(|e| fs_error("read folder", &from_dir, e))?;
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| fs_error("read folder", &from_dir, e))?
+        {
+            let relative = relative.join(entry.file_name());
+            let from = source.join(&relative);
+            let is_dir = entry
+                .file_type()
+                .await
+                .map_err(|e| fs_error("read folder", &from, e))?
+                .is_dir();
+            if is_dir {
+                pending.push(relative);
+                continue;
+            }
+
+            let to = target.join(&relative);
+            retry_transient(|| fs::copy(long_path(&from), long_path(&to)))
+                .await
+                .map_err(|e| fs_error("copy file", &from, e))?;
+            let (_, original) = hash_file(&from).await?;
+            let (_, copy) = hash_file(&to).await?;
+            if original != copy {
+                return Err(AppError::FileSystem(format!(
+                    "Copy of '{}' does not match the original",
+                    from.display()
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Total size in bytes of the files under `dir`.
+async fn folder_size(dir: &Path) -> Result<u64> {
+    let mut size = 0;
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let mut entries = fs::read_dir(long_path(&dir))
+            .await
+            .map_err(|e| fs_error("read folder", &dir, e))?;
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| fs_error("read folder", &dir, e))?
+        {
+            let metadata = entry
+                .metadata()
+                .await
+                .map_err(|e| fs_error("read folder", &entry.path(), e))?;
+            if metadata.is_dir() {
+                pending.push(entry.path());
+            } else {
+                size += metadata.len();
+            }
+        }
+    }
+    Ok(size)
+}
+
+async fn canonical(dir: &Path) -> Result<PathBuf> {
+    fs::canonicalize(long_path(dir))
+        .await
+        .map_err(|e| fs_error("resolve directory", dir, e))
+}
+
+/// Removes a folder that may already be gone.
+async fn remove_folder(folder: &Path) -> Result<()> {
+    match fs::remove_dir_all(long_path(folder)).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(fs_error("remove folder", folder, e)),
+    }
+}
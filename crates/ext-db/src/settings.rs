@@ -0,0 +1,81 @@
+//! `settings.json` in the application data directory.
+//!
+//! Settings live outside the SQLite registry because they decide where the
+//! projects directory is before the database is opened.
+
+use crate::paths::{fs_error, long_path};
+use crate::storage::{retry_transient, write_atomic};
+use ext_core::settings::Settings;
+use ext_error::{AppError, Result};
+use std::io;
+use std::path::Path;
+use tokio::fs;
+
+pub const SETTINGS_FILE_NAME: &str = "settings.json";
+
+/// Reads settings, falling back to defaults when the file does not exist.
+pub async fn load_settings(path: &Path) -> Result<Settings> {
+    let content = match retry_transient(|| fs::read_to_string(long_path(path))).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Settings::default()),
+        Err(e) => return Err(fs_error("read settings file", path, e)),
+    };
+
+    serde_json::from_str(&content).map_err(|e| {
+        AppError::Validation(format!("Settings file '{}' is invalid: {}", path.display(), e))
+    })
+}
+
+/// Writes settings to a temporary file and renames it over the old one, so
+/// a crash mid-write never leaves a truncated settings file.
+pub async fn save_settings(path: &Path, settings: &Settings) -> Result<()> {
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize settings: {}", e)))?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(long_path(parent))
+            .await
+            .map_err(|e| fs_error("create settings directory", parent, e))?;
+    }
+
+    write_atomic(path, &content)
+        .await
+        .map_err(|e| fs_error("write settings file", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ext_core::units::UnitSystem;
+
+    #[tokio::test]
+    async fn test_missing_file_loads_defaults() {
+        let temp = tempfile::tempdir().unwrap();
+        let settings = load_settings(&temp.path().join(SETTINGS_FILE_NAME)).await.unwrap();
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[tokio::test]
+    async fn test_save_and_load_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("nested").join(SETTINGS_FILE_NAME);
+
+        let settings = Settings {
+            units: UnitSystem::Imperial,
+            backup_retention: 3,
+            ..Default::default()
+        };
+        save_settings(&path, &settings).await.unwrap();
+
+        assert_eq!(load_settings(&path).await.unwrap(), settings);
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_file_is_validation_error() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join(SETTINGS_FILE_NAME);
+        std::fs::write(&path, "{ \"units\": ").unwrap();
+
+        assert!(matches!(load_settings(&path).await, Err(AppError::Validation(_))));
+    }
+}
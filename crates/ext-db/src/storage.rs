@@ -0,0 +1,226 @@
+use crate::paths::long_path;
+use crate::PARTIAL_SUFFIX;
+use ext_core::{StorageStatus, SyncProvider};
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use sysinfo::Disks;
+use tokio::fs;
+
+/// How many times a transient filesystem error is retried before giving up.
+const RETRY_ATTEMPTS: u32 = 4;
+
+/// Base delay between retries; grows linearly with each attempt.
+const RETRY_DELAY: Duration = Duration::from_millis(150);
+
+const WRITE_PROBE_NAME: &str = ".etabext-write-probe";
+
+/// Inspects the projects directory once at startup.
+pub fn probe(projects_dir: &Path) -> StorageStatus {
+    let read_only = !is_writable(projects_dir);
+    let sync_provider = detect_sync_provider(projects_dir);
+
+    let mut advisories = Vec::new();
+    if read_only {
+        advisories.push(format!(
+            "Projects directory '{}' is read-only; creating or editing projects is disabled.",
+            projects_dir.display()
+        ));
+    }
+    if let Some(provider) = sync_provider {
+        advisories.push(format!(
+            "Projects directory is inside a {provider:?} folder; sync may briefly lock files \
+             while uploading."
+        ));
+    }
+
+    StorageStatus {
+        projects_dir: projects_dir.to_string_lossy().to_string(),
+        read_only,
+        sync_provider,
+        advisories,
+    }
+}
+
+fn is_writable(dir: &Path) -> bool {
+    let probe = long_path(&dir.join(WRITE_PROBE_NAME));
+    match std::fs::write(&probe, b"") {
+        Ok(()) => {
+            let _ = std::fs::remove_file(&probe);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Space left on the volume that holds `dir`, from the disk with the
+/// longest mount point containing it.
+pub(crate) fn free_space(dir: &Path) -> Option<u64> {
+    let dir = std::path::absolute(dir).ok()?;
+    Disks::new_with_refreshed_list()
+        .list()
+        .iter()
+        .filter(|disk| dir.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
+/// Detects a cloud sync client from marker files or well-known folder names
+/// in `dir` or any of its ancestors.
+pub fn detect_sync_provider(dir: &Path) -> Option<SyncProvider> {
+    for ancestor in dir.ancestors() {
+        if ancestor.join(".dropbox").exists() || ancestor.join(".dropbox.cache").exists() {
+            return Some(SyncProvider::Dropbox);
+        }
+
+        let Some(name) = ancestor.file_name().map(|n| n.to_string_lossy().to_lowercase()) else {
+            continue;
+        };
+
+        if name == "onedrive" || name.starts_with("onedrive - ") {
+            return Some(SyncProvider::OneDrive);
+        }
+        if name == "dropbox" || name.starts_with("dropbox (") {
+            return Some(SyncProvider::Dropbox);
+        }
+        if name == "google drive" || name == "my drive" || name == "googledrive" {
+            return Some(SyncProvider::GoogleDrive);
+        }
+        if name == "icloud drive" || name == "iclouddrive" || name == "mobile documents" {
+            return Some(SyncProvider::ICloud);
+        }
+        if name == "box" || name == "box sync" {
+            return Some(SyncProvider::Box);
+        }
+    }
+    None
+}
+
+/// Whether `err` is a lock that a sync client or another process is likely to
+/// release shortly, as opposed to a permanent failure.
+pub fn is_transient(err: &io::Error) -> bool {
+    match err.raw_os_error() {
+        // ERROR_SHARING_VIOLATION, ERROR_LOCK_VIOLATION
+        Some(32) | Some(33) if cfg!(windows) => true,
+        // EBUSY
+        Some(16) if cfg!(unix) => true,
+        _ => false,
+    }
+}
+
+/// Runs `op`, retrying with a short backoff while it fails with a transient
+/// sharing or lock violation.
+pub async fn retry_transient<T, F, Fut>(mut op: F) -> io::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = io::Result<T>>,
+{
+    let mut attempt = 0;
+    loop {
+        match op().await {
+            Err(err) if is_transient(&err) && attempt < RETRY_ATTEMPTS => {
+                attempt += 1;
+                tokio::time::sleep(RETRY_DELAY * attempt).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Writes `contents` to a temporary file next to `path` and renames it over
+/// `path`, so a crash mid-write leaves the previous file rather than a
+/// truncated one. Callers must not write the same path concurrently.
+pub async fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let partial = partial_path(path);
+    retry_transient(|| fs::write(long_path(&partial), contents.as_ref())).await?;
+
+    if let Err(e) = retry_transient(|| fs::rename(long_path(&partial), long_path(path))).await {
+        let _ = fs::remove_file(long_path(&partial)).await;
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Where [`write_atomic`] writes before renaming into place
+pub fn partial_path(path: &Path) -> PathBuf {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(PARTIAL_SUFFIX);
+    PathBuf::from(partial)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn test_detect_onedrive_business_folder() {
+        let dir = Path::new("/Users/an/OneDrive - Kết Cấu Co/Projects/etab-extension");
+        assert_eq!(detect_sync_provider(dir), Some(SyncProvider::OneDrive));
+    }
+
+    #[test]
+    fn test_detect_dropbox_marker_file() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(temp.path().join(".dropbox"), b"{}").unwrap();
+        let nested = temp.path().join("projects");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(detect_sync_provider(&nested), Some(SyncProvider::Dropbox));
+    }
+
+    #[test]
+    fn test_plain_directory_has_no_provider() {
+        let temp = tempfile::tempdir().unwrap();
+        assert_eq!(detect_sync_provider(temp.path()), None);
+    }
+
+    #[test]
+    fn test_probe_writable_directory() {
+        let temp = tempfile::tempdir().unwrap();
+        let status = probe(temp.path());
+        assert!(!status.read_only);
+        assert!(!temp.path().join(WRITE_PROBE_NAME).exists());
+    }
+
+    #[tokio::test]
+    async fn test_write_atomic_replaces_without_leaving_partial() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("project.json");
+        write_atomic(&path, "old").await.unwrap();
+        write_atomic(&path, "new").await.unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        assert!(!partial_path(&path).exists());
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up_on_permanent_error() {
+        let calls = AtomicU32::new(0);
+        let result: io::Result<()> = retry_transient(|| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Err(io::Error::new(io::ErrorKind::PermissionDenied, "denied")) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_retry_recovers_from_transient_error() {
+        let calls = AtomicU32::new(0);
+        let result = retry_transient(|| {
+            let attempt = calls.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if attempt < 2 {
+                    Err(io::Error::from_raw_os_error(16))
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 2);
+    }
+}
@@ -0,0 +1,157 @@
+//! User project templates, listed after the built-in ones, and creating
+//! projects from either.
+
+use crate::entities::prelude::*;
+use crate::entities::project_template;
+use crate::paths::long_path;
+use crate::Database;
+use ext_core::audit::{AuditAction, AuditEntry};
+use ext_core::combinations;
+use ext_core::duplicates::names_collide;
+use ext_core::templates::{self, ProjectTemplate, TemplateTag};
+use ext_core::{Project, Tag};
+use ext_error::{AppError, Result};
+use sea_orm::{EntityTrait, QueryOrder};
+use tokio::fs;
+use tracing::instrument;
+use uuid::Uuid;
+
+impl Database {
+    /// Built-in templates first, then the user's by name.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn list_templates(&self) -> Result<Vec<ProjectTemplate>> {
+        let user = ProjectTemplateEntity::find()
+            .order_by_asc(project_template::Column::Name)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to list templates: {e}")))?
+            .into_iter()
+            .map(ProjectTemplate::try_from)
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut all = templates::built_in_templates();
+        all.extend(user);
+        Ok(all)
+    }
+
+    #[instrument(level = "debug", skip_all, fields(template_id = %template_id))]
+    pub async fn find_template(&self, template_id: Uuid) -> Result<Option<ProjectTemplate>> {
+        Ok(self
+            .list_templates()
+            .await?
+            .into_iter()
+            .find(|template| template.id == template_id))
+    }
+
+    /// Stores a user template. Template names are unique regardless of case,
+    /// including the built-in ones.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn save_template(&self, template: &ProjectTemplate) -> Result<()> {
+        if self
+            .list_templates()
+            .await?
+            .iter()
+            .any(|existing| names_collide(&existing.name, &template.name))
+        {
+            return Err(AppError::Validation("template name already exists".to_string()));
+        }
+
+        ProjectTemplateEntity::insert(project_template::ActiveModel::from(template))
+            .exec(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to save template: {e}")))?;
+
+        Ok(())
+    }
+
+    #[instrument(level = "debug", skip_all, fields(template_id = %template_id))]
+    pub async fn delete_template(&self, template_id: Uuid) -> Result<()> {
+        templates::ensure_not_built_in(template_id)?;
+
+        let deleted = ProjectTemplateEntity::delete_by_id(template_id.to_string())
+            .exec(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to delete template: {e}")))?;
+
+        if deleted.rows_affected == 0 {
+            return Err(AppError::NotFound(format!("Template {template_id} not found")));
+        }
+        Ok(())
+    }
+
+    /// Creates `project` with the template's tags and load cases, and the
+    /// combinations for its design code. Nothing is left behind on failure.
+    #[instrument(level = "debug", skip_all, fields(project_id = %project.id))]
+    pub async fn create_project_from_template(
+        &self,
+        project: &Project,
+        template: &ProjectTemplate,
+    ) -> Result<()> {
+        self.ensure_writable()?;
+
+        if let Err(e) = self.seed_from_template(project, template).await {
+            let project_dir = self.projects_dir.join(project.id.to_string());
+            let _ = self.discard_project(&project.id.to_string()).await;
+            let _ = fs::remove_dir_all(long_path(&project_dir)).await;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    async fn seed_from_template(
+        &self,
+        project: &Project,
+        template: &ProjectTemplate,
+    ) -> Result<()> {
+        let entry = AuditEntry::new(
+            project.id,
+            AuditAction::Created,
+            format!("Created from template \"{}\"", template.name),
+        );
+        self.save_project_with_audit(project, Some(&entry)).await?;
+
+        let project_id = project.id.to_string();
+        let tags = self.tags_named(&template.default_tags).await?;
+        let tag_ids: Vec<String> = tags.iter().map(|tag| tag.id.to_string()).collect();
+        self.set_project_tags(&project_id, &tag_ids).await?;
+
+        let cases = &template.default_load_cases;
+        if !cases.is_empty() {
+            self.save_load_cases(&project_id, cases).await?;
+            if let Some(code) = template.default_settings.design_code {
+                let generated = combinations::generate_combinations(cases.clone(), code)?;
+                self.save_load_combinations(&project_id, &generated).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Existing tags matching the template's by name, creating the missing
+    /// ones with the template's color.
+    async fn tags_named(&self, wanted: &[TemplateTag]) -> Result<Vec<Tag>> {
+        let mut existing = self.list_tags().await?;
+        let mut tags = Vec::new();
+
+        for template_tag in wanted {
+            let found = existing
+                .iter()
+                .find(|tag| names_collide(&tag.name, &template_tag.name))
+                .cloned();
+            let tag = match found {
+                Some(tag) => tag,
+                None => {
+                    let tag = Tag::new(template_tag.name.clone(), template_tag.color.clone());
+                    tag.validate()?;
+                    self.create_tag(&tag).await?;
+                    existing.push(tag.clone());
+                    tag
+                }
+            };
+            tags.push(tag);
+        }
+
+        Ok(tags)
+    }
+}
+
@@ -0,0 +1,1347 @@
+use ext_core::diagnostics::OrphanedRowAction;
+use ext_core::frame_forces::{FrameForce, FrameForceFilter};
+use ext_core::results::{AnalysisResults, Direction, ModalPeriod, ResultTable, StoryDrift};
+use ext_db::{Database, Project, ProjectFile, ProjectStatus, Tag};
+use ext_error::AppError;
+use migration::{Migrator, MigratorTrait};
+
+async fn open_database(projects_dir: &std::path::Path) -> Database {
+    Database::new("sqlite::memory:", projects_dir.to_str().unwrap())
+        .await
+        .expect("Failed to open database")
+}
+
+#[tokio::test]
+async fn test_save_list_load_round_trip() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let first = Project::new("Tower A".to_string(), "Residential tower".to_string());
+    let second = Project::new("Podium".to_string(), "Retail podium".to_string());
+    db.save_project(&first).await.unwrap();
+    db.save_project(&second).await.unwrap();
+
+    let projects = db.list_projects().await.unwrap();
+    assert_eq!(projects.len(), 2);
+    assert!(projects.iter().any(|p| p.id == first.id && p.name == "Tower A"));
+    assert!(projects.iter().any(|p| p.id == second.id && p.name == "Podium"));
+
+    let loaded = db
+        .load_project(&first.id.to_string())
+        .await
+        .unwrap()
+        .expect("Project should exist");
+    assert_eq!(loaded.name, first.name);
+    assert_eq!(loaded.description, first.description);
+    assert_eq!(loaded.created_at, first.created_at);
+
+    // The JSON artifact is still written next to the row
+    assert!(temp.path().join(first.id.to_string()).join("project.json").exists());
+}
+
+#[tokio::test]
+async fn test_save_existing_project_updates_row() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let mut project = Project::new("Tower A".to_string(), String::new());
+    db.save_project(&project).await.unwrap();
+
+    project.name = "Tower A - Phase 2".to_string();
+    db.save_project(&project).await.unwrap();
+
+    let projects = db.list_projects().await.unwrap();
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].name, "Tower A - Phase 2");
+}
+
+#[tokio::test]
+async fn test_load_falls_back_to_legacy_json() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let legacy = Project::new("Legacy".to_string(), "Created before the registry".to_string());
+    let folder = temp.path().join(legacy.id.to_string());
+    std::fs::create_dir_all(&folder).unwrap();
+    std::fs::write(
+        folder.join("project.json"),
+        serde_json::to_string_pretty(&legacy).unwrap(),
+    )
+    .unwrap();
+
+    let loaded = db
+        .load_project(&legacy.id.to_string())
+        .await
+        .unwrap()
+        .expect("Legacy project should load from JSON");
+    assert_eq!(loaded.name, "Legacy");
+}
+
+#[tokio::test]
+async fn test_load_missing_project_returns_none() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let missing = uuid::Uuid::new_v4().to_string();
+    assert!(db.load_project(&missing).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_migrations_are_idempotent() {
+    let conn = sea_orm::Database::connect("sqlite::memory:").await.unwrap();
+
+    Migrator::up(&conn, None).await.unwrap();
+    let applied = Migrator::get_applied_migrations(&conn).await.unwrap().len();
+
+    Migrator::up(&conn, None).await.unwrap();
+    assert_eq!(Migrator::get_applied_migrations(&conn).await.unwrap().len(), applied);
+    assert!(Migrator::get_pending_migrations(&conn).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_update_project_bumps_updated_at() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let mut project = Project::new("Tower A".to_string(), String::new());
+    db.save_project(&project).await.unwrap();
+
+    project.description = "Revised lateral system".to_string();
+    let updated = db.update_project(&project).await.unwrap();
+    assert!(updated.updated_at >= project.updated_at);
+    assert_eq!(updated.created_at, project.created_at);
+
+    let loaded = db.load_project(&project.id.to_string()).await.unwrap().unwrap();
+    assert_eq!(loaded.description, "Revised lateral system");
+    assert_eq!(loaded.updated_at, updated.updated_at);
+}
+
+#[tokio::test]
+async fn test_update_missing_project_is_not_found() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let project = Project::new("Ghost".to_string(), String::new());
+    let err = db.update_project(&project).await.unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)));
+}
+
+#[tokio::test]
+async fn test_delete_project_removes_row_and_folder() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let project = Project::new("Tower A".to_string(), String::new());
+    db.save_project(&project).await.unwrap();
+
+    db.delete_project(&project.id.to_string()).await.unwrap();
+
+    assert!(db.list_projects().await.unwrap().is_empty());
+    assert!(!temp.path().join(project.id.to_string()).exists());
+}
+
+#[tokio::test]
+async fn test_delete_tolerates_missing_folder() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let project = Project::new("Tower A".to_string(), String::new());
+    db.save_project(&project).await.unwrap();
+    std::fs::remove_dir_all(temp.path().join(project.id.to_string())).unwrap();
+
+    db.delete_project(&project.id.to_string()).await.unwrap();
+    assert!(db.list_projects().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_delete_missing_project_is_not_found() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let err = db
+        .delete_project(&uuid::Uuid::new_v4().to_string())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)));
+}
+
+#[tokio::test]
+async fn test_find_project_by_name_is_case_insensitive() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let project = Project::new("Tower A".to_string(), String::new());
+    db.save_project(&project).await.unwrap();
+
+    let found = db.find_project_by_name("tower a").await.unwrap().unwrap();
+    assert_eq!(found.id, project.id);
+    assert!(db.find_project_by_name("Tower B").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_etabs_model_round_trip() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let project = Project::new("Tower A".to_string(), String::new());
+    db.save_project(&project).await.unwrap();
+    let id = project.id.to_string();
+    assert!(db.load_etabs_model(&id).await.unwrap().is_none());
+
+    let model = ext_core::e2k::parse_e2k_str(
+        "$ POINT COORDINATES\n  POINT \"1\" 0 0\n  POINT \"2\" 6 0\n",
+    )
+    .unwrap();
+    db.save_etabs_model(&id, &model).await.unwrap();
+
+    assert_eq!(db.load_etabs_model(&id).await.unwrap(), Some(model));
+}
+
+#[tokio::test]
+async fn test_load_combinations_round_trip() {
+    use ext_core::combinations::{generate_combinations, DesignCode, LoadCase, LoadCaseKind};
+
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let project = Project::new("Tower A".to_string(), String::new());
+    db.save_project(&project).await.unwrap();
+    let id = project.id.to_string();
+    assert!(db.load_load_combinations(&id).await.unwrap().is_empty());
+
+    let cases = vec![
+        LoadCase {
+            name: "D".to_string(),
+            kind: LoadCaseKind::Dead,
+        },
+        LoadCase {
+            name: "L".to_string(),
+            kind: LoadCaseKind::Live,
+        },
+    ];
+    let combinations = generate_combinations(cases, DesignCode::Asce7Lrfd).unwrap();
+    db.save_load_combinations(&id, &combinations).await.unwrap();
+
+    assert_eq!(db.load_load_combinations(&id).await.unwrap(), combinations);
+}
+
+fn drift(story: &str, drift: f64) -> StoryDrift {
+    StoryDrift {
+        story: story.to_string(),
+        load_case: "EQX".to_string(),
+        direction: Direction::X,
+        drift,
+    }
+}
+
+#[tokio::test]
+async fn test_reimporting_run_label_replaces_rows() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let project = Project::new("Tower A".to_string(), String::new());
+    db.save_project(&project).await.unwrap();
+    let id = project.id.to_string();
+
+    let first = AnalysisResults {
+        story_drifts: vec![drift("Roof", 0.004), drift("L2", 0.003)],
+        ..Default::default()
+    };
+    let run = db.save_analysis_results(&id, "Rev A", &first).await.unwrap();
+
+    let second = AnalysisResults {
+        story_drifts: vec![drift("Roof", 0.005)],
+        ..Default::default()
+    };
+    let rerun = db.save_analysis_results(&id, " Rev A ", &second).await.unwrap();
+
+    assert_eq!(rerun.id, run.id);
+    assert_eq!(db.list_analysis_runs(&id).await.unwrap().len(), 1);
+    assert_eq!(
+        db.list_story_drifts(&id, &run.id.to_string()).await.unwrap(),
+        second.story_drifts
+    );
+}
+
+#[tokio::test]
+async fn test_result_table_import_keeps_other_tables() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let project = Project::new("Tower A".to_string(), String::new());
+    db.save_project(&project).await.unwrap();
+    let id = project.id.to_string();
+
+    let modal = AnalysisResults {
+        modal_periods: vec![ModalPeriod {
+            load_case: "Modal".to_string(),
+            mode: 1,
+            period: 1.25,
+            ux: 0.7,
+            uy: 0.01,
+            rz: 0.02,
+        }],
+        ..Default::default()
+    };
+    let run = db
+        .save_result_table(&id, "Rev A", ResultTable::ModalPeriods, &modal)
+        .await
+        .unwrap();
+
+    let drifts = AnalysisResults {
+        story_drifts: vec![drift("Roof", 0.004)],
+        ..Default::default()
+    };
+    db.save_result_table(&id, "Rev A", ResultTable::StoryDrifts, &drifts)
+        .await
+        .unwrap();
+
+    let run_id = run.id.to_string();
+    assert_eq!(db.list_modal_periods(&id, &run_id).await.unwrap(), modal.modal_periods);
+    assert_eq!(db.list_story_drifts(&id, &run_id).await.unwrap(), drifts.story_drifts);
+}
+
+#[tokio::test]
+async fn test_analysis_runs_are_scoped_to_project() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let tower = Project::new("Tower A".to_string(), String::new());
+    let podium = Project::new("Podium".to_string(), String::new());
+    db.save_project(&tower).await.unwrap();
+    db.save_project(&podium).await.unwrap();
+
+    let results = AnalysisResults {
+        story_drifts: vec![drift("Roof", 0.004)],
+        ..Default::default()
+    };
+    let run = db
+        .save_analysis_results(&tower.id.to_string(), "Rev A", &results)
+        .await
+        .unwrap();
+    let run_id = run.id.to_string();
+
+    let other = db.list_story_drifts(&podium.id.to_string(), &run_id).await;
+    assert!(matches!(other, Err(AppError::NotFound(_))));
+
+    db.delete_project(&tower.id.to_string()).await.unwrap();
+    assert!(db.list_analysis_runs(&tower.id.to_string()).await.unwrap().is_empty());
+}
+
+async fn project_with_file(db: &Database, temp: &std::path::Path) -> (Project, ProjectFile) {
+    let project = Project::new("Tower A".to_string(), String::new());
+    db.save_project(&project).await.unwrap();
+
+    let source = temp.join("Tower A.e2k");
+    std::fs::write(&source, "$ STORIES - IN SEQUENCE FROM TOP\n").unwrap();
+    let file = db
+        .add_file_to_project(&project.id.to_string(), &source)
+        .await
+        .unwrap();
+    (project, file)
+}
+
+#[tokio::test]
+async fn test_add_file_copies_and_records_metadata() {
+    let temp = tempfile::tempdir().unwrap();
+    let projects_dir = temp.path().join("projects");
+    let db = open_database(&projects_dir).await;
+    let (project, file) = project_with_file(&db, temp.path()).await;
+
+    assert_eq!(file.original_name, "Tower A.e2k");
+    assert_eq!(file.size, 33);
+    assert_eq!(file.sha256.len(), 64);
+    let copy = projects_dir.join(project.id.to_string()).join(&file.stored_path);
+    assert_eq!(std::fs::read_to_string(copy).unwrap(), "$ STORIES - IN SEQUENCE FROM TOP\n");
+
+    let files = db.list_project_files(&project.id.to_string()).await.unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].id, file.id);
+    assert_eq!(files[0].sha256, file.sha256);
+}
+
+#[tokio::test]
+async fn test_refresh_file_metadata_tracks_outside_edits() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(&temp.path().join("projects")).await;
+    let (project, file) = project_with_file(&db, temp.path()).await;
+    let (project_id, file_id) = (project.id.to_string(), file.id.to_string());
+
+    assert_eq!(db.refresh_file_metadata(&project_id, &file_id).await.unwrap(), None);
+
+    let copy = temp.path().join("projects").join(&project_id).join(&file.stored_path);
+    std::fs::write(&copy, "$ STORIES - IN SEQUENCE FROM TOP\n$ GRIDS\n").unwrap();
+    let edited = db.refresh_file_metadata(&project_id, &file_id).await.unwrap().unwrap();
+    assert_eq!(edited.size, 41);
+    assert_ne!(edited.sha256, file.sha256);
+
+    std::fs::remove_file(&copy).unwrap();
+    let missing = db.refresh_file_metadata(&project_id, &file_id).await.unwrap().unwrap();
+    assert!(missing.missing);
+    assert_eq!(missing.sha256, edited.sha256);
+    assert_eq!(db.refresh_file_metadata(&project_id, &file_id).await.unwrap(), None);
+
+    let files = db.list_project_files(&project_id).await.unwrap();
+    assert!(files[0].missing);
+}
+
+#[tokio::test]
+async fn test_add_same_content_twice_is_rejected() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(&temp.path().join("projects")).await;
+    let (project, _) = project_with_file(&db, temp.path()).await;
+
+    let renamed = temp.path().join("copy of tower.e2k");
+    std::fs::copy(temp.path().join("Tower A.e2k"), &renamed).unwrap();
+    match db.add_file_to_project(&project.id.to_string(), &renamed).await {
+        Err(AppError::Validation(message)) => assert!(message.contains("Tower A.e2k")),
+        other => panic!("expected validation error, got {other:?}"),
+    }
+
+    let files_dir = temp.path().join("projects").join(project.id.to_string()).join("files");
+    assert_eq!(std::fs::read_dir(files_dir).unwrap().count(), 1);
+}
+
+#[tokio::test]
+async fn test_remove_file_deletes_copy_and_row() {
+    let temp = tempfile::tempdir().unwrap();
+    let projects_dir = temp.path().join("projects");
+    let db = open_database(&projects_dir).await;
+    let (project, file) = project_with_file(&db, temp.path()).await;
+    let id = project.id.to_string();
+
+    db.remove_file_from_project(&id, &file.id.to_string()).await.unwrap();
+
+    assert!(db.list_project_files(&id).await.unwrap().is_empty());
+    let files_dir = projects_dir.join(&id).join("files");
+    assert_eq!(std::fs::read_dir(files_dir).unwrap().count(), 0);
+
+    match db.remove_file_from_project(&id, &file.id.to_string()).await {
+        Err(AppError::NotFound(_)) => {}
+        other => panic!("expected not found, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_interrupted_remove_is_recovered() {
+    let temp = tempfile::tempdir().unwrap();
+    let projects_dir = temp.path().join("projects");
+    let db = open_database(&projects_dir).await;
+    let (project, file) = project_with_file(&db, temp.path()).await;
+    let id = project.id.to_string();
+
+    // Simulate a crash after the copy was set aside but before the row went away
+    let copy = projects_dir.join(&id).join(&file.stored_path);
+    let mut aside = copy.clone().into_os_string();
+    aside.push(".removing");
+    std::fs::rename(&copy, &aside).unwrap();
+
+    assert_eq!(db.list_project_files(&id).await.unwrap().len(), 1);
+    assert!(copy.exists());
+}
+
+#[tokio::test]
+async fn test_legacy_json_without_status_loads_as_active() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let id = uuid::Uuid::new_v4();
+    let folder = temp.path().join(id.to_string());
+    std::fs::create_dir_all(&folder).unwrap();
+    std::fs::write(
+        folder.join("project.json"),
+        format!(
+            r#"{{
+                "id": "{id}",
+                "name": "Legacy",
+                "description": "",
+                "created_at": "2025-01-26T06:40:00+00:00",
+                "updated_at": "2025-01-26T06:40:00+00:00"
+            }}"#
+        ),
+    )
+    .unwrap();
+
+    let loaded = db.load_project(&id.to_string()).await.unwrap().unwrap();
+    assert_eq!(loaded.status, ProjectStatus::Active);
+    assert!(loaded.trashed_at.is_none());
+}
+
+#[tokio::test]
+async fn test_status_filters_project_list() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let active = Project::new("Tower A".to_string(), String::new());
+    let archived = Project::new("Tower B".to_string(), String::new());
+    db.save_project(&active).await.unwrap();
+    db.save_project(&archived).await.unwrap();
+
+    let updated = db
+        .set_project_status(&archived.id.to_string(), ProjectStatus::Archived)
+        .await
+        .unwrap();
+    assert_eq!(updated.status, ProjectStatus::Archived);
+
+    let listed = db.list_projects_with_status(ProjectStatus::Active).await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, active.id);
+
+    let listed = db.list_projects_with_status(ProjectStatus::Archived).await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, archived.id);
+}
+
+#[tokio::test]
+async fn test_recent_projects_put_opened_before_never_opened() {
+    use chrono::{Duration, Utc};
+
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+    let now = Utc::now();
+
+    let project = |name: &str, updated_days_ago: i64, opened_days_ago: Option<i64>| {
+        let mut project = Project::new(name.to_string(), String::new());
+        project.updated_at = now - Duration::days(updated_days_ago);
+        project.last_opened_at = opened_days_ago.map(|days| now - Duration::days(days));
+        project
+    };
+    let stale_open = project("Opened last week", 1, Some(7));
+    let fresh_open = project("Opened today", 30, Some(0));
+    let never_old = project("Never opened, old", 20, None);
+    let never_new = project("Never opened, new", 2, None);
+    let mut trashed = project("Trashed", 0, Some(0));
+    trashed.set_status(ProjectStatus::Trashed);
+    for p in [&stale_open, &fresh_open, &never_old, &never_new, &trashed] {
+        db.save_project(p).await.unwrap();
+    }
+
+    let names: Vec<String> = db
+        .list_recent_projects(10)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|p| p.name)
+        .collect();
+    assert_eq!(
+        names,
+        ["Opened today", "Opened last week", "Never opened, new", "Never opened, old"]
+    );
+    assert_eq!(db.list_recent_projects(1).await.unwrap().len(), 1);
+
+    let touched = db.touch_project_opened(&never_old.id.to_string()).await.unwrap();
+    assert_eq!(touched.updated_at, never_old.updated_at);
+    assert_eq!(db.list_recent_projects(1).await.unwrap()[0].id, never_old.id);
+}
+
+#[tokio::test]
+async fn test_purge_only_removes_expired_trash() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let mut expired = Project::new("Old".to_string(), String::new());
+    expired.set_status(ProjectStatus::Trashed);
+    expired.trashed_at = Some(chrono::Utc::now() - chrono::Duration::days(31));
+    db.save_project(&expired).await.unwrap();
+
+    let recent = Project::new("Recent".to_string(), String::new());
+    db.save_project(&recent).await.unwrap();
+    db.set_project_status(&recent.id.to_string(), ProjectStatus::Trashed)
+        .await
+        .unwrap();
+
+    let purged = db.purge_trashed_projects(chrono::Duration::days(30)).await.unwrap();
+    assert_eq!(purged, vec![expired.id]);
+    assert!(!temp.path().join(expired.id.to_string()).exists());
+
+    let trashed = db.list_projects_with_status(ProjectStatus::Trashed).await.unwrap();
+    assert_eq!(trashed.len(), 1);
+    assert_eq!(trashed[0].id, recent.id);
+}
+
+#[tokio::test]
+async fn test_search_ranks_name_matches_first() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let podium = Project::new("Podium".to_string(), "Transfer slab below the tower".to_string());
+    let tower = Project::new("Tower A".to_string(), "Core wall system".to_string());
+    db.save_project(&podium).await.unwrap();
+    db.save_project(&tower).await.unwrap();
+
+    let hits = db.search_projects("tow").await.unwrap();
+    assert_eq!(hits.len(), 2);
+    assert_eq!(hits[0].project.id, tower.id);
+    assert_eq!(hits[0].matched_field, ext_core::search::SearchField::Name);
+    assert!(hits[0].score > hits[1].score);
+}
+
+#[tokio::test]
+async fn test_search_reflects_updates_and_deletes() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let mut project = Project::new("Tower A".to_string(), String::new());
+    db.save_project(&project).await.unwrap();
+
+    project.name = "Hospital".to_string();
+    db.update_project(&project).await.unwrap();
+    assert!(db.search_projects("tower").await.unwrap().is_empty());
+    assert_eq!(db.search_projects("hospital").await.unwrap().len(), 1);
+
+    db.delete_project(&project.id.to_string()).await.unwrap();
+    assert!(db.search_projects("hospital").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_blank_search_is_validation_error() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let err = db.search_projects("  ").await.unwrap_err();
+    assert!(matches!(err, AppError::Validation(_)));
+}
+
+#[tokio::test]
+async fn test_reindex_counts_all_projects() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    for name in ["Tower A", "Tower B"] {
+        db.save_project(&Project::new(name.to_string(), String::new()))
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(db.reindex_projects().await.unwrap(), 2);
+    assert_eq!(db.search_projects("tower").await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_tag_names_are_unique_case_insensitively() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    db.create_tag(&Tag::new("Client: Acme".to_string(), "#ff0000".to_string()))
+        .await
+        .unwrap();
+    let err = db
+        .create_tag(&Tag::new("client: ACME".to_string(), "#00ff00".to_string()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Validation(_)));
+    assert_eq!(db.list_tags().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_set_project_tags_and_filter_by_tag() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let hospital = Tag::new("Hospital".to_string(), "#09f".to_string());
+    let asce = Tag::new("ASCE 7-16".to_string(), "#333333".to_string());
+    db.create_tag(&hospital).await.unwrap();
+    db.create_tag(&asce).await.unwrap();
+
+    let tagged = Project::new("Tower A".to_string(), String::new());
+    let other = Project::new("Tower B".to_string(), String::new());
+    db.save_project(&tagged).await.unwrap();
+    db.save_project(&other).await.unwrap();
+
+    let id = tagged.id.to_string();
+    let tags = db
+        .set_project_tags(&id, &[hospital.id.to_string(), asce.id.to_string()])
+        .await
+        .unwrap();
+    assert_eq!(tags.len(), 2);
+
+    let projects = db.list_projects_by_tag(&hospital.id.to_string()).await.unwrap();
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].id, tagged.id);
+
+    // Setting tags replaces the previous set
+    db.set_project_tags(&id, &[asce.id.to_string()]).await.unwrap();
+    assert_eq!(db.list_project_tags(&id).await.unwrap(), vec![asce]);
+}
+
+#[tokio::test]
+async fn test_deleting_tag_detaches_it_from_projects() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let tag = Tag::new("Archive 2024".to_string(), "#abcdef".to_string());
+    db.create_tag(&tag).await.unwrap();
+    let project = Project::new("Tower A".to_string(), String::new());
+    db.save_project(&project).await.unwrap();
+    db.set_project_tags(&project.id.to_string(), &[tag.id.to_string()])
+        .await
+        .unwrap();
+
+    db.delete_tag(&tag.id.to_string()).await.unwrap();
+
+    assert!(db.list_tags().await.unwrap().is_empty());
+    assert!(db.list_project_tags(&project.id.to_string()).await.unwrap().is_empty());
+    assert!(db.load_project(&project.id.to_string()).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_export_delete_import_round_trip() {
+    let temp = tempfile::tempdir().unwrap();
+    let projects_dir = temp.path().join("projects");
+    let db = open_database(&projects_dir).await;
+    let (project, file) = project_with_file(&db, temp.path()).await;
+    let id = project.id.to_string();
+    let results = AnalysisResults {
+        story_drifts: vec![drift("Roof", 0.004)],
+        ..Default::default()
+    };
+    db.save_analysis_results(&id, "Rev A", &results).await.unwrap();
+    let frame_force = FrameForce {
+        story: "Roof".to_string(),
+        frame: "C1".to_string(),
+        load_case: "Dead".to_string(),
+        station: 0.0,
+        p: -250_000.0,
+        v2: 0.0,
+        v3: 0.0,
+        t: 0.0,
+        m2: 0.0,
+        m3: 12_000.0,
+    };
+    db.save_frame_forces(&id, "Rev A", std::slice::from_ref(&frame_force))
+        .await
+        .unwrap();
+
+    let archive = temp.path().join("Tower A.etabx");
+    db.export_project(&id, &archive).await.unwrap();
+    db.delete_project(&id).await.unwrap();
+
+    let imported = db.import_project(&archive).await.unwrap();
+    assert_eq!(imported.id, project.id);
+    assert_eq!(imported.name, "Tower A");
+
+    let files = db.list_project_files(&id).await.unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].sha256, file.sha256);
+    assert!(projects_dir.join(&id).join(&files[0].stored_path).exists());
+
+    let runs = db.list_analysis_runs(&id).await.unwrap();
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].label, "Rev A");
+    let run_id = runs[0].id.to_string();
+    let drifts = db.list_story_drifts(&id, &run_id).await.unwrap();
+    assert_eq!(drifts, results.story_drifts);
+    let page = db
+        .query_frame_forces(&id, &run_id, &FrameForceFilter::default(), 0, 10)
+        .await
+        .unwrap();
+    assert_eq!(page.rows, vec![frame_force]);
+}
+
+#[tokio::test]
+async fn test_import_over_existing_project_gets_new_id_and_name() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(&temp.path().join("projects")).await;
+
+    let project = Project::new("Tower A".to_string(), String::new());
+    db.save_project(&project).await.unwrap();
+    let archive = temp.path().join("Tower A.etabx");
+    db.export_project(&project.id.to_string(), &archive).await.unwrap();
+
+    let imported = db.import_project(&archive).await.unwrap();
+    assert_ne!(imported.id, project.id);
+    assert_eq!(imported.name, "Tower A (2)");
+    assert_eq!(db.list_projects().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_corrupt_archive_leaves_no_folder_behind() {
+    let temp = tempfile::tempdir().unwrap();
+    let projects_dir = temp.path().join("projects");
+    let db = open_database(&projects_dir).await;
+
+    let archive = temp.path().join("broken.etabx");
+    std::fs::write(&archive, b"PK\x03\x04 definitely not a zip").unwrap();
+
+    let err = db.import_project(&archive).await.unwrap_err();
+    assert!(matches!(err, AppError::Validation(_)));
+    assert_eq!(std::fs::read_dir(&projects_dir).unwrap().count(), 0);
+}
+
+#[tokio::test]
+async fn test_import_rejects_file_paths_outside_the_project() {
+    use std::io::Write;
+
+    let temp = tempfile::tempdir().unwrap();
+    let projects_dir = temp.path().join("projects");
+    let db = open_database(&projects_dir).await;
+    let (victim, victim_file) = project_with_file(&db, temp.path()).await;
+    let victim_id = victim.id.to_string();
+
+    let archive = temp.path().join("Tower A.etabx");
+    db.export_project(&victim_id, &archive).await.unwrap();
+
+    // Point the manifest entry at the other project's copy of the file
+    let crafted = temp.path().join("crafted.etabx");
+    let mut source = zip::ZipArchive::new(std::fs::File::open(&archive).unwrap()).unwrap();
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(&crafted).unwrap());
+    for index in 0..source.len() {
+        let entry = source.by_index(index).unwrap();
+        if entry.name() != "manifest.json" {
+            writer.raw_copy_file(entry).unwrap();
+            continue;
+        }
+        let mut manifest: serde_json::Value = serde_json::from_reader(entry).unwrap();
+        manifest["files"][0]["stored_path"] =
+            format!("../{victim_id}/{}", victim_file.stored_path).into();
+        writer
+            .start_file("manifest.json", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer
+            .write_all(&serde_json::to_vec(&manifest).unwrap())
+            .unwrap();
+    }
+    writer.finish().unwrap();
+
+    let err = db.import_project(&crafted).await.unwrap_err();
+    assert!(matches!(err, AppError::Validation(_)));
+    assert_eq!(db.list_projects().await.unwrap().len(), 1);
+    assert_eq!(std::fs::read_dir(&projects_dir).unwrap().count(), 1);
+    let files = db.list_project_files(&victim_id).await.unwrap();
+    assert_eq!(files.len(), 1);
+    assert!(
+        projects_dir
+            .join(&victim_id)
+            .join(&files[0].stored_path)
+            .exists()
+    );
+}
+
+#[tokio::test]
+async fn test_duplicate_copies_files_under_new_ids() {
+    let temp = tempfile::tempdir().unwrap();
+    let projects_dir = temp.path().join("projects");
+    let db = open_database(&projects_dir).await;
+    let (project, file) = project_with_file(&db, temp.path()).await;
+    let id = project.id.to_string();
+    let results = AnalysisResults {
+        story_drifts: vec![drift("Roof", 0.004)],
+        ..Default::default()
+    };
+    db.save_analysis_results(&id, "Rev A", &results).await.unwrap();
+
+    let copy = db.duplicate_project(&id, "Tower B", false).await.unwrap();
+    assert_ne!(copy.id, project.id);
+    assert_eq!(copy.name, "Tower B");
+    let copy_id = copy.id.to_string();
+
+    let files = db.list_project_files(&copy_id).await.unwrap();
+    assert_eq!(files.len(), 1);
+    assert_ne!(files[0].id, file.id);
+    assert_ne!(files[0].stored_path, file.stored_path);
+    assert_eq!(files[0].sha256, file.sha256);
+    assert!(projects_dir.join(&copy_id).join(&files[0].stored_path).exists());
+    assert!(db.list_analysis_runs(&copy_id).await.unwrap().is_empty());
+
+    let with_results = db.duplicate_project(&id, "Tower C", true).await.unwrap();
+    let runs = db.list_analysis_runs(&with_results.id.to_string()).await.unwrap();
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].label, "Rev A");
+
+    // The original is untouched
+    assert_eq!(db.list_project_files(&id).await.unwrap(), vec![file]);
+}
+
+#[tokio::test]
+async fn test_failed_duplicate_leaves_nothing_behind() {
+    let temp = tempfile::tempdir().unwrap();
+    let projects_dir = temp.path().join("projects");
+    let db = open_database(&projects_dir).await;
+    let (project, file) = project_with_file(&db, temp.path()).await;
+    let id = project.id.to_string();
+
+    // The stored copy vanished without the row being flagged yet
+    std::fs::remove_file(projects_dir.join(&id).join(&file.stored_path)).unwrap();
+
+    assert!(db.duplicate_project(&id, "Tower B", false).await.is_err());
+    assert_eq!(db.list_projects().await.unwrap().len(), 1);
+    assert_eq!(std::fs::read_dir(&projects_dir).unwrap().count(), 1);
+}
+
+/// Writes a project folder the registry does not know about, with one
+/// stored file.
+fn orphaned_folder(projects_dir: &std::path::Path, name: &str) -> Project {
+    let project = Project::new(name.to_string(), String::new());
+    let folder = projects_dir.join(project.id.to_string());
+    std::fs::create_dir_all(folder.join("files")).unwrap();
+    std::fs::write(
+        folder.join("project.json"),
+        serde_json::to_string_pretty(&project).unwrap(),
+    )
+    .unwrap();
+    let file_id = uuid::Uuid::new_v4();
+    std::fs::write(folder.join("files").join(format!("{file_id}_Tower.e2k")), "$ STORIES\n")
+        .unwrap();
+    project
+}
+
+#[tokio::test]
+async fn test_diagnostics_find_orphans_and_repair_fixes_them() {
+    let temp = tempfile::tempdir().unwrap();
+    let projects_dir = temp.path().join("projects");
+    let db = open_database(&projects_dir).await;
+
+    let healthy = Project::new("Tower A".to_string(), String::new());
+    db.save_project(&healthy).await.unwrap();
+    let (lost, _) = project_with_file(&db, temp.path()).await;
+    std::fs::remove_dir_all(projects_dir.join(lost.id.to_string())).unwrap();
+    let unregistered = orphaned_folder(&projects_dir, "Tower A");
+    std::fs::create_dir_all(projects_dir.join(".import-leftover")).unwrap();
+
+    let diagnostics = db.diagnostics().await.unwrap();
+    assert_eq!(diagnostics.integrity_check, "ok");
+    assert_eq!(diagnostics.database_path, None);
+    assert_eq!(diagnostics.project_count, 2);
+    assert_eq!(diagnostics.orphaned_folders, vec![unregistered.id]);
+    assert_eq!(diagnostics.orphaned_rows, vec![lost.id]);
+    assert!(!diagnostics.is_healthy());
+    // Looking changed nothing
+    assert_eq!(db.diagnostics().await.unwrap(), diagnostics);
+
+    let report = db.repair_storage(OrphanedRowAction::Trash).await.unwrap();
+    assert_eq!(report.registered, vec![unregistered.id]);
+    assert_eq!(report.trashed, vec![lost.id]);
+    assert!(report.removed.is_empty() && report.skipped.is_empty());
+
+    let registered = db
+        .load_project(&unregistered.id.to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(registered.name, "Tower A (2)");
+    let files = db.list_project_files(&unregistered.id.to_string()).await.unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].original_name, "Tower.e2k");
+
+    let trashed = db.load_project(&lost.id.to_string()).await.unwrap().unwrap();
+    assert_eq!(trashed.status, ProjectStatus::Trashed);
+    assert!(db.list_project_files(&lost.id.to_string()).await.unwrap()[0].missing);
+    assert!(db.diagnostics().await.unwrap().is_healthy());
+}
+
+#[tokio::test]
+async fn test_repair_can_remove_rows_without_folders() {
+    let temp = tempfile::tempdir().unwrap();
+    let projects_dir = temp.path().join("projects");
+    let db = open_database(&projects_dir).await;
+    let (lost, _) = project_with_file(&db, temp.path()).await;
+    std::fs::remove_dir_all(projects_dir.join(lost.id.to_string())).unwrap();
+
+    let report = db.repair_storage(OrphanedRowAction::Remove).await.unwrap();
+    assert_eq!(report.removed, vec![lost.id]);
+    assert!(db.list_projects().await.unwrap().is_empty());
+    assert!(db.diagnostics().await.unwrap().is_healthy());
+}
+
+#[tokio::test]
+async fn test_update_keeps_a_backup_of_the_previous_state() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let mut project = Project::new("Tower A".to_string(), "Original".to_string());
+    db.save_project(&project).await.unwrap();
+
+    project.description = "Revised".to_string();
+    db.update_project(&project).await.unwrap();
+
+    let backups = db.list_backups(&project.id.to_string()).await.unwrap();
+    assert_eq!(backups.len(), 1);
+    assert_eq!(backups[0].project.description, "Original");
+}
+
+#[tokio::test]
+async fn test_backups_are_pruned_to_retention() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+    db.set_backup_retention(3);
+
+    let project = Project::new("Tower A".to_string(), String::new());
+    db.save_project(&project).await.unwrap();
+    let id = project.id.to_string();
+
+    for _ in 0..5 {
+        db.backup_project(&id).await.unwrap();
+    }
+
+    let backups = db.list_backups(&id).await.unwrap();
+    assert_eq!(backups.len(), 3);
+    assert!(backups[0].created_at >= backups[1].created_at);
+}
+
+#[tokio::test]
+async fn test_corrupted_project_json_is_recovered_from_latest_backup() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let project = Project::new("Tower A".to_string(), "Core wall system".to_string());
+    db.save_project(&project).await.unwrap();
+    let id = project.id.to_string();
+    db.backup_project(&id).await.unwrap();
+
+    let project_json = temp.path().join(&id).join("project.json");
+    std::fs::write(&project_json, "{ \"id\": \"trunc").unwrap();
+
+    let latest = db.list_backups(&id).await.unwrap().remove(0);
+    let restored = db.restore_backup(&id, &latest.id).await.unwrap();
+    assert_eq!(restored.description, "Core wall system");
+    assert!(restored.updated_at > project.updated_at);
+
+    let on_disk: Project =
+        serde_json::from_str(&std::fs::read_to_string(&project_json).unwrap()).unwrap();
+    assert_eq!(on_disk.description, "Core wall system");
+}
+
+#[tokio::test]
+async fn test_restore_rejects_path_like_backup_ids() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let project = Project::new("Tower A".to_string(), String::new());
+    db.save_project(&project).await.unwrap();
+
+    let err = db
+        .restore_backup(&project.id.to_string(), "../../project")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Validation(_)));
+}
+
+#[tokio::test]
+async fn test_deleted_project_can_be_restored_from_its_last_backup() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let project = Project::new("Tower A".to_string(), "Core wall system".to_string());
+    db.save_project(&project).await.unwrap();
+    let id = project.id.to_string();
+    db.delete_project(&id).await.unwrap();
+    assert!(!temp.path().join(&id).exists());
+
+    let backups = db.list_backups(&id).await.unwrap();
+    assert_eq!(backups.len(), 1);
+    let restored = db.restore_backup(&id, &backups[0].id).await.unwrap();
+    assert_eq!(restored.description, "Core wall system");
+    assert_eq!(db.list_projects().await.unwrap().len(), 1);
+
+    // Purging the trash prunes backups of deleted projects past retention
+    db.delete_project(&id).await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    db.purge_trashed_projects(chrono::Duration::zero()).await.unwrap();
+    assert!(db.list_backups(&id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_interrupted_project_json_write_keeps_previous_file() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+
+    let mut project = Project::new("Tower A".to_string(), String::new());
+    db.save_project(&project).await.unwrap();
+    let project_json = temp.path().join(project.id.to_string()).join("project.json");
+    let partial = temp
+        .path()
+        .join(project.id.to_string())
+        .join("project.json.partial");
+
+    // A crash after the temporary write but before the rename
+    std::fs::write(&partial, "{ \"id\": \"trunc").unwrap();
+    let on_disk: Project =
+        serde_json::from_str(&std::fs::read_to_string(&project_json).unwrap()).unwrap();
+    assert_eq!(on_disk.name, "Tower A");
+    std::fs::remove_file(&partial).unwrap();
+
+    // A temporary write that fails leaves the row and the file unchanged
+    std::fs::create_dir(&partial).unwrap();
+    project.name = "Tower B".to_string();
+    assert!(db.save_project(&project).await.is_err());
+    let row = db.load_project(&project.id.to_string()).await.unwrap().unwrap();
+    assert_eq!(row.name, "Tower A");
+    let on_disk: Project =
+        serde_json::from_str(&std::fs::read_to_string(&project_json).unwrap()).unwrap();
+    assert_eq!(on_disk.name, "Tower A");
+
+    std::fs::remove_dir(&partial).unwrap();
+    db.save_project(&project).await.unwrap();
+    let on_disk: Project =
+        serde_json::from_str(&std::fs::read_to_string(&project_json).unwrap()).unwrap();
+    assert_eq!(on_disk.name, "Tower B");
+    assert!(!partial.exists());
+}
+
+#[tokio::test]
+async fn test_user_templates_persist_after_built_in_ones() {
+    let temp = tempfile::tempdir().unwrap();
+    let db = open_database(temp.path()).await;
+    let built_in = ext_core::templates::built_in_templates();
+
+    let mut template = built_in[1].clone();
+    template.id = uuid::Uuid::new_v4();
+    template.built_in = false;
+    let err = db.save_template(&template).await.unwrap_err();
+    assert!(matches!(err, AppError::Validation(_)));
+
+    template.name = "Mid-rise Steel".to_string();
+    db.save_template(&template).await.unwrap();
+    let listed = db.list_templates().await.unwrap();
+    assert_eq!(listed.len(), built_in.len() + 1);
+    assert_eq!(listed.last(), Some(&template));
+    assert_eq!(db.find_template(template.id).await.unwrap(), Some(template.clone()));
+
+    let err = db.delete_template(built_in[0].id).await.unwrap_err();
+    assert!(matches!(err, AppError::Validation(_)));
+    db.delete_template(template.id).await.unwrap();
+    assert!(db.find_template(template.id).await.unwrap().is_none());
+}
+
+/// Opens the database in `data_dir` the way a second app instance sharing
+/// the data directory would
+async fn open_shared_database(data_dir: &std::path::Path) -> Database {
+    let url = format!("sqlite://{}?mode=rwc", data_dir.join("projects.db").display());
+    let projects_dir = data_dir.join("projects");
+    Database::new(&url, projects_dir.to_str().unwrap())
+        .await
+        .expect("Failed to open database")
+}
+
+#[tokio::test]
+async fn test_second_holder_cannot_write_locked_project() {
+    let temp = tempfile::tempdir().unwrap();
+    let first = open_shared_database(temp.path()).await;
+    let second = open_shared_database(temp.path()).await;
+    let project = Project::new("Tower A".to_string(), String::new());
+    first.save_project(&project).await.unwrap();
+    let id = project.id.to_string();
+    let lock_file = temp.path().join("projects").join(&id).join("project.lock");
+
+    first
+        .acquire_project_lock(&id, "jane@WS-042".to_string())
+        .await
+        .unwrap();
+    assert!(lock_file.exists());
+    first.save_load_cases(&id, &[]).await.unwrap();
+
+    match second
+        .acquire_project_lock(&id, "raj@WS-017".to_string())
+        .await
+    {
+        Err(AppError::Locked(holder)) => assert_eq!(holder, "jane@WS-042"),
+        other => panic!("expected locked error, got {other:?}"),
+    }
+    let err = second.save_load_cases(&id, &[]).await.unwrap_err();
+    assert!(matches!(err, AppError::Locked(_)));
+    let err = second.save_project(&project).await.unwrap_err();
+    assert!(matches!(err, AppError::Locked(_)));
+
+    // Only the instance that took the lock releases it
+    second.release_project_lock(&id).await.unwrap();
+    assert!(lock_file.exists());
+    first.release_project_lock(&id).await.unwrap();
+    assert!(!lock_file.exists());
+
+    second.save_load_cases(&id, &[]).await.unwrap();
+    second
+        .acquire_project_lock(&id, "raj@WS-017".to_string())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_lock_file_is_honoured_across_separate_registries() {
+    let temp = tempfile::tempdir().unwrap();
+    let projects_dir = temp.path().join("projects");
+    let open = |name: &str| {
+        let url = format!("sqlite://{}?mode=rwc", temp.path().join(name).display());
+        let projects_dir = projects_dir.to_str().unwrap().to_string();
+        async move { Database::new(&url, &projects_dir).await.unwrap() }
+    };
+    let first = open("first.db").await;
+    let second = open("second.db").await;
+    let project = Project::new("Tower A".to_string(), String::new());
+    first.save_project(&project).await.unwrap();
+    second.save_project(&project).await.unwrap();
+    let id = project.id.to_string();
+
+    first
+        .acquire_project_lock(&id, "jane@WS-042".to_string())
+        .await
+        .unwrap();
+
+    // The second registry has no lock row, but the lock file is shared
+    match second
+        .acquire_project_lock(&id, "raj@WS-017".to_string())
+        .await
+    {
+        Err(AppError::Locked(holder)) => assert_eq!(holder, "jane@WS-042"),
+        other => panic!("expected locked error, got {other:?}"),
+    }
+    let err = second.save_load_cases(&id, &[]).await.unwrap_err();
+    assert!(matches!(err, AppError::Locked(holder) if holder == "jane@WS-042"));
+
+    first.release_project_lock(&id).await.unwrap();
+    second
+        .acquire_project_lock(&id, "raj@WS-017".to_string())
+        .await
+        .unwrap();
+    let err = first.save_load_cases(&id, &[]).await.unwrap_err();
+    assert!(matches!(err, AppError::Locked(holder) if holder == "raj@WS-017"));
+}
+
+#[tokio::test]
+async fn test_stale_lock_is_taken_over() {
+    let temp = tempfile::tempdir().unwrap();
+    let first = open_shared_database(temp.path()).await;
+    let second = open_shared_database(temp.path()).await;
+    let project = Project::new("Tower A".to_string(), String::new());
+    first.save_project(&project).await.unwrap();
+    let id = project.id.to_string();
+
+    first
+        .acquire_project_lock(&id, "jane@WS-042".to_string())
+        .await
+        .unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+    // Any lock is older than a zero timeout, as if its holder had long gone
+    second.set_lock_timeout(chrono::Duration::zero());
+    let lock = second
+        .acquire_project_lock(&id, "raj@WS-017".to_string())
+        .await
+        .unwrap();
+    assert_eq!(lock.holder, "raj@WS-017");
+    second.save_load_cases(&id, &[]).await.unwrap();
+
+    // The previous holder has lost the lock and can't release the new one
+    let err = first.save_load_cases(&id, &[]).await.unwrap_err();
+    assert!(matches!(err, AppError::Locked(holder) if holder == "raj@WS-017"));
+    first.release_project_lock(&id).await.unwrap();
+    let current = first.load_project_lock(&id).await.unwrap().unwrap();
+    assert_eq!(current.holder, "raj@WS-017");
+
+    // Deleting the project drops its lock
+    second.delete_project(&id).await.unwrap();
+    assert!(second.load_project_lock(&id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_stale_lock_is_taken_over_by_one_instance_only() {
+    use ext_core::project_lock::ProjectLock;
+
+    let temp = tempfile::tempdir().unwrap();
+    let first = open_shared_database(temp.path()).await;
+    let second = open_shared_database(temp.path()).await;
+    let project = Project::new("Tower A".to_string(), String::new());
+    first.save_project(&project).await.unwrap();
+    let id = project.id.to_string();
+
+    // A lock left behind on another machine a day ago
+    let stale = ProjectLock {
+        acquired_at: chrono::Utc::now() - chrono::Duration::days(1),
+        ..ProjectLock::new(
+            project.id,
+            "jane@WS-042".to_string(),
+            4242,
+            "WS-042".to_string(),
+        )
+    };
+    let lock_file = temp.path().join("projects").join(&id).join("project.lock");
+    std::fs::write(&lock_file, serde_json::to_string(&stale).unwrap()).unwrap();
+
+    let (taken_first, taken_second) = tokio::join!(
+        first.acquire_project_lock(&id, "raj@WS-017".to_string()),
+        second.acquire_project_lock(&id, "mei@WS-023".to_string()),
+    );
+    let winner = match (taken_first, taken_second) {
+        (Ok(lock), Err(AppError::Locked(_))) | (Err(AppError::Locked(_)), Ok(lock)) => lock,
+        other => panic!("expected exactly one instance to take the lock, got {other:?}"),
+    };
+    let current = first.load_project_lock(&id).await.unwrap().unwrap();
+    assert_eq!(current.holder, winner.holder);
+    // The stale lock was moved aside and deleted, not left behind
+    let lock_files = std::fs::read_dir(lock_file.parent().unwrap())
+        .unwrap()
+        .filter(|entry| {
+            let name = entry.as_ref().unwrap().file_name();
+            name.to_string_lossy().starts_with("project.lock")
+        })
+        .count();
+    assert_eq!(lock_files, 1);
+}
+
+#[tokio::test]
+async fn test_merge_moves_rows_and_trashes_absorbed_project() {
+    use ext_core::combinations::{LoadCase, LoadCaseKind};
+    use ext_core::duplicates::MergeOptions;
+    use ext_core::notes::Note;
+
+    let temp = tempfile::tempdir().unwrap();
+    let projects_dir = temp.path().join("projects");
+    let db = open_database(&projects_dir).await;
+
+    let keep = Project::new("Tower A".to_string(), String::new());
+    let absorb = Project::new("Tower-A".to_string(), String::new());
+    db.save_project(&keep).await.unwrap();
+    db.save_project(&absorb).await.unwrap();
+    let (keep_id, absorb_id) = (keep.id.to_string(), absorb.id.to_string());
+
+    let source = temp.path().join("tower.e2k");
+    std::fs::write(&source, "$ STORIES").unwrap();
+    let file = db.add_file_to_project(&absorb_id, &source).await.unwrap();
+    db.create_note(&Note::new(absorb.id, "Loads".to_string(), String::new()))
+        .await
+        .unwrap();
+    let results = AnalysisResults {
+        story_drifts: vec![drift("Roof", 0.004)],
+        ..Default::default()
+    };
+    db.save_analysis_results(&keep_id, "Rev A", &results).await.unwrap();
+    db.save_analysis_results(&absorb_id, "Rev A", &results).await.unwrap();
+    let case = |name: &str| LoadCase {
+        name: name.to_string(),
+        kind: LoadCaseKind::Dead,
+    };
+    db.save_load_cases(&keep_id, &[case("D")]).await.unwrap();
+    db.save_load_cases(&absorb_id, &[case("DL")]).await.unwrap();
+
+    let merged = db
+        .merge_projects(&keep_id, &absorb_id, &MergeOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(merged.id, keep.id);
+
+    let files = db.list_project_files(&keep_id).await.unwrap();
+    assert_eq!(files.len(), 1);
+    assert!(!files[0].missing);
+    assert!(projects_dir.join(&keep_id).join(&file.stored_path).exists());
+    assert_eq!(db.list_notes(&keep_id).await.unwrap().len(), 1);
+    let mut labels: Vec<_> = db
+        .list_analysis_runs(&keep_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|run| run.label)
+        .collect();
+    labels.sort();
+    assert_eq!(labels, ["Rev A", "Rev A (2)"]);
+
+    // The kept project's own load cases win unless the options say otherwise
+    assert_eq!(db.load_load_cases(&keep_id).await.unwrap(), [case("D")]);
+    let absorbed = db.load_project(&absorb_id).await.unwrap().unwrap();
+    assert_eq!(absorbed.status, ProjectStatus::Trashed);
+    assert!(db.list_project_files(&absorb_id).await.unwrap().is_empty());
+
+    let err = db
+        .merge_projects(&keep_id, &keep_id, &MergeOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Validation(_)));
+}
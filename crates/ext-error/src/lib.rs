@@ -29,10 +29,19 @@ pub enum AppError {
 
     #[error("File system error: {0}")]
     FileSystem(String),
+
+    #[error("Project is locked by {0}")]
+    Locked(String),
 }
 
 pub type Result<T> = std::result::Result<T, AppError>;
 
+impl From<std::io::Error> for AppError {
+    fn from(err: std::io::Error) -> Self {
+        AppError::FileSystem(err.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -41,4 +50,41 @@ mod tests {
     fn test_export_typescript_bindings() {
         AppError::export(&Default::default()).expect("Failed to export AppError");
     }
+
+    #[test]
+    fn test_validation_serializes_as_tagged_union() {
+        let json = serde_json::to_value(AppError::Validation("name is required".to_string())).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "type": "Validation", "message": "name is required" })
+        );
+    }
+
+    #[test]
+    fn test_database_serializes_as_tagged_union() {
+        let json = serde_json::to_value(AppError::Database("disk I/O error".to_string())).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "type": "Database", "message": "disk I/O error" })
+        );
+    }
+
+    #[test]
+    fn test_deserializes_from_tagged_union() {
+        let err: AppError =
+            serde_json::from_str(r#"{ "type": "NotFound", "message": "Project 42 not found" }"#).unwrap();
+        assert!(matches!(err, AppError::NotFound(message) if message == "Project 42 not found"));
+    }
+
+    #[test]
+    fn test_locked_serializes_holder_as_message() {
+        let json = serde_json::to_value(AppError::Locked("jane@WS-042".to_string())).unwrap();
+        assert_eq!(json, serde_json::json!({ "type": "Locked", "message": "jane@WS-042" }));
+    }
+
+    #[test]
+    fn test_from_io_error() {
+        let err: AppError = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied").into();
+        assert!(matches!(err, AppError::FileSystem(_)));
+    }
 }
\ No newline at end of file
@@ -1,22 +1,989 @@
-use tauri::State;
-use ext_api::AppState;
-use ext_core::Project;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, State};
+use ext_api::{
+    parse_file_id, parse_note_id, parse_project_id, parse_revision_id, parse_run_id, parse_tag_id,
+    parse_task_id, parse_template_id, AppState,
+};
+use ext_error::AppError;
+use ext_core::audit::{AuditAction, AuditEntry};
+use ext_core::combinations::{DesignCode, LoadCase, LoadCombination};
+use ext_core::diagnostics::{Diagnostics, OrphanedRowAction, RepairReport};
+use ext_core::drift::DriftCheckReport;
+use ext_core::duplicates::{DuplicateProjectSet, MergeOptions};
+use ext_core::e2k::EtabsModel;
+use ext_core::estimate::{LateralEstimate, LateralEstimateInput, StoryForceRow};
+use ext_core::export::{ExportFormat, ExportTable};
+use ext_core::frame_forces::{FrameForceFilter, FrameForcePage};
+use ext_core::modal::ModalResults;
+use ext_core::model_diff::ModelDiff;
+use ext_core::notes::{Note, NoteRevision};
+use ext_core::rebar::{DevelopmentLength, DevelopmentLengthInput};
+use ext_core::report::ReportFormat;
+use ext_core::results::{
+    AnalysisRun, BaseReaction, ModalPeriod, PasteImportReport, ResultTable, StoryDrift,
+};
+use ext_core::search::SearchHit;
+use ext_core::seismic::{self, BaseShearResult, SeismicBaseShear, SeismicParams, SeismicStory};
+use ext_core::sections::{SectionProperties, SectionShape};
+use ext_core::settings::{Settings, SettingsPatch};
+use ext_core::sidecar::{EtabsModelInfo, LoadCombinationsReport};
+use ext_core::tasks::{TaskInfo, TaskKind};
+use ext_core::templates::ProjectTemplate;
+use ext_core::units::{self, LengthUnit, Unit};
+use ext_core::wind::{self, WindInput, WindPressures};
+use ext_core::{
+    CreateProjectOptions, EtabsStatus, InitStatus, Project, ProjectBackup, ProjectFile,
+    ProjectStatus, StorageStatus, Tag,
+};
 
 #[tauri::command]
 pub fn greet(name: &str) -> String {
     format!("Hello, {}! Welcome to ETAB Extension.", name)
 }
 
+/// Pure calculation, cheap enough to call on every keystroke.
+#[tauri::command]
+pub fn compute_section_properties(
+    shape: SectionShape,
+    unit: Option<LengthUnit>,
+    output_unit: Option<LengthUnit>,
+) -> Result<SectionProperties, AppError> {
+    let properties = shape.properties()?;
+    Ok(match (unit, output_unit) {
+        (Some(unit), Some(output_unit)) => properties.convert(unit, output_unit),
+        _ => properties,
+    })
+}
+
+/// ACI 318-19 development and lap splice lengths for the calculator page.
+#[tauri::command]
+pub fn compute_development_length(
+    input: DevelopmentLengthInput,
+) -> Result<DevelopmentLength, AppError> {
+    input.compute()
+}
+
+#[tauri::command]
+pub fn compute_base_shear(params: SeismicParams) -> Result<BaseShearResult, AppError> {
+    seismic::compute_base_shear(&params)
+}
+
+#[tauri::command]
+pub fn distribute_story_forces(
+    base_shear: f64,
+    period: f64,
+    stories: Vec<SeismicStory>,
+) -> Result<Vec<StoryForceRow>, AppError> {
+    seismic::distribute_story_forces(base_shear, period, &stories)
+}
+
+#[tauri::command]
+pub fn compute_wind_pressures(input: WindInput) -> Result<WindPressures, AppError> {
+    wind::compute_wind_pressures(&input)
+}
+
+#[tauri::command]
+pub fn convert_quantity(value: f64, from: Unit, to: Unit) -> Result<f64, AppError> {
+    units::convert_quantity(value, from, to)
+}
+
 #[tauri::command]
 pub async fn create_project(
     name: String,
     description: String,
+    options: Option<CreateProjectOptions>,
+    state: State<'_, AppState>,
+) -> Result<Project, AppError> {
+    state
+        .create_project_with_options(name, description, options.unwrap_or_default())
+        .await
+}
+
+#[tauri::command]
+pub async fn get_projects(
+    status: Option<ProjectStatus>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Project>, AppError> {
+    state.get_projects(status).await
+}
+
+#[tauri::command]
+pub async fn get_project(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Project, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.get_project(id).await
+}
+
+/// `etabext://project/<id>`, for the "Copy link" button.
+#[tauri::command]
+pub async fn get_project_deep_link(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<String, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.get_project_deep_link(id).await
+}
+
+#[tauri::command]
+pub async fn update_project(
+    project_id: String,
+    name: String,
+    description: String,
+    state: State<'_, AppState>,
+) -> Result<Project, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.update_project(id, name, description).await
+}
+
+#[tauri::command]
+pub async fn archive_project(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Project, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.archive_project(id).await
+}
+
+#[tauri::command]
+pub async fn restore_project(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Project, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.restore_project(id).await
+}
+
+#[tauri::command]
+pub async fn trash_project(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Project, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.trash_project(id).await
+}
+
+#[tauri::command]
+pub async fn empty_trash(
+    retention_days: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<usize, AppError> {
+    state.empty_trash(retention_days).await
+}
+
+#[tauri::command]
+pub async fn list_trashed_projects(
+    state: State<'_, AppState>,
+) -> Result<Vec<Project>, AppError> {
+    state.list_trashed_projects().await
+}
+
+/// Deletes a trashed project for good; other projects are refused.
+#[tauri::command]
+pub async fn purge_project(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.purge_project(id).await
+}
+
+/// Moves the project to the trash; only `purge_project` deletes for good.
+#[tauri::command]
+pub async fn delete_project(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Project, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.delete_project(id).await
+}
+
+#[tauri::command]
+pub async fn add_project_file(
+    project_id: String,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<ProjectFile, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.add_project_file(id, Path::new(&path)).await
+}
+
+#[tauri::command]
+pub async fn get_project_files(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ProjectFile>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.get_project_files(id).await
+}
+
+#[tauri::command]
+pub async fn open_project(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ProjectFile>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.open_project(id).await
+}
+
+#[tauri::command]
+pub async fn get_recent_projects(
+    limit: usize,
+    state: State<'_, AppState>,
+) -> Result<Vec<Project>, AppError> {
+    state.get_recent_projects(limit).await
+}
+
+#[tauri::command]
+pub async fn get_audit_log(
+    project_id: String,
+    page: usize,
+    page_size: usize,
+    action: Option<AuditAction>,
+    state: State<'_, AppState>,
+) -> Result<Vec<AuditEntry>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.get_audit_log(id, page, page_size, action).await
+}
+
+#[tauri::command]
+pub async fn create_note(
+    project_id: String,
+    title: String,
+    body_markdown: String,
+    state: State<'_, AppState>,
+) -> Result<Note, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.create_note(id, title, body_markdown).await
+}
+
+#[tauri::command]
+pub async fn update_note(
+    note_id: String,
+    title: String,
+    body_markdown: String,
+    state: State<'_, AppState>,
+) -> Result<Note, AppError> {
+    let note_id = parse_note_id(&note_id)?;
+    state.update_note(note_id, title, body_markdown).await
+}
+
+#[tauri::command]
+pub async fn delete_note(note_id: String, state: State<'_, AppState>) -> Result<(), AppError> {
+    let note_id = parse_note_id(&note_id)?;
+    state.delete_note(note_id).await
+}
+
+#[tauri::command]
+pub async fn get_notes(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<Note>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.get_notes(id).await
+}
+
+#[tauri::command]
+pub async fn get_note_history(
+    note_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteRevision>, AppError> {
+    let note_id = parse_note_id(&note_id)?;
+    state.get_note_history(note_id).await
+}
+
+#[tauri::command]
+pub async fn restore_note_revision(
+    note_id: String,
+    revision_id: String,
+    state: State<'_, AppState>,
+) -> Result<Note, AppError> {
+    let note_id = parse_note_id(&note_id)?;
+    let revision_id = parse_revision_id(&revision_id)?;
+    state.restore_note_revision(note_id, revision_id).await
+}
+
+#[tauri::command]
+pub async fn close_project(project_id: String, state: State<'_, AppState>) -> Result<(), AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.close_project(id).await
+}
+
+#[tauri::command]
+pub async fn remove_project_file(
+    project_id: String,
+    file_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    let id = parse_project_id(&project_id)?;
+    let file_id = parse_file_id(&file_id)?;
+    state.remove_project_file(id, file_id).await
+}
+
+#[tauri::command]
+pub async fn list_backups(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ProjectBackup>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.list_backups(id).await
+}
+
+#[tauri::command]
+pub async fn restore_backup(
+    project_id: String,
+    backup_id: String,
+    state: State<'_, AppState>,
+) -> Result<Project, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.restore_backup(id, &backup_id).await
+}
+
+#[tauri::command]
+pub async fn export_project(
+    project_id: String,
+    dest_path: String,
+    state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.export_project(id, Path::new(&dest_path)).await
+}
+
+#[tauri::command]
+pub async fn export_project_bundle(
+    project_id: String,
+    dest_path: String,
+    state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.export_project_bundle(id, Path::new(&dest_path)).await
+}
+
+#[tauri::command]
+pub async fn import_project(
+    archive_path: String,
+    state: State<'_, AppState>,
+) -> Result<Project, AppError> {
+    state.import_project(Path::new(&archive_path)).await
+}
+
+#[tauri::command]
+pub async fn duplicate_project(
+    project_id: String,
+    new_name: Option<String>,
+    include_results: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Project, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state
+        .duplicate_project(id, new_name, include_results.unwrap_or(false))
+        .await
+}
+
+#[tauri::command]
+pub async fn create_tag(
+    name: String,
+    color: String,
+    state: State<'_, AppState>,
+) -> Result<Tag, AppError> {
+    state.create_tag(name, color).await
+}
+
+#[tauri::command]
+pub async fn list_tags(state: State<'_, AppState>) -> Result<Vec<Tag>, AppError> {
+    state.list_tags().await
+}
+
+#[tauri::command]
+pub async fn delete_tag(tag_id: String, state: State<'_, AppState>) -> Result<(), AppError> {
+    let id = parse_tag_id(&tag_id)?;
+    state.delete_tag(id).await
+}
+
+#[tauri::command]
+pub async fn set_project_tags(
+    project_id: String,
+    tag_ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Tag>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    let tag_ids = tag_ids
+        .iter()
+        .map(|tag_id| parse_tag_id(tag_id))
+        .collect::<Result<Vec<_>, _>>()?;
+    state.set_project_tags(id, tag_ids).await
+}
+
+#[tauri::command]
+pub async fn get_project_tags(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<Tag>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.get_project_tags(id).await
+}
+
+#[tauri::command]
+pub async fn get_projects_by_tag(
+    tag_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<Project>, AppError> {
+    let id = parse_tag_id(&tag_id)?;
+    state.get_projects_by_tag(id).await
+}
+
+#[tauri::command]
+pub async fn list_templates(state: State<'_, AppState>) -> Result<Vec<ProjectTemplate>, AppError> {
+    state.list_templates().await
+}
+
+#[tauri::command]
+pub async fn create_template_from_project(
+    project_id: String,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<ProjectTemplate, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.create_template_from_project(id, name).await
+}
+
+#[tauri::command]
+pub async fn create_project_from_template(
+    template_id: String,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<Project, AppError> {
+    let id = parse_template_id(&template_id)?;
+    state.create_project_from_template(id, name).await
+}
+
+#[tauri::command]
+pub async fn delete_template(
+    template_id: String,
     state: State<'_, AppState>,
-) -> Result<Project, String> {
-    state.create_project(name, description).await
+) -> Result<(), AppError> {
+    let id = parse_template_id(&template_id)?;
+    state.delete_template(id).await
 }
 
 #[tauri::command]
-pub async fn get_projects(state: State<'_, AppState>) -> Result<Vec<Project>, String> {
-    state.get_projects().await
-}
\ No newline at end of file
+pub async fn search_projects(
+    query: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<SearchHit>, AppError> {
+    state.search_projects(&query).await
+}
+
+#[tauri::command]
+pub async fn reindex_projects(state: State<'_, AppState>) -> Result<u64, AppError> {
+    state.reindex_projects().await
+}
+
+#[tauri::command]
+pub async fn find_duplicate_projects(
+    state: State<'_, AppState>,
+) -> Result<Vec<DuplicateProjectSet>, AppError> {
+    state.find_duplicate_projects().await
+}
+
+#[tauri::command]
+pub async fn merge_projects(
+    keep_id: String,
+    absorb_id: String,
+    options: Option<MergeOptions>,
+    state: State<'_, AppState>,
+) -> Result<Project, AppError> {
+    let keep_id = parse_project_id(&keep_id)?;
+    let absorb_id = parse_project_id(&absorb_id)?;
+    state
+        .merge_projects(keep_id, absorb_id, options.unwrap_or_default())
+        .await
+}
+
+#[tauri::command]
+pub async fn estimate_lateral(
+    project_id: String,
+    input: LateralEstimateInput,
+    state: State<'_, AppState>,
+) -> Result<LateralEstimate, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.estimate_lateral(id, input).await
+}
+
+#[tauri::command]
+pub async fn get_lateral_estimate(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<LateralEstimate>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.get_lateral_estimate(id).await
+}
+
+/// Saves the base shear and story forces with the project.
+#[tauri::command]
+pub async fn save_seismic_base_shear(
+    project_id: String,
+    params: SeismicParams,
+    stories: Vec<SeismicStory>,
+    state: State<'_, AppState>,
+) -> Result<SeismicBaseShear, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.save_seismic_base_shear(id, params, stories).await
+}
+
+#[tauri::command]
+pub async fn get_seismic_base_shear(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<SeismicBaseShear>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.get_seismic_base_shear(id).await
+}
+
+/// Saves the wall pressures with the project.
+#[tauri::command]
+pub async fn save_wind_pressures(
+    project_id: String,
+    input: WindInput,
+    state: State<'_, AppState>,
+) -> Result<WindPressures, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.save_wind_pressures(id, input).await
+}
+
+#[tauri::command]
+pub async fn get_wind_pressures(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<WindPressures>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.get_wind_pressures(id).await
+}
+
+#[tauri::command]
+pub async fn generate_load_combinations(
+    project_id: String,
+    cases: Vec<LoadCase>,
+    code: DesignCode,
+    state: State<'_, AppState>,
+) -> Result<Vec<LoadCombination>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.generate_load_combinations(id, cases, code).await
+}
+
+#[tauri::command]
+pub async fn get_load_combinations(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<LoadCombination>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.get_load_combinations(id).await
+}
+
+#[tauri::command]
+pub async fn import_results_csv(
+    project_id: String,
+    run_label: String,
+    table: ResultTable,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<AnalysisRun, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state
+        .import_results_csv(id, run_label, table, Path::new(&path))
+        .await
+}
+
+/// Takes clipboard text the frontend read; `table_hint` settles an
+/// ambiguous header.
+#[tauri::command]
+pub async fn paste_import_table(
+    project_id: String,
+    run_label: String,
+    raw_text: String,
+    table_hint: Option<ResultTable>,
+    state: State<'_, AppState>,
+) -> Result<PasteImportReport, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state
+        .paste_import_table(id, run_label, &raw_text, table_hint)
+        .await
+}
+
+#[tauri::command]
+pub async fn get_analysis_runs(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<AnalysisRun>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.get_analysis_runs(id).await
+}
+
+#[tauri::command]
+pub async fn get_story_drifts(
+    project_id: String,
+    run_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<StoryDrift>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    let run_id = parse_run_id(&run_id)?;
+    state.get_story_drifts(id, run_id).await
+}
+
+#[tauri::command]
+pub async fn get_modal_periods(
+    project_id: String,
+    run_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ModalPeriod>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    let run_id = parse_run_id(&run_id)?;
+    state.get_modal_periods(id, run_id).await
+}
+
+#[tauri::command]
+pub async fn get_base_reactions(
+    project_id: String,
+    run_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<BaseReaction>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    let run_id = parse_run_id(&run_id)?;
+    state.get_base_reactions(id, run_id).await
+}
+
+#[tauri::command]
+pub async fn check_drift_limits(
+    project_id: String,
+    run_id: String,
+    limit_ratio: f64,
+    write_csv: bool,
+    state: State<'_, AppState>,
+) -> Result<Vec<DriftCheckReport>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    let run_id = parse_run_id(&run_id)?;
+    state
+        .check_drift_limits(id, run_id, limit_ratio, write_csv)
+        .await
+}
+
+/// Returns the id of the background task; its result is the report's path
+/// so the UI can open it.
+#[tauri::command]
+pub async fn generate_project_report(
+    project_id: String,
+    format: ReportFormat,
+    dest_path: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, AppError> {
+    let id = parse_project_id(&project_id)?;
+    let task_id = state.spawn_task(TaskKind::ProjectReport, move |task| async move {
+        app.state::<AppState>()
+            .generate_project_report(id, format, Path::new(&dest_path), &task)
+            .await
+    });
+    Ok(task_id.to_string())
+}
+
+/// Result tables come from `run_id`, or the latest run when it's omitted.
+#[tauri::command]
+pub async fn export_table(
+    project_id: String,
+    table: ExportTable,
+    format: ExportFormat,
+    run_id: Option<String>,
+    dest_path: String,
+    state: State<'_, AppState>,
+) -> Result<String, AppError> {
+    let id = parse_project_id(&project_id)?;
+    let run_id = run_id.as_deref().map(parse_run_id).transpose()?;
+    let path = state
+        .export_table(id, table, format, run_id, Path::new(&dest_path))
+        .await?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Returns the id of the background task; its result is the parsed model.
+#[tauri::command]
+pub async fn import_etabs_model(
+    project_id: String,
+    path: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, AppError> {
+    let id = parse_project_id(&project_id)?;
+    let task_id = state.spawn_task(TaskKind::ImportEtabsModel, move |task| async move {
+        app.state::<AppState>()
+            .import_etabs_model(id, Path::new(&path), &task)
+            .await
+    });
+    Ok(task_id.to_string())
+}
+
+/// Creates a project per ETABS model in `dir_path` in a background task,
+/// sending `bulk-import:progress` after each file. Returns the task id; the
+/// task's result is the import report.
+#[tauri::command]
+pub async fn bulk_import(
+    dir_path: String,
+    recursive: bool,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, AppError> {
+    let task_id = state.spawn_task(TaskKind::BulkImport, move |task| async move {
+        app.state::<AppState>()
+            .bulk_import(Path::new(&dir_path), recursive, &task)
+            .await
+    });
+    Ok(task_id.to_string())
+}
+
+/// Stops the running bulk import after its current file, like
+/// `cancel_task` with its task id. Returns false when none is running.
+#[tauri::command]
+pub fn cancel_bulk_import(state: State<'_, AppState>) -> bool {
+    state.cancel_bulk_import()
+}
+
+#[tauri::command]
+pub fn get_tasks(state: State<'_, AppState>) -> Vec<TaskInfo> {
+    state.get_tasks()
+}
+
+#[tauri::command]
+pub fn get_task(task_id: String, state: State<'_, AppState>) -> Result<TaskInfo, AppError> {
+    let task_id = parse_task_id(&task_id)?;
+    state.get_task(task_id)
+}
+
+/// Returns false when the task already finished.
+#[tauri::command]
+pub fn cancel_task(task_id: String, state: State<'_, AppState>) -> Result<bool, AppError> {
+    let task_id = parse_task_id(&task_id)?;
+    state.cancel_task(task_id)
+}
+
+#[tauri::command]
+pub async fn get_etabs_model(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<EtabsModel>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.get_etabs_model(id).await
+}
+
+/// `file_id_a` is the earlier revision; `write_json` also saves the diff
+/// in the project's `reports/` folder.
+#[tauri::command]
+pub async fn compare_etabs_files(
+    project_id: String,
+    file_id_a: String,
+    file_id_b: String,
+    write_json: bool,
+    state: State<'_, AppState>,
+) -> Result<ModelDiff, AppError> {
+    let id = parse_project_id(&project_id)?;
+    let file_id_a = parse_file_id(&file_id_a)?;
+    let file_id_b = parse_file_id(&file_id_b)?;
+    state
+        .compare_etabs_files(id, file_id_a, file_id_b, write_json)
+        .await
+}
+
+#[tauri::command]
+pub fn get_init_status(state: State<'_, AppState>) -> InitStatus {
+    state.init_status()
+}
+
+/// Retries opening storage, in `custom_data_dir` when given.
+#[tauri::command]
+pub async fn retry_initialization(
+    custom_data_dir: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<InitStatus, AppError> {
+    state
+        .retry_initialization(custom_data_dir.map(PathBuf::from))
+        .await
+}
+
+#[tauri::command]
+pub async fn get_settings(state: State<'_, AppState>) -> Result<Settings, AppError> {
+    Ok(state.get_settings().await)
+}
+
+#[tauri::command]
+pub async fn update_settings(
+    patch: SettingsPatch,
+    state: State<'_, AppState>,
+) -> Result<Settings, AppError> {
+    state.update_settings(patch).await
+}
+
+/// Moves the projects to `new_path` in a background task and switches to it.
+/// Returns the task id; the task's result is the relocation report.
+#[tauri::command]
+pub async fn relocate_projects_dir(
+    new_path: String,
+    keep_original: bool,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, AppError> {
+    let task_id = state.spawn_task(TaskKind::RelocateProjects, move |task| async move {
+        app.state::<AppState>()
+            .relocate_projects_dir(Path::new(&new_path), keep_original, &task)
+            .await
+    });
+    Ok(task_id.to_string())
+}
+
+#[tauri::command]
+pub async fn get_storage_status(state: State<'_, AppState>) -> Result<StorageStatus, AppError> {
+    state.get_storage_status().await
+}
+
+#[tauri::command]
+pub async fn get_diagnostics(state: State<'_, AppState>) -> Result<Diagnostics, AppError> {
+    state.get_diagnostics().await
+}
+
+/// Tail of the backend's JSON log for the diagnostics page.
+#[tauri::command]
+pub async fn get_recent_backend_logs(
+    limit: usize,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, AppError> {
+    state.get_recent_backend_logs(limit).await
+}
+
+#[tauri::command]
+pub async fn repair_storage(
+    orphaned_rows: Option<OrphanedRowAction>,
+    state: State<'_, AppState>,
+) -> Result<RepairReport, AppError> {
+    state.repair_storage(orphaned_rows.unwrap_or_default()).await
+}
+
+#[tauri::command]
+pub async fn etabs_connect(state: State<'_, AppState>) -> Result<EtabsStatus, AppError> {
+    state.etabs_connect().await
+}
+
+#[tauri::command]
+pub async fn etabs_get_model_info(state: State<'_, AppState>) -> Result<EtabsModelInfo, AppError> {
+    state.etabs_get_model_info().await
+}
+
+/// Story drifts from the connected ETABS model, saved in the project as
+/// the run named after the model file. No `load_cases` means all cases.
+#[tauri::command]
+pub async fn etabs_get_story_drifts(
+    project_id: String,
+    load_cases: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<Vec<StoryDrift>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state
+        .etabs_get_story_drifts(id, load_cases.unwrap_or_default())
+        .await
+}
+
+/// Base reactions from the connected ETABS model, saved in the project as
+/// the run named after the model file and as a CSV in its reports folder.
+/// No `load_cases` means every case and combination.
+#[tauri::command]
+pub async fn etabs_get_base_reactions(
+    project_id: String,
+    load_cases: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<Vec<BaseReaction>, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state
+        .etabs_get_base_reactions(id, load_cases.unwrap_or_default())
+        .await
+}
+
+/// Writes the project's generated load combinations into the model open
+/// in the connected ETABS.
+#[tauri::command]
+pub async fn etabs_write_load_combinations(
+    project_id: String,
+    state: State<'_, AppState>,
+) -> Result<LoadCombinationsReport, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state.etabs_write_load_combinations(id).await
+}
+
+/// Extracts frame member forces from the connected ETABS model in a
+/// background task. Returns the task id; the task's result is the saved run
+/// and its row count.
+#[tauri::command]
+pub async fn etabs_extract_frame_forces(
+    project_id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, AppError> {
+    let id = parse_project_id(&project_id)?;
+    let task_id = state.spawn_task(TaskKind::ExtractFrameForces, move |task| async move {
+        app.state::<AppState>()
+            .etabs_extract_frame_forces(id, &task)
+            .await
+    });
+    Ok(task_id.to_string())
+}
+
+/// One page of a run's frame forces; no `filter` means every row.
+#[tauri::command]
+pub async fn get_frame_forces(
+    project_id: String,
+    run_id: String,
+    filter: Option<FrameForceFilter>,
+    page: usize,
+    page_size: usize,
+    state: State<'_, AppState>,
+) -> Result<FrameForcePage, AppError> {
+    let id = parse_project_id(&project_id)?;
+    let run_id = parse_run_id(&run_id)?;
+    state
+        .get_frame_forces(id, run_id, filter.unwrap_or_default(), page, page_size)
+        .await
+}
+
+/// Modal periods, frequencies and mass participation with the 90% check.
+/// Cached in the project; `refresh` pulls them from ETABS again.
+#[tauri::command]
+pub async fn get_modal_results(
+    project_id: String,
+    load_case: Option<String>,
+    refresh: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<ModalResults, AppError> {
+    let id = parse_project_id(&project_id)?;
+    state
+        .get_modal_results(id, load_case, refresh.unwrap_or(false))
+        .await
+}
+
+/// Opens an `.edb` model in a background task; `hidden` also runs the
+/// analysis. Returns the task id; the task's result is the open report.
+#[tauri::command]
+pub async fn etabs_open_model(
+    path: String,
+    hidden: bool,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, AppError> {
+    let task_id = state.spawn_task(TaskKind::OpenModel, move |_task| async move {
+        app.state::<AppState>()
+            .etabs_open_model(Path::new(&path), hidden)
+            .await
+    });
+    Ok(task_id.to_string())
+}
+
+#[tauri::command]
+pub async fn etabs_disconnect(state: State<'_, AppState>) -> Result<(), AppError> {
+    state.etabs_disconnect().await;
+    Ok(())
+}
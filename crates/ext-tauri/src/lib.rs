@@ -1,36 +1,66 @@
 mod commands;
+mod logging;
 
-use tauri::{Manager};
+use tauri::{AppHandle, Emitter, Manager, RunEvent};
 use tauri_plugin_log::{Target, TargetKind};
-use ext_api::AppState;
-use ext_db::Database;
+use ext_api::init::open_database;
+use ext_api::watcher::{FileWatcher, FILE_WATCH_DEBOUNCE};
+use ext_api::{AppState, EventSink};
+use ext_core::bulk_import::{BulkImportProgress, BULK_IMPORT_PROGRESS};
+use ext_core::deep_link::{DEEP_LINK_SCHEME, NAVIGATE_ERROR, NAVIGATE_PROJECT};
+use ext_core::events::ProjectEvent;
+use ext_core::settings::LogLevel;
+use ext_core::tasks::TaskInfo;
+use ext_error::AppError;
+use ext_db::settings::{load_settings, SETTINGS_FILE_NAME};
+use std::sync::Arc;
+use tauri_plugin_deep_link::DeepLinkExt;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    // ─── Log directory ───────────────────────────────────────────────
-    let app_log_dir = dirs::data_local_dir()
+    let app_dir = dirs::data_local_dir()
         .expect("Failed to resolve data directory")
-        .join("etab-extension")
-        .join("logs");
+        .join("etab-extension");
+
+    // ─── Settings ─────────────────────────────────────────────────────
+    let settings_path = app_dir.join(SETTINGS_FILE_NAME);
+    let settings = tauri::async_runtime::block_on(load_settings(&settings_path))
+        .expect("Failed to load settings");
+
+    // ─── Log directory ───────────────────────────────────────────────
+    let app_log_dir = app_dir.join("logs");
 
     std::fs::create_dir_all(&app_log_dir)
         .expect("failed to create app log dir");
 
+    let log_level = if cfg!(debug_assertions) {
+        log::LevelFilter::Debug
+    } else {
+        level_filter(settings.log_level)
+    };
+
     // ─── Log plugin (GitButler-style) ─────────────────────────────────
     let log_plugin = tauri_plugin_log::Builder::default()
         .target(Target::new(TargetKind::LogDir {
             file_name: Some("ui-logs".to_string()),
         }))
-        .level(if cfg!(debug_assertions) {
-            log::LevelFilter::Debug
-        } else {
-            log::LevelFilter::Error
-        })
+        .level(log_level)
         .build();
 
+    // ─── Backend tracing ──────────────────────────────────────────────
+    // Flushed when dropped, after the app exits
+    let _tracing_guard = logging::init(&app_log_dir, log_level);
+
     tauri::Builder::default()
         // ─── Plugins ──────────────────────────────────────────────────
-        .plugin(tauri_plugin_single_instance::init(|_, _, _| {}))
+        // A second launch, e.g. from a clicked link, hands its arguments to
+        // this instance and exits
+        .plugin(tauri_plugin_single_instance::init(|app, argv, _| {
+            focus_main_window(app);
+            for url in argv.iter().filter(|arg| is_deep_link(arg)) {
+                open_deep_link(app, url.clone());
+            }
+        }))
         .plugin(tauri_plugin_http::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_os::init())
@@ -43,49 +73,243 @@ pub fn run() {
         .plugin(log_plugin)
 
         // ─── Setup ────────────────────────────────────────────────────
-        .setup(|app| {
+        .setup(move |app| {
             let app_handle = app.handle().clone();
 
-            let db = tauri::async_runtime::block_on(async {
-                initialize_database().await
-            })
-            .expect("Failed to initialize database");
+            // A locked or unwritable database still opens a window, which
+            // shows the error and lets the user retry
+            let opened = tauri::async_runtime::block_on(open_database(&app_dir, &settings));
+            let state = match opened {
+                Ok(db) => AppState::with_settings(db, settings, settings_path),
+                Err(e) => {
+                    log::error!("Failed to initialize storage in '{}': {e}", app_dir.display());
+                    AppState::degraded(app_dir, e, settings, Some(settings_path))
+                }
+            };
 
-            app_handle.manage(AppState::new(db));
+            let (watcher, file_changes) =
+                FileWatcher::new(FILE_WATCH_DEBOUNCE).expect("Failed to start file watcher");
+            let events = Arc::new(WebviewEventSink(app_handle.clone()));
+            let mut state = state
+                .with_event_sink(events)
+                .with_file_watcher(watcher);
+            // Where the log plugin's LogDir target writes
+            if let Ok(log_dir) = app.path().app_log_dir() {
+                state = state.with_log_dir(log_dir);
+            }
+            state = state.with_backend_log_dir(app_log_dir);
+            app_handle.manage(state);
+
+            let watch_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                watch_handle
+                    .state::<AppState>()
+                    .watch_file_changes(file_changes)
+                    .await;
+            });
+
+            // ─── Deep links ───────────────────────────────────────────
+            // Installers register the scheme; dev builds register it here
+            #[cfg(any(windows, target_os = "linux"))]
+            {
+                if let Err(e) = app.deep_link().register_all() {
+                    log::warn!("Failed to register {DEEP_LINK_SCHEME}:// links: {e}");
+                }
+            }
+            // The link the app was launched with
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                for url in urls {
+                    open_deep_link(&app_handle, url.to_string());
+                }
+            }
+            // macOS delivers links to the running app instead of starting a
+            // second instance
+            #[cfg(target_os = "macos")]
+            {
+                let link_handle = app_handle.clone();
+                app.deep_link().on_open_url(move |event| {
+                    for url in event.urls() {
+                        open_deep_link(&link_handle, url.to_string());
+                    }
+                });
+            }
             Ok(())
         })
 
         // ─── Commands ─────────────────────────────────────────────────
         .invoke_handler(tauri::generate_handler![
             commands::greet,
+            commands::compute_section_properties,
+            commands::compute_development_length,
+            commands::compute_base_shear,
+            commands::distribute_story_forces,
+            commands::compute_wind_pressures,
+            commands::convert_quantity,
             commands::create_project,
             commands::get_projects,
+            commands::get_project,
+            commands::get_project_deep_link,
+            commands::update_project,
+            commands::archive_project,
+            commands::restore_project,
+            commands::trash_project,
+            commands::empty_trash,
+            commands::list_trashed_projects,
+            commands::purge_project,
+            commands::delete_project,
+            commands::add_project_file,
+            commands::get_project_files,
+            commands::remove_project_file,
+            commands::open_project,
+            commands::close_project,
+            commands::get_recent_projects,
+            commands::get_audit_log,
+            commands::create_note,
+            commands::update_note,
+            commands::delete_note,
+            commands::get_notes,
+            commands::get_note_history,
+            commands::restore_note_revision,
+            commands::list_backups,
+            commands::restore_backup,
+            commands::export_project,
+            commands::export_project_bundle,
+            commands::import_project,
+            commands::duplicate_project,
+            commands::create_tag,
+            commands::list_tags,
+            commands::delete_tag,
+            commands::set_project_tags,
+            commands::get_project_tags,
+            commands::get_projects_by_tag,
+            commands::list_templates,
+            commands::create_template_from_project,
+            commands::create_project_from_template,
+            commands::delete_template,
+            commands::search_projects,
+            commands::reindex_projects,
+            commands::find_duplicate_projects,
+            commands::merge_projects,
+            commands::estimate_lateral,
+            commands::get_lateral_estimate,
+            commands::save_seismic_base_shear,
+            commands::get_seismic_base_shear,
+            commands::save_wind_pressures,
+            commands::get_wind_pressures,
+            commands::generate_load_combinations,
+            commands::get_load_combinations,
+            commands::import_results_csv,
+            commands::paste_import_table,
+            commands::get_analysis_runs,
+            commands::get_story_drifts,
+            commands::get_modal_periods,
+            commands::get_base_reactions,
+            commands::check_drift_limits,
+            commands::generate_project_report,
+            commands::export_table,
+            commands::import_etabs_model,
+            commands::bulk_import,
+            commands::cancel_bulk_import,
+            commands::get_tasks,
+            commands::get_task,
+            commands::cancel_task,
+            commands::get_etabs_model,
+            commands::compare_etabs_files,
+            commands::get_init_status,
+            commands::retry_initialization,
+            commands::get_settings,
+            commands::update_settings,
+            commands::relocate_projects_dir,
+            commands::get_storage_status,
+            commands::get_diagnostics,
+            commands::get_recent_backend_logs,
+            commands::repair_storage,
+            commands::etabs_connect,
+            commands::etabs_get_model_info,
+            commands::etabs_open_model,
+            commands::etabs_get_story_drifts,
+            commands::get_modal_results,
+            commands::etabs_get_base_reactions,
+            commands::etabs_write_load_combinations,
+            commands::etabs_extract_frame_forces,
+            commands::get_frame_forces,
+            commands::etabs_disconnect,
         ])
 
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // Another instance may open the projects once this one is gone
+            if let RunEvent::Exit = event {
+                tauri::async_runtime::block_on(app.state::<AppState>().release_project_locks());
+            }
+        });
 }
 
 
+/// Forwards project events to every webview
+struct WebviewEventSink(AppHandle);
 
-async fn initialize_database() -> Result<Database, Box<dyn std::error::Error>> {
-    let app_dir = dirs::data_local_dir()
-        .ok_or("Failed to get app data directory")?
-        .join("etab-extension");
+impl EventSink for WebviewEventSink {
+    fn emit(&self, event: &ProjectEvent) -> Result<(), AppError> {
+        self.0
+            .emit(event.name(), event)
+            .map_err(|e| AppError::Internal(format!("Failed to emit {}: {e}", event.name())))
+    }
 
-    let db_dir = app_dir.join("db");
-    let projects_dir = app_dir.join("projects");
+    fn emit_bulk_import_progress(&self, progress: &BulkImportProgress) -> Result<(), AppError> {
+        self.0
+            .emit(BULK_IMPORT_PROGRESS, progress)
+            .map_err(|e| AppError::Internal(format!("Failed to emit {BULK_IMPORT_PROGRESS}: {e}")))
+    }
 
-    std::fs::create_dir_all(&db_dir)?;
-    std::fs::create_dir_all(&projects_dir)?;
+    fn emit_task(&self, event: &str, task: &TaskInfo) -> Result<(), AppError> {
+        self.0
+            .emit(event, task)
+            .map_err(|e| AppError::Internal(format!("Failed to emit {event}: {e}")))
+    }
+}
 
-    let db_path = db_dir.join("app.db");
+fn is_deep_link(arg: &str) -> bool {
+    arg.split_once("://")
+        .is_some_and(|(scheme, _)| scheme.eq_ignore_ascii_case(DEEP_LINK_SCHEME))
+}
+
+fn focus_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
 
-    let db_url = format!(
-        "sqlite://{}?mode=rwc",
-        db_path.to_string_lossy().replace('\\', "/")
-    );
+/// Sends the project a link points at to the frontend, or the reason it
+/// can't be opened.
+fn open_deep_link(app: &AppHandle, url: String) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let Some(state) = app.try_state::<AppState>() else {
+            return;
+        };
+        let emitted = match state.resolve_deep_link(&url).await {
+            Ok(project) => app.emit(NAVIGATE_PROJECT, &project),
+            Err(e) => {
+                log::warn!("Failed to open {url}: {e}");
+                app.emit(NAVIGATE_ERROR, &e)
+            }
+        };
+        if let Err(e) = emitted {
+            log::warn!("Failed to emit navigation for {url}: {e}");
+        }
+    });
+}
 
-    let db = Database::new(&db_url, projects_dir.to_str().unwrap()).await?;
-    Ok(db)
+fn level_filter(level: LogLevel) -> log::LevelFilter {
+    match level {
+        LogLevel::Error => log::LevelFilter::Error,
+        LogLevel::Warn => log::LevelFilter::Warn,
+        LogLevel::Info => log::LevelFilter::Info,
+        LogLevel::Debug => log::LevelFilter::Debug,
+        LogLevel::Trace => log::LevelFilter::Trace,
+    }
 }
@@ -0,0 +1,132 @@
+//! Backend tracing: the `#[instrument]` spans of ext-api and ext-db, written
+//! as JSON lines to a daily file next to the UI logs, with each span's
+//! duration when it closes and a warning for slow operations.
+
+use ext_api::logs::BACKEND_LOG_PREFIX;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Level, Subscriber};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{Layer, Registry};
+
+/// Operations taking longer are logged at warn level
+const SLOW_OPERATION: Duration = Duration::from_millis(500);
+
+/// Installs the backend subscriber. The guard flushes the file when
+/// dropped, so keep it until the app exits. Logging is skipped, not
+/// fatal, when the file can't be created.
+pub fn init(log_dir: &Path, level: log::LevelFilter) -> Option<WorkerGuard> {
+    let appender = match RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(BACKEND_LOG_PREFIX)
+        .build(log_dir)
+    {
+        Ok(appender) => appender,
+        Err(e) => {
+            eprintln!("Failed to open backend log in '{}': {e}", log_dir.display());
+            return None;
+        }
+    };
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+
+    let file = tracing_subscriber::fmt::layer()
+        .json()
+        .with_writer(writer)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_filter(level_filter(level));
+    // Times every span of our crates, whatever the file's level, so slow
+    // operations are caught even when their spans aren't logged
+    let slow = SlowSpans.with_filter(
+        Targets::new()
+            .with_target("ext_api", Level::TRACE)
+            .with_target("ext_db", Level::TRACE),
+    );
+
+    // Not `try_init`: the log plugin owns the `log` facade
+    let subscriber = Registry::default().with(slow).with(file);
+    if let Err(e) = tracing::subscriber::set_global_default(subscriber) {
+        eprintln!("Failed to install backend tracing: {e}");
+        return None;
+    }
+    Some(guard)
+}
+
+fn level_filter(level: log::LevelFilter) -> LevelFilter {
+    match level {
+        log::LevelFilter::Off => LevelFilter::OFF,
+        log::LevelFilter::Error => LevelFilter::ERROR,
+        log::LevelFilter::Warn => LevelFilter::WARN,
+        log::LevelFilter::Info => LevelFilter::INFO,
+        log::LevelFilter::Debug => LevelFilter::DEBUG,
+        log::LevelFilter::Trace => LevelFilter::TRACE,
+    }
+}
+
+/// Warns about spans open longer than [`SLOW_OPERATION`]
+struct SlowSpans;
+
+/// When a span opened, and its fields for the warning
+struct Started {
+    at: Instant,
+    fields: String,
+}
+
+/// Renders span fields as `name=value` pairs
+struct FieldText<'a>(&'a mut String);
+
+impl Visit for FieldText<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+        let _ = write!(self.0, "{}={value:?}", field.name());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_debug(field, &format_args!("{value}"));
+    }
+}
+
+impl<S> Layer<S> for SlowSpans
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = String::new();
+        attrs.record(&mut FieldText(&mut fields));
+        span.extensions_mut().insert(Started {
+            at: Instant::now(),
+            fields,
+        });
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        // Released before logging, which looks spans up again
+        let Some(Started { at, fields }) = span.extensions_mut().remove::<Started>() else {
+            return;
+        };
+        let elapsed = at.elapsed();
+        if elapsed > SLOW_OPERATION {
+            tracing::warn!(
+                operation = span.name(),
+                elapsed_ms = elapsed.as_millis() as u64,
+                fields = %fields,
+                "slow operation"
+            );
+        }
+    }
+}
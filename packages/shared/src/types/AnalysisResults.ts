@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BaseReaction } from "./BaseReaction";
+import type { ModalPeriod } from "./ModalPeriod";
+import type { StoryDrift } from "./StoryDrift";
+
+/**
+ * Result tables of one run
+ */
+export type AnalysisResults = { story_drifts: Array<StoryDrift>, modal_periods: Array<ModalPeriod>, base_reactions: Array<BaseReaction>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One imported set of results, identified by a label such as "Rev B"
+ */
+export type AnalysisRun = { id: string, project_id: string, label: string, imported_at: string, };
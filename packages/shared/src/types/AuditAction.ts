@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Kind of change recorded in the audit log
+ */
+export type AuditAction = "Created" | "Imported" | "Updated" | "Archived" | "Restored" | "Trashed" | "FileAttached" | "FileRemoved" | "Merged";
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AuditAction } from "./AuditAction";
+
+/**
+ * One recorded change to a project
+ */
+export type AuditEntry = { id: string, project_id: string, action: AuditAction, 
+/**
+ * Human-readable description, e.g. `Renamed "Tower A" to "Tower B"`
+ */
+detail: string, timestamp: string, };
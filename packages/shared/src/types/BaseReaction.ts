@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Base reactions for one load case (ETABS "Base Reactions")
+ */
+export type BaseReaction = { load_case: string, fx: number, fy: number, fz: number, mx: number, my: number, mz: number, };
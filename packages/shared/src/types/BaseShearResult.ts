@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CsEquation } from "./CsEquation";
+
+/**
+ * Seismic response coefficient and base shear, Eq. 12.8-1
+ */
+export type BaseShearResult = { cs: number, governing_equation: CsEquation, 
+/**
+ * V in kN
+ */
+base_shear: number, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Payload of `bulk-import:progress`
+ */
+export type BulkImportProgress = { 
+/**
+ * Files handled so far, including `path`
+ */
+current: number, total: number, path: string, };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Project } from "./Project";
+
+/**
+ * Outcome of a bulk import
+ */
+export type BulkImportReport = { created: Array<Project>, 
+/**
+ * (path, reason) of files that were not imported
+ */
+skipped: Array<[string, string]>, 
+/**
+ * Stopped by the user; files after the last one reported were not
+ * looked at
+ */
+cancelled: boolean, };
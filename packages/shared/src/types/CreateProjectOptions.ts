@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Options for creating a project
+ */
+export type CreateProjectOptions = { 
+/**
+ * Append " (2)", " (3)", ... instead of rejecting a name that is taken
+ */
+auto_suffix_name: boolean, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Equation of §12.8.1.1 that sets Cs
+ */
+export type CsEquation = "Spectral" | "VelocityCap" | "LongPeriodCap" | "Minimum" | "NearFaultMinimum";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Design method the combinations are generated for
+ */
+export type DesignCode = "Asce7Lrfd" | "Asce7Asd";
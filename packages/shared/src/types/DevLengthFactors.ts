@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Modification factors of Table 25.4.2.5 as applied
+ */
+export type DevLengthFactors = { lambda: number, 
+/**
+ * ψt × ψe, capped at 1.7
+ */
+psi_t_psi_e: number, psi_s: number, psi_g: number, 
+/**
+ * (cb + Ktr) / db, capped at 2.5
+ */
+confinement: number, 
+/**
+ * √fc' in psi, capped at 100
+ */
+sqrt_fc: number, };
@@ -0,0 +1,25 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BarCoating } from "./BarCoating";
+import type { Length } from "./Length";
+
+/**
+ * Site conditions that modify the basic development length
+ */
+export type DevLengthModifiers = { 
+/**
+ * Lightweight concrete, λ = 0.75
+ */
+lightweight: boolean, coating: BarCoating, 
+/**
+ * More than 12 in of fresh concrete cast below the bar, ψt = 1.3
+ */
+top_bar: boolean, 
+/**
+ * Smaller of the cover to the bar centre and half the centre-to-centre
+ * spacing
+ */
+cb: Length, 
+/**
+ * Transverse reinforcement index; zero is always conservative
+ */
+ktr: Length, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DevLengthFactors } from "./DevLengthFactors";
+import type { Length } from "./Length";
+
+/**
+ * Development and lap splice lengths, with the factors that produced them
+ */
+export type DevelopmentLength = { ld: Length, 
+/**
+ * `None` for bars that can't be lap spliced
+ */
+class_a_splice: Length | null, class_b_splice: Length | null, factors: DevLengthFactors, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DevLengthModifiers } from "./DevLengthModifiers";
+import type { RebarSize } from "./RebarSize";
+import type { Stress } from "./Stress";
+
+/**
+ * Input of the development length calculator
+ */
+export type DevelopmentLengthInput = { bar: RebarSize, fc: Stress, fy: Stress, modifiers: DevLengthModifiers, };
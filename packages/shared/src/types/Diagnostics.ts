@@ -0,0 +1,30 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Storage health, gathered without modifying anything
+ */
+export type Diagnostics = { 
+/**
+ * SQLite file; `None` for an in-memory database
+ */
+database_path: string | null, 
+/**
+ * Size of the database file in bytes
+ */
+database_size: number, 
+/**
+ * "ok", or the problems reported by SQLite's integrity_check
+ */
+integrity_check: string, projects_dir: string, 
+/**
+ * Bytes available on the volume holding the projects directory, if known
+ */
+free_space: number | null, project_count: number, 
+/**
+ * Project folders without a registry row
+ */
+orphaned_folders: Array<string>, 
+/**
+ * Registered projects whose folder is missing
+ */
+orphaned_rows: Array<string>, log_dir: string | null, };
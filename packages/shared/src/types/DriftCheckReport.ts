@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Direction } from "./Direction";
+
+/**
+ * Drift check of one story in one direction, enveloped over load cases
+ */
+export type DriftCheckReport = { story: string, direction: Direction, 
+/**
+ * Load case producing the largest drift
+ */
+load_case: string, 
+/**
+ * Story drift Δ = drift ratio · hsx
+ */
+drift: number, 
+/**
+ * Allowable drift Δa = limit ratio · hsx
+ */
+limit: number, utilization: number, pass: boolean, 
+/**
+ * Highest utilization in its direction
+ */
+governing: boolean, };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DuplicateReason } from "./DuplicateReason";
+import type { Project } from "./Project";
+
+/**
+ * Candidate group of projects that may describe the same building
+ */
+export type DuplicateProjectSet = { reason: DuplicateReason, 
+/**
+ * Largest edit distance between normalized names within the set
+ */
+max_distance: number, 
+/**
+ * SHA-256 of the shared model file, for `SameModelFile` sets
+ */
+model_sha256: string | null, projects: Array<Project>, };
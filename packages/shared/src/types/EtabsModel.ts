@@ -0,0 +1,21 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FrameSection } from "./FrameSection";
+import type { LineObject } from "./LineObject";
+import type { LoadPattern } from "./LoadPattern";
+import type { Material } from "./Material";
+import type { Point } from "./Point";
+import type { Story } from "./Story";
+import type { UnitSet } from "./UnitSet";
+
+/**
+ * Structural model read from an `.e2k` file
+ */
+export type EtabsModel = { 
+/**
+ * Units the file was exported in, for displaying values the same way
+ */
+units: UnitSet, stories: Array<Story>, materials: Array<Material>, frame_sections: Array<FrameSection>, points: Array<Point>, lines: Array<LineObject>, 
+/**
+ * Missing from models saved before load patterns were read
+ */
+load_patterns: Array<LoadPattern>, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What is currently open in the attached ETABS instance
+ */
+export type EtabsModelInfo = { etabs_version: string | null, model_filename: string | null, story_names: Array<string>, 
+/**
+ * `None` when the sidecar could not read the analysis state
+ */
+is_analyzed: boolean | null, is_locked: boolean | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A table that can be exported from a project
+ */
+export type ExportTable = "StoryDrifts" | "ModalPeriods" | "BaseReactions" | "LoadCombinations";
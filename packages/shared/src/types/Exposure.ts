@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Surface roughness category, §26.7.3
+ */
+export type Exposure = "B" | "C" | "D";
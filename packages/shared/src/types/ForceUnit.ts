@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Force unit
+ */
+export type ForceUnit = "Newton" | "Kilonewton" | "Pound" | "Kip" | "KilogramForce" | "TonneForce";
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Forces at one output station of a frame member, in N and N·m
+ */
+export type FrameForce = { story: string, 
+/**
+ * Element label, e.g. "C12"
+ */
+frame: string, 
+/**
+ * Load case or combination
+ */
+load_case: string, 
+/**
+ * Distance from the member's I-end, in m
+ */
+station: number, p: number, v2: number, v3: number, t: number, m2: number, m3: number, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Narrows a frame force query; each field that is set must match exactly
+ */
+export type FrameForceFilter = { story: string | null, 
+/**
+ * Load case or combination
+ */
+load_case: string | null, 
+/**
+ * Element label
+ */
+frame: string | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AnalysisRun } from "./AnalysisRun";
+
+/**
+ * Frame forces saved from ETABS: the run holding them and how many rows
+ */
+export type FrameForceImport = { run: AnalysisRun, rows: number, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FrameForce } from "./FrameForce";
+
+/**
+ * One page of frame forces matching a filter
+ */
+export type FrameForcePage = { rows: Array<FrameForce>, 
+/**
+ * Rows matching the filter over all pages
+ */
+total: number, page: number, page_size: number, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Length } from "./Length";
+
+/**
+ * Frame section definition
+ */
+export type FrameSection = { name: string, material: string | null, 
+/**
+ * ETABS shape name, e.g. "Concrete Rectangular" or "I/Wide Flange"
+ */
+shape: string | null, depth: Length | null, width: Length | null, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AppError } from "./AppError";
+
+/**
+ * Whether storage opened at startup. Until it has, only settings and
+ * retrying work; everything else fails with "storage not initialized".
+ */
+export type InitStatus = { "state": "Ready" } | { "state": "Failed", 
+/**
+ * Directory that couldn't be opened
+ */
+data_dir: string, error: AppError, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LateralSystem } from "./LateralSystem";
+import type { StoryForceRow } from "./StoryForceRow";
+
+/**
+ * Result of a lateral estimate, stored with the project for later comparison
+ */
+export type LateralEstimate = { system: LateralSystem, ct: number, x: number, total_height: number, total_weight: number, approximate_period: number, rayleigh_period: number | null, k: number, base_shear: number | null, story_forces: Array<StoryForceRow>, created_at: string, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LateralSystem } from "./LateralSystem";
+import type { StoryEstimateInput } from "./StoryEstimateInput";
+
+/**
+ * Inputs for a rough lateral estimate
+ */
+export type LateralEstimateInput = { system: LateralSystem, stories: Array<StoryEstimateInput>, 
+/**
+ * Seismic response coefficient Cs; when given, story forces are reported
+ */
+cs: number | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Approximate lateral system used to pick Ct and x (ASCE 7-16 Table 12.8-2)
+ */
+export type LateralSystem = "SteelMomentFrame" | "ConcreteMomentFrame" | "BracedFrame" | "ShearWall" | "Dual";
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LineKind } from "./LineKind";
+
+/**
+ * Line object connecting two plan points
+ */
+export type LineObject = { name: string, kind: LineKind, point_i: string, point_j: string, 
+/**
+ * Number of stories a column or brace spans downward; 0 for beams
+ */
+stories_spanned: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LoadCaseKind } from "./LoadCaseKind";
+
+/**
+ * A load case defined in the ETABS model
+ */
+export type LoadCase = { name: string, kind: LoadCaseKind, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What a load case represents, which decides its factors
+ */
+export type LoadCaseKind = "Dead" | "Live" | "RoofLive" | "Snow" | "Wind" | "Seismic";
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A linear combination of load cases
+ */
+export type LoadCombination = { name: string, 
+/**
+ * (case name, factor); lateral cases are negative in the reverse direction
+ */
+factors: Array<[string, number]>, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Combinations written into the open model by
+ * [`EtabsConnection::add_load_combinations`]
+ */
+export type LoadCombinationsReport = { model_filename: string, 
+/**
+ * Combinations the model did not have yet
+ */
+added: Array<string>, 
+/**
+ * Combinations of the same name that were overwritten
+ */
+replaced: Array<string>, messages: Array<string>, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Load pattern definition
+ */
+export type LoadPattern = { name: string, 
+/**
+ * ETABS load pattern type, e.g. "Dead", "Live" or "Seismic"
+ */
+pattern_type: string | null, 
+/**
+ * Multiple of the structure's self weight the pattern includes
+ */
+self_weight_multiplier: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Minimum severity written to the application log
+ */
+export type LogLevel = "Error" | "Warn" | "Info" | "Debug" | "Trace";
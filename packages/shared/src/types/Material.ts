@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Stress } from "./Stress";
+
+/**
+ * Material property definition
+ */
+export type Material = { name: string, 
+/**
+ * ETABS material type, e.g. "Steel" or "Concrete"
+ */
+material_type: string | null, 
+/**
+ * Unit weight in N/m³
+ */
+weight_per_volume: number | null, elastic_modulus: Stress | null, poisson_ratio: number | null, fc: Stress | null, fy: Stress | null, };
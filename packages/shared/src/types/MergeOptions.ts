@@ -0,0 +1,21 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What to do when merging a project into another and both have the same
+ * kind of data. Attached files, notes, analysis runs and tags are always
+ * added to the kept project; the data below is moved when the kept
+ * project has none, and only replaces the kept project's own when chosen.
+ */
+export type MergeOptions = { 
+/**
+ * Replace the imported ETABS model
+ */
+replace_model: boolean, 
+/**
+ * Replace the load cases and generated load combinations
+ */
+replace_combinations: boolean, 
+/**
+ * Replace the lateral estimate, seismic base shear and wind pressures
+ */
+replace_calculations: boolean, };
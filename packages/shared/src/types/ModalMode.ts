@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One vibration mode with the participation accumulated up to it
+ */
+export type ModalMode = { mode: number, 
+/**
+ * Period in s
+ */
+period: number, 
+/**
+ * Frequency in Hz
+ */
+frequency: number, 
+/**
+ * Participating mass ratios of this mode
+ */
+ux: number, uy: number, rz: number, 
+/**
+ * Participating mass ratios of this mode and all before it
+ */
+sum_ux: number, sum_uy: number, sum_rz: number, };
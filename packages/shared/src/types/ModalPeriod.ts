@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One vibration mode (ETABS "Modal Participating Mass Ratios")
+ */
+export type ModalPeriod = { load_case: string, mode: number, 
+/**
+ * Period in s
+ */
+period: number, 
+/**
+ * Participating mass ratios
+ */
+ux: number, uy: number, rz: number, };
@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ModalMode } from "./ModalMode";
+
+/**
+ * Modes of one modal load case and the 90% mass participation check
+ */
+export type ModalResults = { load_case: string, 
+/**
+ * Ordered by mode number
+ */
+modes: Array<ModalMode>, 
+/**
+ * First mode at which the cumulative UX ratio reaches 90%, if any
+ */
+mode_reaching_90_x: number | null, 
+/**
+ * First mode at which the cumulative UY ratio reaches 90%, if any
+ */
+mode_reaching_90_y: number | null, 
+/**
+ * Both horizontal directions reach 90%
+ */
+meets_mass_participation: boolean, };
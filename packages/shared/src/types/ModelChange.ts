@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ChangeKind } from "./ChangeKind";
+import type { ModelObjectKind } from "./ModelObjectKind";
+import type { PropertyChange } from "./PropertyChange";
+
+/**
+ * An added, removed or modified object
+ */
+export type ModelChange = { object: ModelObjectKind, name: string, change: ChangeKind, 
+/**
+ * Differing properties; empty for added and removed objects
+ */
+properties: Array<PropertyChange>, };
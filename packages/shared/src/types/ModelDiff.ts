@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ModelChange } from "./ModelChange";
+
+/**
+ * Changes from one model to another, stories first, then materials,
+ * frame sections and lines
+ */
+export type ModelDiff = { changes: Array<ModelChange>, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ForceUnit } from "./ForceUnit";
+import type { LengthUnit } from "./LengthUnit";
+
+/**
+ * Moment unit, force times length
+ */
+export type MomentUnit = { force: ForceUnit, length: LengthUnit, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Markdown note attached to a project
+ */
+export type Note = { id: string, project_id: string, title: string, body_markdown: string, created_at: string, updated_at: string, };
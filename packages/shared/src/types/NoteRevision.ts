@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Earlier version of a note, kept when it was edited or rolled back
+ */
+export type NoteRevision = { id: string, note_id: string, title: string, body_markdown: string, 
+/**
+ * When this version was written
+ */
+saved_at: string, };
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Outcome of opening a model with [`SidecarClient::open_model`]
+ */
+export type OpenModelReport = { file_path: string, 
+/**
+ * Opened in a hidden instance, which has exited again
+ */
+hidden: boolean, 
+/**
+ * ETABS process the model is open in; `None` after a hidden run
+ */
+pid: number | null, 
+/**
+ * Analysis time; `None` when no analysis ran
+ */
+analysis_time_ms: number | null, case_count: number | null, 
+/**
+ * Load cases the analysis finished
+ */
+finished_case_count: number | null, messages: Array<string>, };
@@ -0,0 +1,17 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AnalysisRun } from "./AnalysisRun";
+import type { ResultTable } from "./ResultTable";
+import type { RowError } from "./RowError";
+
+/**
+ * Outcome of pasting a table into an analysis run
+ */
+export type PasteImportReport = { run: AnalysisRun, 
+/**
+ * Table the text was detected, or hinted, to be
+ */
+table: ResultTable, imported_rows: number, 
+/**
+ * Rows left out of the import
+ */
+failed_rows: Array<RowError>, };
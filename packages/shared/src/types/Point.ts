@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Length } from "./Length";
+
+/**
+ * Plan point; ETABS repeats plan points on every story
+ */
+export type Point = { name: string, x: Length, y: Length, 
+/**
+ * Offset from the story level, only present for special points
+ */
+z: Length | null, };
@@ -1,6 +1,19 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProjectStatus } from "./ProjectStatus";
 
 /**
  * Main project domain model
  */
-export type Project = { id: string, name: string, description: string, created_at: string, updated_at: string, };
+export type Project = { id: string, name: string, description: string, created_at: string, updated_at: string, 
+/**
+ * Missing from project.json files written before archiving existed
+ */
+status: ProjectStatus, 
+/**
+ * When the project was moved to the trash
+ */
+trashed_at: string | null, 
+/**
+ * When the project was last opened; `None` if it never was
+ */
+last_opened_at: string | null, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Project } from "./Project";
+import type { ProjectFile } from "./ProjectFile";
+
+/**
+ * Snapshot of a project's metadata and attached-file list
+ */
+export type ProjectBackup = { 
+/**
+ * Sortable timestamp-based id, also the backup's file name
+ */
+id: string, created_at: string, project: Project, files: Array<ProjectFile>, };
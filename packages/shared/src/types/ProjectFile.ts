@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * File attached to a project, copied into the project's `files/` folder
+ */
+export type ProjectFile = { id: string, project_id: string, 
+/**
+ * File name as it was when the user added it
+ */
+original_name: string, 
+/**
+ * Location of the copy, relative to the project directory
+ */
+stored_path: string, 
+/**
+ * Size in bytes
+ */
+size: number, 
+/**
+ * Lowercase hex SHA-256 of the contents
+ */
+sha256: string, added_at: string, 
+/**
+ * The stored copy was deleted outside the app
+ */
+missing: boolean, };
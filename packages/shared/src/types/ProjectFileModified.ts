@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Payload of `project:file-modified`, sent when an attached file's stored
+ * copy is edited or deleted outside the app
+ */
+export type ProjectFileModified = { project_id: string, file_id: string, 
+/**
+ * Size in bytes
+ */
+size: number, sha256: string, missing: boolean, };
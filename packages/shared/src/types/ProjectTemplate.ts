@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LoadCase } from "./LoadCase";
+import type { TemplateSettings } from "./TemplateSettings";
+import type { TemplateTag } from "./TemplateTag";
+
+export type ProjectTemplate = { id: string, name: string, 
+/**
+ * Also the description of projects created from the template
+ */
+description: string, default_tags: Array<TemplateTag>, default_load_cases: Array<LoadCase>, default_settings: TemplateSettings, 
+/**
+ * Ships with the app and cannot be deleted
+ */
+built_in: boolean, };
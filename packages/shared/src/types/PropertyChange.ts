@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PropertyValue } from "./PropertyValue";
+
+/**
+ * One property that differs on a modified object
+ */
+export type PropertyChange = { 
+/**
+ * Field name as in the parsed model, e.g. "height"
+ */
+property: string, 
+/**
+ * `None` when the first model does not set the property
+ */
+before: PropertyValue | null, 
+/**
+ * `None` when the second model does not set the property
+ */
+after: PropertyValue | null, };
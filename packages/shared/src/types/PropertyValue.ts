@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Value of one property; lengths in metres, stresses in pascals
+ */
+export type PropertyValue = number | string | boolean;
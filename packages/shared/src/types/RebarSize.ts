@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Deformed bar size: ASTM A615 inch-pound sizes and common metric bars
+ */
+export type RebarSize = "#3" | "#4" | "#5" | "#6" | "#7" | "#8" | "#9" | "#10" | "#11" | "#14" | "#18" | "D10" | "D12" | "D16" | "D20" | "D25" | "D28" | "D32" | "D36" | "D40";
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Outcome of moving the projects directory; result of its task
+ */
+export type RelocationReport = { 
+/**
+ * The projects directory now in use
+ */
+projects_dir: string, 
+/**
+ * Project folders copied there
+ */
+copied: number, 
+/**
+ * Folders still in the old directory, kept on request or because they
+ * couldn't be removed
+ */
+left_behind: Array<string>, };
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What a storage repair changed
+ */
+export type RepairReport = { 
+/**
+ * Orphaned folders registered again from their project.json
+ */
+registered: Array<string>, 
+/**
+ * Projects without a folder that were moved to the trash
+ */
+trashed: Array<string>, 
+/**
+ * Projects without a folder whose rows were deleted
+ */
+removed: Array<string>, 
+/**
+ * Folders that could not be registered, with the reason
+ */
+skipped: Array<string>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * An ETABS table that can be imported from CSV
+ */
+export type ResultTable = "StoryDrifts" | "ModalPeriods" | "BaseReactions";
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A pasted row that could not be imported
+ */
+export type RowError = { 
+/**
+ * Line in the pasted text, from 1
+ */
+line: number, message: string, };
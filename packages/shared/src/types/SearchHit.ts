@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Project } from "./Project";
+import type { SearchField } from "./SearchField";
+
+/**
+ * One project found by a search, best matches first
+ */
+export type SearchHit = { project: Project, 
+/**
+ * Relevance; higher is better. Only comparable within one result list
+ */
+score: number, matched_field: SearchField, };
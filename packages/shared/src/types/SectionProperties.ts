@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Section properties about the centroidal axes
+ */
+export type SectionProperties = { area: number, 
+/**
+ * Moments of inertia
+ */
+ix: number, iy: number, 
+/**
+ * Elastic section moduli
+ */
+sx: number, sy: number, 
+/**
+ * Plastic section moduli
+ */
+zx: number, zy: number, 
+/**
+ * Radii of gyration
+ */
+rx: number, ry: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Cross-section shape; x is the strong (depth) axis
+ */
+export type SectionShape = { "kind": "Rectangle", b: number, h: number, } | { "kind": "Circle", d: number, } | { "kind": "IShape", d: number, bf: number, tf: number, tw: number, } | { "kind": "Tube", h: number, b: number, t: number, } | { "kind": "Pipe", od: number, t: number, };
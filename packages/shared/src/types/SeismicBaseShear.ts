@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BaseShearResult } from "./BaseShearResult";
+import type { SeismicParams } from "./SeismicParams";
+import type { StoryForceRow } from "./StoryForceRow";
+
+/**
+ * Base shear and story forces saved with a project
+ */
+export type SeismicBaseShear = { params: SeismicParams, result: BaseShearResult, story_forces: Array<StoryForceRow>, created_at: string, };
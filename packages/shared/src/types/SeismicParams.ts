@@ -0,0 +1,30 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Site and building parameters of the ELF procedure
+ */
+export type SeismicParams = { sds: number, sd1: number, 
+/**
+ * Mapped 1 s acceleration; Eq. 12.8-6 applies when it's 0.6g or more
+ */
+s1: number, 
+/**
+ * Response modification coefficient
+ */
+r: number, 
+/**
+ * Importance factor
+ */
+ie: number, 
+/**
+ * Fundamental period
+ */
+t: number, 
+/**
+ * Long-period transition period
+ */
+tl: number, 
+/**
+ * Effective seismic weight in kN
+ */
+building_weight: number, };
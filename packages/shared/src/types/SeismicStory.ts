@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One story for the vertical distribution, listed from the bottom up
+ */
+export type SeismicStory = { name: string, 
+/**
+ * Floor-to-floor height in m
+ */
+height: number, 
+/**
+ * Seismic weight in kN
+ */
+weight: number, };
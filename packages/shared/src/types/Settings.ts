@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LogLevel } from "./LogLevel";
+import type { TimezonePolicy } from "./TimezonePolicy";
+import type { UnitSystem } from "./UnitSystem";
+
+/**
+ * Application settings. Every field has a default, so settings files
+ * written by older versions keep loading as fields are added.
+ */
+export type Settings = { 
+/**
+ * Overrides the default projects directory; applied on next start
+ */
+projects_dir: string | null, units: UnitSystem, 
+/**
+ * Backups kept per project
+ */
+backup_retention: number, 
+/**
+ * Folder containing ETABS.exe
+ */
+etabs_install_path: string | null, log_level: LogLevel, timezone: TimezonePolicy, 
+/**
+ * Hours after which another instance's project lock counts as abandoned
+ */
+lock_timeout_hours: number, };
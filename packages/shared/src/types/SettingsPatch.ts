@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LogLevel } from "./LogLevel";
+import type { TimezonePolicy } from "./TimezonePolicy";
+import type { UnitSystem } from "./UnitSystem";
+
+/**
+ * Partial settings update; fields left out keep their current value.
+ * For the optional paths an empty string clears the setting.
+ */
+export type SettingsPatch = { projects_dir: string | null, units: UnitSystem | null, backup_retention: number | null, etabs_install_path: string | null, log_level: LogLevel | null, timezone: TimezonePolicy | null, lock_timeout_hours: number | null, };
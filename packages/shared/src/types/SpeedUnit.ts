@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Speed unit, for wind speeds
+ */
+export type SpeedUnit = "MetersPerSecond" | "KilometersPerHour" | "MilesPerHour";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Tension lap splice class, Table 25.5.2.1
+ */
+export type SpliceClass = "A" | "B";
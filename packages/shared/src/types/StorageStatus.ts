@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SyncProvider } from "./SyncProvider";
+
+/**
+ * Writability and sync state of the projects directory
+ */
+export type StorageStatus = { projects_dir: string, read_only: boolean, sync_provider: SyncProvider | null, advisories: Array<string>, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Length } from "./Length";
+
+/**
+ * Story level, listed from the top down as in the `.e2k` file
+ */
+export type Story = { name: string, 
+/**
+ * Height below this story's floor; 0 for the base
+ */
+height: Length, 
+/**
+ * Floor elevation, accumulated from the base story
+ */
+elevation: Length, master_story: boolean, similar_to: string | null, };
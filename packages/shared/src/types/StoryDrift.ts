@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Direction } from "./Direction";
+
+/**
+ * Inter-story drift ratio of one story (ETABS "Story Drifts")
+ */
+export type StoryDrift = { story: string, load_case: string, direction: Direction, 
+/**
+ * Drift divided by story height
+ */
+drift: number, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One manually entered story, listed from the bottom up
+ */
+export type StoryEstimateInput = { name: string, 
+/**
+ * Floor-to-floor height in m
+ */
+height: number, 
+/**
+ * Seismic weight in kN
+ */
+weight: number, 
+/**
+ * Lateral story stiffness in kN/m, needed for the Rayleigh estimate
+ */
+stiffness: number | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Vertical distribution of the ELF base shear to one story
+ */
+export type StoryForceRow = { story: string, elevation: number, weight: number, cvx: number, force: number | null, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ForceUnit } from "./ForceUnit";
+import type { LengthUnit } from "./LengthUnit";
+
+/**
+ * Stress unit, force per length squared
+ */
+export type StressUnit = { force: ForceUnit, length: LengthUnit, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Cloud sync client detected from well-known folder markers
+ */
+export type SyncProvider = "OneDrive" | "Dropbox" | "GoogleDrive" | "ICloud" | "Box";
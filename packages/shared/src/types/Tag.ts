@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Label used to group projects, e.g. by client or code version
+ */
+export type Tag = { id: string, name: string, 
+/**
+ * Hex color such as "#1e90ff" or "#09f"
+ */
+color: string, };
@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TaskKind } from "./TaskKind";
+import type { TaskStatus } from "./TaskStatus";
+
+/**
+ * A background task; payload of `task:progress` and `task:completed`
+ */
+export type TaskInfo = { id: string, kind: TaskKind, 
+/**
+ * Fraction done, from 0 to 1
+ */
+progress: number, status: TaskStatus, started_at: string, finished_at: string | null, 
+/**
+ * What the operation returned, once done
+ */
+result: unknown, 
+/**
+ * Why the task failed
+ */
+error: string | null, };
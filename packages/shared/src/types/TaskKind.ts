@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Operation a task runs
+ */
+export type TaskKind = "ImportEtabsModel" | "BulkImport" | "ProjectReport" | "RelocateProjects" | "OpenModel" | "ExtractFrameForces";
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesignCode } from "./DesignCode";
+
+/**
+ * Project settings a template starts from
+ */
+export type TemplateSettings = { 
+/**
+ * Combinations for the template's load cases are generated with this
+ * code when set
+ */
+design_code: DesignCode | null, };
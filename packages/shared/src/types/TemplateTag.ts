@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Tag given to projects created from a template, by name so templates
+ * don't depend on tag ids
+ */
+export type TemplateTag = { name: string, 
+/**
+ * Used when no tag with this name exists yet
+ */
+color: string, };
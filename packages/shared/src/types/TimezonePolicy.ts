@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How timestamps are presented to users. Storage is always UTC.
+ */
+export type TimezonePolicy = { "kind": "SystemLocal" } | { "kind": "FixedOffset", minutes: number, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ForceUnit } from "./ForceUnit";
+import type { LengthUnit } from "./LengthUnit";
+import type { SpeedUnit } from "./SpeedUnit";
+
+/**
+ * Unit of any supported quantity, for conversions requested by the frontend
+ */
+export type Unit = { "kind": "Length", unit: LengthUnit, } | { "kind": "Force", unit: ForceUnit, } | { "kind": "Moment", force: ForceUnit, length: LengthUnit, } | { "kind": "Stress", force: ForceUnit, length: LengthUnit, } | { "kind": "Speed", unit: SpeedUnit, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ForceUnit } from "./ForceUnit";
+import type { LengthUnit } from "./LengthUnit";
+
+/**
+ * Force and length units of an ETABS model
+ */
+export type UnitSet = { force: ForceUnit, length: LengthUnit, };
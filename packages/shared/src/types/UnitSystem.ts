@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Unit system for displaying and entering engineering quantities
+ */
+export type UnitSystem = "Metric" | "Imperial";
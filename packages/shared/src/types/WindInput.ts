@@ -0,0 +1,42 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Exposure } from "./Exposure";
+import type { Length } from "./Length";
+import type { Speed } from "./Speed";
+import type { WindStory } from "./WindStory";
+
+/**
+ * Inputs of the MWFRS wall pressure calculation
+ */
+export type WindInput = { 
+/**
+ * Basic wind speed V
+ */
+v: Speed, exposure: Exposure, 
+/**
+ * Topographic factor, 1.0 on flat ground
+ */
+kzt: number, 
+/**
+ * Directionality factor, 0.85 for buildings
+ */
+kd: number, 
+/**
+ * Ground elevation factor, 1.0 at sea level
+ */
+ke: number, 
+/**
+ * Gust-effect factor, 0.85 for rigid buildings
+ */
+gust_factor: number, 
+/**
+ * Plan dimension parallel to the wind, L
+ */
+length: Length, 
+/**
+ * Plan dimension normal to the wind, B
+ */
+width: Length, 
+/**
+ * The highest elevation is taken as the mean roof height
+ */
+stories: Array<WindStory>, };
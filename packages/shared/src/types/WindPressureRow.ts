@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Length } from "./Length";
+import type { Stress } from "./Stress";
+
+/**
+ * Velocity and wall pressures at one story
+ */
+export type WindPressureRow = { story: string, elevation: Length, kz: number, qz: Stress, 
+/**
+ * qz·G·Cp, positive towards the wall
+ */
+windward: Stress, 
+/**
+ * qh·G·Cp, negative (suction) and the same at every story
+ */
+leeward: Stress, 
+/**
+ * Windward minus leeward, the pressure on the building as a whole
+ */
+net: Stress, };
@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Stress } from "./Stress";
+import type { WindInput } from "./WindInput";
+import type { WindPressureRow } from "./WindPressureRow";
+
+/**
+ * Wall pressures of a building, stored with the project
+ */
+export type WindPressures = { input: WindInput, 
+/**
+ * Velocity pressure at the mean roof height
+ */
+qh: Stress, windward_cp: number, leeward_cp: number, rows: Array<WindPressureRow>, created_at: string, };
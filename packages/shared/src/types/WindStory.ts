@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Length } from "./Length";
+
+/**
+ * One story level where pressures are reported
+ */
+export type WindStory = { name: string, 
+/**
+ * Height above ground
+ */
+elevation: Length, };
@@ -3,7 +3,33 @@
 
 // Core domain types
 export type { Project } from './Project';
+export type { ProjectStatus } from './ProjectStatus';
+export type { CreateProjectOptions } from './CreateProjectOptions';
+export type { ProjectFile } from './ProjectFile';
+export type { Tag } from './Tag';
+export type { ProjectBackup } from './ProjectBackup';
 export type { AppError } from './AppError';
+export type { TimezonePolicy } from './TimezonePolicy';
+export type { StorageStatus } from './StorageStatus';
+export type { SyncProvider } from './SyncProvider';
+export type { InitStatus } from './InitStatus';
+export type { DuplicateProjectSet } from './DuplicateProjectSet';
+export type { DuplicateReason } from './DuplicateReason';
+export type { MergeOptions } from './MergeOptions';
+export type { SearchHit } from './SearchHit';
+export type { SearchField } from './SearchField';
+export type { ProjectRef } from './ProjectRef';
+export type { ProjectFileModified } from './ProjectFileModified';
+export type { AuditEntry } from './AuditEntry';
+export type { AuditAction } from './AuditAction';
+export type { Note } from './Note';
+export type { NoteRevision } from './NoteRevision';
+export type { Diagnostics } from './Diagnostics';
+export type { OrphanedRowAction } from './OrphanedRowAction';
+export type { RepairReport } from './RepairReport';
+export type { ProjectTemplate } from './ProjectTemplate';
+export type { TemplateTag } from './TemplateTag';
+export type { TemplateSettings } from './TemplateSettings';
 
 // ETABS CLI types
 export type { CliResult } from './CliResult';
@@ -19,8 +45,104 @@ export type { BranchData } from './BranchData';
 export type { VersionInfo } from './VersionInfo';
 export type { WorkingFileInfo } from './WorkingFileInfo';
 export type { EtabsStatus } from './EtabsStatus';
+export type { EtabsModelInfo } from './EtabsModelInfo';
+export type { OpenModelReport } from './OpenModelReport';
+export type { LoadCombinationsReport } from './LoadCombinationsReport';
 
 // Command request/response types
 export type { CreateBranchRequest } from './CreateBranchRequest';
 export type { SaveVersionRequest } from './SaveVersionRequest';
-export type { CompareVersionsRequest } from './CompareVersionsRequest';
\ No newline at end of file
+export type { CompareVersionsRequest } from './CompareVersionsRequest';
+
+// Engineering calculation types
+export type { LateralSystem } from './LateralSystem';
+export type { StoryEstimateInput } from './StoryEstimateInput';
+export type { LateralEstimateInput } from './LateralEstimateInput';
+export type { StoryForceRow } from './StoryForceRow';
+export type { LateralEstimate } from './LateralEstimate';
+export type { SeismicParams } from './SeismicParams';
+export type { CsEquation } from './CsEquation';
+export type { BaseShearResult } from './BaseShearResult';
+export type { SeismicStory } from './SeismicStory';
+export type { SeismicBaseShear } from './SeismicBaseShear';
+export type { Exposure } from './Exposure';
+export type { WindStory } from './WindStory';
+export type { WindInput } from './WindInput';
+export type { WindPressureRow } from './WindPressureRow';
+export type { WindPressures } from './WindPressures';
+export type { LoadCaseKind } from './LoadCaseKind';
+export type { LoadCase } from './LoadCase';
+export type { DesignCode } from './DesignCode';
+export type { LoadCombination } from './LoadCombination';
+export type { SectionShape } from './SectionShape';
+export type { SectionProperties } from './SectionProperties';
+export type { RebarSize } from './RebarSize';
+export type { BarCoating } from './BarCoating';
+export type { DevLengthModifiers } from './DevLengthModifiers';
+export type { DevelopmentLengthInput } from './DevelopmentLengthInput';
+export type { DevelopmentLength } from './DevelopmentLength';
+export type { DevLengthFactors } from './DevLengthFactors';
+export type { SpliceClass } from './SpliceClass';
+
+// Unit types
+export type { UnitSystem } from './UnitSystem';
+export type { Unit } from './Unit';
+export type { UnitSet } from './UnitSet';
+export type { LengthUnit } from './LengthUnit';
+export type { ForceUnit } from './ForceUnit';
+export type { MomentUnit } from './MomentUnit';
+export type { StressUnit } from './StressUnit';
+export type { SpeedUnit } from './SpeedUnit';
+export type { Length } from './Length';
+export type { Force } from './Force';
+export type { Moment } from './Moment';
+export type { Stress } from './Stress';
+export type { Speed } from './Speed';
+
+// ETABS model types
+export type { EtabsModel } from './EtabsModel';
+export type { Story } from './Story';
+export type { Material } from './Material';
+export type { FrameSection } from './FrameSection';
+export type { Point } from './Point';
+export type { LineObject } from './LineObject';
+export type { LoadPattern } from './LoadPattern';
+export type { LineKind } from './LineKind';
+export type { ModelDiff } from './ModelDiff';
+export type { ModelChange } from './ModelChange';
+export type { ModelObjectKind } from './ModelObjectKind';
+export type { ChangeKind } from './ChangeKind';
+export type { PropertyChange } from './PropertyChange';
+export type { PropertyValue } from './PropertyValue';
+
+// Analysis result types
+export type { AnalysisRun } from './AnalysisRun';
+export type { AnalysisResults } from './AnalysisResults';
+export type { ResultTable } from './ResultTable';
+export type { RowError } from './RowError';
+export type { PasteImportReport } from './PasteImportReport';
+export type { Direction } from './Direction';
+export type { StoryDrift } from './StoryDrift';
+export type { ModalPeriod } from './ModalPeriod';
+export type { ModalMode } from './ModalMode';
+export type { ModalResults } from './ModalResults';
+export type { BaseReaction } from './BaseReaction';
+export type { FrameForce } from './FrameForce';
+export type { FrameForceFilter } from './FrameForceFilter';
+export type { FrameForcePage } from './FrameForcePage';
+export type { FrameForceImport } from './FrameForceImport';
+export type { DriftCheckReport } from './DriftCheckReport';
+export type { ReportFormat } from './ReportFormat';
+export type { ExportTable } from './ExportTable';
+export type { ExportFormat } from './ExportFormat';
+export type { BulkImportProgress } from './BulkImportProgress';
+export type { BulkImportReport } from './BulkImportReport';
+export type { TaskKind } from './TaskKind';
+export type { TaskStatus } from './TaskStatus';
+export type { TaskInfo } from './TaskInfo';
+
+// Settings types
+export type { Settings } from './Settings';
+export type { SettingsPatch } from './SettingsPatch';
+export type { LogLevel } from './LogLevel';
+export type { RelocationReport } from './RelocationReport';